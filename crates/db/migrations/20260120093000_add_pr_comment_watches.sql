@@ -0,0 +1,36 @@
+-- Per-workspace opt-in for turning new PR review comments into agent follow-ups
+CREATE TABLE pr_comment_watches (
+    id           BLOB PRIMARY KEY,
+    workspace_id BLOB NOT NULL UNIQUE,
+    mode         TEXT NOT NULL DEFAULT 'confirm' CHECK (mode IN ('auto', 'confirm')),
+    -- created_at of the newest review comment already handled
+    last_seen_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    created_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+);
+
+-- A batch of review comments turned into a single follow-up prompt
+CREATE TABLE pr_comment_follow_ups (
+    id                   BLOB PRIMARY KEY,
+    workspace_id         BLOB NOT NULL,
+    merge_id             BLOB NOT NULL,
+    session_id           BLOB,
+    execution_process_id BLOB,
+    status               TEXT NOT NULL DEFAULT 'pending_confirmation'
+                         CHECK (status IN ('pending_confirmation', 'queued', 'started', 'replied', 'dismissed')),
+    prompt               TEXT NOT NULL,
+    -- JSON array of review comment ids to reply to once addressed
+    comment_ids          TEXT NOT NULL,
+    -- Branch head when the batch was created, used to detect new pushed commits
+    head_commit          TEXT,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+    FOREIGN KEY (merge_id) REFERENCES merges(id) ON DELETE CASCADE,
+    FOREIGN KEY (session_id) REFERENCES sessions(id) ON DELETE SET NULL,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_pr_comment_follow_ups_workspace_id ON pr_comment_follow_ups(workspace_id);
+CREATE INDEX idx_pr_comment_follow_ups_status ON pr_comment_follow_ups(status);
@@ -0,0 +1,27 @@
+-- Optional per-repo check run after every coding agent turn
+ALTER TABLE repos ADD COLUMN verify_script TEXT;
+ALTER TABLE repos ADD COLUMN verify_max_retries INTEGER NOT NULL DEFAULT 0;
+
+-- One row per repo verified after a coding agent execution process
+CREATE TABLE execution_process_verifications (
+    id                         BLOB PRIMARY KEY,
+    execution_process_id       BLOB NOT NULL,
+    repo_id                    BLOB NOT NULL,
+    passed                     INTEGER NOT NULL,
+    exit_code                  INTEGER,
+    -- Combined stdout/stderr, truncated to the tail
+    output                     TEXT NOT NULL,
+    -- 1 for the first verification of a prompt, incremented per automatic retry
+    attempt                    INTEGER NOT NULL DEFAULT 1,
+    -- Follow-up started with the failing output, if any
+    retry_execution_process_id BLOB,
+    created_at                 TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE,
+    FOREIGN KEY (repo_id) REFERENCES repos(id) ON DELETE CASCADE,
+    FOREIGN KEY (retry_execution_process_id) REFERENCES execution_processes(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_execution_process_verifications_execution_process_id
+    ON execution_process_verifications(execution_process_id);
+CREATE INDEX idx_execution_process_verifications_retry_execution_process_id
+    ON execution_process_verifications(retry_execution_process_id);
@@ -0,0 +1,27 @@
+-- Tokens reported by the agent for the turn, when the executor exposes usage
+ALTER TABLE coding_agent_turns ADD COLUMN total_tokens INTEGER;
+
+-- A set of workspaces running the same task with different executors
+CREATE TABLE fan_out_groups (
+    id                  BLOB PRIMARY KEY,
+    task_id             BLOB NOT NULL,
+    winner_workspace_id BLOB,
+    promoted_at         TEXT,
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (winner_workspace_id) REFERENCES workspaces(id) ON DELETE SET NULL
+);
+
+CREATE TABLE fan_out_attempts (
+    id                  BLOB PRIMARY KEY,
+    fan_out_group_id    BLOB NOT NULL,
+    workspace_id        BLOB NOT NULL UNIQUE,
+    -- JSON ExecutorProfileId the workspace was started with
+    executor_profile_id TEXT NOT NULL,
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (fan_out_group_id) REFERENCES fan_out_groups(id) ON DELETE CASCADE,
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_fan_out_groups_task_id ON fan_out_groups(task_id);
+CREATE INDEX idx_fan_out_attempts_fan_out_group_id ON fan_out_attempts(fan_out_group_id);
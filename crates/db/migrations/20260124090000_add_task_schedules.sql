@@ -0,0 +1,40 @@
+-- Recurring tasks: a cron schedule plus the template of the task created at each fire time
+CREATE TABLE task_schedules (
+    id                  BLOB PRIMARY KEY,
+    project_id          BLOB NOT NULL,
+    name                TEXT NOT NULL,
+    -- Five-field cron expression, evaluated in UTC
+    cron_expression     TEXT NOT NULL,
+    enabled             BOOLEAN NOT NULL DEFAULT TRUE,
+    task_title          TEXT NOT NULL,
+    task_description    TEXT,
+    -- JSON ExecutorProfileId
+    executor_profile_id TEXT NOT NULL,
+    -- JSON array of { repo_id, target_branch }
+    repos               TEXT NOT NULL,
+    next_run_at         TEXT,
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_schedules_project_id ON task_schedules(project_id);
+CREATE INDEX idx_task_schedules_enabled_next_run_at ON task_schedules(enabled, next_run_at);
+
+-- One row per fire time, whether or not a task was started
+CREATE TABLE task_schedule_runs (
+    id            BLOB PRIMARY KEY,
+    schedule_id   BLOB NOT NULL,
+    scheduled_for TEXT NOT NULL,
+    status        TEXT NOT NULL CHECK (status IN ('started', 'skipped', 'failed')),
+    task_id       BLOB,
+    workspace_id  BLOB,
+    -- Why the run was skipped or failed
+    message       TEXT,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (schedule_id) REFERENCES task_schedules(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE SET NULL,
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_task_schedule_runs_schedule_id_created_at ON task_schedule_runs(schedule_id, created_at);
@@ -0,0 +1,5 @@
+-- Tasks broken out of a larger one, e.g. nested items of an imported checklist.
+-- Unlike parent_workspace_id this does not require the parent to have been started.
+ALTER TABLE tasks ADD COLUMN parent_task_id BLOB REFERENCES tasks(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_tasks_parent_task_id ON tasks(parent_task_id);
@@ -0,0 +1,53 @@
+-- Per-project opt-in to keeping linked tasks and GitHub issues in sync
+CREATE TABLE github_issue_sync_settings (
+    project_id  BLOB PRIMARY KEY,
+    -- Repository whose GitHub remote holds the issues
+    repo_id     BLOB NOT NULL,
+    enabled     BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (repo_id) REFERENCES repos(id) ON DELETE CASCADE
+);
+
+-- A task linked to an issue. The local_* and remote_* columns hold each side as
+-- it was after the last sync, so a side that differs from them has changed since.
+CREATE TABLE task_github_issues (
+    id                   BLOB PRIMARY KEY,
+    task_id              BLOB NOT NULL UNIQUE,
+    repo_id              BLOB NOT NULL,
+    issue_number         INTEGER NOT NULL,
+    issue_url            TEXT NOT NULL,
+    local_title          TEXT NOT NULL,
+    local_body           TEXT NOT NULL,
+    local_status         TEXT NOT NULL,
+    remote_title         TEXT NOT NULL,
+    remote_body          TEXT NOT NULL,
+    remote_closed        BOOLEAN NOT NULL,
+    -- JSON array of pull request URLs already posted to the issue
+    commented_pr_urls    TEXT NOT NULL DEFAULT '[]',
+    -- Coding agent turn whose summary was last posted to the issue
+    last_summary_turn_id BLOB,
+    last_synced_at       TEXT,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (repo_id) REFERENCES repos(id) ON DELETE CASCADE,
+    UNIQUE (repo_id, issue_number)
+);
+
+-- Audit trail of every change the sync made on either side
+CREATE TABLE github_issue_sync_events (
+    id         BLOB PRIMARY KEY,
+    task_id    BLOB NOT NULL,
+    direction  TEXT NOT NULL CHECK (direction IN ('push', 'pull')),
+    -- title, body, status, pr_link or summary
+    field      TEXT NOT NULL,
+    value      TEXT,
+    -- Both sides had changed; the most recent edit won
+    conflict   BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_github_issue_sync_events_task_id_created_at ON github_issue_sync_events(task_id, created_at);
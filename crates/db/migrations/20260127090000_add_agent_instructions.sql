@@ -0,0 +1,7 @@
+-- Instructions merged into every coding agent prompt for the project
+ALTER TABLE projects ADD COLUMN agent_instructions TEXT;
+
+-- Per-repo instructions, written inline and/or read from a file in the repo
+-- (relative to the repo root, e.g. AGENTS.md) when each process starts
+ALTER TABLE repos ADD COLUMN agent_instructions TEXT;
+ALTER TABLE repos ADD COLUMN agent_instructions_file TEXT;
@@ -0,0 +1,21 @@
+-- Secrets injected into execution processes as environment variables.
+-- Values are encrypted with a key kept outside the database (OS keychain or a
+-- local key file); only the ciphertext is stored here.
+CREATE TABLE project_secrets (
+    id          BLOB PRIMARY KEY,
+    project_id  BLOB NOT NULL,
+    -- NULL applies the secret to every repo in the project
+    repo_id     BLOB,
+    name        TEXT NOT NULL,
+    -- Nonce followed by the AES-256-GCM ciphertext
+    ciphertext  BLOB NOT NULL,
+    -- JSON array of execution_process_run_reason values the secret is injected for
+    run_reasons TEXT NOT NULL DEFAULT '["setupscript","cleanupscript","codingagent","devserver"]',
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (repo_id) REFERENCES repos(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX idx_project_secrets_scope_name
+    ON project_secrets(project_id, COALESCE(repo_id, X''), name);
@@ -0,0 +1,10 @@
+-- Last measured size of each workspace directory, refreshed after every
+-- execution process so the API and the cleanup policy never walk the disk.
+CREATE TABLE workspace_disk_usage (
+    workspace_id   BLOB PRIMARY KEY,
+    total_bytes    INTEGER NOT NULL,
+    -- Bytes in build artifact directories (node_modules, target, ...)
+    artifact_bytes INTEGER NOT NULL,
+    measured_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+);
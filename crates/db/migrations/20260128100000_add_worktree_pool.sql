@@ -0,0 +1,27 @@
+-- Number of pre-warmed worktrees to keep for each repo; 0 disables the pool.
+ALTER TABLE repos ADD COLUMN worktree_pool_size INTEGER NOT NULL DEFAULT 0;
+
+-- Detached worktrees checked out at the latest commit of a repo's target
+-- branch with setup already run. A new workspace claims a ready entry and
+-- moves it onto its task branch instead of creating a worktree from scratch.
+CREATE TABLE worktree_pool_entries (
+    id            BLOB PRIMARY KEY,
+    repo_id       BLOB NOT NULL,
+    path          TEXT NOT NULL,
+    target_branch TEXT NOT NULL,
+    -- Commit the worktree and its setup were last prepared at
+    base_commit   TEXT,
+    status        TEXT NOT NULL DEFAULT 'preparing'
+                  CHECK (status IN ('preparing', 'ready', 'failed', 'claimed')),
+    -- Set once claimed, so the workspace can skip the repo's setup script
+    workspace_id  BLOB,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (repo_id) REFERENCES repos(id) ON DELETE CASCADE,
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_worktree_pool_entries_repo_status
+    ON worktree_pool_entries(repo_id, status);
+CREATE INDEX idx_worktree_pool_entries_workspace_id
+    ON worktree_pool_entries(workspace_id);
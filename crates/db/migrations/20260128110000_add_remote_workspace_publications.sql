@@ -0,0 +1,10 @@
+-- Last snapshot of each workspace pushed to the remote server, used to only
+-- send changes. There is no foreign key to workspaces so that rows outlive a
+-- deleted workspace until the remote copy has been removed too.
+CREATE TABLE remote_workspace_publications (
+    workspace_id      BLOB PRIMARY KEY,
+    remote_project_id BLOB NOT NULL,
+    -- JSON body of the last successful push
+    payload           TEXT NOT NULL,
+    published_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
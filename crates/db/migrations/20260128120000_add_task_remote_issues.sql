@@ -0,0 +1,14 @@
+-- Tasks started from an issue on the remote server. The link sets the issue
+-- on the published workspace and moves the issue across the remote board.
+CREATE TABLE task_remote_issues (
+    task_id           BLOB PRIMARY KEY,
+    remote_project_id BLOB NOT NULL,
+    remote_issue_id   BLOB NOT NULL,
+    -- Set once the issue was moved to the done status after a merged PR
+    completed_at      TEXT,
+    created_at        TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_remote_issues_remote_issue_id
+    ON task_remote_issues(remote_issue_id);
@@ -0,0 +1,3 @@
+-- Executors report how much of the context window the conversation fills,
+-- not tokens consumed by the turn, so the column is named for what it holds
+ALTER TABLE coding_agent_turns RENAME COLUMN total_tokens TO context_tokens;
@@ -0,0 +1,5 @@
+-- Verify scripts check the agent's work, so existing secrets reach them
+-- wherever they already reached the coding agent
+UPDATE project_secrets
+SET run_reasons = json_insert(run_reasons, '$[#]', 'verifyscript')
+WHERE EXISTS (SELECT 1 FROM json_each(project_secrets.run_reasons) WHERE value = 'codingagent');
@@ -0,0 +1,7 @@
+-- Loopback port of each workspace's preview proxy, reused whenever the proxy
+-- starts so preview URLs stay valid across restarts
+CREATE TABLE workspace_preview_ports (
+    workspace_id BLOB PRIMARY KEY,
+    port         INTEGER NOT NULL UNIQUE,
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+);
@@ -0,0 +1,4 @@
+-- Tokens a coding agent turn consumed, as reported by the executor. Unlike
+-- context_tokens these add up across turns.
+ALTER TABLE coding_agent_turns ADD COLUMN input_tokens INTEGER;
+ALTER TABLE coding_agent_turns ADD COLUMN output_tokens INTEGER;
@@ -72,6 +72,35 @@ impl CodingAgentTurn {
         .await
     }
 
+    /// Most recent turn with a summary across all of a task's workspaces
+    pub async fn find_latest_with_summary_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            CodingAgentTurn,
+            r#"SELECT
+                cat.id as "id!: Uuid",
+                cat.execution_process_id as "execution_process_id!: Uuid",
+                cat.agent_session_id,
+                cat.prompt,
+                cat.summary,
+                cat.seen as "seen!: bool",
+                cat.created_at as "created_at!: DateTime<Utc>",
+                cat.updated_at as "updated_at!: DateTime<Utc>"
+               FROM coding_agent_turns cat
+               JOIN execution_processes ep ON cat.execution_process_id = ep.id
+               JOIN sessions s ON ep.session_id = s.id
+               JOIN workspaces w ON s.workspace_id = w.id
+               WHERE w.task_id = $1 AND cat.summary IS NOT NULL AND ep.status != 'running'
+               ORDER BY cat.created_at DESC
+               LIMIT 1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
     /// Create a new coding agent turn
     pub async fn create(
         pool: &SqlitePool,
@@ -157,6 +186,95 @@ impl CodingAgentTurn {
         Ok(())
     }
 
+    /// Record how much of the context window the agent reported as used at
+    /// the end of this turn
+    pub async fn update_context_tokens(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        context_tokens: i64,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query!(
+            r#"UPDATE coding_agent_turns
+               SET context_tokens = $1, updated_at = $2
+               WHERE execution_process_id = $3"#,
+            context_tokens,
+            now,
+            execution_process_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Record the input and output tokens the agent reported consuming in
+    /// this turn
+    pub async fn update_consumed_tokens(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        input_tokens: Option<i64>,
+        output_tokens: Option<i64>,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query!(
+            r#"UPDATE coding_agent_turns
+               SET input_tokens = $1, output_tokens = $2, updated_at = $3
+               WHERE execution_process_id = $4"#,
+            input_tokens,
+            output_tokens,
+            now,
+            execution_process_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Input and output tokens consumed across all coding agent turns in a
+    /// workspace. Each total is None when no turn reported it.
+    pub async fn consumed_tokens_for_workspace(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<(Option<i64>, Option<i64>), sqlx::Error> {
+        let totals = sqlx::query!(
+            r#"SELECT
+                SUM(cat.input_tokens) as "input_tokens: i64",
+                SUM(cat.output_tokens) as "output_tokens: i64"
+               FROM coding_agent_turns cat
+               JOIN execution_processes ep ON cat.execution_process_id = ep.id
+               JOIN sessions s ON ep.session_id = s.id
+               WHERE s.workspace_id = $1 AND ep.run_reason = 'codingagent'"#,
+            workspace_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok((totals.input_tokens, totals.output_tokens))
+    }
+
+    /// Context window usage from the most recent turn in a workspace that
+    /// reported it. Usage is an occupancy figure, so it is never summed
+    /// across turns.
+    pub async fn latest_context_tokens_for_workspace(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Option<i64>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT cat.context_tokens as "context_tokens!: i64"
+               FROM coding_agent_turns cat
+               JOIN execution_processes ep ON cat.execution_process_id = ep.id
+               JOIN sessions s ON ep.session_id = s.id
+               WHERE s.workspace_id = $1 AND cat.context_tokens IS NOT NULL
+               ORDER BY cat.created_at DESC
+               LIMIT 1"#,
+            workspace_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
     /// Mark all coding agent turns for a workspace as seen
     pub async fn mark_seen_by_workspace_id(
         pool: &SqlitePool,
@@ -57,6 +57,7 @@ pub enum ExecutionProcessRunReason {
     CleanupScript,
     CodingAgent,
     DevServer,
+    VerifyScript,
 }
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
@@ -446,6 +447,28 @@ impl ExecutionProcess {
         .await
     }
 
+    /// Total wall-clock time spent in finished coding agent processes of a workspace
+    pub async fn coding_agent_duration_ms_for_workspace(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        let duration = sqlx::query_scalar!(
+            r#"SELECT CAST(COALESCE(SUM(
+                        (julianday(ep.completed_at) - julianday(ep.started_at)) * 86400000
+                    ), 0) AS INTEGER) as "duration_ms!: i64"
+               FROM execution_processes ep
+               JOIN sessions s ON ep.session_id = s.id
+               WHERE s.workspace_id = $1
+                 AND ep.run_reason = 'codingagent'
+                 AND ep.dropped = FALSE
+                 AND ep.completed_at IS NOT NULL"#,
+            workspace_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(duration)
+    }
+
     /// Create a new execution process
     ///
     /// Note: We intentionally avoid using a transaction here. SQLite update
@@ -715,14 +738,14 @@ impl ExecutionProcess {
             JOIN sessions s ON ep.session_id = s.id
             JOIN workspaces w ON s.workspace_id = w.id
             WHERE w.archived = $1
-              AND ep.run_reason IN ('codingagent', 'setupscript', 'cleanupscript')
+              AND ep.run_reason IN ('codingagent', 'setupscript', 'cleanupscript', 'verifyscript')
               AND ep.dropped = FALSE
               AND ep.created_at = (
                   SELECT MAX(ep2.created_at)
                   FROM execution_processes ep2
                   JOIN sessions s2 ON ep2.session_id = s2.id
                   WHERE s2.workspace_id = s.workspace_id
-                    AND ep2.run_reason IN ('codingagent', 'setupscript', 'cleanupscript')
+                    AND ep2.run_reason IN ('codingagent', 'setupscript', 'cleanupscript', 'verifyscript')
                     AND ep2.dropped = FALSE
               )
             "#,
@@ -0,0 +1,156 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Result of a repo's verify script after a coding agent execution process
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ExecutionProcessVerification {
+    pub id: Uuid,
+    pub execution_process_id: Uuid,
+    pub repo_id: Uuid,
+    pub passed: bool,
+    pub exit_code: Option<i64>,
+    pub output: String,
+    pub attempt: i64,
+    pub retry_execution_process_id: Option<Uuid>,
+    pub verify_execution_process_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateExecutionProcessVerification {
+    pub repo_id: Uuid,
+    pub passed: bool,
+    pub exit_code: Option<i64>,
+    pub output: String,
+    pub attempt: i64,
+    /// None when the verify script could not be started
+    pub verify_execution_process_id: Option<Uuid>,
+}
+
+impl ExecutionProcessVerification {
+    pub async fn create(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        data: &CreateExecutionProcessVerification,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let now = Utc::now();
+        sqlx::query_as!(
+            ExecutionProcessVerification,
+            r#"INSERT INTO execution_process_verifications (
+                id, execution_process_id, repo_id, passed, exit_code, output, attempt,
+                verify_execution_process_id, created_at
+               )
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+               RETURNING
+                id as "id!: Uuid",
+                execution_process_id as "execution_process_id!: Uuid",
+                repo_id as "repo_id!: Uuid",
+                passed as "passed!: bool",
+                exit_code,
+                output,
+                attempt as "attempt!: i64",
+                retry_execution_process_id as "retry_execution_process_id: Uuid",
+                verify_execution_process_id as "verify_execution_process_id: Uuid",
+                created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            execution_process_id,
+            data.repo_id,
+            data.passed,
+            data.exit_code,
+            data.output,
+            data.attempt,
+            data.verify_execution_process_id,
+            now
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_execution_process_id(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcessVerification,
+            r#"SELECT
+                id as "id!: Uuid",
+                execution_process_id as "execution_process_id!: Uuid",
+                repo_id as "repo_id!: Uuid",
+                passed as "passed!: bool",
+                exit_code,
+                output,
+                attempt as "attempt!: i64",
+                retry_execution_process_id as "retry_execution_process_id: Uuid",
+                verify_execution_process_id as "verify_execution_process_id: Uuid",
+                created_at as "created_at!: DateTime<Utc>"
+               FROM execution_process_verifications
+               WHERE execution_process_id = $1
+               ORDER BY created_at ASC"#,
+            execution_process_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Attempt number of the verification that started the given process as a
+    /// retry, if it was one
+    pub async fn find_attempt_for_retry(
+        pool: &SqlitePool,
+        retry_execution_process_id: Uuid,
+    ) -> Result<Option<i64>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT MAX(attempt) as "attempt: i64"
+               FROM execution_process_verifications
+               WHERE retry_execution_process_id = $1"#,
+            retry_execution_process_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn set_retry_execution_process_id(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        retry_execution_process_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE execution_process_verifications
+               SET retry_execution_process_id = $1
+               WHERE execution_process_id = $2 AND passed = 0"#,
+            retry_execution_process_id,
+            execution_process_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Whether the most recent coding agent turn in the session failed verification
+    pub async fn latest_coding_agent_turn_failed(
+        pool: &SqlitePool,
+        session_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let failed = sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                   SELECT 1 FROM execution_process_verifications v
+                   WHERE v.passed = 0
+                     AND v.execution_process_id = (
+                         SELECT ep.id FROM execution_processes ep
+                         WHERE ep.session_id = $1
+                           AND ep.run_reason = 'codingagent'
+                           AND ep.dropped = FALSE
+                         ORDER BY ep.created_at DESC
+                         LIMIT 1
+                     )
+               ) as "failed!: bool""#,
+            session_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(failed)
+    }
+}
@@ -0,0 +1,162 @@
+use chrono::{DateTime, Utc};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A task run in several workspaces at once with different executors
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct FanOutGroup {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub winner_workspace_id: Option<Uuid>,
+    pub promoted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct FanOutAttempt {
+    pub id: Uuid,
+    pub fan_out_group_id: Uuid,
+    pub workspace_id: Uuid,
+    #[ts(type = "ExecutorProfileId")]
+    pub executor_profile_id: Json<ExecutorProfileId>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl FanOutGroup {
+    pub async fn create(pool: &SqlitePool, id: Uuid, task_id: Uuid) -> Result<Self, sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query_as!(
+            FanOutGroup,
+            r#"INSERT INTO fan_out_groups (id, task_id, created_at)
+               VALUES ($1, $2, $3)
+               RETURNING
+                id as "id!: Uuid",
+                task_id as "task_id!: Uuid",
+                winner_workspace_id as "winner_workspace_id: Uuid",
+                promoted_at as "promoted_at: DateTime<Utc>",
+                created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            task_id,
+            now
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            FanOutGroup,
+            r#"SELECT
+                id as "id!: Uuid",
+                task_id as "task_id!: Uuid",
+                winner_workspace_id as "winner_workspace_id: Uuid",
+                promoted_at as "promoted_at: DateTime<Utc>",
+                created_at as "created_at!: DateTime<Utc>"
+               FROM fan_out_groups
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            FanOutGroup,
+            r#"SELECT
+                id as "id!: Uuid",
+                task_id as "task_id!: Uuid",
+                winner_workspace_id as "winner_workspace_id: Uuid",
+                promoted_at as "promoted_at: DateTime<Utc>",
+                created_at as "created_at!: DateTime<Utc>"
+               FROM fan_out_groups
+               WHERE task_id = $1
+               ORDER BY created_at DESC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn set_winner(
+        pool: &SqlitePool,
+        id: Uuid,
+        workspace_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query!(
+            "UPDATE fan_out_groups SET winner_workspace_id = $1, promoted_at = $2 WHERE id = $3",
+            workspace_id,
+            now,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!("DELETE FROM fan_out_groups WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+}
+
+impl FanOutAttempt {
+    pub async fn create(
+        pool: &SqlitePool,
+        fan_out_group_id: Uuid,
+        workspace_id: Uuid,
+        executor_profile_id: &ExecutorProfileId,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let now = Utc::now();
+        let executor_profile_id = Json(executor_profile_id);
+        sqlx::query_as!(
+            FanOutAttempt,
+            r#"INSERT INTO fan_out_attempts (id, fan_out_group_id, workspace_id, executor_profile_id, created_at)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING
+                id as "id!: Uuid",
+                fan_out_group_id as "fan_out_group_id!: Uuid",
+                workspace_id as "workspace_id!: Uuid",
+                executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            fan_out_group_id,
+            workspace_id,
+            executor_profile_id,
+            now
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_group_id(
+        pool: &SqlitePool,
+        fan_out_group_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            FanOutAttempt,
+            r#"SELECT
+                id as "id!: Uuid",
+                fan_out_group_id as "fan_out_group_id!: Uuid",
+                workspace_id as "workspace_id!: Uuid",
+                executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                created_at as "created_at!: DateTime<Utc>"
+               FROM fan_out_attempts
+               WHERE fan_out_group_id = $1
+               ORDER BY created_at ASC"#,
+            fan_out_group_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -0,0 +1,432 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::TaskStatus;
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct GitHubIssueSyncSettings {
+    pub project_id: Uuid,
+    /// Repository whose GitHub remote holds the issues
+    pub repo_id: Uuid,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpsertGitHubIssueSyncSettings {
+    pub repo_id: Uuid,
+    pub enabled: bool,
+}
+
+/// A task linked to a GitHub issue
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskGitHubIssue {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub repo_id: Uuid,
+    #[ts(type = "number")]
+    pub issue_number: i64,
+    pub issue_url: String,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub local_title: String,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub local_body: String,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub local_status: TaskStatus,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub remote_title: String,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub remote_body: String,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub remote_closed: bool,
+    #[ts(type = "Array<string>")]
+    pub commented_pr_urls: Json<Vec<String>>,
+    pub last_summary_turn_id: Option<Uuid>,
+    pub last_synced_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Both sides of a link as they were after a sync
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitHubIssueSyncState {
+    pub local_title: String,
+    pub local_body: String,
+    pub local_status: TaskStatus,
+    pub remote_title: String,
+    pub remote_body: String,
+    pub remote_closed: bool,
+}
+
+impl TaskGitHubIssue {
+    pub fn sync_state(&self) -> GitHubIssueSyncState {
+        GitHubIssueSyncState {
+            local_title: self.local_title.clone(),
+            local_body: self.local_body.clone(),
+            local_status: self.local_status.clone(),
+            remote_title: self.remote_title.clone(),
+            remote_body: self.remote_body.clone(),
+            remote_closed: self.remote_closed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "github_issue_sync_direction", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum GitHubIssueSyncDirection {
+    /// Local task to GitHub issue
+    Push,
+    /// GitHub issue to local task
+    Pull,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct GitHubIssueSyncEvent {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub direction: GitHubIssueSyncDirection,
+    /// title, body, status, pr_link or summary
+    pub field: String,
+    pub value: Option<String>,
+    /// Both sides had changed; the most recent edit won
+    pub conflict: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateGitHubIssueSyncEvent {
+    pub task_id: Uuid,
+    pub direction: GitHubIssueSyncDirection,
+    pub field: String,
+    pub value: Option<String>,
+    pub conflict: bool,
+}
+
+impl GitHubIssueSyncSettings {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            GitHubIssueSyncSettings,
+            r#"SELECT
+                project_id as "project_id!: Uuid",
+                repo_id as "repo_id!: Uuid",
+                enabled as "enabled!: bool",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+               FROM github_issue_sync_settings
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_enabled(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            GitHubIssueSyncSettings,
+            r#"SELECT
+                project_id as "project_id!: Uuid",
+                repo_id as "repo_id!: Uuid",
+                enabled as "enabled!: bool",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+               FROM github_issue_sync_settings
+               WHERE enabled = TRUE"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &UpsertGitHubIssueSyncSettings,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            GitHubIssueSyncSettings,
+            r#"INSERT INTO github_issue_sync_settings (project_id, repo_id, enabled)
+               VALUES ($1, $2, $3)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   repo_id = excluded.repo_id,
+                   enabled = excluded.enabled,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING
+                project_id as "project_id!: Uuid",
+                repo_id as "repo_id!: Uuid",
+                enabled as "enabled!: bool",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            data.repo_id,
+            data.enabled
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, project_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM github_issue_sync_settings WHERE project_id = $1",
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+impl TaskGitHubIssue {
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskGitHubIssue,
+            r#"SELECT
+                id as "id!: Uuid",
+                task_id as "task_id!: Uuid",
+                repo_id as "repo_id!: Uuid",
+                issue_number,
+                issue_url,
+                local_title,
+                local_body,
+                local_status as "local_status!: TaskStatus",
+                remote_title,
+                remote_body,
+                remote_closed as "remote_closed!: bool",
+                commented_pr_urls as "commented_pr_urls!: Json<Vec<String>>",
+                last_summary_turn_id as "last_summary_turn_id: Uuid",
+                last_synced_at as "last_synced_at: DateTime<Utc>",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_github_issues
+               WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Links of tasks in the project that point at issues of `repo_id`
+    pub async fn find_by_project_and_repo(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        repo_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskGitHubIssue,
+            r#"SELECT
+                tgi.id as "id!: Uuid",
+                tgi.task_id as "task_id!: Uuid",
+                tgi.repo_id as "repo_id!: Uuid",
+                tgi.issue_number,
+                tgi.issue_url,
+                tgi.local_title,
+                tgi.local_body,
+                tgi.local_status as "local_status!: TaskStatus",
+                tgi.remote_title,
+                tgi.remote_body,
+                tgi.remote_closed as "remote_closed!: bool",
+                tgi.commented_pr_urls as "commented_pr_urls!: Json<Vec<String>>",
+                tgi.last_summary_turn_id as "last_summary_turn_id: Uuid",
+                tgi.last_synced_at as "last_synced_at: DateTime<Utc>",
+                tgi.created_at as "created_at!: DateTime<Utc>",
+                tgi.updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_github_issues tgi
+               JOIN tasks t ON t.id = tgi.task_id
+               WHERE t.project_id = $1 AND tgi.repo_id = $2
+               ORDER BY tgi.issue_number ASC"#,
+            project_id,
+            repo_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        repo_id: Uuid,
+        issue_number: i64,
+        issue_url: &str,
+        state: &GitHubIssueSyncState,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            TaskGitHubIssue,
+            r#"INSERT INTO task_github_issues (
+                id, task_id, repo_id, issue_number, issue_url, local_title, local_body,
+                local_status, remote_title, remote_body, remote_closed
+               )
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+               RETURNING
+                id as "id!: Uuid",
+                task_id as "task_id!: Uuid",
+                repo_id as "repo_id!: Uuid",
+                issue_number,
+                issue_url,
+                local_title,
+                local_body,
+                local_status as "local_status!: TaskStatus",
+                remote_title,
+                remote_body,
+                remote_closed as "remote_closed!: bool",
+                commented_pr_urls as "commented_pr_urls!: Json<Vec<String>>",
+                last_summary_turn_id as "last_summary_turn_id: Uuid",
+                last_synced_at as "last_synced_at: DateTime<Utc>",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            task_id,
+            repo_id,
+            issue_number,
+            issue_url,
+            state.local_title,
+            state.local_body,
+            state.local_status,
+            state.remote_title,
+            state.remote_body,
+            state.remote_closed
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Record how both sides look after a sync
+    pub async fn update_synced(
+        pool: &SqlitePool,
+        id: Uuid,
+        state: &GitHubIssueSyncState,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE task_github_issues
+               SET local_title = $2, local_body = $3, local_status = $4, remote_title = $5,
+                   remote_body = $6, remote_closed = $7, last_synced_at = datetime('now', 'subsec'),
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            state.local_title,
+            state.local_body,
+            state.local_status,
+            state.remote_title,
+            state.remote_body,
+            state.remote_closed
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Record the pull requests already linked from the issue, saved after each
+    /// comment so a later failure doesn't post it again
+    pub async fn update_commented_pr_urls(
+        pool: &SqlitePool,
+        id: Uuid,
+        commented_pr_urls: &[String],
+    ) -> Result<(), sqlx::Error> {
+        let commented_pr_urls = Json(commented_pr_urls);
+        sqlx::query!(
+            r#"UPDATE task_github_issues
+               SET commented_pr_urls = $2, updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            commented_pr_urls
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn update_last_summary_turn_id(
+        pool: &SqlitePool,
+        id: Uuid,
+        last_summary_turn_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE task_github_issues
+               SET last_summary_turn_id = $2, updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            last_summary_turn_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete_by_task_id(pool: &SqlitePool, task_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_github_issues WHERE task_id = $1", task_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+impl GitHubIssueSyncEvent {
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateGitHubIssueSyncEvent,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            GitHubIssueSyncEvent,
+            r#"INSERT INTO github_issue_sync_events (id, task_id, direction, field, value, conflict)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING
+                id as "id!: Uuid",
+                task_id as "task_id!: Uuid",
+                direction as "direction!: GitHubIssueSyncDirection",
+                field,
+                value,
+                conflict as "conflict!: bool",
+                created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            data.task_id,
+            data.direction,
+            data.field,
+            data.value,
+            data.conflict
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            GitHubIssueSyncEvent,
+            r#"SELECT
+                id as "id!: Uuid",
+                task_id as "task_id!: Uuid",
+                direction as "direction!: GitHubIssueSyncDirection",
+                field,
+                value,
+                conflict as "conflict!: bool",
+                created_at as "created_at!: DateTime<Utc>"
+               FROM github_issue_sync_events
+               WHERE task_id = $1
+               ORDER BY created_at DESC
+               LIMIT $2"#,
+            task_id,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -1,6 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, SqlitePool};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
 use ts_rs::TS;
 use uuid::Uuid;
 
@@ -184,20 +184,33 @@ impl TaskImage {
         image_ids: &[Uuid],
     ) -> Result<(), sqlx::Error> {
         for &image_id in image_ids {
-            let id = Uuid::new_v4();
-            sqlx::query!(
-                r#"INSERT INTO task_images (id, task_id, image_id)
+            Self::associate_dedup(pool, task_id, image_id).await?;
+        }
+        Ok(())
+    }
+
+    /// Associate one image with a task unless it already is
+    pub async fn associate_dedup<'e, E>(
+        executor: E,
+        task_id: Uuid,
+        image_id: Uuid,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            r#"INSERT INTO task_images (id, task_id, image_id)
                    SELECT $1, $2, $3
                    WHERE NOT EXISTS (
                        SELECT 1 FROM task_images WHERE task_id = $2 AND image_id = $3
                    )"#,
-                id,
-                task_id,
-                image_id
-            )
-            .execute(pool)
-            .await?;
-        }
+            id,
+            task_id,
+            image_id
+        )
+        .execute(executor)
+        .await?;
         Ok(())
     }
 
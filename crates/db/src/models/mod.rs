@@ -2,14 +2,25 @@ pub mod coding_agent_turn;
 pub mod execution_process;
 pub mod execution_process_logs;
 pub mod execution_process_repo_state;
+pub mod execution_process_verification;
+pub mod fan_out;
+pub mod github_issue_sync;
 pub mod image;
 pub mod merge;
+pub mod pr_comment_watch;
 pub mod project;
 pub mod project_repo;
+pub mod project_secret;
+pub mod remote_workspace_publication;
 pub mod repo;
 pub mod scratch;
 pub mod session;
 pub mod tag;
 pub mod task;
+pub mod task_remote_issue;
+pub mod task_schedule;
 pub mod workspace;
+pub mod workspace_disk_usage;
+pub mod workspace_preview_port;
 pub mod workspace_repo;
+pub mod worktree_pool_entry;
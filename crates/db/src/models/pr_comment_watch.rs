@@ -0,0 +1,308 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// How new PR review comments are handed to the agent
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "pr_comment_watch_mode", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum PrCommentWatchMode {
+    /// Start (or queue) the follow-up immediately
+    Auto,
+    /// Notify the user and wait for confirmation
+    Confirm,
+}
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "pr_comment_follow_up_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PrCommentFollowUpStatus {
+    PendingConfirmation,
+    Queued,
+    Started,
+    Replied,
+    Dismissed,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct PrCommentWatch {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub mode: PrCommentWatchMode,
+    pub last_seen_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct PrCommentFollowUp {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub merge_id: Uuid,
+    pub session_id: Option<Uuid>,
+    pub execution_process_id: Option<Uuid>,
+    pub status: PrCommentFollowUpStatus,
+    pub prompt: String,
+    #[ts(type = "Array<bigint>")]
+    pub comment_ids: Json<Vec<i64>>,
+    pub head_commit: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreatePrCommentFollowUp {
+    pub workspace_id: Uuid,
+    pub merge_id: Uuid,
+    pub status: PrCommentFollowUpStatus,
+    pub prompt: String,
+    pub comment_ids: Vec<i64>,
+    pub head_commit: Option<String>,
+}
+
+impl PrCommentWatch {
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PrCommentWatch,
+            r#"SELECT
+                id as "id!: Uuid",
+                workspace_id as "workspace_id!: Uuid",
+                mode as "mode!: PrCommentWatchMode",
+                last_seen_at as "last_seen_at!: DateTime<Utc>",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+               FROM pr_comment_watches
+               WHERE workspace_id = $1"#,
+            workspace_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Enable watching for a workspace, or change the mode of an existing watch.
+    /// Comments created before the watch was first enabled are never picked up.
+    pub async fn upsert(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        mode: PrCommentWatchMode,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let now = Utc::now();
+        sqlx::query_as!(
+            PrCommentWatch,
+            r#"INSERT INTO pr_comment_watches (id, workspace_id, mode, last_seen_at, created_at, updated_at)
+               VALUES ($1, $2, $3, $4, $4, $4)
+               ON CONFLICT(workspace_id) DO UPDATE SET
+                   mode = excluded.mode,
+                   updated_at = excluded.updated_at
+               RETURNING
+                id as "id!: Uuid",
+                workspace_id as "workspace_id!: Uuid",
+                mode as "mode!: PrCommentWatchMode",
+                last_seen_at as "last_seen_at!: DateTime<Utc>",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            workspace_id,
+            mode,
+            now
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update_last_seen_at(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        last_seen_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query!(
+            "UPDATE pr_comment_watches SET last_seen_at = $1, updated_at = $2 WHERE workspace_id = $3",
+            last_seen_at,
+            now,
+            workspace_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM pr_comment_watches WHERE workspace_id = $1",
+            workspace_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+impl PrCommentFollowUp {
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PrCommentFollowUp,
+            r#"SELECT
+                id as "id!: Uuid",
+                workspace_id as "workspace_id!: Uuid",
+                merge_id as "merge_id!: Uuid",
+                session_id as "session_id: Uuid",
+                execution_process_id as "execution_process_id: Uuid",
+                status as "status!: PrCommentFollowUpStatus",
+                prompt,
+                comment_ids as "comment_ids!: Json<Vec<i64>>",
+                head_commit,
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+               FROM pr_comment_follow_ups
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PrCommentFollowUp,
+            r#"SELECT
+                id as "id!: Uuid",
+                workspace_id as "workspace_id!: Uuid",
+                merge_id as "merge_id!: Uuid",
+                session_id as "session_id: Uuid",
+                execution_process_id as "execution_process_id: Uuid",
+                status as "status!: PrCommentFollowUpStatus",
+                prompt,
+                comment_ids as "comment_ids!: Json<Vec<i64>>",
+                head_commit,
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+               FROM pr_comment_follow_ups
+               WHERE workspace_id = $1
+               ORDER BY created_at DESC"#,
+            workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Follow-ups handed to the agent whose threads have not been replied to yet
+    pub async fn find_awaiting_reply_for_merge(
+        pool: &SqlitePool,
+        merge_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PrCommentFollowUp,
+            r#"SELECT
+                id as "id!: Uuid",
+                workspace_id as "workspace_id!: Uuid",
+                merge_id as "merge_id!: Uuid",
+                session_id as "session_id: Uuid",
+                execution_process_id as "execution_process_id: Uuid",
+                status as "status!: PrCommentFollowUpStatus",
+                prompt,
+                comment_ids as "comment_ids!: Json<Vec<i64>>",
+                head_commit,
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+               FROM pr_comment_follow_ups
+               WHERE merge_id = $1 AND status IN ('queued', 'started')
+               ORDER BY created_at ASC"#,
+            merge_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreatePrCommentFollowUp,
+        id: Uuid,
+    ) -> Result<Self, sqlx::Error> {
+        let now = Utc::now();
+        let comment_ids = Json(&data.comment_ids);
+        sqlx::query_as!(
+            PrCommentFollowUp,
+            r#"INSERT INTO pr_comment_follow_ups (
+                id, workspace_id, merge_id, status, prompt, comment_ids, head_commit,
+                created_at, updated_at
+               )
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $8)
+               RETURNING
+                id as "id!: Uuid",
+                workspace_id as "workspace_id!: Uuid",
+                merge_id as "merge_id!: Uuid",
+                session_id as "session_id: Uuid",
+                execution_process_id as "execution_process_id: Uuid",
+                status as "status!: PrCommentFollowUpStatus",
+                prompt,
+                comment_ids as "comment_ids!: Json<Vec<i64>>",
+                head_commit,
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.workspace_id,
+            data.merge_id,
+            data.status,
+            data.prompt,
+            comment_ids,
+            data.head_commit,
+            now
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update_status(
+        pool: &SqlitePool,
+        id: Uuid,
+        status: PrCommentFollowUpStatus,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query!(
+            "UPDATE pr_comment_follow_ups SET status = $1, updated_at = $2 WHERE id = $3",
+            status,
+            now,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Record where the follow-up was handed to the agent
+    pub async fn mark_dispatched(
+        pool: &SqlitePool,
+        id: Uuid,
+        status: PrCommentFollowUpStatus,
+        session_id: Uuid,
+        execution_process_id: Option<Uuid>,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query!(
+            r#"UPDATE pr_comment_follow_ups
+               SET status = $1, session_id = $2, execution_process_id = $3, updated_at = $4
+               WHERE id = $5"#,
+            status,
+            session_id,
+            execution_process_id,
+            now,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -82,6 +82,11 @@ impl ProjectRepo {
                       r.copy_files,
                       r.parallel_setup_script as "parallel_setup_script!: bool",
                       r.dev_server_script,
+                      r.verify_script,
+                      r.verify_max_retries as "verify_max_retries!: i64",
+                      r.agent_instructions,
+                      r.agent_instructions_file,
+                      r.worktree_pool_size as "worktree_pool_size!: i64",
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>"
                FROM repos r
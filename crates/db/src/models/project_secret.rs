@@ -0,0 +1,165 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::execution_process::ExecutionProcessRunReason;
+
+/// An encrypted environment variable for a project, optionally limited to one
+/// repo. The value itself is never serialized.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectSecret {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    /// None applies the secret to every repo in the project
+    pub repo_id: Option<Uuid>,
+    pub name: String,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub ciphertext: Vec<u8>,
+    /// Kinds of execution process the secret is injected into
+    #[ts(type = "Array<ExecutionProcessRunReason>")]
+    pub run_reasons: Json<Vec<ExecutionProcessRunReason>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateProjectSecret {
+    pub name: String,
+    pub value: String,
+    pub repo_id: Option<Uuid>,
+    pub run_reasons: Vec<ExecutionProcessRunReason>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateProjectSecret {
+    /// Omit to keep the current value
+    pub value: Option<String>,
+    pub run_reasons: Option<Vec<ExecutionProcessRunReason>>,
+}
+
+impl ProjectSecret {
+    pub fn applies_to(&self, repo_ids: &[Uuid], run_reason: &ExecutionProcessRunReason) -> bool {
+        self.repo_id
+            .is_none_or(|repo_id| repo_ids.contains(&repo_id))
+            && self.run_reasons.contains(run_reason)
+    }
+
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectSecret,
+            r#"SELECT
+                id as "id!: Uuid",
+                project_id as "project_id!: Uuid",
+                repo_id as "repo_id: Uuid",
+                name,
+                ciphertext,
+                run_reasons as "run_reasons!: Json<Vec<ExecutionProcessRunReason>>",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_secrets
+               WHERE project_id = $1
+               ORDER BY name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectSecret,
+            r#"SELECT
+                id as "id!: Uuid",
+                project_id as "project_id!: Uuid",
+                repo_id as "repo_id: Uuid",
+                name,
+                ciphertext,
+                run_reasons as "run_reasons!: Json<Vec<ExecutionProcessRunReason>>",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_secrets
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        repo_id: Option<Uuid>,
+        name: &str,
+        ciphertext: &[u8],
+        run_reasons: &[ExecutionProcessRunReason],
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let run_reasons = Json(run_reasons.to_vec());
+        sqlx::query_as!(
+            ProjectSecret,
+            r#"INSERT INTO project_secrets (id, project_id, repo_id, name, ciphertext, run_reasons)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING
+                id as "id!: Uuid",
+                project_id as "project_id!: Uuid",
+                repo_id as "repo_id: Uuid",
+                name,
+                ciphertext,
+                run_reasons as "run_reasons!: Json<Vec<ExecutionProcessRunReason>>",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            repo_id,
+            name,
+            ciphertext,
+            run_reasons
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        ciphertext: &[u8],
+        run_reasons: &[ExecutionProcessRunReason],
+    ) -> Result<Self, sqlx::Error> {
+        let run_reasons = Json(run_reasons.to_vec());
+        sqlx::query_as!(
+            ProjectSecret,
+            r#"UPDATE project_secrets
+               SET ciphertext = $2,
+                   run_reasons = $3,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING
+                id as "id!: Uuid",
+                project_id as "project_id!: Uuid",
+                repo_id as "repo_id: Uuid",
+                name,
+                ciphertext,
+                run_reasons as "run_reasons!: Json<Vec<ExecutionProcessRunReason>>",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            ciphertext,
+            run_reasons
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM project_secrets WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,94 @@
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, FromRow)]
+pub struct RemoteWorkspacePublication {
+    pub workspace_id: Uuid,
+    pub remote_project_id: Uuid,
+    /// JSON body of the last successful push
+    pub payload: String,
+    pub published_at: DateTime<Utc>,
+}
+
+/// A local workspace whose project is linked to a remote project
+#[derive(Debug, Clone, FromRow)]
+pub struct LinkedWorkspace {
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub remote_project_id: Uuid,
+    /// Issue the task was started from, if it belongs to the linked project
+    pub remote_issue_id: Option<Uuid>,
+    /// Whether the issue was already moved to the done status
+    pub remote_issue_completed: bool,
+}
+
+impl RemoteWorkspacePublication {
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            RemoteWorkspacePublication,
+            r#"SELECT
+                workspace_id as "workspace_id!: Uuid",
+                remote_project_id as "remote_project_id!: Uuid",
+                payload,
+                published_at as "published_at!: DateTime<Utc>"
+               FROM remote_workspace_publications"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_linked_workspaces(
+        pool: &SqlitePool,
+    ) -> Result<Vec<LinkedWorkspace>, sqlx::Error> {
+        sqlx::query_as!(
+            LinkedWorkspace,
+            r#"SELECT
+                w.id as "workspace_id!: Uuid",
+                t.id as "task_id!: Uuid",
+                p.remote_project_id as "remote_project_id!: Uuid",
+                tri.remote_issue_id as "remote_issue_id: Uuid",
+                tri.completed_at IS NOT NULL as "remote_issue_completed!: bool"
+               FROM workspaces w
+               JOIN tasks t ON t.id = w.task_id
+               JOIN projects p ON p.id = t.project_id
+               LEFT JOIN task_remote_issues tri
+                 ON tri.task_id = t.id AND tri.remote_project_id = p.remote_project_id
+               WHERE p.remote_project_id IS NOT NULL"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        remote_project_id: Uuid,
+        payload: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO remote_workspace_publications (workspace_id, remote_project_id, payload)
+               VALUES ($1, $2, $3)
+               ON CONFLICT(workspace_id) DO UPDATE SET
+                   remote_project_id = excluded.remote_project_id,
+                   payload = excluded.payload,
+                   published_at = datetime('now', 'subsec')"#,
+            workspace_id,
+            remote_project_id,
+            payload
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, workspace_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "DELETE FROM remote_workspace_publications WHERE workspace_id = $1",
+            workspace_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -8,6 +8,9 @@ use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
 
+/// Upper bound for automatic verify retries, each of which is a full agent turn
+pub const MAX_VERIFY_RETRIES: i64 = 10;
+
 #[derive(Debug, Error)]
 pub enum RepoError {
     #[error(transparent)]
@@ -27,6 +30,20 @@ pub struct Repo {
     pub copy_files: Option<String>,
     pub parallel_setup_script: bool,
     pub dev_server_script: Option<String>,
+    /// Run in the worktree after each coding agent turn; the task only moves
+    /// to review once it exits successfully
+    pub verify_script: Option<String>,
+    /// Automatic follow-ups fed the failing output before giving up
+    #[ts(type = "number")]
+    pub verify_max_retries: i64,
+    /// Merged into every coding agent prompt for workspaces using this repo
+    pub agent_instructions: Option<String>,
+    /// File in the repo, e.g. `AGENTS.md`, whose contents are merged in as well
+    pub agent_instructions_file: Option<String>,
+    /// Detached worktrees kept checked out at the latest target branch with
+    /// setup already run, claimed by new workspaces instead of creating one
+    #[ts(type = "number")]
+    pub worktree_pool_size: i64,
     #[ts(type = "Date")]
     pub created_at: DateTime<Utc>,
     #[ts(type = "Date")]
@@ -83,6 +100,46 @@ pub struct UpdateRepo {
     )]
     #[ts(optional, type = "string | null")]
     pub dev_server_script: Option<Option<String>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "string | null")]
+    pub verify_script: Option<Option<String>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "number | null")]
+    pub verify_max_retries: Option<Option<i64>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "string | null")]
+    pub agent_instructions: Option<Option<String>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "string | null")]
+    pub agent_instructions_file: Option<Option<String>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "number | null")]
+    pub worktree_pool_size: Option<Option<i64>>,
 }
 
 impl Repo {
@@ -100,6 +157,11 @@ impl Repo {
                       copy_files,
                       parallel_setup_script as "parallel_setup_script!: bool",
                       dev_server_script,
+                      verify_script,
+                      verify_max_retries as "verify_max_retries!: i64",
+                      agent_instructions,
+                      agent_instructions_file,
+                      worktree_pool_size as "worktree_pool_size!: i64",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM repos
@@ -138,6 +200,11 @@ impl Repo {
                       copy_files,
                       parallel_setup_script as "parallel_setup_script!: bool",
                       dev_server_script,
+                      verify_script,
+                      verify_max_retries as "verify_max_retries!: i64",
+                      agent_instructions,
+                      agent_instructions_file,
+                      worktree_pool_size as "worktree_pool_size!: i64",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM repos
@@ -193,6 +260,11 @@ impl Repo {
                          copy_files,
                          parallel_setup_script as "parallel_setup_script!: bool",
                          dev_server_script,
+                         verify_script,
+                         verify_max_retries as "verify_max_retries!: i64",
+                         agent_instructions,
+                         agent_instructions_file,
+                         worktree_pool_size as "worktree_pool_size!: i64",
                          created_at as "created_at!: DateTime<Utc>",
                          updated_at as "updated_at!: DateTime<Utc>""#,
             id,
@@ -227,6 +299,11 @@ impl Repo {
                       copy_files,
                       parallel_setup_script as "parallel_setup_script!: bool",
                       dev_server_script,
+                      verify_script,
+                      verify_max_retries as "verify_max_retries!: i64",
+                      agent_instructions,
+                      agent_instructions_file,
+                      worktree_pool_size as "worktree_pool_size!: i64",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM repos
@@ -272,6 +349,26 @@ impl Repo {
             None => existing.dev_server_script,
             Some(v) => v.clone(),
         };
+        let verify_script = match &payload.verify_script {
+            None => existing.verify_script,
+            Some(v) => v.clone(),
+        };
+        let verify_max_retries = match &payload.verify_max_retries {
+            None => existing.verify_max_retries,
+            Some(v) => v.unwrap_or(0).clamp(0, MAX_VERIFY_RETRIES),
+        };
+        let agent_instructions = match &payload.agent_instructions {
+            None => existing.agent_instructions,
+            Some(v) => v.clone(),
+        };
+        let agent_instructions_file = match &payload.agent_instructions_file {
+            None => existing.agent_instructions_file,
+            Some(v) => v.clone(),
+        };
+        let worktree_pool_size = match &payload.worktree_pool_size {
+            None => existing.worktree_pool_size,
+            Some(v) => v.unwrap_or(0).max(0),
+        };
 
         sqlx::query_as!(
             Repo,
@@ -282,8 +379,13 @@ impl Repo {
                    copy_files = $4,
                    parallel_setup_script = $5,
                    dev_server_script = $6,
+                   verify_script = $7,
+                   verify_max_retries = $8,
+                   agent_instructions = $9,
+                   agent_instructions_file = $10,
+                   worktree_pool_size = $11,
                    updated_at = datetime('now', 'subsec')
-               WHERE id = $7
+               WHERE id = $12
                RETURNING id as "id!: Uuid",
                          path,
                          name,
@@ -293,6 +395,11 @@ impl Repo {
                          copy_files,
                          parallel_setup_script as "parallel_setup_script!: bool",
                          dev_server_script,
+                         verify_script,
+                         verify_max_retries as "verify_max_retries!: i64",
+                         agent_instructions,
+                         agent_instructions_file,
+                         worktree_pool_size as "worktree_pool_size!: i64",
                          created_at as "created_at!: DateTime<Utc>",
                          updated_at as "updated_at!: DateTime<Utc>""#,
             display_name,
@@ -301,6 +408,11 @@ impl Repo {
             copy_files,
             parallel_setup_script,
             dev_server_script,
+            verify_script,
+            verify_max_retries,
+            agent_instructions,
+            agent_instructions_file,
+            worktree_pool_size,
             id
         )
         .fetch_one(pool)
@@ -28,6 +28,9 @@ pub struct DraftFollowUpData {
 /// Data for preview settings scratch (URL override and screen size)
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct PreviewSettingsData {
+    /// URL override. The workspace's preview proxy reuses a saved port whenever
+    /// it starts, so its URL survives dev server and app restarts unless another
+    /// program has taken that port in the meantime.
     pub url: String,
     #[serde(default)]
     pub screen_size: Option<String>,
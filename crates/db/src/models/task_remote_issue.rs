@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+/// A task started from an issue on the remote server
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TaskRemoteIssue {
+    pub task_id: Uuid,
+    pub remote_project_id: Uuid,
+    pub remote_issue_id: Uuid,
+    /// When the issue was moved to the done status after a merged PR
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TaskRemoteIssue {
+    pub async fn create(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        remote_project_id: Uuid,
+        remote_issue_id: Uuid,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            TaskRemoteIssue,
+            r#"INSERT INTO task_remote_issues (task_id, remote_project_id, remote_issue_id)
+               VALUES ($1, $2, $3)
+               RETURNING
+                task_id as "task_id!: Uuid",
+                remote_project_id as "remote_project_id!: Uuid",
+                remote_issue_id as "remote_issue_id!: Uuid",
+                completed_at as "completed_at: DateTime<Utc>",
+                created_at as "created_at!: DateTime<Utc>""#,
+            task_id,
+            remote_project_id,
+            remote_issue_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskRemoteIssue,
+            r#"SELECT
+                task_id as "task_id!: Uuid",
+                remote_project_id as "remote_project_id!: Uuid",
+                remote_issue_id as "remote_issue_id!: Uuid",
+                completed_at as "completed_at: DateTime<Utc>",
+                created_at as "created_at!: DateTime<Utc>"
+               FROM task_remote_issues
+               WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn mark_completed(pool: &SqlitePool, task_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE task_remote_issues
+               SET completed_at = datetime('now', 'subsec')
+               WHERE task_id = $1"#,
+            task_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,364 @@
+use chrono::{DateTime, Utc};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Repo and target branch the scheduled workspace is created on
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct TaskScheduleRepo {
+    pub repo_id: Uuid,
+    pub target_branch: String,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskSchedule {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    /// Five-field cron expression, evaluated in UTC
+    pub cron_expression: String,
+    pub enabled: bool,
+    pub task_title: String,
+    pub task_description: Option<String>,
+    #[ts(type = "ExecutorProfileId")]
+    pub executor_profile_id: Json<ExecutorProfileId>,
+    #[ts(type = "Array<TaskScheduleRepo>")]
+    pub repos: Json<Vec<TaskScheduleRepo>>,
+    /// None while the schedule is disabled
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateTaskSchedule {
+    pub project_id: Uuid,
+    pub name: String,
+    pub cron_expression: String,
+    pub enabled: Option<bool>,
+    pub task_title: String,
+    pub task_description: Option<String>,
+    pub executor_profile_id: ExecutorProfileId,
+    pub repos: Vec<TaskScheduleRepo>,
+}
+
+/// Replaces every editable field of a schedule
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateTaskSchedule {
+    pub name: String,
+    pub cron_expression: String,
+    pub enabled: bool,
+    pub task_title: String,
+    pub task_description: Option<String>,
+    pub executor_profile_id: ExecutorProfileId,
+    pub repos: Vec<TaskScheduleRepo>,
+}
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "task_schedule_run_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum TaskScheduleRunStatus {
+    Started,
+    /// The previous run's workspace was still running
+    Skipped,
+    Failed,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskScheduleRun {
+    pub id: Uuid,
+    pub schedule_id: Uuid,
+    pub scheduled_for: DateTime<Utc>,
+    pub status: TaskScheduleRunStatus,
+    pub task_id: Option<Uuid>,
+    pub workspace_id: Option<Uuid>,
+    pub message: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateTaskScheduleRun {
+    pub schedule_id: Uuid,
+    pub scheduled_for: DateTime<Utc>,
+    pub status: TaskScheduleRunStatus,
+    pub task_id: Option<Uuid>,
+    pub workspace_id: Option<Uuid>,
+    pub message: Option<String>,
+}
+
+impl TaskSchedule {
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskSchedule,
+            r#"SELECT
+                id as "id!: Uuid",
+                project_id as "project_id!: Uuid",
+                name,
+                cron_expression,
+                enabled as "enabled!: bool",
+                task_title,
+                task_description,
+                executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                repos as "repos!: Json<Vec<TaskScheduleRepo>>",
+                next_run_at as "next_run_at: DateTime<Utc>",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_schedules
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskSchedule,
+            r#"SELECT
+                id as "id!: Uuid",
+                project_id as "project_id!: Uuid",
+                name,
+                cron_expression,
+                enabled as "enabled!: bool",
+                task_title,
+                task_description,
+                executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                repos as "repos!: Json<Vec<TaskScheduleRepo>>",
+                next_run_at as "next_run_at: DateTime<Utc>",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_schedules
+               WHERE project_id = $1
+               ORDER BY created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Enabled schedules whose next fire time is at or before `now`
+    pub async fn find_due(pool: &SqlitePool, now: DateTime<Utc>) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskSchedule,
+            r#"SELECT
+                id as "id!: Uuid",
+                project_id as "project_id!: Uuid",
+                name,
+                cron_expression,
+                enabled as "enabled!: bool",
+                task_title,
+                task_description,
+                executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                repos as "repos!: Json<Vec<TaskScheduleRepo>>",
+                next_run_at as "next_run_at: DateTime<Utc>",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_schedules
+               WHERE enabled = TRUE
+                 AND next_run_at IS NOT NULL
+                 AND next_run_at <= $1
+               ORDER BY next_run_at ASC"#,
+            now
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateTaskSchedule,
+        id: Uuid,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> Result<Self, sqlx::Error> {
+        let enabled = data.enabled.unwrap_or(true);
+        let executor_profile_id = Json(&data.executor_profile_id);
+        let repos = Json(&data.repos);
+        sqlx::query_as!(
+            TaskSchedule,
+            r#"INSERT INTO task_schedules (
+                id, project_id, name, cron_expression, enabled, task_title,
+                task_description, executor_profile_id, repos, next_run_at
+               )
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+               RETURNING
+                id as "id!: Uuid",
+                project_id as "project_id!: Uuid",
+                name,
+                cron_expression,
+                enabled as "enabled!: bool",
+                task_title,
+                task_description,
+                executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                repos as "repos!: Json<Vec<TaskScheduleRepo>>",
+                next_run_at as "next_run_at: DateTime<Utc>",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.project_id,
+            data.name,
+            data.cron_expression,
+            enabled,
+            data.task_title,
+            data.task_description,
+            executor_profile_id,
+            repos,
+            next_run_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateTaskSchedule,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> Result<Self, sqlx::Error> {
+        let executor_profile_id = Json(&data.executor_profile_id);
+        let repos = Json(&data.repos);
+        sqlx::query_as!(
+            TaskSchedule,
+            r#"UPDATE task_schedules
+               SET name = $2, cron_expression = $3, enabled = $4, task_title = $5,
+                   task_description = $6, executor_profile_id = $7, repos = $8,
+                   next_run_at = $9, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING
+                id as "id!: Uuid",
+                project_id as "project_id!: Uuid",
+                name,
+                cron_expression,
+                enabled as "enabled!: bool",
+                task_title,
+                task_description,
+                executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                repos as "repos!: Json<Vec<TaskScheduleRepo>>",
+                next_run_at as "next_run_at: DateTime<Utc>",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.name,
+            data.cron_expression,
+            data.enabled,
+            data.task_title,
+            data.task_description,
+            executor_profile_id,
+            repos,
+            next_run_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn set_next_run_at(
+        pool: &SqlitePool,
+        id: Uuid,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE task_schedules SET next_run_at = $1 WHERE id = $2",
+            next_run_at,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_schedules WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+impl TaskScheduleRun {
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateTaskScheduleRun,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            TaskScheduleRun,
+            r#"INSERT INTO task_schedule_runs (id, schedule_id, scheduled_for, status, task_id, workspace_id, message)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING
+                id as "id!: Uuid",
+                schedule_id as "schedule_id!: Uuid",
+                scheduled_for as "scheduled_for!: DateTime<Utc>",
+                status as "status!: TaskScheduleRunStatus",
+                task_id as "task_id: Uuid",
+                workspace_id as "workspace_id: Uuid",
+                message,
+                created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            data.schedule_id,
+            data.scheduled_for,
+            data.status,
+            data.task_id,
+            data.workspace_id,
+            data.message
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_schedule_id(
+        pool: &SqlitePool,
+        schedule_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskScheduleRun,
+            r#"SELECT
+                id as "id!: Uuid",
+                schedule_id as "schedule_id!: Uuid",
+                scheduled_for as "scheduled_for!: DateTime<Utc>",
+                status as "status!: TaskScheduleRunStatus",
+                task_id as "task_id: Uuid",
+                workspace_id as "workspace_id: Uuid",
+                message,
+                created_at as "created_at!: DateTime<Utc>"
+               FROM task_schedule_runs
+               WHERE schedule_id = $1
+               ORDER BY created_at DESC
+               LIMIT $2"#,
+            schedule_id,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Most recent run that actually started a workspace
+    pub async fn find_latest_started(
+        pool: &SqlitePool,
+        schedule_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskScheduleRun,
+            r#"SELECT
+                id as "id!: Uuid",
+                schedule_id as "schedule_id!: Uuid",
+                scheduled_for as "scheduled_for!: DateTime<Utc>",
+                status as "status!: TaskScheduleRunStatus",
+                task_id as "task_id: Uuid",
+                workspace_id as "workspace_id: Uuid",
+                message,
+                created_at as "created_at!: DateTime<Utc>"
+               FROM task_schedule_runs
+               WHERE schedule_id = $1 AND status = 'started'
+               ORDER BY created_at DESC
+               LIMIT 1"#,
+            schedule_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
@@ -562,7 +562,7 @@ impl Workspace {
                     JOIN execution_processes ep ON ep.session_id = s.id
                     WHERE s.workspace_id = w.id
                       AND ep.status = 'running'
-                      AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+                      AND ep.run_reason IN ('setupscript','cleanupscript','codingagent','verifyscript')
                     LIMIT 1
                 ) THEN 1 ELSE 0 END AS "is_running!: i64",
 
@@ -571,7 +571,7 @@ impl Workspace {
                     FROM sessions s
                     JOIN execution_processes ep ON ep.session_id = s.id
                     WHERE s.workspace_id = w.id
-                      AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+                      AND ep.run_reason IN ('setupscript','cleanupscript','codingagent','verifyscript')
                     ORDER BY ep.created_at DESC
                     LIMIT 1
                 ) IN ('failed','killed') THEN 1 ELSE 0 END AS "is_errored!: i64"
@@ -663,7 +663,7 @@ impl Workspace {
                     JOIN execution_processes ep ON ep.session_id = s.id
                     WHERE s.workspace_id = w.id
                       AND ep.status = 'running'
-                      AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+                      AND ep.run_reason IN ('setupscript','cleanupscript','codingagent','verifyscript')
                     LIMIT 1
                 ) THEN 1 ELSE 0 END AS "is_running!: i64",
 
@@ -672,7 +672,7 @@ impl Workspace {
                     FROM sessions s
                     JOIN execution_processes ep ON ep.session_id = s.id
                     WHERE s.workspace_id = w.id
-                      AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+                      AND ep.run_reason IN ('setupscript','cleanupscript','codingagent','verifyscript')
                     ORDER BY ep.created_at DESC
                     LIMIT 1
                 ) IN ('failed','killed') THEN 1 ELSE 0 END AS "is_errored!: i64"
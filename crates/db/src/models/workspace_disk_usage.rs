@@ -0,0 +1,149 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::workspace::Workspace;
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct WorkspaceDiskUsage {
+    pub workspace_id: Uuid,
+    #[ts(type = "number")]
+    pub total_bytes: i64,
+    #[ts(type = "number")]
+    pub artifact_bytes: i64,
+    pub measured_at: DateTime<Utc>,
+}
+
+/// Measured usage of a workspace that still has a worktree on disk, with the
+/// context needed to attribute it and decide whether it can be evicted
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct WorkspaceDiskUsageEntry {
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub task_title: String,
+    pub project_id: Uuid,
+    pub project_name: String,
+    pub branch: String,
+    pub archived: bool,
+    pub pinned: bool,
+    pub is_running: bool,
+    /// Latest of the workspace's last update and its last finished process
+    pub last_used_at: DateTime<Utc>,
+    #[ts(type = "number")]
+    pub total_bytes: i64,
+    #[ts(type = "number")]
+    pub artifact_bytes: i64,
+    pub measured_at: DateTime<Utc>,
+}
+
+impl WorkspaceDiskUsage {
+    pub async fn upsert(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        total_bytes: i64,
+        artifact_bytes: i64,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceDiskUsage,
+            r#"INSERT INTO workspace_disk_usage (workspace_id, total_bytes, artifact_bytes)
+               VALUES ($1, $2, $3)
+               ON CONFLICT(workspace_id) DO UPDATE SET
+                   total_bytes = excluded.total_bytes,
+                   artifact_bytes = excluded.artifact_bytes,
+                   measured_at = datetime('now', 'subsec')
+               RETURNING
+                workspace_id as "workspace_id!: Uuid",
+                total_bytes,
+                artifact_bytes,
+                measured_at as "measured_at!: DateTime<Utc>""#,
+            workspace_id,
+            total_bytes,
+            artifact_bytes
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, workspace_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "DELETE FROM workspace_disk_usage WHERE workspace_id = $1",
+            workspace_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn find_entries(
+        pool: &SqlitePool,
+    ) -> Result<Vec<WorkspaceDiskUsageEntry>, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceDiskUsageEntry,
+            r#"SELECT
+                w.id as "workspace_id!: Uuid",
+                t.id as "task_id!: Uuid",
+                t.title as "task_title!",
+                p.id as "project_id!: Uuid",
+                p.name as "project_name!",
+                w.branch as "branch!",
+                w.archived as "archived!: bool",
+                w.pinned as "pinned!: bool",
+                EXISTS (
+                    SELECT 1
+                    FROM sessions s
+                    JOIN execution_processes ep ON ep.session_id = s.id
+                    WHERE s.workspace_id = w.id AND ep.completed_at IS NULL
+                ) as "is_running!: bool",
+                MAX(
+                    datetime(w.updated_at),
+                    COALESCE(
+                        (SELECT datetime(MAX(ep.completed_at))
+                         FROM sessions s
+                         JOIN execution_processes ep ON ep.session_id = s.id
+                         WHERE s.workspace_id = w.id),
+                        datetime(w.updated_at)
+                    )
+                ) as "last_used_at!: DateTime<Utc>",
+                u.total_bytes,
+                u.artifact_bytes,
+                u.measured_at as "measured_at!: DateTime<Utc>"
+               FROM workspace_disk_usage u
+               JOIN workspaces w ON w.id = u.workspace_id
+               JOIN tasks t ON t.id = w.task_id
+               JOIN projects p ON p.id = t.project_id
+               WHERE w.container_ref IS NOT NULL
+               ORDER BY u.total_bytes DESC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Workspaces with a worktree on disk that have never been measured, e.g.
+    /// ones created before usage tracking existed
+    pub async fn find_unmeasured_workspaces(
+        pool: &SqlitePool,
+    ) -> Result<Vec<Workspace>, sqlx::Error> {
+        sqlx::query_as!(
+            Workspace,
+            r#"SELECT
+                w.id as "id!: Uuid",
+                w.task_id as "task_id!: Uuid",
+                w.container_ref,
+                w.branch as "branch!",
+                w.agent_working_dir,
+                w.setup_completed_at as "setup_completed_at: DateTime<Utc>",
+                w.created_at as "created_at!: DateTime<Utc>",
+                w.updated_at as "updated_at!: DateTime<Utc>",
+                w.archived as "archived!: bool",
+                w.pinned as "pinned!: bool",
+                w.name
+               FROM workspaces w
+               LEFT JOIN workspace_disk_usage u ON u.workspace_id = w.id
+               WHERE w.container_ref IS NOT NULL AND u.workspace_id IS NULL"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -0,0 +1,39 @@
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+/// Port a workspace's preview proxy listens on
+#[derive(Debug, Clone, FromRow)]
+pub struct WorkspacePreviewPort {
+    pub workspace_id: Uuid,
+    pub port: i64,
+}
+
+impl WorkspacePreviewPort {
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspacePreviewPort,
+            r#"SELECT workspace_id as "workspace_id!: Uuid", port
+               FROM workspace_preview_ports"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        port: u16,
+    ) -> Result<(), sqlx::Error> {
+        let port = i64::from(port);
+        sqlx::query!(
+            r#"INSERT INTO workspace_preview_ports (workspace_id, port)
+               VALUES ($1, $2)
+               ON CONFLICT(workspace_id) DO UPDATE SET port = excluded.port"#,
+            workspace_id,
+            port
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -120,6 +120,11 @@ impl WorkspaceRepo {
                       r.copy_files,
                       r.parallel_setup_script as "parallel_setup_script!: bool",
                       r.dev_server_script,
+                      r.verify_script,
+                      r.verify_max_retries as "verify_max_retries!: i64",
+                      r.agent_instructions,
+                      r.agent_instructions_file,
+                      r.worktree_pool_size as "worktree_pool_size!: i64",
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>"
                FROM repos r
@@ -146,6 +151,11 @@ impl WorkspaceRepo {
                       r.copy_files,
                       r.parallel_setup_script as "parallel_setup_script!: bool",
                       r.dev_server_script,
+                      r.verify_script,
+                      r.verify_max_retries as "verify_max_retries!: i64",
+                      r.agent_instructions,
+                      r.agent_instructions_file,
+                      r.worktree_pool_size as "worktree_pool_size!: i64",
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>",
                       wr.target_branch
@@ -171,6 +181,11 @@ impl WorkspaceRepo {
                     copy_files: row.copy_files,
                     parallel_setup_script: row.parallel_setup_script,
                     dev_server_script: row.dev_server_script,
+                    verify_script: row.verify_script,
+                    verify_max_retries: row.verify_max_retries,
+                    agent_instructions: row.agent_instructions,
+                    agent_instructions_file: row.agent_instructions_file,
+                    worktree_pool_size: row.worktree_pool_size,
                     created_at: row.created_at,
                     updated_at: row.updated_at,
                 },
@@ -257,6 +272,11 @@ impl WorkspaceRepo {
                       r.copy_files,
                       r.parallel_setup_script as "parallel_setup_script!: bool",
                       r.dev_server_script,
+                      r.verify_script,
+                      r.verify_max_retries as "verify_max_retries!: i64",
+                      r.agent_instructions,
+                      r.agent_instructions_file,
+                      r.worktree_pool_size as "worktree_pool_size!: i64",
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>"
                FROM repos r
@@ -295,4 +315,25 @@ impl WorkspaceRepo {
             })
             .collect())
     }
+
+    /// Target branches workspaces of this repo were based on, most recently
+    /// used first
+    pub async fn recent_target_branches(
+        pool: &SqlitePool,
+        repo_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT target_branch as "target_branch!"
+               FROM workspace_repos
+               WHERE repo_id = $1
+               GROUP BY target_branch
+               ORDER BY MAX(created_at) DESC
+               LIMIT $2"#,
+            repo_id,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
 }
@@ -0,0 +1,199 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq)]
+#[sqlx(type_name = "worktree_pool_entry_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum WorktreePoolEntryStatus {
+    /// Being created or refreshed; not claimable
+    Preparing,
+    Ready,
+    /// Setup failed at `base_commit`; retried once the target branch moves
+    Failed,
+    Claimed,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct WorktreePoolEntry {
+    pub id: Uuid,
+    pub repo_id: Uuid,
+    pub path: String,
+    pub target_branch: String,
+    pub base_commit: Option<String>,
+    pub status: WorktreePoolEntryStatus,
+    pub workspace_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WorktreePoolEntry {
+    pub async fn create(
+        pool: &SqlitePool,
+        id: Uuid,
+        repo_id: Uuid,
+        path: &str,
+        target_branch: &str,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            WorktreePoolEntry,
+            r#"INSERT INTO worktree_pool_entries (id, repo_id, path, target_branch)
+               VALUES ($1, $2, $3, $4)
+               RETURNING
+                id as "id!: Uuid",
+                repo_id as "repo_id!: Uuid",
+                path,
+                target_branch,
+                base_commit,
+                status as "status!: WorktreePoolEntryStatus",
+                workspace_id as "workspace_id: Uuid",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            repo_id,
+            path,
+            target_branch
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Entries that have not been handed to a workspace, oldest first
+    pub async fn find_unclaimed(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WorktreePoolEntry,
+            r#"SELECT
+                id as "id!: Uuid",
+                repo_id as "repo_id!: Uuid",
+                path,
+                target_branch,
+                base_commit,
+                status as "status!: WorktreePoolEntryStatus",
+                workspace_id as "workspace_id: Uuid",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+               FROM worktree_pool_entries
+               WHERE status != 'claimed'
+               ORDER BY created_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn set_status(
+        pool: &SqlitePool,
+        id: Uuid,
+        status: WorktreePoolEntryStatus,
+        target_branch: &str,
+        base_commit: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE worktree_pool_entries
+               SET status = $1,
+                   target_branch = $2,
+                   base_commit = $3,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $4"#,
+            status,
+            target_branch,
+            base_commit,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Move a ready or failed entry back to preparing. Returns false if it was
+    /// claimed in the meantime.
+    pub async fn begin_refresh(pool: &SqlitePool, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE worktree_pool_entries
+               SET status = 'preparing', updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND status IN ('ready', 'failed')"#,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Atomically hand the oldest ready entry prepared at `base_commit` to a
+    /// workspace
+    pub async fn claim(
+        pool: &SqlitePool,
+        repo_id: Uuid,
+        target_branch: &str,
+        base_commit: &str,
+        workspace_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WorktreePoolEntry,
+            r#"UPDATE worktree_pool_entries
+               SET status = 'claimed',
+                   workspace_id = $1,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = (
+                   SELECT id FROM worktree_pool_entries
+                   WHERE repo_id = $2
+                     AND target_branch = $3
+                     AND base_commit = $4
+                     AND status = 'ready'
+                   ORDER BY created_at ASC
+                   LIMIT 1
+               ) AND status = 'ready'
+               RETURNING
+                id as "id!: Uuid",
+                repo_id as "repo_id!: Uuid",
+                path,
+                target_branch,
+                base_commit,
+                status as "status!: WorktreePoolEntryStatus",
+                workspace_id as "workspace_id: Uuid",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>""#,
+            workspace_id,
+            repo_id,
+            target_branch,
+            base_commit
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Repos whose worktree in this workspace came from the pool, so their
+    /// setup script has already run
+    pub async fn find_claimed_repo_ids(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT repo_id as "repo_id!: Uuid"
+               FROM worktree_pool_entries
+               WHERE workspace_id = $1 AND status = 'claimed'"#,
+            workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Forget a workspace's claims, e.g. when creating its other worktrees
+    /// failed and the claimed ones were removed
+    pub async fn delete_claimed(pool: &SqlitePool, workspace_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "DELETE FROM worktree_pool_entries WHERE workspace_id = $1",
+            workspace_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!("DELETE FROM worktree_pool_entries WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -3,12 +3,23 @@ use std::sync::Arc;
 use anyhow::Error as AnyhowError;
 use async_trait::async_trait;
 use axum::response::sse::Event;
+use chrono::{DateTime, Utc};
 use db::{
     DBService,
     models::{
+        execution_process::ExecutionProcess,
+        pr_comment_watch::{PrCommentFollowUp, PrCommentFollowUpStatus},
         project::{CreateProject, Project},
         project_repo::CreateProjectRepo,
-        workspace::WorkspaceError,
+        repo::Repo,
+        scratch::DraftFollowUpData,
+        session::{CreateSession, Session, SessionError},
+        task::{CreateTask, Task},
+        task_schedule::{
+            CreateTaskScheduleRun, TaskSchedule, TaskScheduleRun, TaskScheduleRunStatus,
+        },
+        workspace::{CreateWorkspace, Workspace, WorkspaceError},
+        workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
     },
 };
 use executors::executors::ExecutorError;
@@ -26,17 +37,23 @@ use services::services::{
     filesystem::{FilesystemError, FilesystemService},
     filesystem_watcher::FilesystemWatcherError,
     git::{GitService, GitServiceError},
+    github_issue_sync::GitHubIssueSyncService,
     image::{ImageError, ImageService},
+    mcp_tokens::McpTokenService,
     pr_monitor::PrMonitorService,
+    preview::PreviewService,
     project::ProjectService,
     queued_message::QueuedMessageService,
     repo::RepoService,
+    scheduler::{ScheduledFire, TaskSchedulerService},
+    secrets::{SecretsError, SecretsService},
     worktree_manager::WorktreeError,
 };
 use sqlx::Error as SqlxError;
 use thiserror::Error;
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, mpsc};
 use utils::sentry as sentry_utils;
+use uuid::Uuid;
 
 #[derive(Debug, Clone, Copy, Error)]
 #[error("Remote client not configured")]
@@ -57,6 +74,8 @@ pub enum DeploymentError {
     #[error(transparent)]
     Workspace(#[from] WorkspaceError),
     #[error(transparent)]
+    Session(#[from] SessionError),
+    #[error(transparent)]
     Container(#[from] ContainerError),
     #[error(transparent)]
     Executor(#[from] ExecutorError),
@@ -73,6 +92,8 @@ pub enum DeploymentError {
     #[error("Remote client not configured")]
     RemoteClientNotConfigured,
     #[error(transparent)]
+    Secrets(#[from] SecretsError),
+    #[error(transparent)]
     Other(#[from] AnyhowError),
 }
 
@@ -108,6 +129,12 @@ pub trait Deployment: Clone + Send + Sync + 'static {
 
     fn queued_message_service(&self) -> &QueuedMessageService;
 
+    fn preview(&self) -> &PreviewService;
+
+    fn mcp_tokens(&self) -> &McpTokenService;
+
+    fn secrets(&self) -> &SecretsService;
+
     fn auth_context(&self) -> &AuthContext;
 
     async fn update_sentry_scope(&self) -> Result<(), DeploymentError> {
@@ -129,7 +156,318 @@ pub trait Deployment: Clone + Send + Sync + 'static {
                 user_id: self.user_id().to_string(),
                 analytics_service: analytics_service.clone(),
             });
-        PrMonitorService::spawn(db, analytics).await
+
+        let (follow_up_tx, mut follow_up_rx) = mpsc::unbounded_channel::<Uuid>();
+        let deployment = self.clone();
+        tokio::spawn(async move {
+            while let Some(follow_up_id) = follow_up_rx.recv().await {
+                if let Err(e) = deployment.dispatch_pr_comment_follow_up(follow_up_id).await {
+                    tracing::error!(
+                        "Failed to dispatch PR comment follow-up {}: {}",
+                        follow_up_id,
+                        e
+                    );
+                }
+            }
+        });
+
+        PrMonitorService::spawn(
+            db,
+            self.git().clone(),
+            self.container().notification_service().clone(),
+            follow_up_tx,
+            analytics,
+        )
+        .await
+    }
+
+    /// Hand a batch of PR review comments to the workspace's agent. The prompt is
+    /// queued behind a running turn, or started as a follow-up when the agent is idle.
+    async fn dispatch_pr_comment_follow_up(
+        &self,
+        follow_up_id: Uuid,
+    ) -> Result<PrCommentFollowUp, DeploymentError> {
+        let pool = &self.db().pool;
+        let follow_up = PrCommentFollowUp::find_by_id(pool, follow_up_id)
+            .await?
+            .ok_or_else(|| {
+                WorkspaceError::ValidationError("PR comment follow-up not found".to_string())
+            })?;
+        if follow_up.status != PrCommentFollowUpStatus::PendingConfirmation {
+            return Ok(follow_up);
+        }
+        let workspace = Workspace::find_by_id(pool, follow_up.workspace_id)
+            .await?
+            .ok_or_else(|| WorkspaceError::ValidationError("Workspace not found".to_string()))?;
+
+        let session = match Session::find_latest_by_workspace_id(pool, workspace.id).await? {
+            Some(session) => session,
+            None => {
+                // Without a session there is no earlier executor to follow, so
+                // start one with the configured default agent
+                let executor = self
+                    .config()
+                    .read()
+                    .await
+                    .executor_profile
+                    .executor
+                    .to_string();
+                Session::create(
+                    pool,
+                    &CreateSession {
+                        executor: Some(executor),
+                    },
+                    Uuid::new_v4(),
+                    workspace.id,
+                )
+                .await?
+            }
+        };
+
+        // Queue before checking whether the agent is busy: the exit handler marks
+        // the process finished before it drains the queue, so a process seen
+        // running afterwards is guaranteed to pick the message up. Anything the
+        // user already queued is kept and the review comments appended to it.
+        let data = match self.queued_message_service().get_queued(session.id) {
+            Some(queued) => DraftFollowUpData {
+                message: format!("{}\n\n{}", queued.data.message, follow_up.prompt),
+                variant: queued.data.variant,
+            },
+            None => DraftFollowUpData {
+                message: follow_up.prompt.clone(),
+                variant: None,
+            },
+        };
+        self.queued_message_service()
+            .queue_message(session.id, data);
+
+        let running = ExecutionProcess::has_running_non_dev_server_processes_for_workspace(
+            pool,
+            workspace.id,
+        )
+        .await?;
+        // When the agent is idle, take the message back and start it ourselves.
+        // If it is already gone, an exiting process drained it.
+        let idle_message = if running {
+            None
+        } else {
+            self.queued_message_service().take_queued(session.id)
+        };
+
+        match idle_message {
+            Some(queued) => {
+                let started = match self.container().ensure_container_exists(&workspace).await {
+                    Ok(_) => {
+                        self.container()
+                            .start_follow_up(&workspace, &session, &queued.data)
+                            .await
+                    }
+                    Err(e) => Err(e),
+                };
+                let execution_process = match started {
+                    Ok(execution_process) => execution_process,
+                    Err(e) => {
+                        // Leave the message queued rather than lose what the user wrote
+                        self.queued_message_service()
+                            .queue_message(session.id, queued.data);
+                        return Err(e.into());
+                    }
+                };
+                PrCommentFollowUp::mark_dispatched(
+                    pool,
+                    follow_up.id,
+                    PrCommentFollowUpStatus::Started,
+                    session.id,
+                    Some(execution_process.id),
+                )
+                .await?;
+            }
+            None => {
+                PrCommentFollowUp::mark_dispatched(
+                    pool,
+                    follow_up.id,
+                    PrCommentFollowUpStatus::Queued,
+                    session.id,
+                    None,
+                )
+                .await?;
+            }
+        }
+
+        PrCommentFollowUp::find_by_id(pool, follow_up.id)
+            .await?
+            .ok_or_else(|| {
+                DeploymentError::from(WorkspaceError::ValidationError(
+                    "PR comment follow-up not found".to_string(),
+                ))
+            })
+    }
+
+    async fn spawn_github_issue_sync_service(&self) -> tokio::task::JoinHandle<()> {
+        GitHubIssueSyncService::spawn(self.db().clone(), self.git().clone()).await
+    }
+
+    async fn spawn_task_scheduler(&self) -> tokio::task::JoinHandle<()> {
+        let (fire_tx, mut fire_rx) = mpsc::unbounded_channel::<ScheduledFire>();
+        let deployment = self.clone();
+        tokio::spawn(async move {
+            while let Some(fire) = fire_rx.recv().await {
+                if let Err(e) = deployment
+                    .run_task_schedule(fire.schedule_id, fire.scheduled_for)
+                    .await
+                {
+                    tracing::error!("Failed to run task schedule {}: {}", fire.schedule_id, e);
+                }
+            }
+        });
+
+        TaskSchedulerService::spawn(self.db().clone(), fire_tx).await
+    }
+
+    /// Create the schedule's task and start a workspace for it. The run is skipped
+    /// when the workspace started by the previous run is still busy; every outcome
+    /// is recorded in the schedule's run history.
+    async fn run_task_schedule(
+        &self,
+        schedule_id: Uuid,
+        scheduled_for: DateTime<Utc>,
+    ) -> Result<TaskScheduleRun, DeploymentError> {
+        let pool = &self.db().pool;
+        let schedule = TaskSchedule::find_by_id(pool, schedule_id)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+
+        let mut run = CreateTaskScheduleRun {
+            schedule_id,
+            scheduled_for,
+            status: TaskScheduleRunStatus::Started,
+            task_id: None,
+            workspace_id: None,
+            message: None,
+        };
+
+        if let Some(previous_workspace_id) = TaskScheduleRun::find_latest_started(pool, schedule_id)
+            .await?
+            .and_then(|previous| previous.workspace_id)
+            && ExecutionProcess::has_running_non_dev_server_processes_for_workspace(
+                pool,
+                previous_workspace_id,
+            )
+            .await?
+        {
+            tracing::info!(
+                "Skipping schedule {}: previous run is still running",
+                schedule_id
+            );
+            run.status = TaskScheduleRunStatus::Skipped;
+            run.workspace_id = Some(previous_workspace_id);
+            run.message = Some("Previous run is still running".to_string());
+            return Ok(TaskScheduleRun::create(pool, &run).await?);
+        }
+
+        if let Err(e) = self.start_scheduled_task(&schedule, &mut run).await {
+            tracing::error!("Scheduled task for {} failed to start: {}", schedule_id, e);
+            run.status = TaskScheduleRunStatus::Failed;
+            run.message = Some(e.to_string());
+        }
+
+        self.track_if_analytics_allowed(
+            "task_schedule_fired",
+            serde_json::json!({
+                "schedule_id": schedule_id.to_string(),
+                "project_id": schedule.project_id.to_string(),
+                "status": run.status,
+            }),
+        )
+        .await;
+
+        Ok(TaskScheduleRun::create(pool, &run).await?)
+    }
+
+    /// Task and workspace ids are recorded on `run` as soon as they exist, so a
+    /// failed start still links to what was created
+    async fn start_scheduled_task(
+        &self,
+        schedule: &TaskSchedule,
+        run: &mut CreateTaskScheduleRun,
+    ) -> Result<(), DeploymentError> {
+        let pool = &self.db().pool;
+        let repos = &schedule.repos.0;
+        if repos.is_empty() {
+            return Err(WorkspaceError::ValidationError(
+                "Schedule has no repositories".to_string(),
+            )
+            .into());
+        }
+
+        let task = Task::create(
+            pool,
+            &CreateTask::from_title_description(
+                schedule.project_id,
+                schedule.task_title.clone(),
+                schedule.task_description.clone(),
+            ),
+            Uuid::new_v4(),
+        )
+        .await?;
+        run.task_id = Some(task.id);
+
+        let workspace_repos: Vec<CreateWorkspaceRepo> = repos
+            .iter()
+            .map(|r| CreateWorkspaceRepo {
+                repo_id: r.repo_id,
+                target_branch: r.target_branch.clone(),
+            })
+            .collect();
+        let workspace = self.create_workspace(&task, &workspace_repos).await?;
+        run.workspace_id = Some(workspace.id);
+
+        self.container()
+            .start_workspace(&workspace, schedule.executor_profile_id.0.clone())
+            .await?;
+        Ok(())
+    }
+
+    /// Create a workspace for the task on the given repos, along with its
+    /// branch and repo rows. Nothing is started in it.
+    async fn create_workspace(
+        &self,
+        task: &Task,
+        repos: &[CreateWorkspaceRepo],
+    ) -> Result<Workspace, DeploymentError> {
+        let pool = &self.db().pool;
+
+        // Single repo: the agent runs in the repo directory, otherwise in the workspace root
+        let agent_working_dir = match repos {
+            [repo] => Some(
+                Repo::find_by_id(pool, repo.repo_id)
+                    .await?
+                    .ok_or_else(|| {
+                        WorkspaceError::ValidationError("Repository not found".to_string())
+                    })?
+                    .name,
+            ),
+            _ => None,
+        };
+
+        let workspace_id = Uuid::new_v4();
+        let branch = self
+            .container()
+            .git_branch_from_workspace(&workspace_id, &task.title)
+            .await;
+        let workspace = Workspace::create(
+            pool,
+            &CreateWorkspace {
+                branch,
+                agent_working_dir,
+            },
+            workspace_id,
+            task.id,
+        )
+        .await?;
+        WorkspaceRepo::create_many(pool, workspace.id, repos).await?;
+
+        Ok(workspace)
     }
 
     async fn track_if_analytics_allowed(&self, event_name: &str, properties: Value) {
@@ -7,7 +7,7 @@ use ts_rs::TS;
 #[cfg(not(feature = "qa-mode"))]
 use crate::profile::ExecutorConfigs;
 use crate::{
-    actions::Executable,
+    actions::{Executable, prompt_with_instructions},
     approvals::ExecutorApprovalService,
     env::ExecutionEnv,
     executors::{BaseCodingAgent, ExecutorError, SpawnedChild, StandardCodingAgentExecutor},
@@ -26,6 +26,10 @@ pub struct CodingAgentFollowUpRequest {
     /// If None, uses the container_ref directory directly.
     #[serde(default)]
     pub working_dir: Option<String>,
+    /// Project and repository instructions merged into the prompt, resolved when
+    /// the process starts
+    #[serde(default)]
+    pub instructions: Option<String>,
 }
 
 impl CodingAgentFollowUpRequest {
@@ -34,6 +38,11 @@ impl CodingAgentFollowUpRequest {
         self.executor_profile_id.clone()
     }
 
+    /// Prompt sent to the agent, including any instructions
+    pub fn effective_prompt(&self) -> String {
+        prompt_with_instructions(&self.prompt, self.instructions.as_deref())
+    }
+
     pub fn effective_dir(&self, current_dir: &Path) -> std::path::PathBuf {
         match &self.working_dir {
             Some(rel_path) => current_dir.join(rel_path),
@@ -62,7 +71,12 @@ impl Executable for CodingAgentFollowUpRequest {
             tracing::info!("QA mode: using mock executor for follow-up instead of real agent");
             let executor = crate::executors::qa_mock::QaMockExecutor;
             return executor
-                .spawn_follow_up(&effective_dir, &self.prompt, &self.session_id, env)
+                .spawn_follow_up(
+                    &effective_dir,
+                    &self.effective_prompt(),
+                    &self.session_id,
+                    env,
+                )
                 .await;
         }
 
@@ -78,7 +92,12 @@ impl Executable for CodingAgentFollowUpRequest {
             agent.use_approvals(approvals.clone());
 
             agent
-                .spawn_follow_up(&effective_dir, &self.prompt, &self.session_id, env)
+                .spawn_follow_up(
+                    &effective_dir,
+                    &self.effective_prompt(),
+                    &self.session_id,
+                    env,
+                )
                 .await
         }
     }
@@ -7,7 +7,7 @@ use ts_rs::TS;
 #[cfg(not(feature = "qa-mode"))]
 use crate::profile::ExecutorConfigs;
 use crate::{
-    actions::Executable,
+    actions::{Executable, prompt_with_instructions},
     approvals::ExecutorApprovalService,
     env::ExecutionEnv,
     executors::{BaseCodingAgent, ExecutorError, SpawnedChild, StandardCodingAgentExecutor},
@@ -25,6 +25,10 @@ pub struct CodingAgentInitialRequest {
     /// If None, uses the container_ref directory directly.
     #[serde(default)]
     pub working_dir: Option<String>,
+    /// Project and repository instructions merged into the prompt, resolved when
+    /// the process starts
+    #[serde(default)]
+    pub instructions: Option<String>,
 }
 
 impl CodingAgentInitialRequest {
@@ -32,6 +36,11 @@ impl CodingAgentInitialRequest {
         self.executor_profile_id.executor
     }
 
+    /// Prompt sent to the agent, including any instructions
+    pub fn effective_prompt(&self) -> String {
+        prompt_with_instructions(&self.prompt, self.instructions.as_deref())
+    }
+
     pub fn effective_dir(&self, current_dir: &Path) -> std::path::PathBuf {
         match &self.working_dir {
             Some(rel_path) => current_dir.join(rel_path),
@@ -55,7 +64,9 @@ impl Executable for CodingAgentInitialRequest {
         {
             tracing::info!("QA mode: using mock executor instead of real agent");
             let executor = crate::executors::qa_mock::QaMockExecutor;
-            return executor.spawn(&effective_dir, &self.prompt, env).await;
+            return executor
+                .spawn(&effective_dir, &self.effective_prompt(), env)
+                .await;
         }
 
         #[cfg(not(feature = "qa-mode"))]
@@ -69,7 +80,9 @@ impl Executable for CodingAgentInitialRequest {
 
             agent.use_approvals(approvals.clone());
 
-            agent.spawn(&effective_dir, &self.prompt, env).await
+            agent
+                .spawn(&effective_dir, &self.effective_prompt(), env)
+                .await
         }
     }
 }
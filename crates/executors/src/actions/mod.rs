@@ -32,6 +32,27 @@ pub enum ExecutorActionType {
     ReviewRequest,
 }
 
+impl ExecutorActionType {
+    /// Attach project and repository instructions to coding agent requests;
+    /// scripts are left alone
+    pub fn set_instructions(&mut self, instructions: Option<String>) {
+        match self {
+            Self::CodingAgentInitialRequest(request) => request.instructions = instructions,
+            Self::CodingAgentFollowUpRequest(request) => request.instructions = instructions,
+            Self::ReviewRequest(request) => request.instructions = instructions,
+            Self::ScriptRequest(_) => {}
+        }
+    }
+}
+
+/// Instructions come first so the agent reads them before the task itself
+pub fn prompt_with_instructions(prompt: &str, instructions: Option<&str>) -> String {
+    match instructions.map(str::trim).filter(|text| !text.is_empty()) {
+        Some(instructions) => format!("{instructions}\n\n---\n\n{prompt}"),
+        None => prompt.to_string(),
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct ExecutorAction {
     pub typ: ExecutorActionType,
@@ -93,3 +114,18 @@ impl Executable for ExecutorAction {
         self.typ.spawn(current_dir, approvals, env).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prompt_with_instructions_prepends_non_empty_instructions() {
+        assert_eq!(prompt_with_instructions("Fix it", None), "Fix it");
+        assert_eq!(prompt_with_instructions("Fix it", Some("  \n")), "Fix it");
+        assert_eq!(
+            prompt_with_instructions("Fix it", Some("Run just fmt\n")),
+            "Run just fmt\n\n---\n\nFix it"
+        );
+    }
+}
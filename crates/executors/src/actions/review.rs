@@ -6,7 +6,7 @@ use ts_rs::TS;
 use uuid::Uuid;
 
 use crate::{
-    actions::Executable,
+    actions::{Executable, prompt_with_instructions},
     approvals::ExecutorApprovalService,
     env::ExecutionEnv,
     executors::{BaseCodingAgent, ExecutorError, SpawnedChild, StandardCodingAgentExecutor},
@@ -31,6 +31,10 @@ pub struct ReviewRequest {
     /// Optional relative path to execute the agent in (relative to container_ref).
     #[serde(default)]
     pub working_dir: Option<String>,
+    /// Project and repository instructions merged into the prompt, resolved when
+    /// the process starts
+    #[serde(default)]
+    pub instructions: Option<String>,
 }
 
 impl ReviewRequest {
@@ -38,6 +42,11 @@ impl ReviewRequest {
         self.executor_profile_id.executor
     }
 
+    /// Prompt sent to the agent, including any instructions
+    pub fn effective_prompt(&self) -> String {
+        prompt_with_instructions(&self.prompt, self.instructions.as_deref())
+    }
+
     pub fn effective_dir(&self, current_dir: &Path) -> std::path::PathBuf {
         match &self.working_dir {
             Some(rel_path) => current_dir.join(rel_path),
@@ -72,7 +81,7 @@ impl Executable for ReviewRequest {
         agent
             .spawn_review(
                 &effective_dir,
-                &self.prompt,
+                &self.effective_prompt(),
                 self.session_id.as_deref(),
                 env,
             )
@@ -354,6 +354,9 @@ pub struct ClaudeLogProcessor {
     main_model_name: Option<String>,
     main_model_context_window: u32,
     context_tokens_used: u32,
+    // Tokens consumed by the whole run, as reported by the result message
+    input_tokens_used: Option<u64>,
+    output_tokens_used: Option<u64>,
 }
 
 impl ClaudeLogProcessor {
@@ -372,6 +375,8 @@ impl ClaudeLogProcessor {
             streaming_message_id: None,
             main_model_context_window: DEFAULT_CLAUDE_CONTEXT_WINDOW,
             context_tokens_used: 0,
+            input_tokens_used: None,
+            output_tokens_used: None,
         }
     }
 
@@ -1198,8 +1203,10 @@ impl ClaudeLogProcessor {
             ClaudeJson::Result {
                 is_error,
                 model_usage,
+                usage,
                 ..
             } => {
+                let mut usage_changed = false;
                 // get the real model context window and correct the context usage entry
                 if let Some(context_window) = model_usage.as_ref().and_then(|model_usage| {
                     self.main_model_name
@@ -1208,6 +1215,19 @@ impl ClaudeLogProcessor {
                         .and_then(|usage| usage.context_window)
                 }) {
                     self.main_model_context_window = context_window;
+                    usage_changed = true;
+                }
+                // the result's usage covers every request made during the run
+                if let Some(usage) = usage {
+                    self.input_tokens_used = Some(
+                        usage.input_tokens.unwrap_or(0)
+                            + usage.cache_creation_input_tokens.unwrap_or(0)
+                            + usage.cache_read_input_tokens.unwrap_or(0),
+                    );
+                    self.output_tokens_used = Some(usage.output_tokens.unwrap_or(0));
+                    usage_changed = true;
+                }
+                if usage_changed {
                     patches.push(self.add_token_usage_entry(entry_index_provider));
                 }
 
@@ -1385,6 +1405,8 @@ impl ClaudeLogProcessor {
             entry_type: NormalizedEntryType::TokenUsageInfo(crate::logs::TokenUsageInfo {
                 total_tokens: self.context_tokens_used,
                 model_context_window: self.main_model_context_window,
+                input_tokens: self.input_tokens_used,
+                output_tokens: self.output_tokens_used,
             }),
             content: format!(
                 "Tokens used: {} / Context window: {}",
@@ -2032,6 +2054,20 @@ mod tests {
         assert_eq!(entries.len(), 0); // Should be ignored like in old implementation
     }
 
+    #[test]
+    fn test_result_usage_reports_consumed_tokens() {
+        let result_json = r#"{"type":"result","subtype":"success","is_error":false,"usage":{"input_tokens":10,"cache_creation_input_tokens":200,"cache_read_input_tokens":3000,"output_tokens":45}}"#;
+        let parsed: ClaudeJson = serde_json::from_str(result_json).unwrap();
+
+        let entries = normalize(&parsed, "");
+        assert_eq!(entries.len(), 1);
+        let NormalizedEntryType::TokenUsageInfo(usage) = &entries[0].entry_type else {
+            panic!("expected a token usage entry");
+        };
+        assert_eq!(usage.input_tokens, Some(3210));
+        assert_eq!(usage.output_tokens, Some(45));
+    }
+
     #[test]
     fn test_thinking_content() {
         let thinking_json = r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"thinking","thinking":"Let me think about this..."}]}}"#;
@@ -215,6 +215,9 @@ struct LogState {
     mcp_tools: HashMap<String, McpToolState>,
     patches: HashMap<String, PatchState>,
     web_searches: HashMap<String, WebSearchState>,
+    /// Session-wide (input, output) token totals from before this execution,
+    /// so resumed sessions only count what this execution consumed
+    token_usage_baseline: Option<(i64, i64)>,
 }
 
 enum StreamingTextKind {
@@ -232,6 +235,7 @@ impl LogState {
             mcp_tools: HashMap::new(),
             patches: HashMap::new(),
             web_searches: HashMap::new(),
+            token_usage_baseline: None,
         }
     }
 
@@ -969,6 +973,12 @@ pub fn normalize_logs(msg_store: Arc<MsgStore>, worktree_path: &Path) {
                 }
                 EventMsg::TokenCount(payload) => {
                     if let Some(info) = payload.info {
+                        let total = &info.total_token_usage;
+                        let (input_baseline, output_baseline) =
+                            *state.token_usage_baseline.get_or_insert((
+                                total.input_tokens - info.last_token_usage.input_tokens,
+                                total.output_tokens - info.last_token_usage.output_tokens,
+                            ));
                         add_normalized_entry(
                             &msg_store,
                             &entry_index,
@@ -981,6 +991,12 @@ pub fn normalize_logs(msg_store: Arc<MsgStore>, worktree_path: &Path) {
                                             .model_context_window
                                             .unwrap_or_default()
                                             as u32,
+                                        input_tokens: Some(
+                                            (total.input_tokens - input_baseline).max(0) as u64,
+                                        ),
+                                        output_tokens: Some(
+                                            (total.output_tokens - output_baseline).max(0) as u64,
+                                        ),
                                     },
                                 ),
                                 content: format!(
@@ -108,6 +108,15 @@ pub enum CodingAgent {
     QaMock(QaMockExecutor),
 }
 
+impl BaseCodingAgent {
+    /// Whether a session started in one directory can be resumed from another.
+    /// Claude Code and Droid store sessions in per-directory folders, so a new
+    /// worktree can't find a session that began elsewhere.
+    pub fn resumes_across_directories(&self) -> bool {
+        !matches!(self, Self::ClaudeCode | Self::Droid)
+    }
+}
+
 impl CodingAgent {
     pub fn get_mcp_config(&self) -> McpConfig {
         match self {
@@ -371,4 +380,12 @@ mod tests {
         assert!(result.is_ok(), "CURSOR should deserialize via serde");
         assert_eq!(result.unwrap(), BaseCodingAgent::CursorAgent);
     }
+
+    #[test]
+    fn test_directory_scoped_sessions_do_not_resume_across_directories() {
+        assert!(!BaseCodingAgent::ClaudeCode.resumes_across_directories());
+        assert!(!BaseCodingAgent::Droid.resumes_across_directories());
+        assert!(BaseCodingAgent::Codex.resumes_across_directories());
+        assert!(BaseCodingAgent::Amp.resumes_across_directories());
+    }
 }
@@ -102,6 +102,15 @@ pub enum NormalizedEntryType {
 pub struct TokenUsageInfo {
     pub total_tokens: u32,
     pub model_context_window: u32,
+    /// Input tokens, cached ones included, consumed by the execution so far.
+    /// None when the executor does not report them.
+    #[serde(default)]
+    #[ts(type = "number | null")]
+    pub input_tokens: Option<u64>,
+    /// Output tokens generated by the execution so far
+    #[serde(default)]
+    #[ts(type = "number | null")]
+    pub output_tokens: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -2,7 +2,6 @@ use std::{
     collections::HashMap,
     io,
     path::{Path, PathBuf},
-    str::FromStr,
     sync::Arc,
     time::Duration,
 };
@@ -18,38 +17,46 @@ use db::{
             ExecutionContext, ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus,
         },
         execution_process_repo_state::ExecutionProcessRepoState,
+        execution_process_verification::{
+            CreateExecutionProcessVerification, ExecutionProcessVerification,
+        },
         repo::Repo,
         scratch::{DraftFollowUpData, Scratch, ScratchType},
         task::{Task, TaskStatus},
         workspace::Workspace,
+        workspace_disk_usage::WorkspaceDiskUsage,
         workspace_repo::WorkspaceRepo,
+        worktree_pool_entry::WorktreePoolEntry,
     },
 };
 use deployment::DeploymentError;
 use executors::{
-    actions::{
-        Executable, ExecutorAction, ExecutorActionType,
-        coding_agent_follow_up::CodingAgentFollowUpRequest,
-        coding_agent_initial::CodingAgentInitialRequest,
-    },
+    actions::{Executable, ExecutorAction, ExecutorActionType, script::ScriptContext},
     approvals::{ExecutorApprovalService, NoopExecutorApprovalService},
     env::{ExecutionEnv, RepoContext},
     executors::{BaseCodingAgent, ExecutorExitResult, ExecutorExitSignal, InterruptSender},
-    logs::{NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch},
-    profile::ExecutorProfileId,
+    logs::{
+        NormalizedEntryType, TokenUsageInfo, utils::patch::extract_normalized_entry_from_patch,
+    },
 };
-use futures::{FutureExt, TryStreamExt, stream::select};
+use futures::{FutureExt, StreamExt, TryStreamExt, stream::select};
 use serde_json::json;
 use services::services::{
     analytics::AnalyticsContext,
     approvals::{Approvals, executor_approvals::ExecutorApprovalBridge},
     config::Config,
-    container::{ContainerError, ContainerRef, ContainerService},
+    container::{
+        ContainerError, ContainerRef, ContainerService, FinishedConversationCache,
+        finished_conversation_cache,
+    },
     diff_stream::{self, DiffStreamHandle},
     git::{GitCli, GitService},
     image::ImageService,
+    mcp_tokens::McpTokenService,
     notification::NotificationService,
+    preview::PreviewService,
     queued_message::QueuedMessageService,
+    secrets::SecretsService,
     workspace_manager::{RepoWorkspaceInput, WorkspaceManager},
 };
 use tokio::{sync::RwLock, task::JoinHandle};
@@ -61,7 +68,7 @@ use utils::{
 };
 use uuid::Uuid;
 
-use crate::{command, copy};
+use crate::{command, copy, verify, worktree_pool::WorktreePool};
 
 #[derive(Clone)]
 pub struct LocalContainerService {
@@ -69,6 +76,7 @@ pub struct LocalContainerService {
     child_store: Arc<RwLock<HashMap<Uuid, Arc<RwLock<AsyncGroupChild>>>>>,
     interrupt_senders: Arc<RwLock<HashMap<Uuid, InterruptSender>>>,
     msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
+    finished_conversations: FinishedConversationCache,
     config: Arc<RwLock<Config>>,
     git: GitService,
     image_service: ImageService,
@@ -76,6 +84,12 @@ pub struct LocalContainerService {
     approvals: Approvals,
     queued_message_service: QueuedMessageService,
     notification_service: NotificationService,
+    preview: PreviewService,
+    mcp_tokens: McpTokenService,
+    secrets: SecretsService,
+    /// copy_files progress per workspace, replayed at the top of its next setup script log
+    copy_logs: Arc<RwLock<HashMap<Uuid, Vec<String>>>>,
+    worktree_pool: WorktreePool,
 }
 
 impl LocalContainerService {
@@ -89,16 +103,21 @@ impl LocalContainerService {
         analytics: Option<AnalyticsContext>,
         approvals: Approvals,
         queued_message_service: QueuedMessageService,
+        preview: PreviewService,
+        mcp_tokens: McpTokenService,
+        secrets: SecretsService,
     ) -> Self {
         let child_store = Arc::new(RwLock::new(HashMap::new()));
         let interrupt_senders = Arc::new(RwLock::new(HashMap::new()));
         let notification_service = NotificationService::new(config.clone());
+        let worktree_pool = WorktreePool::new(db.clone(), git.clone());
 
         let container = LocalContainerService {
             db,
             child_store,
             interrupt_senders,
             msg_stores,
+            finished_conversations: finished_conversation_cache(),
             config,
             git,
             image_service,
@@ -106,9 +125,15 @@ impl LocalContainerService {
             approvals,
             queued_message_service,
             notification_service,
+            preview,
+            mcp_tokens,
+            secrets,
+            copy_logs: Arc::new(RwLock::new(HashMap::new())),
+            worktree_pool,
         };
 
         container.spawn_workspace_cleanup();
+        container.worktree_pool.spawn();
 
         container
     }
@@ -172,6 +197,7 @@ impl LocalContainerService {
 
         // Clear container_ref so this workspace won't be picked up again
         let _ = Workspace::clear_container_ref(&db.pool, workspace.id).await;
+        let _ = WorkspaceDiskUsage::delete(&db.pool, workspace.id).await;
     }
 
     pub async fn cleanup_expired_workspaces(db: &DBService) -> Result<(), DeploymentError> {
@@ -192,6 +218,7 @@ impl LocalContainerService {
 
     pub fn spawn_workspace_cleanup(&self) {
         let db = self.db.clone();
+        let container = self.clone();
         let cleanup_expired = Self::cleanup_expired_workspaces;
         tokio::spawn(async move {
             WorkspaceManager::cleanup_orphan_workspaces(&db.pool).await;
@@ -204,6 +231,16 @@ impl LocalContainerService {
                 cleanup_expired(&db).await.unwrap_or_else(|e| {
                     tracing::error!("Failed to clean up expired workspaces: {}", e)
                 });
+
+                let policy = container.config.read().await.workspace_disk.clone();
+                match container.enforce_disk_policy(&policy).await {
+                    Ok(evictions) if !evictions.is_empty() => tracing::info!(
+                        "Disk policy evicted data from {} workspaces",
+                        evictions.len()
+                    ),
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("Failed to enforce workspace disk policy: {}", e),
+                }
             }
         });
     }
@@ -336,6 +373,283 @@ impl LocalContainerService {
         Ok(false)
     }
 
+    /// Start the verify scripts of every repo that has one after a successful
+    /// coding agent turn. They run as their own execution processes, chained
+    /// in front of `next_action`. Returns true if verification was started, in
+    /// which case the caller must not continue the normal completion flow.
+    async fn start_verify_scripts(
+        &self,
+        ctx: &ExecutionContext,
+        next_action: Option<ExecutorAction>,
+    ) -> bool {
+        let Some(action) = self.verify_actions_for_repos(&ctx.repos, next_action) else {
+            return false;
+        };
+
+        match self
+            .start_execution(
+                &ctx.workspace,
+                &ctx.session,
+                &action,
+                &ExecutionProcessRunReason::VerifyScript,
+            )
+            .await
+        {
+            Ok(_) => true,
+            Err(e) => {
+                tracing::error!("Failed to start verify script: {}", e);
+                self.record_unstarted_verify_scripts(ctx, &action, &e.to_string())
+                    .await;
+                false
+            }
+        }
+    }
+
+    /// The coding agent turn the verify scripts in this session check, with
+    /// the attempt number of the current round of verification
+    async fn verified_turn(&self, ctx: &ExecutionContext) -> Option<(ExecutionProcess, i64)> {
+        let pool = &self.db.pool;
+        let verified = match ExecutionProcess::find_latest_by_session_and_run_reason(
+            pool,
+            ctx.session.id,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await
+        {
+            Ok(Some(process)) => process,
+            Ok(None) => {
+                tracing::error!(
+                    "No coding agent turn to attach verify results to in session {}",
+                    ctx.session.id
+                );
+                return None;
+            }
+            Err(e) => {
+                tracing::error!("Failed to load verified coding agent turn: {}", e);
+                return None;
+            }
+        };
+        let attempt =
+            match ExecutionProcessVerification::find_attempt_for_retry(pool, verified.id).await {
+                Ok(previous) => previous.unwrap_or(0) + 1,
+                Err(e) => {
+                    tracing::error!("Failed to load previous verification attempt: {}", e);
+                    1
+                }
+            };
+        Some((verified, attempt))
+    }
+
+    /// Record the verify scripts at the front of `action` as failed when they
+    /// could not be started, so the task stays in progress
+    async fn record_unstarted_verify_scripts(
+        &self,
+        ctx: &ExecutionContext,
+        action: &ExecutorAction,
+        error: &str,
+    ) {
+        let Some((verified, attempt)) = self.verified_turn(ctx).await else {
+            return;
+        };
+        let repos = verify::verify_script_repos(action, &ctx.repos);
+        if let Err(e) = verify::record_unstarted_verify_scripts(
+            &self.db.pool,
+            verified.id,
+            &repos,
+            attempt,
+            error,
+        )
+        .await
+        {
+            tracing::error!("Failed to store verification result: {}", e);
+        }
+    }
+
+    /// Record the result of a finished verify script against the coding agent
+    /// turn it checked. Once every repo has been verified, failures are handed
+    /// back to the agent as a follow-up while retries remain; otherwise the
+    /// rest of the action chain continues. A killed script is recorded as
+    /// failed and ends verification. Returns true if another process was
+    /// started, in which case the caller must not finalize.
+    async fn finish_verify_script(&self, ctx: &ExecutionContext, exit_code: Option<i64>) -> bool {
+        let pool = &self.db.pool;
+        let action = match ctx.execution_process.executor_action() {
+            Ok(action) => action,
+            Err(e) => {
+                tracing::error!("Failed to load verify script action: {}", e);
+                return false;
+            }
+        };
+        let ExecutorActionType::ScriptRequest(request) = action.typ() else {
+            return false;
+        };
+        let Some(repo) = ctx
+            .repos
+            .iter()
+            .find(|r| request.working_dir.as_deref() == Some(r.name.as_str()))
+        else {
+            tracing::error!(
+                "Verify script {} does not belong to a workspace repo",
+                ctx.execution_process.id
+            );
+            return false;
+        };
+
+        let Some((verified, attempt)) = self.verified_turn(ctx).await else {
+            return false;
+        };
+
+        let history = match self.get_msg_store_by_id(&ctx.execution_process.id).await {
+            Some(store) => store.get_history(),
+            None => Vec::new(),
+        };
+        let data = CreateExecutionProcessVerification {
+            repo_id: repo.id,
+            passed: verify::verify_passed(&ctx.execution_process.status, exit_code),
+            exit_code,
+            output: verify::collect_verify_output(&history),
+            attempt,
+            verify_execution_process_id: Some(ctx.execution_process.id),
+        };
+        tracing::info!(
+            "Verify script for repo '{}' in workspace {} {} (attempt {})",
+            repo.name,
+            ctx.workspace.id,
+            if data.passed { "passed" } else { "failed" },
+            attempt
+        );
+        if let Err(e) = ExecutionProcessVerification::create(pool, verified.id, &data).await {
+            tracing::error!("Failed to store verification result: {}", e);
+        }
+
+        // Stopping a verify script stops the rest of verification with it
+        if matches!(ctx.execution_process.status, ExecutionProcessStatus::Killed) {
+            return false;
+        }
+
+        // More repos to verify in this round
+        if let Some(next) = action.next_action().filter(|next| {
+            matches!(
+                next.typ(),
+                ExecutorActionType::ScriptRequest(request)
+                    if request.context == ScriptContext::VerifyScript
+            )
+        }) {
+            return match self.try_start_next_action(ctx).await {
+                Ok(()) => true,
+                Err(e) => {
+                    tracing::error!("Failed to start verify script: {}", e);
+                    self.record_unstarted_verify_scripts(ctx, next, &e.to_string())
+                        .await;
+                    false
+                }
+            };
+        }
+
+        let results =
+            match ExecutionProcessVerification::find_by_execution_process_id(pool, verified.id)
+                .await
+            {
+                Ok(results) => results,
+                Err(e) => {
+                    tracing::error!("Failed to load verification results: {}", e);
+                    Vec::new()
+                }
+            };
+        let failures: Vec<(&Repo, &ExecutionProcessVerification)> = results
+            .iter()
+            .filter(|result| result.attempt == attempt && !result.passed)
+            .filter_map(|result| {
+                ctx.repos
+                    .iter()
+                    .find(|r| r.id == result.repo_id)
+                    .map(|repo| (repo, result))
+            })
+            .collect();
+
+        // Every failing repo allows as many retries as the strictest one
+        let max_retries = failures
+            .iter()
+            .map(|(repo, _)| repo.verify_max_retries)
+            .min()
+            .unwrap_or(0);
+        if failures.is_empty() || attempt > max_retries {
+            if action.next_action().is_some() {
+                return self.start_next_after_verify(ctx).await;
+            }
+            return false;
+        }
+
+        let prompt = verify::build_verify_retry_prompt(
+            &failures
+                .iter()
+                .map(|(repo, result)| {
+                    (
+                        repo.name.as_str(),
+                        repo.verify_script.as_deref().unwrap_or_default(),
+                        result.exit_code,
+                        result.output.as_str(),
+                    )
+                })
+                .collect::<Vec<_>>(),
+        );
+        let variant = match verified.executor_action() {
+            Ok(action) => match action.typ() {
+                ExecutorActionType::CodingAgentInitialRequest(req) => {
+                    req.executor_profile_id.variant.clone()
+                }
+                ExecutorActionType::CodingAgentFollowUpRequest(req) => {
+                    req.executor_profile_id.variant.clone()
+                }
+                _ => None,
+            },
+            Err(_) => None,
+        };
+
+        match self
+            .start_follow_up(
+                &ctx.workspace,
+                &ctx.session,
+                &DraftFollowUpData {
+                    message: prompt,
+                    variant,
+                },
+            )
+            .await
+        {
+            Ok(retry) => {
+                tracing::info!(
+                    "Verification failed for workspace {}, retry follow-up started",
+                    ctx.workspace.id
+                );
+                if let Err(e) = ExecutionProcessVerification::set_retry_execution_process_id(
+                    pool,
+                    verified.id,
+                    retry.id,
+                )
+                .await
+                {
+                    tracing::error!("Failed to link verification retry: {}", e);
+                }
+                true
+            }
+            Err(e) => {
+                tracing::error!("Failed to start verification retry follow-up: {}", e);
+                false
+            }
+        }
+    }
+
+    async fn start_next_after_verify(&self, ctx: &ExecutionContext) -> bool {
+        match self.try_start_next_action(ctx).await {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::error!("Failed to start next action after verify script: {}", e);
+                false
+            }
+        }
+    }
+
     /// Commit changes to each repo. Logs failures but continues with other repos.
     fn commit_repos(&self, repos_with_changes: Vec<(Repo, PathBuf)>, message: &str) -> bool {
         let mut any_committed = false;
@@ -454,7 +768,20 @@ impl LocalContainerService {
                     ExecutionProcessStatus::Running
                 );
 
-                if success || cleanup_done {
+                // Verification takes over the chain until it has decided what runs next
+                let mut verify_in_progress = false;
+
+                let verify_done = matches!(
+                    ctx.execution_process.run_reason,
+                    ExecutionProcessRunReason::VerifyScript
+                ) && !matches!(
+                    ctx.execution_process.status,
+                    ExecutionProcessStatus::Running
+                );
+
+                if verify_done {
+                    verify_in_progress = container.finish_verify_script(&ctx, exit_code).await;
+                } else if success || cleanup_done {
                     // Commit changes (if any) and get feedback about whether changes were made
                     let changes_committed = match container.try_commit_changes(&ctx).await {
                         Ok(committed) => committed,
@@ -479,7 +806,25 @@ impl LocalContainerService {
                         true
                     };
 
-                    if should_start_next {
+                    if matches!(
+                        ctx.execution_process.run_reason,
+                        ExecutionProcessRunReason::CodingAgent
+                    ) {
+                        // Verify scripts run before the rest of the chain, which is
+                        // only kept when the cleanup scripts would have run anyway
+                        let next_action = ctx
+                            .execution_process
+                            .executor_action()
+                            .ok()
+                            .and_then(|action| action.next_action().cloned())
+                            .filter(|_| should_start_next);
+                        verify_in_progress =
+                            container.start_verify_scripts(&ctx, next_action).await;
+                    }
+
+                    if verify_in_progress {
+                        tracing::info!("Verify scripts started for workspace {}", ctx.workspace.id);
+                    } else if should_start_next {
                         // If the process exited successfully, start the next action
                         if let Err(e) = container.try_start_next_action(&ctx).await {
                             tracing::error!("Failed to start next action after completion: {}", e);
@@ -495,13 +840,15 @@ impl LocalContainerService {
                     }
                 }
 
-                if container.should_finalize(&ctx) {
+                if !verify_in_progress && container.should_finalize(&ctx) {
                     // Only execute queued messages if the execution succeeded
-                    // If it failed or was killed, just clear the queue and finalize
-                    let should_execute_queued = !matches!(
-                        ctx.execution_process.status,
-                        ExecutionProcessStatus::Failed | ExecutionProcessStatus::Killed
-                    );
+                    // If it failed or was killed, just clear the queue and finalize.
+                    // A failing verify script leaves the agent's turn itself intact.
+                    let should_execute_queued = match ctx.execution_process.status {
+                        ExecutionProcessStatus::Killed => false,
+                        ExecutionProcessStatus::Failed => verify_done,
+                        _ => true,
+                    };
 
                     if let Some(queued_msg) =
                         container.queued_message_service.take_queued(ctx.session.id)
@@ -528,7 +875,7 @@ impl LocalContainerService {
 
                             // Execute the queued follow-up
                             if let Err(e) = container
-                                .start_queued_follow_up(&ctx, &queued_msg.data)
+                                .start_follow_up(&ctx.workspace, &ctx.session, &queued_msg.data)
                                 .await
                             {
                                 tracing::error!("Failed to start queued follow-up: {}", e);
@@ -588,6 +935,14 @@ impl LocalContainerService {
 
             // Cleanup child handle
             child_store.write().await.remove(&exec_id);
+
+            // The process has stopped writing to the workspace; re-measure it
+            if let Ok(ctx) = ExecutionProcess::load_context(&db.pool, exec_id).await {
+                let artifact_dirs = config.read().await.workspace_disk.artifact_dirs.clone();
+                container
+                    .refresh_disk_usage(&ctx.workspace, &artifact_dirs)
+                    .await;
+            }
         })
     }
 
@@ -628,13 +983,55 @@ impl LocalContainerService {
         rx
     }
 
+    /// Run copy_files off the async runtime, returning its progress lines
+    async fn copy_project_files_with_progress(
+        &self,
+        source_dir: &Path,
+        target_dir: &Path,
+        copy_files: &str,
+    ) -> Result<Vec<String>, ContainerError> {
+        let source_dir = source_dir.to_path_buf();
+        let target_dir = target_dir.to_path_buf();
+        let copy_files = copy_files.to_string();
+
+        tokio::time::timeout(
+            std::time::Duration::from_secs(30),
+            tokio::task::spawn_blocking(move || {
+                let mut lines = Vec::new();
+                copy::copy_project_files_with_progress(
+                    &source_dir,
+                    &target_dir,
+                    &copy_files,
+                    &mut |line| {
+                        tracing::info!("copy_files {}: {}", target_dir.display(), line);
+                        lines.push(line);
+                    },
+                )?;
+                Ok(lines)
+            }),
+        )
+        .await
+        .map_err(|_| ContainerError::Other(anyhow!("Copy project files timed out after 30s")))?
+        .map_err(|e| ContainerError::Other(anyhow!("Copy files task failed: {e}")))?
+    }
+
     pub fn dir_name_from_workspace(workspace_id: &Uuid, task_title: &str) -> String {
         let task_title_id = git_branch_id(task_title);
         format!("{}-{}", short_uuid(workspace_id), task_title_id)
     }
 
-    async fn track_child_msgs_in_store(&self, id: Uuid, child: &mut AsyncGroupChild) {
+    async fn track_child_msgs_in_store(
+        &self,
+        id: Uuid,
+        child: &mut AsyncGroupChild,
+        redactions: Vec<String>,
+        preamble: Vec<String>,
+    ) {
         let store = Arc::new(MsgStore::new());
+        store.add_redactions(redactions);
+        for line in preamble {
+            store.push_stdout(format!("{line}\n"));
+        }
 
         let out = child.inner().stdout.take().expect("no stdout");
         let err = child.inner().stderr.take().expect("no stderr");
@@ -657,6 +1054,32 @@ impl LocalContainerService {
         map.insert(id, store);
     }
 
+    /// Watch dev-server output until it reports the URL it is listening on
+    async fn spawn_preview_url_detector(&self, workspace_id: Uuid, exec_id: Uuid) {
+        let Some(store) = self.msg_stores.read().await.get(&exec_id).cloned() else {
+            return;
+        };
+        let preview = self.preview.clone();
+        tokio::spawn(async move {
+            let mut stream = store.history_plus_stream();
+            while let Some(Ok(msg)) = stream.next().await {
+                match msg {
+                    LogMsg::Stdout(chunk) | LogMsg::Stderr(chunk) => {
+                        preview.record_output(workspace_id, &chunk);
+                        if preview
+                            .get(workspace_id)
+                            .is_none_or(|target| target.detected_url.is_some())
+                        {
+                            break;
+                        }
+                    }
+                    LogMsg::Finished => break,
+                    _ => {}
+                }
+            }
+        });
+    }
+
     /// Create a live diff log stream for ongoing attempts for WebSocket
     /// Returns a stream that owns the filesystem watcher - when dropped, watcher is cleaned up
     async fn create_live_diff_stream(
@@ -699,6 +1122,22 @@ impl LocalContainerService {
         None
     }
 
+    /// Extract the token usage last reported in the MsgStore history.
+    /// Waits for the lock so a busy store doesn't silently drop the reading.
+    async fn extract_last_token_usage(&self, exec_id: &Uuid) -> Option<TokenUsageInfo> {
+        let msg_store = self.msg_stores.read().await.get(exec_id).cloned()?;
+
+        msg_store.get_history().iter().rev().find_map(|msg| {
+            let LogMsg::JsonPatch(patch) = msg else {
+                return None;
+            };
+            match extract_normalized_entry_from_patch(patch)?.1.entry_type {
+                NormalizedEntryType::TokenUsageInfo(usage) => Some(usage),
+                _ => None,
+            }
+        })
+    }
+
     /// Update the coding agent turn summary with the final assistant message
     async fn update_executor_session_summary(&self, exec_id: &Uuid) -> Result<(), anyhow::Error> {
         // Check if there's a coding agent turn for this execution process
@@ -713,6 +1152,24 @@ impl LocalContainerService {
                     tracing::debug!("No assistant message found for execution {}", exec_id);
                 }
             }
+
+            if let Some(usage) = self.extract_last_token_usage(exec_id).await {
+                CodingAgentTurn::update_context_tokens(
+                    &self.db.pool,
+                    *exec_id,
+                    i64::from(usage.total_tokens),
+                )
+                .await?;
+                if usage.input_tokens.is_some() || usage.output_tokens.is_some() {
+                    CodingAgentTurn::update_consumed_tokens(
+                        &self.db.pool,
+                        *exec_id,
+                        usage.input_tokens.and_then(|t| i64::try_from(t).ok()),
+                        usage.output_tokens.and_then(|t| i64::try_from(t).ok()),
+                    )
+                    .await?;
+                }
+            }
         }
 
         Ok(())
@@ -727,22 +1184,39 @@ impl LocalContainerService {
     ) -> Result<(), ContainerError> {
         let repos = WorkspaceRepo::find_repos_with_copy_files(&self.db.pool, workspace.id).await?;
 
+        let mut copy_log = Vec::new();
         for repo in &repos {
             if let Some(copy_files) = &repo.copy_files
                 && !copy_files.trim().is_empty()
             {
                 let worktree_path = workspace_dir.join(&repo.name);
-                self.copy_project_files(&repo.path, &worktree_path, copy_files)
+                match self
+                    .copy_project_files_with_progress(&repo.path, &worktree_path, copy_files)
                     .await
-                    .unwrap_or_else(|e| {
+                {
+                    Ok(lines) => copy_log.extend(
+                        lines
+                            .into_iter()
+                            .map(|line| format!("[copy_files] {}/{}", repo.name, line)),
+                    ),
+                    Err(e) => {
                         tracing::warn!(
                             "Failed to copy project files for repo '{}': {}",
                             repo.name,
                             e
                         );
-                    });
+                    }
+                }
             }
         }
+        if !copy_log.is_empty() {
+            self.copy_logs
+                .write()
+                .await
+                .entry(workspace.id)
+                .or_default()
+                .extend(copy_log);
+        }
 
         if let Err(e) = self
             .image_service
@@ -814,84 +1288,6 @@ impl LocalContainerService {
 
         Ok(())
     }
-
-    /// Start a follow-up execution from a queued message
-    async fn start_queued_follow_up(
-        &self,
-        ctx: &ExecutionContext,
-        queued_data: &DraftFollowUpData,
-    ) -> Result<ExecutionProcess, ContainerError> {
-        // Get executor from the latest CodingAgent process, or fall back to session's executor
-        let base_executor = match ExecutionProcess::latest_executor_profile_for_session(
-            &self.db.pool,
-            ctx.session.id,
-        )
-        .await
-        .map_err(|e| ContainerError::Other(anyhow!("Failed to get executor profile: {e}")))?
-        {
-            Some(profile) => profile.executor,
-            None => {
-                // No prior execution - use session's executor field
-                let executor_str = ctx.session.executor.as_ref().ok_or_else(|| {
-                    ContainerError::Other(anyhow!(
-                        "No prior execution and no executor configured on session"
-                    ))
-                })?;
-                BaseCodingAgent::from_str(&executor_str.replace('-', "_").to_ascii_uppercase())
-                    .map_err(|_| {
-                        ContainerError::Other(anyhow!("Invalid executor: {}", executor_str))
-                    })?
-            }
-        };
-
-        let executor_profile_id = ExecutorProfileId {
-            executor: base_executor,
-            variant: queued_data.variant.clone(),
-        };
-
-        // Get latest agent session ID for session continuity (from coding agent turns)
-        let latest_agent_session_id = ExecutionProcess::find_latest_coding_agent_turn_session_id(
-            &self.db.pool,
-            ctx.session.id,
-        )
-        .await?;
-
-        let repos =
-            WorkspaceRepo::find_repos_for_workspace(&self.db.pool, ctx.workspace.id).await?;
-        let cleanup_action = self.cleanup_actions_for_repos(&repos);
-
-        let working_dir = ctx
-            .workspace
-            .agent_working_dir
-            .as_ref()
-            .filter(|dir| !dir.is_empty())
-            .cloned();
-
-        let action_type = if let Some(agent_session_id) = latest_agent_session_id {
-            ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
-                prompt: queued_data.message.clone(),
-                session_id: agent_session_id,
-                executor_profile_id: executor_profile_id.clone(),
-                working_dir: working_dir.clone(),
-            })
-        } else {
-            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
-                prompt: queued_data.message.clone(),
-                executor_profile_id: executor_profile_id.clone(),
-                working_dir,
-            })
-        };
-
-        let action = ExecutorAction::new(action_type, cleanup_action.map(Box::new));
-
-        self.start_execution(
-            &ctx.workspace,
-            &ctx.session,
-            &action,
-            &ExecutionProcessRunReason::CodingAgent,
-        )
-        .await
-    }
 }
 
 fn failure_exit_status() -> std::process::ExitStatus {
@@ -913,6 +1309,10 @@ impl ContainerService for LocalContainerService {
         &self.msg_stores
     }
 
+    fn finished_conversations(&self) -> &FinishedConversationCache {
+        &self.finished_conversations
+    }
+
     fn db(&self) -> &DBService {
         &self.db
     }
@@ -967,35 +1367,60 @@ impl ContainerService for LocalContainerService {
             })
             .collect();
 
-        let created_workspace = WorkspaceManager::create_workspace(
-            &workspace_dir,
-            &workspace_inputs,
-            &workspace.branch,
-        )
-        .await?;
+        // Take pre-warmed worktrees where available and create the rest
+        tokio::fs::create_dir_all(&workspace_dir).await?;
+        let mut pooled = Vec::new();
+        let mut remaining = Vec::new();
+        for input in workspace_inputs {
+            let claimed = self
+                .worktree_pool
+                .claim(
+                    &input.repo,
+                    &input.target_branch,
+                    &workspace.branch,
+                    &workspace_dir.join(&input.repo.name),
+                    workspace.id,
+                )
+                .await;
+            if claimed {
+                pooled.push(input.repo);
+            } else {
+                remaining.push(input);
+            }
+        }
+
+        if !remaining.is_empty()
+            && let Err(e) =
+                WorkspaceManager::create_workspace(&workspace_dir, &remaining, &workspace.branch)
+                    .await
+        {
+            if !pooled.is_empty() {
+                let _ = WorkspaceManager::cleanup_workspace(&workspace_dir, &pooled).await;
+                let _ = WorktreePoolEntry::delete_claimed(&self.db.pool, workspace.id).await;
+            }
+            return Err(e.into());
+        }
 
         // Copy project files and images to workspace
-        self.copy_files_and_images(&created_workspace.workspace_dir, workspace)
+        self.copy_files_and_images(&workspace_dir, workspace)
             .await?;
 
-        Self::create_workspace_config_files(&created_workspace.workspace_dir, &repositories)
-            .await?;
+        Self::create_workspace_config_files(&workspace_dir, &repositories).await?;
 
         Workspace::update_container_ref(
             &self.db.pool,
             workspace.id,
-            &created_workspace.workspace_dir.to_string_lossy(),
+            &workspace_dir.to_string_lossy(),
         )
         .await?;
 
-        Ok(created_workspace
-            .workspace_dir
-            .to_string_lossy()
-            .to_string())
+        Ok(workspace_dir.to_string_lossy().to_string())
     }
 
     async fn delete(&self, workspace: &Workspace) -> Result<(), ContainerError> {
         self.try_stop(workspace, true).await;
+        self.preview.release(workspace.id);
+        self.mcp_tokens.revoke(workspace.id);
         Self::cleanup_workspace(&self.db, workspace).await;
         Ok(())
     }
@@ -1127,6 +1552,57 @@ impl ContainerService for LocalContainerService {
         env.insert("VK_WORKSPACE_ID", workspace.id.to_string());
         env.insert("VK_WORKSPACE_BRANCH", &workspace.branch);
 
+        // Project secrets for this kind of process; their values are masked in the logs
+        let repo_ids: Vec<Uuid> = repos.iter().map(|r| r.id).collect();
+        let secrets = self
+            .secrets
+            .env_for_execution(
+                &self.db.pool,
+                project.id,
+                &repo_ids,
+                &execution_process.run_reason,
+            )
+            .await
+            .map_err(|e| ContainerError::Other(anyhow!("Failed to load project secrets: {e}")))?;
+        for (name, value) in &secrets {
+            env.insert(name, value);
+        }
+
+        // Lets the coding agent, and the vibe_kanban MCP server it launches, reach
+        // this server as this workspace. Scripts never talk to MCP, so they don't
+        // get the token.
+        if matches!(
+            execution_process.run_reason,
+            ExecutionProcessRunReason::CodingAgent
+        ) && let (Some(server_url), Some(mcp_url)) =
+            (self.mcp_tokens.server_url(), self.mcp_tokens.mcp_url())
+        {
+            env.insert("VIBE_BACKEND_URL", server_url);
+            env.insert("VK_MCP_URL", mcp_url);
+            env.insert(
+                "VK_MCP_TOKEN",
+                self.mcp_tokens.token_for_workspace(workspace.id),
+            );
+        }
+
+        // Give each workspace's dev server its own port so several can run at once
+        let is_dev_server = matches!(
+            execution_process.run_reason,
+            ExecutionProcessRunReason::DevServer
+        );
+        let port_reservation = if is_dev_server {
+            let reservation = self.preview.allocate_port(workspace.id).map_err(|e| {
+                ContainerError::Other(anyhow!("Failed to allocate dev server port: {e}"))
+            })?;
+            env.insert("PORT", reservation.port().to_string());
+            env.insert("VK_PREVIEW_PORT", reservation.port().to_string());
+            Some(reservation)
+        } else {
+            None
+        };
+        // Free the port only now so the dev server can bind it
+        drop(port_reservation);
+
         // Create the child and stream, add to execution tracker with timeout
         let mut spawned = tokio::time::timeout(
             Duration::from_secs(30),
@@ -1139,8 +1615,31 @@ impl ContainerService for LocalContainerService {
             ))
         })??;
 
-        self.track_child_msgs_in_store(execution_process.id, &mut spawned.child)
-            .await;
+        let preamble = if matches!(
+            execution_process.run_reason,
+            ExecutionProcessRunReason::SetupScript
+        ) {
+            self.copy_logs
+                .write()
+                .await
+                .remove(&workspace.id)
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        self.track_child_msgs_in_store(
+            execution_process.id,
+            &mut spawned.child,
+            secrets.into_iter().map(|(_, value)| value).collect(),
+            preamble,
+        )
+        .await;
+
+        if is_dev_server {
+            self.spawn_preview_url_detector(workspace.id, execution_process.id)
+                .await;
+        }
 
         self.add_child_to_store(execution_process.id, spawned.child)
             .await;
@@ -1358,19 +1857,9 @@ impl ContainerService for LocalContainerService {
         target_dir: &Path,
         copy_files: &str,
     ) -> Result<(), ContainerError> {
-        let source_dir = source_dir.to_path_buf();
-        let target_dir = target_dir.to_path_buf();
-        let copy_files = copy_files.to_string();
-
-        tokio::time::timeout(
-            std::time::Duration::from_secs(30),
-            tokio::task::spawn_blocking(move || {
-                copy::copy_project_files_impl(&source_dir, &target_dir, &copy_files)
-            }),
-        )
-        .await
-        .map_err(|_| ContainerError::Other(anyhow!("Copy project files timed out after 30s")))?
-        .map_err(|e| ContainerError::Other(anyhow!("Copy files task failed: {e}")))?
+        self.copy_project_files_with_progress(source_dir, target_dir, copy_files)
+            .await
+            .map(|_| ())
     }
 
     async fn kill_all_running_processes(&self) -> Result<(), ContainerError> {
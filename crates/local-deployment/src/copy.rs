@@ -1,85 +1,263 @@
 use std::{
     collections::HashSet,
-    fs,
+    fs, io,
     path::{Path, PathBuf},
+    time::Instant,
 };
 
 use anyhow::anyhow;
 use globwalk::GlobWalkerBuilder;
 use services::services::container::ContainerError;
 
+/// Emit a progress line every this many files while walking a pattern
+const PROGRESS_EVERY_FILES: u64 = 2000;
+
+/// How a pattern's files are materialized in the worktree, selected with a
+/// `mode:` prefix, e.g. `symlink:node_modules, reflink:.venv, .env`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CopyMode {
+    Copy,
+    /// Copy-on-write clone (btrfs, XFS, APFS), falling back to a copy
+    Reflink,
+    /// Hard link, falling back to a copy across filesystems
+    Hardlink,
+    /// Link back to the original; a matched directory is linked as a whole
+    Symlink,
+}
+
+impl CopyMode {
+    fn as_str(self) -> &'static str {
+        match self {
+            CopyMode::Copy => "copy",
+            CopyMode::Reflink => "reflink",
+            CopyMode::Hardlink => "hardlink",
+            CopyMode::Symlink => "symlink",
+        }
+    }
+}
+
+/// Split an optional `mode:` prefix off a pattern. Unknown prefixes are kept
+/// as part of the pattern.
+fn parse_pattern(pattern: &str) -> (CopyMode, &str) {
+    if let Some((prefix, rest)) = pattern.split_once(':') {
+        let mode = match prefix.trim().to_ascii_lowercase().as_str() {
+            "copy" => Some(CopyMode::Copy),
+            "reflink" | "cow" => Some(CopyMode::Reflink),
+            "hardlink" => Some(CopyMode::Hardlink),
+            "symlink" => Some(CopyMode::Symlink),
+            _ => None,
+        };
+        if let Some(mode) = mode {
+            return (mode, rest.trim());
+        }
+    }
+    (CopyMode::Copy, pattern)
+}
+
 /// Normalize pattern for cross-platform glob matching (convert backslashes to forward slashes)
 fn normalize_pattern(pattern: &str) -> String {
     pattern.replace('\\', "/")
 }
 
+/// What actually happened to a file; links fall back to copies when the
+/// filesystem refuses them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placed {
+    Copied,
+    Reflinked,
+    Hardlinked,
+    Symlinked,
+}
+
+#[derive(Debug, Default)]
+struct PatternStats {
+    files: u64,
+    bytes: u64,
+    copied: u64,
+    reflinked: u64,
+    hardlinked: u64,
+    symlinked: u64,
+}
+
+impl PatternStats {
+    fn record(&mut self, placed: Placed, bytes: u64) {
+        self.files += 1;
+        self.bytes += bytes;
+        match placed {
+            Placed::Copied => self.copied += 1,
+            Placed::Reflinked => self.reflinked += 1,
+            Placed::Hardlinked => self.hardlinked += 1,
+            Placed::Symlinked => self.symlinked += 1,
+        }
+    }
+
+    fn summary(&self) -> String {
+        let parts: Vec<String> = [
+            ("copied", self.copied),
+            ("reflinked", self.reflinked),
+            ("hardlinked", self.hardlinked),
+            ("symlinked", self.symlinked),
+        ]
+        .iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(label, count)| format!("{count} {label}"))
+        .collect();
+        format!("{} ({})", parts.join(", "), format_bytes(self.bytes))
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
 /// Copy project files from source to target directory based on glob patterns.
 /// Skips files that already exist at target with same size.
 pub(crate) fn copy_project_files_impl(
     source_dir: &Path,
     target_dir: &Path,
     copy_files: &str,
+) -> Result<(), ContainerError> {
+    copy_project_files_with_progress(source_dir, target_dir, copy_files, &mut |_| {})
+}
+
+/// Like `copy_project_files_impl`, reporting progress and a per-pattern
+/// summary through `progress`
+pub(crate) fn copy_project_files_with_progress(
+    source_dir: &Path,
+    target_dir: &Path,
+    copy_files: &str,
+    progress: &mut dyn FnMut(String),
 ) -> Result<(), ContainerError> {
     let patterns: Vec<&str> = copy_files
         .split(',')
         .map(|s| s.trim())
         .filter(|s| !s.is_empty())
         .collect();
+    if patterns.is_empty() {
+        return Ok(());
+    }
+
+    let canonical_source = source_dir.canonicalize()?;
 
     // Track files to avoid duplicates
     let mut seen = HashSet::new();
 
     for pattern in patterns {
+        let (mode, pattern) = parse_pattern(pattern);
         let pattern = normalize_pattern(pattern);
         let pattern_path = source_dir.join(&pattern);
+        let started = Instant::now();
+        let mut stats = PatternStats::default();
 
         if pattern_path.is_file() {
-            if let Err(e) = copy_single_file(&pattern_path, source_dir, target_dir, &mut seen) {
-                tracing::warn!(
+            match place_single_file(
+                &pattern_path,
+                source_dir,
+                &canonical_source,
+                target_dir,
+                mode,
+                &mut seen,
+            ) {
+                Ok(Some((placed, bytes))) => stats.record(placed, bytes),
+                Ok(None) => {}
+                Err(e) => tracing::warn!(
                     "Failed to copy file {} (from {}): {}",
                     pattern,
                     pattern_path.display(),
                     e
-                );
+                ),
+            }
+        } else if pattern_path.is_dir() {
+            if let Err(e) = place_directory(
+                &pattern_path,
+                source_dir,
+                &canonical_source,
+                target_dir,
+                mode,
+                &mut seen,
+                &mut stats,
+                &mut |stats: &PatternStats| {
+                    progress(format!(
+                        "{pattern}: {} files ({})",
+                        stats.files,
+                        format_bytes(stats.bytes)
+                    ))
+                },
+            ) {
+                tracing::warn!("Failed to copy directory {}: {}", pattern, e);
             }
-            continue;
-        }
-
-        let glob_pattern = if pattern_path.is_dir() {
-            // For directories, append /** to match all contents recursively
-            format!("{pattern}/**")
         } else {
-            pattern.clone()
-        };
-
-        let walker = match GlobWalkerBuilder::from_patterns(source_dir, &[&glob_pattern])
-            .file_type(globwalk::FileType::FILE)
-            .build()
-        {
-            Ok(w) => w,
-            Err(e) => {
-                tracing::warn!("Invalid glob pattern '{glob_pattern}': {e}");
-                continue;
+            let walker = match GlobWalkerBuilder::from_patterns(source_dir, &[&pattern])
+                .file_type(globwalk::FileType::FILE)
+                .build()
+            {
+                Ok(w) => w,
+                Err(e) => {
+                    tracing::warn!("Invalid glob pattern '{pattern}': {e}");
+                    continue;
+                }
+            };
+
+            for entry in walker.flatten() {
+                match place_single_file(
+                    entry.path(),
+                    source_dir,
+                    &canonical_source,
+                    target_dir,
+                    mode,
+                    &mut seen,
+                ) {
+                    Ok(Some((placed, bytes))) => {
+                        stats.record(placed, bytes);
+                        if stats.files % PROGRESS_EVERY_FILES == 0 {
+                            progress(format!(
+                                "{pattern}: {} files ({})",
+                                stats.files,
+                                format_bytes(stats.bytes)
+                            ));
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => tracing::warn!("Failed to copy file {:?}: {e}", entry.path()),
+                }
             }
-        };
+        }
 
-        for entry in walker.flatten() {
-            if let Err(e) = copy_single_file(entry.path(), source_dir, target_dir, &mut seen) {
-                tracing::warn!("Failed to copy file {:?}: {e}", entry.path());
-            }
+        // Re-running on an existing worktree places nothing; keep the log quiet
+        if stats.files > 0 {
+            progress(format!(
+                "{pattern} ({}): {} in {:.1}s",
+                mode.as_str(),
+                stats.summary(),
+                started.elapsed().as_secs_f64()
+            ));
         }
     }
 
     Ok(())
 }
 
-fn copy_single_file(
+/// Validate that `source_file` is inside the source directory, dedupe it and
+/// place it in the target. Returns None when it was already handled or exists.
+fn place_single_file(
     source_file: &Path,
     source_root: &Path,
+    canonical_source: &Path,
     target_root: &Path,
+    mode: CopyMode,
     seen: &mut HashSet<PathBuf>,
-) -> Result<bool, ContainerError> {
-    let canonical_source = source_root.canonicalize()?;
+) -> Result<Option<(Placed, u64)>, ContainerError> {
     let canonical_file = source_file.canonicalize()?;
     // Validate path is within source_dir
     if !canonical_file.starts_with(canonical_source) {
@@ -89,7 +267,7 @@ fn copy_single_file(
     }
 
     if !seen.insert(canonical_file.clone()) {
-        return Ok(false);
+        return Ok(None);
     }
 
     let relative_path = source_file.strip_prefix(source_root).map_err(|e| {
@@ -100,8 +278,8 @@ fn copy_single_file(
 
     let target_file = target_root.join(relative_path);
 
-    if target_file.exists() {
-        return Ok(false);
+    if target_file.symlink_metadata().is_ok() {
+        return Ok(None);
     }
 
     if let Some(parent) = target_file.parent()
@@ -109,9 +287,132 @@ fn copy_single_file(
     {
         fs::create_dir_all(parent)?;
     }
-    fs::copy(source_file, &target_file)?;
+    let bytes = fs::metadata(&canonical_file)?.len();
+    let placed = place_file(&canonical_file, &target_file, mode)?;
 
-    Ok(true)
+    Ok(Some((placed, bytes)))
+}
+
+/// Place a whole directory. Symlink mode links the directory itself; other
+/// modes walk it without following symlinks, which are skipped.
+#[allow(clippy::too_many_arguments)]
+fn place_directory(
+    source_dir: &Path,
+    source_root: &Path,
+    canonical_source: &Path,
+    target_root: &Path,
+    mode: CopyMode,
+    seen: &mut HashSet<PathBuf>,
+    stats: &mut PatternStats,
+    on_progress: &mut dyn FnMut(&PatternStats),
+) -> Result<(), ContainerError> {
+    let canonical_dir = source_dir.canonicalize()?;
+    if !canonical_dir.starts_with(canonical_source) {
+        return Err(ContainerError::Other(anyhow!(
+            "Directory {source_dir:?} is outside project directory"
+        )));
+    }
+
+    if mode == CopyMode::Symlink {
+        let relative_path = source_dir.strip_prefix(source_root).map_err(|e| {
+            ContainerError::Other(anyhow!(
+                "Failed to get relative path for {source_dir:?}: {e}"
+            ))
+        })?;
+        let target = target_root.join(relative_path);
+        if target.symlink_metadata().is_ok() {
+            return Ok(());
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        symlink_dir(&canonical_dir, &target)?;
+        stats.record(Placed::Symlinked, 0);
+        return Ok(());
+    }
+
+    let mut stack = vec![source_dir.to_path_buf()];
+    while let Some(current) = stack.pop() {
+        let entries = match fs::read_dir(&current) {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::warn!("Failed to read directory {:?}: {e}", current);
+                continue;
+            }
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                stack.push(entry.path());
+            } else if file_type.is_file() {
+                match place_single_file(
+                    &entry.path(),
+                    source_root,
+                    canonical_source,
+                    target_root,
+                    mode,
+                    seen,
+                ) {
+                    Ok(Some((placed, bytes))) => {
+                        stats.record(placed, bytes);
+                        if stats.files % PROGRESS_EVERY_FILES == 0 {
+                            on_progress(stats);
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => tracing::warn!("Failed to copy file {:?}: {e}", entry.path()),
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn place_file(source: &Path, target: &Path, mode: CopyMode) -> io::Result<Placed> {
+    match mode {
+        CopyMode::Copy => fs::copy(source, target).map(|_| Placed::Copied),
+        CopyMode::Reflink => match reflink_copy::reflink_or_copy(source, target)? {
+            None => Ok(Placed::Reflinked),
+            Some(_) => Ok(Placed::Copied),
+        },
+        CopyMode::Hardlink => match fs::hard_link(source, target) {
+            Ok(()) => Ok(Placed::Hardlinked),
+            Err(e) => {
+                tracing::debug!("Hard link failed for {:?}, copying instead: {e}", source);
+                fs::copy(source, target).map(|_| Placed::Copied)
+            }
+        },
+        CopyMode::Symlink => match symlink_file(source, target) {
+            Ok(()) => Ok(Placed::Symlinked),
+            Err(e) => {
+                tracing::debug!("Symlink failed for {:?}, copying instead: {e}", source);
+                fs::copy(source, target).map(|_| Placed::Copied)
+            }
+        },
+    }
+}
+
+#[cfg(unix)]
+fn symlink_file(source: &Path, target: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(source, target)
+}
+
+#[cfg(windows)]
+fn symlink_file(source: &Path, target: &Path) -> io::Result<()> {
+    std::os::windows::fs::symlink_file(source, target)
+}
+
+#[cfg(unix)]
+fn symlink_dir(source: &Path, target: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(source, target)
+}
+
+#[cfg(windows)]
+fn symlink_dir(source: &Path, target: &Path) -> io::Result<()> {
+    std::os::windows::fs::symlink_dir(source, target)
 }
 
 #[cfg(test)]
@@ -324,4 +625,115 @@ mod tests {
 
         assert_eq!(std::fs::read_dir(dst.path()).unwrap().count(), 0);
     }
+
+    #[test]
+    fn test_parse_pattern_modes() {
+        assert_eq!(parse_pattern(".env"), (CopyMode::Copy, ".env"));
+        assert_eq!(
+            parse_pattern("symlink:node_modules"),
+            (CopyMode::Symlink, "node_modules")
+        );
+        assert_eq!(
+            parse_pattern("Reflink: .venv"),
+            (CopyMode::Reflink, ".venv")
+        );
+        assert_eq!(
+            parse_pattern("hardlink:models/*.bin"),
+            (CopyMode::Hardlink, "models/*.bin")
+        );
+        // Unknown prefixes stay part of the pattern
+        assert_eq!(parse_pattern("a:b.txt"), (CopyMode::Copy, "a:b.txt"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_mode_links_directory_as_a_whole() {
+        let source_dir = TempDir::new().unwrap();
+        let target_dir = TempDir::new().unwrap();
+
+        let modules = source_dir.path().join("node_modules/pkg");
+        fs::create_dir_all(&modules).unwrap();
+        fs::write(modules.join("index.js"), "module").unwrap();
+
+        copy_project_files_impl(source_dir.path(), target_dir.path(), "symlink:node_modules")
+            .unwrap();
+
+        let linked = target_dir.path().join("node_modules");
+        assert!(linked.symlink_metadata().unwrap().file_type().is_symlink());
+        assert_eq!(
+            fs::read_to_string(linked.join("pkg/index.js")).unwrap(),
+            "module"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hardlink_mode_shares_inode() {
+        use std::os::unix::fs::MetadataExt;
+
+        let source_dir = TempDir::new().unwrap();
+        let target_dir = TempDir::new().unwrap();
+
+        let weights = source_dir.path().join("models");
+        fs::create_dir(&weights).unwrap();
+        fs::write(weights.join("model.bin"), "weights").unwrap();
+
+        copy_project_files_impl(source_dir.path(), target_dir.path(), "hardlink:models").unwrap();
+
+        let source_meta = fs::metadata(weights.join("model.bin")).unwrap();
+        let target_meta = fs::metadata(target_dir.path().join("models/model.bin")).unwrap();
+        assert_eq!(source_meta.ino(), target_meta.ino());
+    }
+
+    #[test]
+    fn test_reflink_mode_falls_back_to_copy() {
+        let source_dir = TempDir::new().unwrap();
+        let target_dir = TempDir::new().unwrap();
+
+        let venv = source_dir.path().join(".venv/lib");
+        fs::create_dir_all(&venv).unwrap();
+        fs::write(venv.join("site.py"), "site").unwrap();
+
+        copy_project_files_impl(source_dir.path(), target_dir.path(), "reflink:.venv").unwrap();
+
+        let copied = target_dir.path().join(".venv/lib/site.py");
+        assert!(!copied.symlink_metadata().unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_to_string(copied).unwrap(), "site");
+    }
+
+    #[test]
+    fn test_progress_reports_summary_per_pattern() {
+        let source_dir = TempDir::new().unwrap();
+        let target_dir = TempDir::new().unwrap();
+
+        fs::write(source_dir.path().join(".env"), "secret").unwrap();
+        let config_dir = source_dir.path().join("config");
+        fs::create_dir(&config_dir).unwrap();
+        fs::write(config_dir.join("a.json"), "{}").unwrap();
+        fs::write(config_dir.join("b.json"), "{}").unwrap();
+
+        let mut lines = Vec::new();
+        copy_project_files_with_progress(
+            source_dir.path(),
+            target_dir.path(),
+            ".env, config",
+            &mut |line| lines.push(line),
+        )
+        .unwrap();
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with(".env (copy): 1 copied"));
+        assert!(lines[1].starts_with("config (copy): 2 copied"));
+
+        // Nothing left to place on a second run
+        lines.clear();
+        copy_project_files_with_progress(
+            source_dir.path(),
+            target_dir.path(),
+            ".env, config",
+            &mut |line| lines.push(line),
+        )
+        .unwrap();
+        assert!(lines.is_empty());
+    }
 }
@@ -15,16 +15,20 @@ use services::services::{
     filesystem::FilesystemService,
     git::GitService,
     image::ImageService,
+    mcp_tokens::McpTokenService,
     oauth_credentials::OAuthCredentials,
+    preview::PreviewService,
     project::ProjectService,
     queued_message::QueuedMessageService,
     remote_client::{RemoteClient, RemoteClientError},
     repo::RepoService,
+    secrets::SecretsService,
+    workspace_publisher::WorkspacePublisherService,
 };
 use tokio::sync::RwLock;
 use utils::{
     api::oauth::LoginStatus,
-    assets::{config_path, credentials_path},
+    assets::{config_path, credentials_path, secrets_key_path},
     msg_store::MsgStore,
 };
 use uuid::Uuid;
@@ -34,6 +38,8 @@ mod command;
 pub mod container;
 mod copy;
 pub mod pty;
+mod verify;
+mod worktree_pool;
 
 #[derive(Clone)]
 pub struct LocalDeployment {
@@ -51,6 +57,9 @@ pub struct LocalDeployment {
     file_search_cache: Arc<FileSearchCache>,
     approvals: Approvals,
     queued_message_service: QueuedMessageService,
+    preview: PreviewService,
+    mcp_tokens: McpTokenService,
+    secrets: SecretsService,
     remote_client: Result<RemoteClient, RemoteClientNotConfigured>,
     auth_context: AuthContext,
     oauth_handoffs: Arc<RwLock<HashMap<Uuid, PendingHandoff>>>,
@@ -126,6 +135,9 @@ impl Deployment for LocalDeployment {
 
         let approvals = Approvals::new(msg_stores.clone());
         let queued_message_service = QueuedMessageService::new();
+        let preview = PreviewService::new();
+        let mcp_tokens = McpTokenService::new();
+        let secrets = SecretsService::load(secrets_key_path())?;
 
         let oauth_credentials = Arc::new(OAuthCredentials::new(credentials_path()));
         if let Err(e) = oauth_credentials.load().await {
@@ -173,6 +185,9 @@ impl Deployment for LocalDeployment {
             analytics_ctx,
             approvals.clone(),
             queued_message_service.clone(),
+            preview.clone(),
+            mcp_tokens.clone(),
+            secrets.clone(),
         )
         .await;
 
@@ -197,6 +212,9 @@ impl Deployment for LocalDeployment {
             file_search_cache,
             approvals,
             queued_message_service,
+            preview,
+            mcp_tokens,
+            secrets,
             remote_client,
             auth_context,
             oauth_handoffs,
@@ -262,6 +280,18 @@ impl Deployment for LocalDeployment {
         &self.queued_message_service
     }
 
+    fn preview(&self) -> &PreviewService {
+        &self.preview
+    }
+
+    fn mcp_tokens(&self) -> &McpTokenService {
+        &self.mcp_tokens
+    }
+
+    fn secrets(&self) -> &SecretsService {
+        &self.secrets
+    }
+
     fn auth_context(&self) -> &AuthContext {
         &self.auth_context
     }
@@ -272,6 +302,16 @@ impl LocalDeployment {
         self.remote_client.clone()
     }
 
+    /// Start pushing workspaces of linked projects to the remote server. Does
+    /// nothing when no remote server is configured.
+    pub async fn spawn_workspace_publisher(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let remote_client = self.remote_client().ok()?;
+        Some(
+            WorkspacePublisherService::spawn(self.db.clone(), self.git.clone(), remote_client)
+                .await,
+        )
+    }
+
     pub async fn get_login_status(&self) -> LoginStatus {
         if self.auth_context.get_credentials().await.is_none() {
             self.auth_context.clear_profile().await;
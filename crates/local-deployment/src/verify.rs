@@ -0,0 +1,246 @@
+use db::models::{
+    execution_process::ExecutionProcessStatus,
+    execution_process_verification::{
+        CreateExecutionProcessVerification, ExecutionProcessVerification,
+    },
+    repo::Repo,
+};
+use executors::actions::{ExecutorAction, ExecutorActionType, script::ScriptContext};
+use sqlx::SqlitePool;
+use utils::log_msg::LogMsg;
+use uuid::Uuid;
+
+/// Bytes of combined output kept for storage and retry prompts
+const VERIFY_OUTPUT_LIMIT: usize = 16 * 1024;
+
+/// Combine a verify script's stdout and stderr in the order they were written,
+/// keeping only the tail
+pub(crate) fn collect_verify_output(history: &[LogMsg]) -> String {
+    let combined: String = history
+        .iter()
+        .filter_map(|msg| match msg {
+            LogMsg::Stdout(content) | LogMsg::Stderr(content) => Some(content.as_str()),
+            _ => None,
+        })
+        .collect();
+    tail_to_char_boundary(&combined, VERIFY_OUTPUT_LIMIT).to_string()
+}
+
+/// Keep the last `max_len` bytes, since failures are usually reported at the end
+fn tail_to_char_boundary(content: &str, max_len: usize) -> &str {
+    if content.len() <= max_len {
+        return content;
+    }
+    let mut start = content.len() - max_len;
+    while !content.is_char_boundary(start) {
+        start += 1;
+    }
+    &content[start..]
+}
+
+/// Whether a finished verify script passed. A killed script never does, so
+/// stopping verification can't let unverified work through.
+pub(crate) fn verify_passed(status: &ExecutionProcessStatus, exit_code: Option<i64>) -> bool {
+    !matches!(status, ExecutionProcessStatus::Killed) && exit_code == Some(0)
+}
+
+/// Repos whose verify scripts are chained at the front of `action`, in run order
+pub(crate) fn verify_script_repos<'a>(action: &ExecutorAction, repos: &'a [Repo]) -> Vec<&'a Repo> {
+    let mut verified = Vec::new();
+    let mut current = Some(action);
+    while let Some(action) = current {
+        let ExecutorActionType::ScriptRequest(request) = action.typ() else {
+            break;
+        };
+        if request.context != ScriptContext::VerifyScript {
+            break;
+        }
+        if let Some(repo) = repos
+            .iter()
+            .find(|r| request.working_dir.as_deref() == Some(r.name.as_str()))
+        {
+            verified.push(repo);
+        }
+        current = action.next_action();
+    }
+    verified
+}
+
+/// Record a failed result for every repo whose verify script could not be
+/// started, so the turn is not mistaken for one that passed verification
+pub(crate) async fn record_unstarted_verify_scripts(
+    pool: &SqlitePool,
+    verified_execution_process_id: Uuid,
+    repos: &[&Repo],
+    attempt: i64,
+    error: &str,
+) -> Result<(), sqlx::Error> {
+    for repo in repos {
+        let data = CreateExecutionProcessVerification {
+            repo_id: repo.id,
+            passed: false,
+            exit_code: None,
+            output: format!("Verify script failed to start: {error}"),
+            attempt,
+            verify_execution_process_id: None,
+        };
+        ExecutionProcessVerification::create(pool, verified_execution_process_id, &data).await?;
+    }
+    Ok(())
+}
+
+/// Build the follow-up prompt that hands failing verification output back to the agent
+pub(crate) fn build_verify_retry_prompt(failures: &[(&str, &str, Option<i64>, &str)]) -> String {
+    let mut prompt = String::from(
+        "The verification script failed after your last changes. Fix the problems below, \
+         then make sure the script passes.\n",
+    );
+    for (repo_name, script, exit_code, output) in failures {
+        let exit = exit_code
+            .map(|code| code.to_string())
+            .unwrap_or_else(|| "none".to_string());
+        prompt.push_str(&format!(
+            "\nRepository `{repo_name}`: `{script}` (exit code {exit})\n```\n{}\n```\n",
+            output.trim_end()
+        ));
+    }
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::actions::script::{ScriptRequest, ScriptRequestLanguage};
+    use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn script(context: ScriptContext, repo: &str, next: Option<ExecutorAction>) -> ExecutorAction {
+        ExecutorAction::new(
+            ExecutorActionType::ScriptRequest(ScriptRequest {
+                script: "true".to_string(),
+                language: ScriptRequestLanguage::Bash,
+                context,
+                working_dir: Some(repo.to_string()),
+            }),
+            next.map(Box::new),
+        )
+    }
+
+    #[test]
+    fn test_tail_to_char_boundary_keeps_end() {
+        assert_eq!(tail_to_char_boundary("short", 10), "short");
+        assert_eq!(tail_to_char_boundary("abcdef", 3), "def");
+        // "é" is two bytes; never split it
+        assert_eq!(tail_to_char_boundary("aéb", 2), "b");
+    }
+
+    #[test]
+    fn test_collect_verify_output_interleaves_streams() {
+        let history = vec![
+            LogMsg::Stdout("running tests\n".to_string()),
+            LogMsg::Stderr("test foo ... FAILED\n".to_string()),
+            LogMsg::Finished,
+        ];
+        assert_eq!(
+            collect_verify_output(&history),
+            "running tests\ntest foo ... FAILED\n"
+        );
+    }
+
+    #[test]
+    fn test_build_verify_retry_prompt_lists_each_repo() {
+        let prompt = build_verify_retry_prompt(&[
+            ("api", "cargo test", Some(101), "test foo ... FAILED\n"),
+            ("web", "pnpm test", None, "timed out"),
+        ]);
+        assert!(prompt.contains("Repository `api`: `cargo test` (exit code 101)"));
+        assert!(prompt.contains("test foo ... FAILED\n```"));
+        assert!(prompt.contains("Repository `web`: `pnpm test` (exit code none)"));
+    }
+
+    #[test]
+    fn test_killed_verify_script_never_passes() {
+        assert!(verify_passed(&ExecutionProcessStatus::Completed, Some(0)));
+        assert!(!verify_passed(&ExecutionProcessStatus::Completed, Some(1)));
+        assert!(!verify_passed(&ExecutionProcessStatus::Failed, None));
+        assert!(!verify_passed(&ExecutionProcessStatus::Killed, Some(0)));
+        assert!(!verify_passed(&ExecutionProcessStatus::Killed, None));
+    }
+
+    #[tokio::test]
+    async fn test_unstarted_verify_scripts_fail_the_turn() {
+        let dir = TempDir::new().unwrap();
+        let options = SqliteConnectOptions::new()
+            .filename(dir.path().join("db.sqlite"))
+            .create_if_missing(true);
+        // One connection, so the pragma below applies to every query
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .unwrap();
+        sqlx::migrate!("../db/migrations").run(&pool).await.unwrap();
+        // The coding agent turn belongs to a session these tests don't create
+        sqlx::query("PRAGMA foreign_keys = OFF")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let api = Repo::find_or_create(&pool, &dir.path().join("api"), "api")
+            .await
+            .unwrap();
+        let web = Repo::find_or_create(&pool, &dir.path().join("web"), "web")
+            .await
+            .unwrap();
+        let session_id = Uuid::new_v4();
+        let turn_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO execution_processes (id, session_id, run_reason, status) VALUES ($1, $2, 'codingagent', 'completed')",
+        )
+        .bind(turn_id)
+        .bind(session_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        assert!(
+            !ExecutionProcessVerification::latest_coding_agent_turn_failed(&pool, session_id)
+                .await
+                .unwrap()
+        );
+
+        // Both verify scripts are pending in front of the cleanup script
+        let chain = script(
+            ScriptContext::VerifyScript,
+            "api",
+            Some(script(
+                ScriptContext::VerifyScript,
+                "web",
+                Some(script(ScriptContext::CleanupScript, "api", None)),
+            )),
+        );
+        let repos = [api.clone(), web.clone()];
+        let pending = verify_script_repos(&chain, &repos);
+        assert_eq!(
+            pending.iter().map(|r| r.id).collect::<Vec<_>>(),
+            vec![api.id, web.id]
+        );
+
+        record_unstarted_verify_scripts(&pool, turn_id, &pending, 1, "spawn failed")
+            .await
+            .unwrap();
+        let results = ExecutionProcessVerification::find_by_execution_process_id(&pool, turn_id)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| !r.passed
+            && r.exit_code.is_none()
+            && r.verify_execution_process_id.is_none()
+            && r.output.contains("spawn failed")));
+        assert!(
+            ExecutionProcessVerification::latest_coding_agent_turn_failed(&pool, session_id)
+                .await
+                .unwrap()
+        );
+    }
+}
@@ -0,0 +1,772 @@
+//! Pre-warmed worktrees for repos with `worktree_pool_size > 0`.
+//!
+//! Pools are kept per repo and base branch: each entry is a detached worktree
+//! checked out at the latest commit of one of the branches the repo's recent
+//! workspaces were based on (or its current branch before there are any), with
+//! copy_files applied and the setup script already run. Creating a workspace
+//! claims a ready entry for its target branch, moves it into the workspace
+//! directory and creates the task branch at its HEAD, so the repo's setup
+//! script is skipped.
+//!
+//! Setup runs outside any workspace, so it gets neither the `VK_*` workspace
+//! variables nor project secrets, and entries are moved after setup: scripts
+//! whose output depends on the worktree's absolute path (e.g. Python
+//! virtualenvs) should not use the pool.
+
+use std::{
+    collections::HashMap,
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::anyhow;
+use db::{
+    DBService,
+    models::{
+        repo::Repo,
+        workspace_repo::WorkspaceRepo,
+        worktree_pool_entry::{WorktreePoolEntry, WorktreePoolEntryStatus},
+    },
+};
+use futures::StreamExt;
+use services::services::{
+    git::{GitCli, GitService},
+    worktree_manager::{WorktreeCleanup, WorktreeManager},
+};
+use tokio::{process::Command, sync::Notify};
+use utils::shell::get_shell_command;
+use uuid::Uuid;
+
+use crate::copy;
+
+/// How often the pool checks whether target branches moved
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Upper bound for a single setup script run in a pool worktree
+const SETUP_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Bytes of setup output logged when it fails
+const SETUP_OUTPUT_LOG_LIMIT: usize = 4 * 1024;
+
+/// Base branches of a repo kept warm, the most recently used ones
+const MAX_POOLED_BRANCHES: i64 = 2;
+
+/// Repos whose pools are prepared at the same time. Entries of one repo are
+/// still prepared one at a time, so setup scripts don't compete for the same
+/// caches.
+const MAX_CONCURRENT_REPOS: usize = 4;
+
+#[derive(Clone)]
+pub(crate) struct WorktreePool {
+    db: DBService,
+    git: GitService,
+    base_dir: PathBuf,
+    wake: Arc<Notify>,
+}
+
+impl WorktreePool {
+    pub(crate) fn new(db: DBService, git: GitService) -> Self {
+        Self::with_base_dir(
+            db,
+            git,
+            utils::path::get_vibe_kanban_temp_dir().join("worktree-pool"),
+        )
+    }
+
+    fn with_base_dir(db: DBService, git: GitService, base_dir: PathBuf) -> Self {
+        Self {
+            db,
+            git,
+            base_dir,
+            wake: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Clean up after the previous run, then keep every repo's pools filled and
+    /// up to date.
+    pub(crate) fn spawn(&self) {
+        let pool = self.clone();
+        tokio::spawn(async move {
+            pool.discard_interrupted().await;
+
+            let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = pool.wake.notified() => {}
+                }
+                if let Err(e) = pool.reconcile().await {
+                    tracing::error!("Failed to maintain worktree pool: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Hand a ready worktree of `repo` at the current head of `target_branch`
+    /// to a workspace, placing it at `dest` on a new `branch_name`. Returns
+    /// false if none was available, in which case the caller creates the
+    /// worktree itself.
+    pub(crate) async fn claim(
+        &self,
+        repo: &Repo,
+        target_branch: &str,
+        branch_name: &str,
+        dest: &Path,
+        workspace_id: Uuid,
+    ) -> bool {
+        if repo.worktree_pool_size <= 0 {
+            return false;
+        }
+        let Ok(head) = self.git.get_branch_oid(&repo.path, target_branch) else {
+            return false;
+        };
+
+        let entry = match WorktreePoolEntry::claim(
+            &self.db.pool,
+            repo.id,
+            target_branch,
+            &head,
+            workspace_id,
+        )
+        .await
+        {
+            Ok(Some(entry)) => entry,
+            Ok(None) => {
+                // Nothing ready at this commit; the pool may just be behind
+                self.wake.notify_one();
+                return false;
+            }
+            Err(e) => {
+                tracing::warn!("Failed to claim pooled worktree for '{}': {}", repo.name, e);
+                return false;
+            }
+        };
+        // Replenish in the background
+        self.wake.notify_one();
+
+        match Self::adopt(repo, &entry, branch_name, dest).await {
+            Ok(()) => {
+                tracing::info!(
+                    "Claimed pooled worktree for '{}' at {}",
+                    repo.name,
+                    &head[..head.len().min(8)]
+                );
+                let _ = tokio::fs::remove_dir(self.entry_dir(&entry)).await;
+                true
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to adopt pooled worktree for '{}', creating one instead: {}",
+                    repo.name,
+                    e
+                );
+                for path in [dest.to_path_buf(), PathBuf::from(&entry.path)] {
+                    if path.exists() {
+                        let cleanup = WorktreeCleanup::new(path, Some(repo.path.clone()));
+                        if let Err(e) = WorktreeManager::cleanup_worktree(&cleanup).await {
+                            tracing::warn!("Failed to remove pooled worktree: {}", e);
+                        }
+                    }
+                }
+                let _ = tokio::fs::remove_dir_all(self.entry_dir(&entry)).await;
+                let _ = WorktreePoolEntry::delete(&self.db.pool, entry.id).await;
+                false
+            }
+        }
+    }
+
+    async fn adopt(
+        repo: &Repo,
+        entry: &WorktreePoolEntry,
+        branch_name: &str,
+        dest: &Path,
+    ) -> anyhow::Result<()> {
+        WorktreeManager::move_worktree(&repo.path, Path::new(&entry.path), dest).await?;
+
+        let dest = dest.to_path_buf();
+        let branch_name = branch_name.to_string();
+        tokio::task::spawn_blocking(move || {
+            GitCli::new().git(&dest, ["checkout", "-b", branch_name.as_str()])
+        })
+        .await??;
+        Ok(())
+    }
+
+    /// Entries left preparing by a previous run never finished setup, and
+    /// directories without an entry are leftovers from failed discards
+    async fn discard_interrupted(&self) {
+        let entries = match WorktreePoolEntry::find_unclaimed(&self.db.pool).await {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::error!("Failed to load worktree pool entries: {}", e);
+                return;
+            }
+        };
+
+        let mut known_dirs = Vec::new();
+        for entry in entries {
+            if entry.status == WorktreePoolEntryStatus::Preparing
+                || !Path::new(&entry.path).exists()
+            {
+                let repo_path = Repo::find_by_id(&self.db.pool, entry.repo_id)
+                    .await
+                    .ok()
+                    .flatten()
+                    .map(|repo| repo.path);
+                self.discard(repo_path, &entry).await;
+            } else {
+                known_dirs.push(self.entry_dir(&entry));
+            }
+        }
+
+        let Ok(dirs) = std::fs::read_dir(&self.base_dir) else {
+            return;
+        };
+        for dir in dirs.filter_map(|d| d.ok()).map(|d| d.path()) {
+            if !dir.is_dir() || known_dirs.contains(&dir) {
+                continue;
+            }
+            tracing::info!("Removing orphaned pool worktree {}", dir.display());
+            for worktree in std::fs::read_dir(&dir)
+                .into_iter()
+                .flatten()
+                .filter_map(|w| w.ok())
+            {
+                if let Err(e) = WorktreeManager::cleanup_suspected_worktree(&worktree.path()).await
+                {
+                    tracing::warn!("Failed to clean up orphaned pool worktree: {}", e);
+                }
+            }
+            let _ = tokio::fs::remove_dir_all(&dir).await;
+        }
+    }
+
+    /// Bring each repo's pools to their configured size at the current heads
+    /// of their base branches
+    async fn reconcile(&self) -> anyhow::Result<()> {
+        let mut entries_by_repo: HashMap<Uuid, Vec<WorktreePoolEntry>> = HashMap::new();
+        for entry in WorktreePoolEntry::find_unclaimed(&self.db.pool).await? {
+            entries_by_repo
+                .entry(entry.repo_id)
+                .or_default()
+                .push(entry);
+        }
+
+        let repos: Vec<(Repo, Vec<WorktreePoolEntry>)> = Repo::list_all(&self.db.pool)
+            .await?
+            .into_iter()
+            .map(|repo| {
+                let entries = entries_by_repo.remove(&repo.id).unwrap_or_default();
+                (repo, entries)
+            })
+            .collect();
+        futures::stream::iter(repos)
+            .for_each_concurrent(MAX_CONCURRENT_REPOS, |(repo, entries)| async move {
+                if let Err(e) = self.reconcile_repo(&repo, entries).await {
+                    tracing::error!(
+                        "Failed to maintain worktree pool for '{}': {}",
+                        repo.name,
+                        e
+                    );
+                }
+            })
+            .await;
+
+        Ok(())
+    }
+
+    async fn reconcile_repo(
+        &self,
+        repo: &Repo,
+        entries: Vec<WorktreePoolEntry>,
+    ) -> anyhow::Result<()> {
+        let size = repo.worktree_pool_size.max(0) as usize;
+        let branches = if size == 0 {
+            Vec::new()
+        } else {
+            self.pool_branches(repo).await?
+        };
+
+        let mut entries_by_branch: HashMap<String, Vec<WorktreePoolEntry>> = HashMap::new();
+        for entry in entries {
+            if branches.contains(&entry.target_branch) {
+                entries_by_branch
+                    .entry(entry.target_branch.clone())
+                    .or_default()
+                    .push(entry);
+            } else {
+                // Pool disabled, or the branch is no longer used as a base
+                self.discard(Some(repo.path.clone()), &entry).await;
+            }
+        }
+
+        for branch in &branches {
+            let mut entries = entries_by_branch.remove(branch).unwrap_or_default();
+            while entries.len() > size {
+                if let Some(entry) = entries.pop() {
+                    self.discard(Some(repo.path.clone()), &entry).await;
+                }
+            }
+
+            let head = match self.git.get_branch_oid(&repo.path, branch) {
+                Ok(head) => head,
+                Err(e) => {
+                    tracing::debug!(
+                        "Skipping worktree pool for '{}' on {}: {}",
+                        repo.name,
+                        branch,
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            for entry in &entries {
+                let stale = entry.base_commit.as_deref() != Some(head.as_str());
+                if stale && WorktreePoolEntry::begin_refresh(&self.db.pool, entry.id).await? {
+                    self.refresh(repo, entry, branch, &head).await;
+                }
+            }
+
+            for _ in entries.len()..size {
+                self.add_entry(repo, branch, &head).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Base branches to keep worktrees ready for: the ones the repo's recent
+    /// workspaces were based on, or its checked-out branch before there are any
+    async fn pool_branches(&self, repo: &Repo) -> anyhow::Result<Vec<String>> {
+        let branches =
+            WorkspaceRepo::recent_target_branches(&self.db.pool, repo.id, MAX_POOLED_BRANCHES)
+                .await?;
+        if !branches.is_empty() {
+            return Ok(branches);
+        }
+        Ok(vec![self.git.get_current_branch(&repo.path)?])
+    }
+
+    async fn add_entry(&self, repo: &Repo, target_branch: &str, head: &str) -> anyhow::Result<()> {
+        let id = Uuid::new_v4();
+        let path = self.base_dir.join(id.simple().to_string()).join(&repo.name);
+        let entry = WorktreePoolEntry::create(
+            &self.db.pool,
+            id,
+            repo.id,
+            &path.to_string_lossy(),
+            target_branch,
+        )
+        .await?;
+
+        tracing::info!(
+            "Adding pooled worktree for '{}' at {}",
+            repo.name,
+            path.display()
+        );
+        let created = async {
+            tokio::fs::create_dir_all(self.entry_dir(&entry)).await?;
+            let repo_path = repo.path.clone();
+            let worktree_path = path.clone();
+            let head = head.to_string();
+            tokio::task::spawn_blocking(move || {
+                GitCli::new().git(
+                    &repo_path,
+                    [
+                        OsStr::new("worktree"),
+                        OsStr::new("add"),
+                        OsStr::new("--detach"),
+                        worktree_path.as_os_str(),
+                        OsStr::new(&head),
+                    ],
+                )
+            })
+            .await??;
+            Ok::<(), anyhow::Error>(())
+        }
+        .await;
+        if let Err(e) = created {
+            tracing::warn!(
+                "Failed to create pooled worktree for '{}': {}",
+                repo.name,
+                e
+            );
+            self.discard(Some(repo.path.clone()), &entry).await;
+            return Ok(());
+        }
+
+        self.prepare(repo, &entry, target_branch, head).await;
+        Ok(())
+    }
+
+    /// Check out the new head in place, keeping ignored build artifacts so
+    /// setup runs incrementally, then re-run setup
+    async fn refresh(
+        &self,
+        repo: &Repo,
+        entry: &WorktreePoolEntry,
+        target_branch: &str,
+        head: &str,
+    ) {
+        tracing::info!(
+            "Refreshing pooled worktree for '{}' to {}",
+            repo.name,
+            &head[..head.len().min(8)]
+        );
+        let worktree_path = PathBuf::from(&entry.path);
+        let head_owned = head.to_string();
+        let checkout = async {
+            tokio::task::spawn_blocking(move || {
+                GitCli::new().git(
+                    &worktree_path,
+                    ["checkout", "--detach", "--force", head_owned.as_str()],
+                )
+            })
+            .await??;
+            Ok::<(), anyhow::Error>(())
+        }
+        .await;
+        match checkout {
+            Ok(()) => self.prepare(repo, entry, target_branch, head).await,
+            Err(e) => {
+                tracing::warn!("Failed to refresh pooled worktree, replacing it: {}", e);
+                self.discard(Some(repo.path.clone()), entry).await;
+            }
+        }
+    }
+
+    /// Apply copy_files and run the setup script, then mark the entry ready,
+    /// or failed so it is only retried once the target branch moves again
+    async fn prepare(
+        &self,
+        repo: &Repo,
+        entry: &WorktreePoolEntry,
+        target_branch: &str,
+        head: &str,
+    ) {
+        let worktree_path = PathBuf::from(&entry.path);
+
+        if let Some(copy_files) = repo.copy_files.clone().filter(|c| !c.trim().is_empty()) {
+            let source = repo.path.clone();
+            let target = worktree_path.clone();
+            match tokio::task::spawn_blocking(move || {
+                copy::copy_project_files_impl(&source, &target, &copy_files)
+            })
+            .await
+            {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => tracing::warn!(
+                    "Failed to copy project files into pooled worktree for '{}': {}",
+                    repo.name,
+                    e
+                ),
+                Err(e) => tracing::warn!("Copy files task failed: {}", e),
+            }
+        }
+
+        let status = match &repo.setup_script {
+            Some(script) => match run_setup_script(script, &worktree_path).await {
+                Ok(()) => WorktreePoolEntryStatus::Ready,
+                Err(e) => {
+                    tracing::warn!(
+                        "Setup script failed in pooled worktree for '{}': {}",
+                        repo.name,
+                        e
+                    );
+                    WorktreePoolEntryStatus::Failed
+                }
+            },
+            None => WorktreePoolEntryStatus::Ready,
+        };
+
+        if let Err(e) = WorktreePoolEntry::set_status(
+            &self.db.pool,
+            entry.id,
+            status,
+            target_branch,
+            Some(head),
+        )
+        .await
+        {
+            tracing::error!("Failed to update worktree pool entry: {}", e);
+        }
+    }
+
+    async fn discard(&self, repo_path: Option<PathBuf>, entry: &WorktreePoolEntry) {
+        let worktree_path = PathBuf::from(&entry.path);
+        if worktree_path.exists() {
+            let cleanup = WorktreeCleanup::new(worktree_path, repo_path);
+            if let Err(e) = WorktreeManager::cleanup_worktree(&cleanup).await {
+                tracing::warn!("Failed to remove pooled worktree: {}", e);
+            }
+        }
+        let _ = tokio::fs::remove_dir_all(self.entry_dir(entry)).await;
+        if let Err(e) = WorktreePoolEntry::delete(&self.db.pool, entry.id).await {
+            tracing::error!("Failed to delete worktree pool entry: {}", e);
+        }
+    }
+
+    /// Per-entry directory holding the worktree, so the worktree itself keeps
+    /// the repo's name
+    fn entry_dir(&self, entry: &WorktreePoolEntry) -> PathBuf {
+        Path::new(&entry.path)
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| self.base_dir.clone())
+    }
+}
+
+async fn run_setup_script(script: &str, worktree_path: &Path) -> anyhow::Result<()> {
+    let (shell_cmd, shell_arg) = get_shell_command();
+    let mut command = Command::new(shell_cmd);
+    command
+        .kill_on_drop(true)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .arg(shell_arg)
+        .arg(script)
+        .current_dir(worktree_path);
+
+    let output = tokio::time::timeout(SETUP_TIMEOUT, command.output())
+        .await
+        .map_err(|_| anyhow!("timed out after {} minutes", SETUP_TIMEOUT.as_secs() / 60))??;
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let mut start = stderr.len().saturating_sub(SETUP_OUTPUT_LOG_LIMIT);
+    while !stderr.is_char_boundary(start) {
+        start += 1;
+    }
+    Err(anyhow!("{}: {}", output.status, &stderr[start..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+    use tempfile::TempDir;
+
+    use super::*;
+
+    struct Fixture {
+        dir: TempDir,
+        pool: WorktreePool,
+        repo: Repo,
+    }
+
+    impl Fixture {
+        /// A repo on `main` with a pool of one and a setup script that leaves a
+        /// marker file behind
+        async fn new() -> Self {
+            let dir = TempDir::new().unwrap();
+            let options = SqliteConnectOptions::new()
+                .filename(dir.path().join("db.sqlite"))
+                .create_if_missing(true);
+            // One connection, so the pragma below applies to every query
+            let db_pool = SqlitePoolOptions::new()
+                .max_connections(1)
+                .connect_with(options)
+                .await
+                .unwrap();
+            sqlx::migrate!("../db/migrations")
+                .run(&db_pool)
+                .await
+                .unwrap();
+            // Claims and workspace repos point at workspaces these tests don't create
+            sqlx::query("PRAGMA foreign_keys = OFF")
+                .execute(&db_pool)
+                .await
+                .unwrap();
+
+            let git = GitService::new();
+            let repo_path = dir.path().join("repo");
+            git.initialize_repo_with_main_branch(&repo_path).unwrap();
+            let repo = Repo::find_or_create(&db_pool, &repo_path, "repo")
+                .await
+                .unwrap();
+            sqlx::query(
+                "UPDATE repos SET worktree_pool_size = 1, setup_script = 'echo ok > setup-ran' WHERE id = $1",
+            )
+            .bind(repo.id)
+            .execute(&db_pool)
+            .await
+            .unwrap();
+            let repo = Repo::find_by_id(&db_pool, repo.id).await.unwrap().unwrap();
+
+            let pool = WorktreePool::with_base_dir(
+                DBService { pool: db_pool },
+                git,
+                dir.path().join("worktree-pool"),
+            );
+            Self { dir, pool, repo }
+        }
+
+        fn git(&self, args: &[&str]) {
+            GitCli::new()
+                .git(
+                    &self.repo.path,
+                    ["-c", "user.name=Test", "-c", "user.email=test@example.com"]
+                        .iter()
+                        .chain(args),
+                )
+                .unwrap();
+        }
+
+        fn head(&self, branch: &str) -> String {
+            self.pool
+                .git
+                .get_branch_oid(&self.repo.path, branch)
+                .unwrap()
+        }
+
+        async fn entries(&self) -> Vec<WorktreePoolEntry> {
+            WorktreePoolEntry::find_unclaimed(&self.pool.db.pool)
+                .await
+                .unwrap()
+        }
+
+        async fn claim(&self, target_branch: &str, workspace_id: Uuid) -> (bool, PathBuf) {
+            let dest = self
+                .dir
+                .path()
+                .join("workspaces")
+                .join(workspace_id.simple().to_string())
+                .join("repo");
+            tokio::fs::create_dir_all(dest.parent().unwrap())
+                .await
+                .unwrap();
+            let claimed = self
+                .pool
+                .claim(
+                    &self.repo,
+                    target_branch,
+                    "task-branch",
+                    &dest,
+                    workspace_id,
+                )
+                .await;
+            (claimed, dest)
+        }
+    }
+
+    #[tokio::test]
+    async fn claimed_worktrees_are_set_up_and_replenished() {
+        let fixture = Fixture::new().await;
+        fixture.pool.reconcile().await.unwrap();
+
+        let entries = fixture.entries().await;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].status, WorktreePoolEntryStatus::Ready);
+        assert_eq!(entries[0].target_branch, "main");
+        assert_eq!(
+            entries[0].base_commit.as_deref(),
+            Some(fixture.head("main").as_str())
+        );
+
+        let workspace_id = Uuid::new_v4();
+        let (claimed, dest) = fixture.claim("main", workspace_id).await;
+        assert!(claimed);
+        assert!(dest.join("setup-ran").exists());
+        assert_eq!(
+            fixture.pool.git.get_current_branch(&dest).unwrap(),
+            "task-branch"
+        );
+        assert_eq!(
+            WorktreePoolEntry::find_claimed_repo_ids(&fixture.pool.db.pool, workspace_id)
+                .await
+                .unwrap(),
+            vec![fixture.repo.id]
+        );
+        assert!(fixture.entries().await.is_empty());
+
+        fixture.pool.reconcile().await.unwrap();
+        let replenished = fixture.entries().await;
+        assert_eq!(replenished.len(), 1);
+        assert_ne!(replenished[0].id, entries[0].id);
+        assert_eq!(replenished[0].status, WorktreePoolEntryStatus::Ready);
+    }
+
+    #[tokio::test]
+    async fn entries_are_refreshed_when_their_branch_moves() {
+        let fixture = Fixture::new().await;
+        fixture.pool.reconcile().await.unwrap();
+        let before = fixture.entries().await;
+
+        fixture.git(&["commit", "--allow-empty", "-m", "move main"]);
+        let head = fixture.head("main");
+
+        // Nothing is ready at the new head until the pool catches up
+        assert!(!fixture.claim("main", Uuid::new_v4()).await.0);
+
+        fixture.pool.reconcile().await.unwrap();
+        let after = fixture.entries().await;
+        assert_eq!(after.len(), 1);
+        assert_eq!(after[0].id, before[0].id);
+        assert_eq!(after[0].status, WorktreePoolEntryStatus::Ready);
+        assert_eq!(after[0].base_commit.as_deref(), Some(head.as_str()));
+
+        let (claimed, dest) = fixture.claim("main", Uuid::new_v4()).await;
+        assert!(claimed);
+        assert_eq!(fixture.pool.git.get_head_info(&dest).unwrap().oid, head);
+    }
+
+    #[tokio::test]
+    async fn pools_follow_the_branches_workspaces_are_based_on() {
+        let fixture = Fixture::new().await;
+        fixture.git(&["branch", "develop"]);
+        sqlx::query(
+            "INSERT INTO workspace_repos (id, workspace_id, repo_id, target_branch) VALUES ($1, $2, $3, 'develop')",
+        )
+        .bind(Uuid::new_v4())
+        .bind(Uuid::new_v4())
+        .bind(fixture.repo.id)
+        .execute(&fixture.pool.db.pool)
+        .await
+        .unwrap();
+
+        // Checking out another branch in the repo doesn't change the pool
+        fixture.git(&["checkout", "-b", "scratch"]);
+        fixture.pool.reconcile().await.unwrap();
+
+        let entries = fixture.entries().await;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].target_branch, "develop");
+        assert!(!fixture.claim("scratch", Uuid::new_v4()).await.0);
+        assert!(fixture.claim("develop", Uuid::new_v4()).await.0);
+    }
+
+    #[tokio::test]
+    async fn failed_setup_is_not_claimable() {
+        let fixture = Fixture::new().await;
+        sqlx::query("UPDATE repos SET setup_script = 'exit 1' WHERE id = $1")
+            .bind(fixture.repo.id)
+            .execute(&fixture.pool.db.pool)
+            .await
+            .unwrap();
+        let repo = Repo::find_by_id(&fixture.pool.db.pool, fixture.repo.id)
+            .await
+            .unwrap()
+            .unwrap();
+        let fixture = Fixture { repo, ..fixture };
+
+        fixture.pool.reconcile().await.unwrap();
+        let entries = fixture.entries().await;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].status, WorktreePoolEntryStatus::Failed);
+        assert!(!fixture.claim("main", Uuid::new_v4()).await.0);
+
+        // Only retried once the branch moves; until then the entry stays put
+        fixture.pool.reconcile().await.unwrap();
+        assert_eq!(fixture.entries().await[0].id, entries[0].id);
+        assert_eq!(
+            fixture.entries().await[0].status,
+            WorktreePoolEntryStatus::Failed
+        );
+    }
+}
@@ -0,0 +1,3 @@
+-- Local clients push workspaces by their local id, so keep one row per owner
+CREATE UNIQUE INDEX idx_workspaces_owner_local_workspace
+    ON workspaces(owner_user_id, local_workspace_id);
@@ -0,0 +1,34 @@
+-- Service accounts are bot users owned by an organization. They sign in with
+-- API tokens only, so they never have an oauth account.
+CREATE TABLE service_accounts (
+    user_id            UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    organization_id    UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    name               TEXT NOT NULL,
+    created_by_user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at         TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    disabled_at        TIMESTAMPTZ
+);
+
+CREATE INDEX idx_service_accounts_organization
+    ON service_accounts (organization_id);
+
+CREATE TYPE api_token_scope AS ENUM ('issues_read', 'issues_write', 'reviews_trigger');
+
+-- Long-lived bearer tokens for personal use or service accounts. Only a
+-- SHA-256 hash of the secret is stored.
+CREATE TABLE api_tokens (
+    id                 UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id            UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name               TEXT NOT NULL,
+    token_hash         TEXT NOT NULL UNIQUE,
+    token_prefix       TEXT NOT NULL,
+    scopes             api_token_scope[] NOT NULL CHECK (cardinality(scopes) > 0),
+    expires_at         TIMESTAMPTZ,
+    last_used_at       TIMESTAMPTZ,
+    created_by_user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at         TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    revoked_at         TIMESTAMPTZ
+);
+
+CREATE INDEX idx_api_tokens_user
+    ON api_tokens (user_id);
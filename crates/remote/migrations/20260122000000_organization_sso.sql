@@ -0,0 +1,25 @@
+-- PKCE verifier for providers that use it, generated when the handoff starts
+ALTER TABLE oauth_handoffs ADD COLUMN provider_code_verifier TEXT;
+
+-- Provider the session was signed in with, used to enforce SSO. Sessions
+-- created before this column existed have no provider.
+ALTER TABLE auth_sessions ADD COLUMN provider TEXT;
+
+CREATE TABLE organization_sso_settings (
+    organization_id UUID PRIMARY KEY REFERENCES organizations(id) ON DELETE CASCADE,
+    sso_required    BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Email domains claimed by an organization. Users signing in through SSO
+-- with an address on a claimed domain join the organization automatically.
+CREATE TABLE organization_domains (
+    domain             TEXT PRIMARY KEY CHECK (domain = lower(domain)),
+    organization_id    UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    auto_join          BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by_user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at         TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_organization_domains_organization
+    ON organization_domains (organization_id);
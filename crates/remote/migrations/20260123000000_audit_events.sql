@@ -0,0 +1,44 @@
+CREATE TYPE audit_action AS ENUM (
+    'invitation_created',
+    'invitation_revoked',
+    'invitation_accepted',
+    'member_removed',
+    'member_role_changed',
+    'github_app_installed',
+    'github_app_uninstalled',
+    'repository_review_enabled_changed',
+    'project_deleted',
+    'issue_deleted'
+);
+
+-- Append-only record of administrative actions within an organization. There
+-- are no foreign keys on purpose: events must outlive the users, projects and
+-- even the organization they describe.
+CREATE TABLE audit_events (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL,
+    -- NULL when GitHub or the server itself performed the action
+    actor_user_id   UUID,
+    action          audit_action NOT NULL,
+    target_id       UUID,
+    metadata        JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_audit_events_organization_created
+    ON audit_events (organization_id, created_at DESC);
+
+CREATE INDEX idx_audit_events_organization_actor
+    ON audit_events (organization_id, actor_user_id, created_at DESC);
+
+CREATE OR REPLACE FUNCTION reject_audit_event_changes()
+RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_events is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_audit_events_append_only
+    BEFORE UPDATE OR DELETE ON audit_events
+    FOR EACH ROW
+    EXECUTE FUNCTION reject_audit_event_changes();
@@ -0,0 +1,26 @@
+-- Viewers can read every non-private project but not change anything.
+-- Guests only see the projects they have been added to.
+ALTER TYPE member_role ADD VALUE IF NOT EXISTS 'viewer';
+ALTER TYPE member_role ADD VALUE IF NOT EXISTS 'guest';
+
+-- Private projects are only visible to organization admins and the members
+-- listed in project_members
+ALTER TABLE projects ADD COLUMN is_private BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE project_members (
+    project_id       UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    user_id          UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    added_by_user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (project_id, user_id)
+);
+
+CREATE INDEX idx_project_members_user
+    ON project_members (user_id);
+
+-- The projects shape filters on project_members, so Electric has to see it
+SELECT electric_sync_table('public', 'project_members');
+
+ALTER TYPE audit_action ADD VALUE IF NOT EXISTS 'project_visibility_changed';
+ALTER TYPE audit_action ADD VALUE IF NOT EXISTS 'project_member_added';
+ALTER TYPE audit_action ADD VALUE IF NOT EXISTS 'project_member_removed';
@@ -0,0 +1,31 @@
+CREATE TYPE email_digest_frequency AS ENUM ('off', 'immediate', 'hourly', 'daily');
+
+-- Per-user email delivery settings. Users without a row get the defaults.
+CREATE TABLE user_notification_settings (
+    user_id              UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    email_frequency      email_digest_frequency NOT NULL DEFAULT 'daily',
+    -- Local wall-clock window in which no email is sent; may wrap midnight
+    quiet_hours_start    TIME,
+    quiet_hours_end      TIME,
+    utc_offset_minutes   INTEGER NOT NULL DEFAULT 0,
+    last_digest_sent_at  TIMESTAMPTZ,
+    -- Embedded in unsubscribe links so they work without signing in
+    unsubscribe_token    UUID NOT NULL UNIQUE DEFAULT gen_random_uuid(),
+    updated_at           TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK ((quiet_hours_start IS NULL) = (quiet_hours_end IS NULL)),
+    CHECK (utc_offset_minutes BETWEEN -720 AND 840)
+);
+
+ALTER TABLE project_notification_preferences
+    ADD COLUMN email_enabled BOOLEAN NOT NULL DEFAULT TRUE;
+
+-- Set once a notification has been emailed, or skipped because the user
+-- muted email for its project
+ALTER TABLE notifications ADD COLUMN emailed_at TIMESTAMPTZ;
+
+-- Don't email everything that piled up before digests existed
+UPDATE notifications SET emailed_at = NOW();
+
+CREATE INDEX idx_notifications_pending_email
+    ON notifications (user_id, created_at)
+    WHERE emailed_at IS NULL AND seen = FALSE AND dismissed_at IS NULL;
@@ -0,0 +1,20 @@
+ALTER TYPE notification_type ADD VALUE IF NOT EXISTS 'user_mentioned';
+
+-- Users @mentioned in an issue description (comment_id IS NULL) or in one of
+-- its comments
+CREATE TABLE issue_mentions (
+    id                UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    issue_id          UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    comment_id        UUID REFERENCES issue_comments(id) ON DELETE CASCADE,
+    mentioned_user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    author_user_id    UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at        TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (comment_id, mentioned_user_id)
+);
+
+CREATE UNIQUE INDEX idx_issue_mentions_description
+    ON issue_mentions (issue_id, mentioned_user_id)
+    WHERE comment_id IS NULL;
+
+CREATE INDEX idx_issue_mentions_user
+    ON issue_mentions (mentioned_user_id, created_at DESC);
@@ -0,0 +1,37 @@
+CREATE TYPE issue_event_type AS ENUM (
+    'created',
+    'status_changed',
+    'priority_changed',
+    'assignee_added',
+    'assignee_removed',
+    'start_date_changed',
+    'target_date_changed',
+    'completed_at_changed'
+);
+
+-- Append-only history of issue field changes. old_value/new_value hold the
+-- field's JSON value (a status id, priority, user id or timestamp).
+CREATE TABLE issue_events (
+    id            UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    issue_id      UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    actor_user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    event_type    issue_event_type NOT NULL,
+    old_value     JSONB,
+    new_value     JSONB,
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_issue_events_issue
+    ON issue_events (issue_id, created_at);
+
+-- Earlier history is unknown, so existing issues start in their current
+-- status from the moment they were created
+INSERT INTO issue_events (issue_id, event_type, new_value, created_at)
+SELECT
+    id,
+    'created',
+    jsonb_build_object('status_id', status_id, 'priority', priority),
+    created_at
+FROM issues;
+
+SELECT electric_sync_table('public', 'issue_events');
@@ -0,0 +1,13 @@
+-- What a status means, independent of its user-editable name: issues moved to
+-- a completed status get completed_at, and integrations find "in progress"
+-- and "done" without matching names
+CREATE TYPE status_category AS ENUM ('backlog', 'unstarted', 'started', 'completed', 'cancelled');
+
+ALTER TABLE project_statuses
+    ADD COLUMN category status_category NOT NULL DEFAULT 'unstarted';
+
+-- Statuses created from the defaults keep their meaning
+UPDATE project_statuses SET category = 'backlog' WHERE lower(trim(name)) = 'backlog';
+UPDATE project_statuses SET category = 'started' WHERE lower(trim(name)) IN ('in progress', 'in review');
+UPDATE project_statuses SET category = 'completed' WHERE lower(trim(name)) = 'done';
+UPDATE project_statuses SET category = 'cancelled' WHERE lower(trim(name)) IN ('cancelled', 'canceled');
@@ -0,0 +1,17 @@
+-- Domains are no longer first-come: any organization may add a domain, but it
+-- only takes effect once the organization proves control of it by publishing
+-- the verification token in a DNS TXT record. A domain can be verified by one
+-- organization at a time. Existing claims start out unverified.
+ALTER TABLE organization_domains
+    ADD COLUMN verification_token TEXT NOT NULL
+        DEFAULT replace(gen_random_uuid()::text, '-', ''),
+    ADD COLUMN verified_at TIMESTAMPTZ;
+
+ALTER TABLE organization_domains DROP CONSTRAINT organization_domains_pkey;
+ALTER TABLE organization_domains ADD PRIMARY KEY (organization_id, domain);
+
+CREATE UNIQUE INDEX idx_organization_domains_verified_domain
+    ON organization_domains (domain)
+    WHERE verified_at IS NOT NULL;
+
+DROP INDEX idx_organization_domains_organization;
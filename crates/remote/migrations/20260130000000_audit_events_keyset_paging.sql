@@ -0,0 +1,6 @@
+-- Events are paged on (created_at, id) so that events sharing a timestamp are
+-- neither skipped nor repeated across pages
+DROP INDEX idx_audit_events_organization_created;
+
+CREATE INDEX idx_audit_events_organization_created
+    ON audit_events (organization_id, created_at DESC, id DESC);
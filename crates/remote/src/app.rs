@@ -8,12 +8,13 @@ use crate::{
     AppState,
     auth::{
         GitHubOAuthProvider, GoogleOAuthProvider, JwtService, OAuthHandoffService,
-        OAuthTokenValidator, ProviderRegistry,
+        OAuthTokenValidator, OidcProvider, ProviderRegistry,
     },
     config::RemoteServerConfig,
     db,
     github_app::GitHubAppService,
     mail::LoopsMailer,
+    notification_digest::NotificationDigestJob,
     r2::R2Service,
     routes,
 };
@@ -60,6 +61,14 @@ impl Server {
             )?);
         }
 
+        if let Some(oidc) = auth_config.oidc() {
+            registry.register(
+                OidcProvider::discover(oidc)
+                    .await
+                    .context("failed to set up OIDC provider")?,
+            );
+        }
+
         if registry.is_empty() {
             bail!("no OAuth providers configured");
         }
@@ -78,7 +87,8 @@ impl Server {
 
         let api_key = std::env::var("LOOPS_EMAIL_API_KEY")
             .context("LOOPS_EMAIL_API_KEY environment variable is required")?;
-        let mailer = Arc::new(LoopsMailer::new(api_key));
+        let digest_template_id = std::env::var("LOOPS_DIGEST_TEMPLATE_ID").ok();
+        let mailer = Arc::new(LoopsMailer::new(api_key, digest_template_id));
 
         let server_public_base_url = config.server_public_base_url.clone().ok_or_else(|| {
             anyhow::anyhow!(
@@ -124,6 +134,19 @@ impl Server {
             }
         };
 
+        if mailer.supports_digests() {
+            NotificationDigestJob::new(
+                pool.clone(),
+                mailer.clone(),
+                server_public_base_url.clone(),
+            )
+            .spawn();
+        } else {
+            tracing::info!(
+                "Notification email digests disabled. Set LOOPS_DIGEST_TEMPLATE_ID to enable."
+            );
+        }
+
         let state = AppState::new(
             pool.clone(),
             config.clone(),
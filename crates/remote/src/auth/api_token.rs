@@ -0,0 +1,183 @@
+use axum::http::Method;
+use rand::{Rng, distr::Alphanumeric};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::db::api_tokens::ApiTokenScope;
+
+/// Prefix of every API token secret. It tells `require_session` to look the
+/// token up instead of decoding it as a JWT, and makes leaked tokens easy to
+/// spot in logs and secret scanners.
+pub const API_TOKEN_PREFIX: &str = "vkp_";
+const SECRET_LENGTH: usize = 40;
+/// Characters of the secret kept in clear text so users can tell tokens apart
+const DISPLAY_PREFIX_LENGTH: usize = 12;
+
+/// The API token a request was authenticated with
+#[derive(Debug, Clone)]
+pub struct ApiTokenContext {
+    pub id: Uuid,
+    pub scopes: Vec<ApiTokenScope>,
+}
+
+pub struct GeneratedApiToken {
+    pub secret: String,
+    pub hash: String,
+    pub display_prefix: String,
+}
+
+pub fn generate_api_token() -> GeneratedApiToken {
+    let random: String = rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(SECRET_LENGTH)
+        .map(char::from)
+        .collect();
+    let secret = format!("{API_TOKEN_PREFIX}{random}");
+    GeneratedApiToken {
+        hash: hash_api_token(&secret),
+        display_prefix: secret[..DISPLAY_PREFIX_LENGTH].to_string(),
+        secret,
+    }
+}
+
+pub fn hash_api_token(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
+
+/// Scope a token needs for a route, keyed by the route's matched path. Routes
+/// without a scope are not available to API tokens at all, which keeps account,
+/// organization and token management limited to signed-in users.
+pub fn required_scope(method: &Method, matched_path: &str) -> Option<ApiTokenScope> {
+    let path = matched_path.strip_prefix("/v1").unwrap_or(matched_path);
+    let is_read = method == Method::GET || method == Method::HEAD;
+
+    let issue_route = path.starts_with("/projects/{project_id}/issues")
+        || path == "/issues/{issue_id}/comments"
+        || path.starts_with("/comments/{comment_id}")
+        || path == "/reactions/{reaction_id}";
+    let project_route = matches!(
+        path,
+        "/projects"
+            | "/projects/{project_id}"
+            | "/projects/{project_id}/statuses"
+            | "/projects/{project_id}/tags"
+    );
+
+    if path == "/organizations/{org_id}/github-app/reviews" && method == Method::POST {
+        Some(ApiTokenScope::ReviewsTrigger)
+    } else if is_read && (issue_route || project_route) {
+        Some(ApiTokenScope::IssuesRead)
+    } else if !is_read && issue_route {
+        Some(ApiTokenScope::IssuesWrite)
+    } else {
+        None
+    }
+}
+
+/// Whether a token with `scopes` may call the route. Any token may look up
+/// the identity it belongs to.
+pub fn is_permitted(scopes: &[ApiTokenScope], method: &Method, matched_path: &str) -> bool {
+    let path = matched_path.strip_prefix("/v1").unwrap_or(matched_path);
+    if path == "/identity" && method == Method::GET {
+        return true;
+    }
+    required_scope(method, matched_path).is_some_and(|scope| scopes.contains(&scope))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_tokens_hash_to_stored_value() {
+        let token = generate_api_token();
+        assert!(token.secret.starts_with(API_TOKEN_PREFIX));
+        assert_eq!(token.secret.len(), API_TOKEN_PREFIX.len() + SECRET_LENGTH);
+        assert!(token.secret.starts_with(&token.display_prefix));
+        assert_eq!(token.hash, hash_api_token(&token.secret));
+        assert_eq!(token.hash.len(), 64);
+    }
+
+    #[test]
+    fn issue_routes_require_issue_scopes() {
+        assert_eq!(
+            required_scope(&Method::GET, "/v1/projects/{project_id}/issues"),
+            Some(ApiTokenScope::IssuesRead)
+        );
+        assert_eq!(
+            required_scope(
+                &Method::PATCH,
+                "/v1/projects/{project_id}/issues/{issue_id}"
+            ),
+            Some(ApiTokenScope::IssuesWrite)
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/v1/comments/{comment_id}/reactions"),
+            Some(ApiTokenScope::IssuesWrite)
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/v1/projects/{project_id}/statuses"),
+            Some(ApiTokenScope::IssuesRead)
+        );
+    }
+
+    #[test]
+    fn project_and_account_writes_are_not_available() {
+        assert_eq!(required_scope(&Method::POST, "/v1/projects"), None);
+        assert_eq!(
+            required_scope(&Method::POST, "/v1/projects/{project_id}/statuses"),
+            None
+        );
+        assert_eq!(required_scope(&Method::POST, "/v1/api-tokens"), None);
+        assert_eq!(
+            required_scope(&Method::GET, "/v1/organizations/{org_id}/members"),
+            None
+        );
+    }
+
+    #[test]
+    fn review_trigger_requires_review_scope() {
+        let path = "/v1/organizations/{org_id}/github-app/reviews";
+        assert_eq!(
+            required_scope(&Method::POST, path),
+            Some(ApiTokenScope::ReviewsTrigger)
+        );
+        assert!(!is_permitted(
+            &[ApiTokenScope::IssuesRead, ApiTokenScope::IssuesWrite],
+            &Method::POST,
+            path
+        ));
+        assert!(is_permitted(
+            &[ApiTokenScope::ReviewsTrigger],
+            &Method::POST,
+            path
+        ));
+    }
+
+    #[test]
+    fn debug_review_trigger_is_not_available() {
+        assert_eq!(
+            required_scope(&Method::POST, "/v1/debug/pr-review/trigger"),
+            None
+        );
+        assert!(!is_permitted(
+            &[ApiTokenScope::ReviewsTrigger],
+            &Method::POST,
+            "/v1/debug/pr-review/trigger"
+        ));
+    }
+
+    #[test]
+    fn identity_is_available_to_every_token() {
+        assert!(is_permitted(
+            &[ApiTokenScope::ReviewsTrigger],
+            &Method::GET,
+            "/v1/identity"
+        ));
+        assert!(!is_permitted(
+            &[ApiTokenScope::IssuesRead],
+            &Method::POST,
+            "/v1/projects/{project_id}/issues"
+        ));
+    }
+}
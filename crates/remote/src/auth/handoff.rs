@@ -14,7 +14,9 @@ use uuid::Uuid;
 use super::{
     ProviderRegistry,
     jwt::{JwtError, JwtService},
+    oidc::{OIDC_PROVIDER_NAME, code_challenge, generate_code_verifier},
     provider::{AuthorizationGrant, AuthorizationProvider, ProviderUser},
+    sso::email_domain,
 };
 use crate::{
     configure_user_scope,
@@ -26,6 +28,7 @@ use crate::{
             OAuthHandoffRepository,
         },
         oauth_accounts::{OAuthAccountError, OAuthAccountInsert, OAuthAccountRepository},
+        organization_sso::{OrganizationSsoError, OrganizationSsoRepository},
         organizations::OrganizationRepository,
         users::{UpsertUser, UserRepository},
     },
@@ -67,6 +70,8 @@ pub enum HandoffError {
     Jwt(#[from] JwtError),
     #[error(transparent)]
     Authorization(#[from] OAuthHandoffError),
+    #[error(transparent)]
+    Sso(#[from] OrganizationSsoError),
 }
 
 #[derive(Debug, Clone)]
@@ -145,6 +150,7 @@ impl OAuthHandoffService {
         }
 
         let state = generate_state();
+        let provider_code_verifier = provider.uses_pkce().then(generate_code_verifier);
         let expires_at = Utc::now() + Duration::minutes(HANDOFF_TTL);
         let repo = OAuthHandoffRepository::new(&self.pool);
         let record = repo
@@ -153,6 +159,7 @@ impl OAuthHandoffService {
                 state: &state,
                 return_to: return_to_url.as_str(),
                 app_challenge,
+                provider_code_verifier: provider_code_verifier.as_deref(),
                 expires_at,
             })
             .await?;
@@ -204,8 +211,16 @@ impl OAuthHandoffService {
             provider.name()
         );
 
+        let challenge = match record.provider_code_verifier.as_deref() {
+            Some(verifier) => Some(code_challenge(verifier)),
+            None if provider.uses_pkce() => {
+                return Err(HandoffError::Failed("missing_code_verifier".into()));
+            }
+            None => None,
+        };
+
         provider
-            .authorize_url(&record.state, &redirect_uri)
+            .authorize_url(&record.state, &redirect_uri, challenge.as_deref())
             .map(|url| url.into())
             .map_err(HandoffError::Provider)
     }
@@ -262,7 +277,11 @@ impl OAuthHandoffService {
         );
 
         let grant = provider
-            .exchange_code(code, &redirect_uri)
+            .exchange_code(
+                code,
+                &redirect_uri,
+                record.provider_code_verifier.as_deref(),
+            )
             .await
             .map_err(HandoffError::Provider)?;
 
@@ -270,6 +289,19 @@ impl OAuthHandoffService {
 
         let user = self.upsert_identity(&provider, &user_profile).await?;
 
+        if let Some(err_code) = self
+            .apply_sso_policy(&provider, &user_profile, user.id)
+            .await?
+        {
+            repo.set_status(record.id, AuthorizationStatus::Error, Some(err_code))
+                .await?;
+            return Ok(CallbackResult::Error {
+                handoff_id: Some(record.id),
+                return_to: Some(record.return_to.clone()),
+                error: err_code.to_string(),
+            });
+        }
+
         let provider_token_details = crate::auth::ProviderTokenDetails {
             provider: provider.name().to_string(),
             access_token: grant.access_token.expose_secret().to_string(),
@@ -281,7 +313,7 @@ impl OAuthHandoffService {
         };
 
         let session_repo = AuthSessionRepository::new(&self.pool);
-        let session_record = session_repo.create(user.id, None).await?;
+        let session_record = session_repo.create(user.id, provider.name(), None).await?;
 
         let app_code = generate_app_code();
         let app_code_hash = hash_sha256_hex(&app_code);
@@ -420,6 +452,33 @@ impl OAuthHandoffService {
         }
     }
 
+    /// Members of organizations that require SSO may only sign in with the
+    /// OIDC provider. SSO sign-ins join organizations that claimed the user's
+    /// email domain. Returns an error code when the sign-in is refused.
+    async fn apply_sso_policy(
+        &self,
+        provider: &Arc<dyn AuthorizationProvider>,
+        profile: &ProviderUser,
+        user_id: Uuid,
+    ) -> Result<Option<&'static str>, HandoffError> {
+        if provider.name() != OIDC_PROVIDER_NAME {
+            if OrganizationSsoRepository::is_sso_required_for_user(&self.pool, user_id).await? {
+                return Ok(Some("sso_required"));
+            }
+            return Ok(None);
+        }
+
+        // Only a verified address from the identity provider decides membership
+        if let Some(domain) = profile.email.as_deref().and_then(email_domain)
+            && let Some(organization_id) =
+                OrganizationSsoRepository::auto_join_by_domain(&self.pool, user_id, &domain).await?
+        {
+            tracing::info!(%user_id, %organization_id, %domain, "user joined organization by email domain");
+        }
+
+        Ok(None)
+    }
+
     async fn upsert_identity(
         &self,
         provider: &Arc<dyn AuthorizationProvider>,
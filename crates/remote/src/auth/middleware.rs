@@ -1,6 +1,6 @@
 use axum::{
     body::Body,
-    extract::State,
+    extract::{MatchedPath, State},
     http::{Request, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
@@ -10,11 +10,14 @@ use chrono::{DateTime, Utc};
 use tracing::warn;
 use uuid::Uuid;
 
+use super::api_token::{API_TOKEN_PREFIX, ApiTokenContext, hash_api_token, is_permitted};
 use crate::{
     AppState, configure_user_scope,
     db::{
+        api_tokens::ApiTokenRepository,
         auth::{AuthSessionError, AuthSessionRepository, MAX_SESSION_INACTIVITY_DURATION},
         identity_errors::IdentityError,
+        service_accounts::ServiceAccountRepository,
         users::{User, UserRepository},
     },
 };
@@ -22,9 +25,15 @@ use crate::{
 #[derive(Clone)]
 pub struct RequestContext {
     pub user: User,
-    pub session_id: Uuid,
+    /// Set when the request was authenticated with a session JWT
+    pub session_id: Option<Uuid>,
+    /// Provider the session was signed in with
+    pub session_provider: Option<String>,
     #[allow(dead_code)]
-    pub access_token_expires_at: DateTime<Utc>,
+    pub access_token_expires_at: Option<DateTime<Utc>>,
+    /// Set when the request was authenticated with an API token
+    #[allow(dead_code)]
+    pub api_token: Option<ApiTokenContext>,
 }
 
 pub async fn require_session(
@@ -37,6 +46,10 @@ pub async fn require_session(
         None => return StatusCode::UNAUTHORIZED.into_response(),
     };
 
+    if bearer.starts_with(API_TOKEN_PREFIX) {
+        return require_api_token(state, req, next, &bearer).await;
+    }
+
     let jwt = state.jwt();
     let identity = match jwt.decode_access_token(&bearer) {
         Ok(details) => details,
@@ -80,35 +93,128 @@ pub async fn require_session(
         return StatusCode::UNAUTHORIZED.into_response();
     }
 
-    let user_repo = UserRepository::new(pool);
-    let user = match user_repo.fetch_user(identity.user_id).await {
+    let user = match load_user(&state, identity.user_id).await {
         Ok(user) => user,
-        Err(IdentityError::NotFound) => {
-            warn!("user `{}` missing", identity.user_id);
+        Err(response) => return response,
+    };
+
+    configure_user_scope(user.id, user.username.as_deref(), Some(user.email.as_str()));
+
+    req.extensions_mut().insert(RequestContext {
+        user,
+        session_id: Some(session.id),
+        session_provider: session.provider.clone(),
+        access_token_expires_at: Some(identity.expires_at),
+        api_token: None,
+    });
+
+    match session_repo.touch(session.id).await {
+        Ok(_) => {}
+        Err(error) => warn!(?error, "failed to update session last-used timestamp"),
+    }
+
+    next.run(req).await
+}
+
+/// Authenticate a personal access token or service account token. Tokens are
+/// limited to the routes their scopes allow.
+async fn require_api_token(
+    state: AppState,
+    mut req: Request<Body>,
+    next: Next,
+    secret: &str,
+) -> Response {
+    let pool = state.pool();
+    let token = match ApiTokenRepository::find_by_hash(pool, &hash_api_token(secret)).await {
+        Ok(Some(token)) => token,
+        Ok(None) => {
+            warn!("unknown api token");
             return StatusCode::UNAUTHORIZED.into_response();
         }
-        Err(IdentityError::Database(error)) => {
-            warn!(?error, "failed to load user");
+        Err(error) => {
+            warn!(?error, "failed to load api token");
             return StatusCode::INTERNAL_SERVER_ERROR.into_response();
         }
-        Err(_) => {
-            warn!("unexpected error loading user");
+    };
+
+    if token.revoked_at.is_some() {
+        warn!("api token `{}` rejected (revoked)", token.id);
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+    if token.is_expired(Utc::now()) {
+        warn!("api token `{}` rejected (expired)", token.id);
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+
+    match ServiceAccountRepository::find_by_user_id(pool, token.user_id).await {
+        Ok(Some(account)) if account.disabled_at.is_some() => {
+            warn!(
+                "api token `{}` rejected (service account disabled)",
+                token.id
+            );
+            return StatusCode::UNAUTHORIZED.into_response();
+        }
+        Ok(_) => {}
+        Err(error) => {
+            warn!(?error, "failed to load service account");
             return StatusCode::INTERNAL_SERVER_ERROR.into_response();
         }
+    }
+
+    let permitted = req
+        .extensions()
+        .get::<MatchedPath>()
+        .is_some_and(|path| is_permitted(&token.scopes, req.method(), path.as_str()));
+    if !permitted {
+        warn!(
+            "api token `{}` lacks scope for {} {}",
+            token.id,
+            req.method(),
+            req.uri().path()
+        );
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    let user = match load_user(&state, token.user_id).await {
+        Ok(user) => user,
+        Err(response) => return response,
     };
 
     configure_user_scope(user.id, user.username.as_deref(), Some(user.email.as_str()));
 
     req.extensions_mut().insert(RequestContext {
         user,
-        session_id: session.id,
-        access_token_expires_at: identity.expires_at,
+        session_id: None,
+        session_provider: None,
+        access_token_expires_at: token.expires_at,
+        api_token: Some(ApiTokenContext {
+            id: token.id,
+            scopes: token.scopes,
+        }),
     });
 
-    match session_repo.touch(session.id).await {
-        Ok(_) => {}
-        Err(error) => warn!(?error, "failed to update session last-used timestamp"),
+    if let Err(error) = ApiTokenRepository::touch(pool, token.id).await {
+        warn!(?error, "failed to update api token last-used timestamp");
     }
 
     next.run(req).await
 }
+
+async fn load_user(state: &AppState, user_id: Uuid) -> Result<User, Response> {
+    let user_repo = UserRepository::new(state.pool());
+    match user_repo.fetch_user(user_id).await {
+        Ok(user) => Ok(user),
+        Err(IdentityError::NotFound) => {
+            warn!("user `{}` missing", user_id);
+            Err(StatusCode::UNAUTHORIZED.into_response())
+        }
+        Err(IdentityError::Database(error)) => {
+            warn!(?error, "failed to load user");
+            Err(StatusCode::INTERNAL_SERVER_ERROR.into_response())
+        }
+        Err(_) => {
+            warn!("unexpected error loading user");
+            Err(StatusCode::INTERNAL_SERVER_ERROR.into_response())
+        }
+    }
+}
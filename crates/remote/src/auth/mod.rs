@@ -1,13 +1,18 @@
+mod api_token;
 mod handoff;
 mod jwt;
 mod middleware;
 mod oauth_token_validator;
+mod oidc;
 mod provider;
+pub(crate) mod sso;
 
+pub use api_token::generate_api_token;
 pub use handoff::{CallbackResult, HandoffError, OAuthHandoffService};
 pub use jwt::{JwtError, JwtService};
 pub use middleware::{RequestContext, require_session};
 pub use oauth_token_validator::{OAuthTokenValidationError, OAuthTokenValidator};
+pub use oidc::{OIDC_PROVIDER_NAME, OidcProvider};
 pub use provider::{
     GitHubOAuthProvider, GoogleOAuthProvider, ProviderRegistry, ProviderTokenDetails,
 };
@@ -0,0 +1,477 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::Duration;
+use rand::{Rng, distr::Alphanumeric};
+use reqwest::Client;
+use secrecy::{ExposeSecret, SecretString};
+use serde::Deserialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tracing::info;
+use url::Url;
+
+use super::provider::{
+    AuthorizationGrant, AuthorizationProvider, ProviderTokenDetails, ProviderUser,
+    TokenValidationError,
+};
+use crate::config::{OidcClaimsConfig, OidcProviderConfig};
+
+/// Name the OIDC provider is registered under. Sessions signed in with it
+/// count as SSO sessions.
+pub const OIDC_PROVIDER_NAME: &str = "oidc";
+
+const USER_AGENT: &str = "VibeKanbanRemote/1.0";
+const CODE_VERIFIER_LENGTH: usize = 64;
+const TOKEN_EXPIRATION_LEEWAY_SECONDS: i64 = 20;
+const RETRY_INTERVAL_SECONDS: u64 = 2;
+
+#[derive(Debug, Clone, Deserialize)]
+struct DiscoveryDocument {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    userinfo_endpoint: String,
+    #[serde(default)]
+    code_challenge_methods_supported: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    token_type: String,
+    scope: Option<String>,
+    expires_in: Option<i64>,
+    refresh_token: Option<String>,
+    id_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenErrorResponse {
+    error: String,
+    error_description: Option<String>,
+}
+
+#[derive(Debug, Error)]
+enum TokenRequestError {
+    /// The provider refused the grant, e.g. an expired code or revoked refresh token
+    #[error("rejected by provider: {0}")]
+    Rejected(String),
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+}
+
+/// OpenID Connect provider configured from the issuer's discovery document
+pub struct OidcProvider {
+    client: Client,
+    client_id: String,
+    client_secret: SecretString,
+    claims: OidcClaimsConfig,
+    discovery: DiscoveryDocument,
+}
+
+impl OidcProvider {
+    /// Fetch the issuer's discovery document and build the provider
+    pub async fn discover(config: &OidcProviderConfig) -> Result<Self> {
+        let client = Client::builder().user_agent(USER_AGENT).build()?;
+        let issuer = config.issuer_url.trim_end_matches('/');
+        let discovery: DiscoveryDocument = client
+            .get(format!("{issuer}/.well-known/openid-configuration"))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("failed to parse OIDC discovery document")?;
+
+        if discovery.issuer.trim_end_matches('/') != issuer {
+            anyhow::bail!(
+                "OIDC discovery issuer `{}` does not match configured issuer `{issuer}`",
+                discovery.issuer
+            );
+        }
+        if !discovery.code_challenge_methods_supported.is_empty()
+            && !discovery
+                .code_challenge_methods_supported
+                .iter()
+                .any(|method| method == "S256")
+        {
+            anyhow::bail!("OIDC provider `{issuer}` does not support S256 PKCE");
+        }
+
+        info!(issuer, "OIDC provider discovered");
+
+        Ok(Self {
+            client,
+            client_id: config.client_id.clone(),
+            client_secret: config.client_secret.clone(),
+            claims: config.claims.clone(),
+            discovery,
+        })
+    }
+
+    async fn request_tokens(
+        &self,
+        form: &[(&str, &str)],
+    ) -> Result<TokenResponse, TokenRequestError> {
+        let mut params = vec![
+            ("client_id", self.client_id.as_str()),
+            ("client_secret", self.client_secret.expose_secret()),
+        ];
+        params.extend_from_slice(form);
+
+        let response = self
+            .client
+            .post(&self.discovery.token_endpoint)
+            .header("Accept", "application/json")
+            .form(&params)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status.is_client_error() {
+            let detail = match response.json::<TokenErrorResponse>().await {
+                Ok(error) => error.error_description.unwrap_or(error.error),
+                Err(_) => status.to_string(),
+            };
+            return Err(TokenRequestError::Rejected(detail));
+        }
+
+        Ok(response.error_for_status()?.json().await?)
+    }
+
+    async fn refresh_token(
+        &self,
+        refresh_token: &str,
+        max_retries: u32,
+    ) -> Result<ProviderTokenDetails, TokenValidationError> {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            match self
+                .request_tokens(&[
+                    ("grant_type", "refresh_token"),
+                    ("refresh_token", refresh_token),
+                ])
+                .await
+            {
+                Ok(tokens) => {
+                    return Ok(ProviderTokenDetails {
+                        provider: OIDC_PROVIDER_NAME.to_string(),
+                        access_token: tokens.access_token,
+                        refresh_token: Some(
+                            tokens
+                                .refresh_token
+                                .unwrap_or_else(|| refresh_token.to_string()),
+                        ),
+                        expires_at: tokens
+                            .expires_in
+                            .map(|seconds| chrono::Utc::now().timestamp() + seconds),
+                    });
+                }
+                Err(TokenRequestError::Rejected(_)) => {
+                    return Err(TokenValidationError::InvalidOrRevoked);
+                }
+                Err(err) => {
+                    if attempt >= max_retries {
+                        return Err(TokenValidationError::Temporary(format!(
+                            "oidc token refresh failed: {err}"
+                        )));
+                    }
+                    tokio::time::sleep(tokio::time::Duration::from_secs(RETRY_INTERVAL_SECONDS))
+                        .await;
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl AuthorizationProvider for OidcProvider {
+    fn name(&self) -> &'static str {
+        OIDC_PROVIDER_NAME
+    }
+
+    fn scopes(&self) -> &[&str] {
+        &["openid", "email", "profile"]
+    }
+
+    fn uses_pkce(&self) -> bool {
+        true
+    }
+
+    fn authorize_url(
+        &self,
+        state: &str,
+        redirect_uri: &str,
+        code_challenge: Option<&str>,
+    ) -> Result<Url> {
+        let mut url = Url::parse(&self.discovery.authorization_endpoint)?;
+        {
+            let mut qp = url.query_pairs_mut();
+            qp.append_pair("client_id", &self.client_id);
+            qp.append_pair("redirect_uri", redirect_uri);
+            qp.append_pair("response_type", "code");
+            qp.append_pair("scope", &self.scopes().join(" "));
+            qp.append_pair("state", state);
+            if let Some(challenge) = code_challenge {
+                qp.append_pair("code_challenge", challenge);
+                qp.append_pair("code_challenge_method", "S256");
+            }
+        }
+        Ok(url)
+    }
+
+    async fn exchange_code(
+        &self,
+        code: &str,
+        redirect_uri: &str,
+        code_verifier: Option<&str>,
+    ) -> Result<AuthorizationGrant> {
+        let mut form = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", redirect_uri),
+        ];
+        if let Some(verifier) = code_verifier {
+            form.push(("code_verifier", verifier));
+        }
+        let tokens = self
+            .request_tokens(&form)
+            .await
+            .map_err(|err| anyhow::anyhow!("oidc token exchange failed: {err}"))?;
+
+        Ok(AuthorizationGrant {
+            access_token: SecretString::new(tokens.access_token.into()),
+            token_type: tokens.token_type,
+            scopes: tokens
+                .scope
+                .unwrap_or_default()
+                .split_whitespace()
+                .map(str::to_string)
+                .collect(),
+            refresh_token: tokens.refresh_token.map(|v| SecretString::new(v.into())),
+            expires_in: tokens.expires_in.map(Duration::seconds),
+            id_token: tokens.id_token.map(|v| SecretString::new(v.into())),
+        })
+    }
+
+    async fn fetch_user(&self, access_token: &SecretString) -> Result<ProviderUser> {
+        let claims: Value = self
+            .client
+            .get(&self.discovery.userinfo_endpoint)
+            .bearer_auth(access_token.expose_secret())
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("failed to parse OIDC userinfo response")?;
+
+        map_claims(&claims, &self.claims)
+    }
+
+    async fn validate_token(
+        &self,
+        token_details: &ProviderTokenDetails,
+        max_retries: u32,
+    ) -> Result<Option<ProviderTokenDetails>, TokenValidationError> {
+        if let Some(expires_at) = token_details.expires_at
+            && chrono::Utc::now().timestamp() >= expires_at - TOKEN_EXPIRATION_LEEWAY_SECONDS
+        {
+            let Some(refresh_token) = &token_details.refresh_token else {
+                return Err(TokenValidationError::InvalidOrRevoked);
+            };
+            info!("Token expired, attempting refresh for OIDC");
+            return self
+                .refresh_token(refresh_token, max_retries)
+                .await
+                .map(Some);
+        }
+
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let status = match self
+                .client
+                .get(&self.discovery.userinfo_endpoint)
+                .bearer_auth(&token_details.access_token)
+                .send()
+                .await
+            {
+                Ok(response) => response.status(),
+                Err(err) => {
+                    if attempt >= max_retries {
+                        return Err(TokenValidationError::Temporary(format!(
+                            "userinfo request failed: {err}"
+                        )));
+                    }
+                    tokio::time::sleep(tokio::time::Duration::from_secs(RETRY_INTERVAL_SECONDS))
+                        .await;
+                    continue;
+                }
+            };
+
+            match status {
+                reqwest::StatusCode::OK => return Ok(None),
+                reqwest::StatusCode::UNAUTHORIZED => {
+                    let Some(refresh_token) = &token_details.refresh_token else {
+                        return Err(TokenValidationError::InvalidOrRevoked);
+                    };
+                    info!("Token rejected during validation, attempting refresh");
+                    return self
+                        .refresh_token(refresh_token, max_retries)
+                        .await
+                        .map(Some);
+                }
+                status => {
+                    if attempt >= max_retries {
+                        return Err(TokenValidationError::Temporary(format!(
+                            "unexpected userinfo status: {status}"
+                        )));
+                    }
+                    tokio::time::sleep(tokio::time::Duration::from_secs(RETRY_INTERVAL_SECONDS))
+                        .await;
+                }
+            }
+        }
+    }
+}
+
+/// Build the user profile from userinfo claims. The email is dropped unless the
+/// provider marks it verified, since it decides domain auto-join.
+fn map_claims(claims: &Value, mapping: &OidcClaimsConfig) -> Result<ProviderUser> {
+    let claim = |name: &str| {
+        claims
+            .get(name)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+
+    let id = claim("sub").context("OIDC userinfo response has no `sub` claim")?;
+    let email_verified = match claims.get("email_verified") {
+        Some(Value::Bool(verified)) => *verified,
+        Some(Value::String(verified)) => verified.eq_ignore_ascii_case("true"),
+        _ => false,
+    };
+    let email = claim(&mapping.email).filter(|_| email_verified);
+    let name = claim(&mapping.name).or_else(|| match (claim("given_name"), claim("family_name")) {
+        (Some(first), Some(last)) => Some(format!("{first} {last}")),
+        (first, last) => first.or(last),
+    });
+
+    Ok(ProviderUser {
+        id,
+        login: claim(&mapping.username),
+        email,
+        name,
+        avatar_url: claim("picture"),
+    })
+}
+
+pub(super) fn generate_code_verifier() -> String {
+    rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(CODE_VERIFIER_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+pub(super) fn code_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn default_mapping() -> OidcClaimsConfig {
+        OidcClaimsConfig {
+            email: "email".to_string(),
+            name: "name".to_string(),
+            username: "preferred_username".to_string(),
+        }
+    }
+
+    #[test]
+    fn code_challenge_matches_rfc7636_example() {
+        assert_eq!(
+            code_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+        assert_eq!(generate_code_verifier().len(), CODE_VERIFIER_LENGTH);
+    }
+
+    #[test]
+    fn maps_standard_claims() {
+        let user = map_claims(
+            &json!({
+                "sub": "abc-123",
+                "email": "ada@example.com",
+                "email_verified": true,
+                "given_name": "Ada",
+                "family_name": "Lovelace",
+                "preferred_username": "ada",
+                "picture": "https://example.com/ada.png"
+            }),
+            &default_mapping(),
+        )
+        .unwrap();
+
+        assert_eq!(user.id, "abc-123");
+        assert_eq!(user.email.as_deref(), Some("ada@example.com"));
+        assert_eq!(user.name.as_deref(), Some("Ada Lovelace"));
+        assert_eq!(user.login.as_deref(), Some("ada"));
+        assert_eq!(
+            user.avatar_url.as_deref(),
+            Some("https://example.com/ada.png")
+        );
+    }
+
+    #[test]
+    fn uses_configured_claim_names() {
+        let mapping = OidcClaimsConfig {
+            email: "upn".to_string(),
+            name: "display_name".to_string(),
+            username: "upn".to_string(),
+        };
+        let user = map_claims(
+            &json!({
+                "sub": "abc-123",
+                "upn": "ada@corp.example",
+                "email_verified": true,
+                "display_name": "Ada L."
+            }),
+            &mapping,
+        )
+        .unwrap();
+
+        assert_eq!(user.email.as_deref(), Some("ada@corp.example"));
+        assert_eq!(user.login.as_deref(), Some("ada@corp.example"));
+        assert_eq!(user.name.as_deref(), Some("Ada L."));
+    }
+
+    #[test]
+    fn drops_unverified_email_and_requires_subject() {
+        let user = map_claims(
+            &json!({ "sub": "abc-123", "email": "ada@example.com", "email_verified": "false" }),
+            &default_mapping(),
+        )
+        .unwrap();
+        assert_eq!(user.email, None);
+
+        let user = map_claims(
+            &json!({ "sub": "abc-123", "email": "ada@example.com" }),
+            &default_mapping(),
+        )
+        .unwrap();
+        assert_eq!(user.email, None);
+
+        assert!(map_claims(&json!({ "email": "ada@example.com" }), &default_mapping()).is_err());
+    }
+}
@@ -61,8 +61,24 @@ pub struct ProviderTokenDetails {
 pub trait AuthorizationProvider: Send + Sync {
     fn name(&self) -> &'static str;
     fn scopes(&self) -> &[&str];
-    fn authorize_url(&self, state: &str, redirect_uri: &str) -> Result<Url>;
-    async fn exchange_code(&self, code: &str, redirect_uri: &str) -> Result<AuthorizationGrant>;
+    /// Whether the authorization code flow is protected with PKCE. The handoff
+    /// then passes a code challenge to `authorize_url` and the matching
+    /// verifier to `exchange_code`.
+    fn uses_pkce(&self) -> bool {
+        false
+    }
+    fn authorize_url(
+        &self,
+        state: &str,
+        redirect_uri: &str,
+        code_challenge: Option<&str>,
+    ) -> Result<Url>;
+    async fn exchange_code(
+        &self,
+        code: &str,
+        redirect_uri: &str,
+        code_verifier: Option<&str>,
+    ) -> Result<AuthorizationGrant>;
     async fn fetch_user(&self, access_token: &SecretString) -> Result<ProviderUser>;
     async fn validate_token(
         &self,
@@ -167,7 +183,12 @@ impl AuthorizationProvider for GitHubOAuthProvider {
         &["read:user", "user:email"]
     }
 
-    fn authorize_url(&self, state: &str, redirect_uri: &str) -> Result<Url> {
+    fn authorize_url(
+        &self,
+        state: &str,
+        redirect_uri: &str,
+        _code_challenge: Option<&str>,
+    ) -> Result<Url> {
         let mut url = Url::parse("https://github.com/login/oauth/authorize")?;
         {
             let mut qp = url.query_pairs_mut();
@@ -180,7 +201,12 @@ impl AuthorizationProvider for GitHubOAuthProvider {
         Ok(url)
     }
 
-    async fn exchange_code(&self, code: &str, redirect_uri: &str) -> Result<AuthorizationGrant> {
+    async fn exchange_code(
+        &self,
+        code: &str,
+        redirect_uri: &str,
+        _code_verifier: Option<&str>,
+    ) -> Result<AuthorizationGrant> {
         let response = self
             .client
             .post("https://github.com/login/oauth/access_token")
@@ -500,7 +526,12 @@ impl AuthorizationProvider for GoogleOAuthProvider {
         &["openid", "email", "profile"]
     }
 
-    fn authorize_url(&self, state: &str, redirect_uri: &str) -> Result<Url> {
+    fn authorize_url(
+        &self,
+        state: &str,
+        redirect_uri: &str,
+        _code_challenge: Option<&str>,
+    ) -> Result<Url> {
         let mut url = Url::parse("https://accounts.google.com/o/oauth2/v2/auth")?;
         {
             let mut qp = url.query_pairs_mut();
@@ -515,7 +546,12 @@ impl AuthorizationProvider for GoogleOAuthProvider {
         Ok(url)
     }
 
-    async fn exchange_code(&self, code: &str, redirect_uri: &str) -> Result<AuthorizationGrant> {
+    async fn exchange_code(
+        &self,
+        code: &str,
+        redirect_uri: &str,
+        _code_verifier: Option<&str>,
+    ) -> Result<AuthorizationGrant> {
         let response = self
             .client
             .post("https://oauth2.googleapis.com/token")
@@ -0,0 +1,166 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// Subdomain holding the TXT record that proves control of a claimed domain
+const VERIFICATION_RECORD_LABEL: &str = "_vibe-kanban-verification";
+const VERIFICATION_VALUE_PREFIX: &str = "vibe-kanban-verification=";
+/// DNS record type number of TXT records
+const TXT_RECORD_TYPE: u16 = 16;
+
+/// Lowercased domain part of an email address
+pub fn email_domain(email: &str) -> Option<String> {
+    let (local, domain) = email.trim().rsplit_once('@')?;
+    if local.is_empty() {
+        return None;
+    }
+    normalize_domain(domain)
+}
+
+/// Validate a domain an organization wants to claim and bring it into the
+/// form it is stored in. Rejects anything that is not a plain DNS name with
+/// at least two labels.
+pub fn normalize_domain(input: &str) -> Option<String> {
+    let domain = input.trim().trim_end_matches('.').to_ascii_lowercase();
+    if domain.len() > 253 {
+        return None;
+    }
+    let labels: Vec<&str> = domain.split('.').collect();
+    let valid = labels.len() >= 2
+        && labels.iter().all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || ch == '-')
+        });
+    valid.then_some(domain)
+}
+
+/// Name of the TXT record an organization publishes to verify `domain`
+pub fn verification_record_name(domain: &str) -> String {
+    format!("{VERIFICATION_RECORD_LABEL}.{domain}")
+}
+
+/// Value the TXT record must hold for the organization's claim to verify
+pub fn verification_record_value(token: &str) -> String {
+    format!("{VERIFICATION_VALUE_PREFIX}{token}")
+}
+
+#[derive(Debug, Deserialize)]
+struct DnsJsonResponse {
+    #[serde(rename = "Status")]
+    status: u32,
+    #[serde(rename = "Answer", default)]
+    answer: Vec<DnsJsonAnswer>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DnsJsonAnswer {
+    #[serde(rename = "type")]
+    record_type: u16,
+    data: String,
+}
+
+/// Look up the TXT records of `name` through a DNS-over-HTTPS resolver that
+/// speaks the JSON API (Cloudflare, Google). A name without records resolves
+/// to an empty list.
+pub async fn lookup_txt_records(
+    client: &reqwest::Client,
+    resolver_url: &str,
+    name: &str,
+) -> Result<Vec<String>> {
+    let response: DnsJsonResponse = client
+        .get(resolver_url)
+        .query(&[("name", name), ("type", "TXT")])
+        .header(reqwest::header::ACCEPT, "application/dns-json")
+        .send()
+        .await
+        .context("DNS-over-HTTPS request failed")?
+        .error_for_status()
+        .context("DNS-over-HTTPS resolver returned an error")?
+        .json()
+        .await
+        .context("invalid DNS-over-HTTPS response")?;
+
+    // 3 is NXDOMAIN; anything else non-zero means the lookup itself failed
+    match response.status {
+        0 | 3 => {}
+        status => anyhow::bail!("DNS lookup for {name} failed with status {status}"),
+    }
+
+    Ok(response
+        .answer
+        .into_iter()
+        .filter(|answer| answer.record_type == TXT_RECORD_TYPE)
+        .map(|answer| parse_txt_data(&answer.data))
+        .collect())
+}
+
+/// Join the character-strings of a TXT record as resolvers print them, e.g.
+/// `"first part" "second part"`, into the record's value
+fn parse_txt_data(data: &str) -> String {
+    let data = data.trim();
+    if !data.starts_with('"') {
+        return data.to_string();
+    }
+
+    let mut value = String::new();
+    let mut in_string = false;
+    let mut chars = data.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' => in_string = !in_string,
+            '\\' if in_string => value.extend(chars.next()),
+            ch if in_string => value.push(ch),
+            _ => {}
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_email_domain() {
+        assert_eq!(
+            email_domain("Ada@Corp.Example.com").as_deref(),
+            Some("corp.example.com")
+        );
+        assert_eq!(email_domain("@example.com"), None);
+        assert_eq!(email_domain("not-an-email"), None);
+    }
+
+    #[test]
+    fn normalizes_claimed_domains() {
+        assert_eq!(
+            normalize_domain(" Example.COM. ").as_deref(),
+            Some("example.com")
+        );
+        assert_eq!(normalize_domain("localhost"), None);
+        assert_eq!(normalize_domain("exa mple.com"), None);
+        assert_eq!(normalize_domain("-bad.example.com"), None);
+        assert_eq!(normalize_domain("ada@example.com"), None);
+    }
+
+    #[test]
+    fn parses_txt_record_data() {
+        assert_eq!(
+            parse_txt_data("\"vibe-kanban-verification=abc\""),
+            "vibe-kanban-verification=abc"
+        );
+        assert_eq!(
+            parse_txt_data("\"vibe-kanban-\" \"verification=abc\""),
+            "vibe-kanban-verification=abc"
+        );
+        assert_eq!(parse_txt_data("\"say \\\"hi\\\"\""), "say \"hi\"");
+        assert_eq!(parse_txt_data("unquoted"), "unquoted");
+        assert_eq!(
+            verification_record_name("example.com"),
+            "_vibe-kanban-verification.example.com"
+        );
+    }
+}
@@ -6,6 +6,7 @@ use remote::{
         issue_comment_reactions::IssueCommentReaction,
         issue_comments::IssueComment,
         issue_dependencies::IssueDependency,
+        issue_events::{IssueEvent, IssueEventType},
         issue_followers::IssueFollower,
         issue_tags::IssueTag,
         issues::Issue,
@@ -13,7 +14,7 @@ use remote::{
         project_statuses::ProjectStatus,
         projects::Project,
         tags::Tag,
-        types::{IssuePriority, WorkspacePrStatus},
+        types::{IssuePriority, StatusCategory, WorkspacePrStatus},
         users::UserData,
         workspaces::Workspace,
     },
@@ -81,7 +82,10 @@ fn export_shapes() -> String {
         IssueDependency::decl(),
         IssueComment::decl(),
         IssueCommentReaction::decl(),
+        IssueEvent::decl(),
+        IssueEventType::decl(),
         IssuePriority::decl(),
+        StatusCategory::decl(),
         WorkspacePrStatus::decl(),
         UserData::decl(),
     ];
@@ -16,6 +16,9 @@ pub struct RemoteServerConfig {
     pub r2: Option<R2Config>,
     pub review_worker_base_url: Option<String>,
     pub github_app: Option<GitHubAppConfig>,
+    /// DNS-over-HTTPS endpoint (JSON API) used to look up domain verification
+    /// TXT records
+    pub dns_over_https_url: String,
 }
 
 #[derive(Debug, Clone)]
@@ -154,6 +157,9 @@ impl RemoteServerConfig {
 
         let github_app = GitHubAppConfig::from_env()?;
 
+        let dns_over_https_url = env::var("DNS_OVER_HTTPS_URL")
+            .unwrap_or_else(|_| "https://cloudflare-dns.com/dns-query".to_string());
+
         Ok(Self {
             database_url,
             listen_addr,
@@ -165,6 +171,7 @@ impl RemoteServerConfig {
             r2,
             review_worker_base_url,
             github_app,
+            dns_over_https_url,
         })
     }
 }
@@ -192,10 +199,55 @@ impl OAuthProviderConfig {
     }
 }
 
+/// Generic OpenID Connect provider such as Okta, Azure AD or Keycloak
+#[derive(Debug, Clone)]
+pub struct OidcProviderConfig {
+    pub issuer_url: String,
+    pub client_id: String,
+    pub client_secret: SecretString,
+    pub claims: OidcClaimsConfig,
+}
+
+/// Names of the claims the user profile is read from
+#[derive(Debug, Clone)]
+pub struct OidcClaimsConfig {
+    pub email: String,
+    pub name: String,
+    pub username: String,
+}
+
+impl OidcProviderConfig {
+    fn from_env() -> Result<Option<Self>, ConfigError> {
+        let issuer_url = match env::var("OIDC_ISSUER_URL") {
+            Ok(v) if !v.trim().is_empty() => v,
+            _ => return Ok(None),
+        };
+        let client_id =
+            env::var("OIDC_CLIENT_ID").map_err(|_| ConfigError::MissingVar("OIDC_CLIENT_ID"))?;
+        let client_secret = env::var("OIDC_CLIENT_SECRET")
+            .map_err(|_| ConfigError::MissingVar("OIDC_CLIENT_SECRET"))?;
+
+        let claims = OidcClaimsConfig {
+            email: env::var("OIDC_EMAIL_CLAIM").unwrap_or_else(|_| "email".to_string()),
+            name: env::var("OIDC_NAME_CLAIM").unwrap_or_else(|_| "name".to_string()),
+            username: env::var("OIDC_USERNAME_CLAIM")
+                .unwrap_or_else(|_| "preferred_username".to_string()),
+        };
+
+        Ok(Some(Self {
+            issuer_url,
+            client_id,
+            client_secret: SecretString::new(client_secret.into()),
+            claims,
+        }))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct AuthConfig {
     github: Option<OAuthProviderConfig>,
     google: Option<OAuthProviderConfig>,
+    oidc: Option<OidcProviderConfig>,
     jwt_secret: SecretString,
     public_base_url: String,
 }
@@ -231,7 +283,9 @@ impl AuthConfig {
             Err(_) => None,
         };
 
-        if github.is_none() && google.is_none() {
+        let oidc = OidcProviderConfig::from_env()?;
+
+        if github.is_none() && google.is_none() && oidc.is_none() {
             return Err(ConfigError::NoOAuthProviders);
         }
 
@@ -241,6 +295,7 @@ impl AuthConfig {
         Ok(Self {
             github,
             google,
+            oidc,
             jwt_secret,
             public_base_url,
         })
@@ -254,6 +309,10 @@ impl AuthConfig {
         self.google.as_ref()
     }
 
+    pub fn oidc(&self) -> Option<&OidcProviderConfig> {
+        self.oidc.as_ref()
+    }
+
     pub fn jwt_secret(&self) -> &SecretString {
         &self.jwt_secret
     }
@@ -0,0 +1,233 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, Postgres};
+use thiserror::Error;
+use uuid::Uuid;
+
+/// Permission granted to an API token
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, sqlx::Type,
+)]
+#[sqlx(type_name = "api_token_scope", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ApiTokenScope {
+    IssuesRead,
+    IssuesWrite,
+    ReviewsTrigger,
+}
+
+/// A personal access token or a service account token. The secret itself is
+/// only returned once on creation.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ApiToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub token_prefix: String,
+    pub scopes: Vec<ApiTokenScope>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_by_user_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ApiToken {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ApiTokenError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+pub struct ApiTokenRepository;
+
+impl ApiTokenRepository {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        name: &str,
+        token_hash: &str,
+        token_prefix: &str,
+        scopes: &[ApiTokenScope],
+        expires_at: Option<DateTime<Utc>>,
+        created_by_user_id: Uuid,
+    ) -> Result<ApiToken, ApiTokenError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let token = sqlx::query_as!(
+            ApiToken,
+            r#"
+            INSERT INTO api_tokens (
+                user_id, name, token_hash, token_prefix, scopes, expires_at, created_by_user_id
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING
+                id                 AS "id!: Uuid",
+                user_id            AS "user_id!: Uuid",
+                name               AS "name!",
+                token_prefix       AS "token_prefix!",
+                scopes             AS "scopes!: Vec<ApiTokenScope>",
+                expires_at         AS "expires_at?: DateTime<Utc>",
+                last_used_at       AS "last_used_at?: DateTime<Utc>",
+                created_by_user_id AS "created_by_user_id?: Uuid",
+                created_at         AS "created_at!: DateTime<Utc>",
+                revoked_at         AS "revoked_at?: DateTime<Utc>"
+            "#,
+            user_id,
+            name,
+            token_hash,
+            token_prefix,
+            scopes as &[ApiTokenScope],
+            expires_at,
+            created_by_user_id
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(token)
+    }
+
+    pub async fn find_by_hash<'e, E>(
+        executor: E,
+        token_hash: &str,
+    ) -> Result<Option<ApiToken>, ApiTokenError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let token = sqlx::query_as!(
+            ApiToken,
+            r#"
+            SELECT
+                id                 AS "id!: Uuid",
+                user_id            AS "user_id!: Uuid",
+                name               AS "name!",
+                token_prefix       AS "token_prefix!",
+                scopes             AS "scopes!: Vec<ApiTokenScope>",
+                expires_at         AS "expires_at?: DateTime<Utc>",
+                last_used_at       AS "last_used_at?: DateTime<Utc>",
+                created_by_user_id AS "created_by_user_id?: Uuid",
+                created_at         AS "created_at!: DateTime<Utc>",
+                revoked_at         AS "revoked_at?: DateTime<Utc>"
+            FROM api_tokens
+            WHERE token_hash = $1
+            "#,
+            token_hash
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(token)
+    }
+
+    /// Tokens of a user that were not revoked, newest first
+    pub async fn list_by_user<'e, E>(
+        executor: E,
+        user_id: Uuid,
+    ) -> Result<Vec<ApiToken>, ApiTokenError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let tokens = sqlx::query_as!(
+            ApiToken,
+            r#"
+            SELECT
+                id                 AS "id!: Uuid",
+                user_id            AS "user_id!: Uuid",
+                name               AS "name!",
+                token_prefix       AS "token_prefix!",
+                scopes             AS "scopes!: Vec<ApiTokenScope>",
+                expires_at         AS "expires_at?: DateTime<Utc>",
+                last_used_at       AS "last_used_at?: DateTime<Utc>",
+                created_by_user_id AS "created_by_user_id?: Uuid",
+                created_at         AS "created_at!: DateTime<Utc>",
+                revoked_at         AS "revoked_at?: DateTime<Utc>"
+            FROM api_tokens
+            WHERE user_id = $1
+              AND revoked_at IS NULL
+            ORDER BY created_at DESC
+            "#,
+            user_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(tokens)
+    }
+
+    /// Revoke one of a user's tokens. Returns false if the user has no such
+    /// active token.
+    pub async fn revoke<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        token_id: Uuid,
+    ) -> Result<bool, ApiTokenError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let result = sqlx::query!(
+            r#"
+            UPDATE api_tokens
+            SET revoked_at = NOW()
+            WHERE id = $1
+              AND user_id = $2
+              AND revoked_at IS NULL
+            "#,
+            token_id,
+            user_id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn revoke_all_for_user<'e, E>(executor: E, user_id: Uuid) -> Result<(), ApiTokenError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"
+            UPDATE api_tokens
+            SET revoked_at = NOW()
+            WHERE user_id = $1
+              AND revoked_at IS NULL
+            "#,
+            user_id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Record that a token was used. Writes at most once a minute per token
+    /// so busy CI jobs do not update the row on every request.
+    pub async fn touch<'e, E>(executor: E, token_id: Uuid) -> Result<(), ApiTokenError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"
+            UPDATE api_tokens
+            SET last_used_at = NOW()
+            WHERE id = $1
+              AND (
+                last_used_at IS NULL
+                OR last_used_at < NOW() - INTERVAL '1 minute'
+              )
+            "#,
+            token_id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,198 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{Executor, FromRow, Postgres};
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "audit_action", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    InvitationCreated,
+    InvitationRevoked,
+    InvitationAccepted,
+    MemberRemoved,
+    MemberRoleChanged,
+    GithubAppInstalled,
+    GithubAppUninstalled,
+    RepositoryReviewEnabledChanged,
+    ProjectDeleted,
+    IssueDeleted,
+    ProjectVisibilityChanged,
+    ProjectMemberAdded,
+    ProjectMemberRemoved,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct AuditEvent {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub actor_user_id: Option<Uuid>,
+    pub action: AuditAction,
+    pub target_id: Option<Uuid>,
+    pub metadata: Value,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Optional filters for listing events; `from` is inclusive and `to` exclusive
+#[derive(Debug, Clone, Default)]
+pub struct AuditEventFilter {
+    pub actor_user_id: Option<Uuid>,
+    pub action: Option<AuditAction>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Position of an event in `(created_at, id)` order, for keyset paging
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuditEventCursor {
+    pub created_at: DateTime<Utc>,
+    /// Without an id, paging falls back to the timestamp alone and skips any
+    /// other events that share it
+    pub id: Option<Uuid>,
+}
+
+impl From<&AuditEvent> for AuditEventCursor {
+    fn from(event: &AuditEvent) -> Self {
+        Self {
+            created_at: event.created_at,
+            id: Some(event.id),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum AuditEventError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+pub struct AuditEventRepository;
+
+impl AuditEventRepository {
+    /// Pass the transaction that performs the audited change where there is
+    /// one, so the event is only recorded if the change commits.
+    pub async fn record<'e, E>(
+        executor: E,
+        organization_id: Uuid,
+        actor_user_id: Option<Uuid>,
+        action: AuditAction,
+        target_id: Option<Uuid>,
+        metadata: Value,
+    ) -> Result<(), AuditEventError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"
+            INSERT INTO audit_events (organization_id, actor_user_id, action, target_id, metadata)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            organization_id,
+            actor_user_id,
+            action as AuditAction,
+            target_id,
+            metadata
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Newest first, starting after the `before` cursor
+    pub async fn list<'e, E>(
+        executor: E,
+        organization_id: Uuid,
+        filter: &AuditEventFilter,
+        before: Option<AuditEventCursor>,
+        limit: i64,
+    ) -> Result<Vec<AuditEvent>, AuditEventError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let events = sqlx::query_as!(
+            AuditEvent,
+            r#"
+            SELECT
+                id              AS "id!: Uuid",
+                organization_id AS "organization_id!: Uuid",
+                actor_user_id   AS "actor_user_id?: Uuid",
+                action          AS "action!: AuditAction",
+                target_id       AS "target_id?: Uuid",
+                metadata        AS "metadata!: Value",
+                created_at      AS "created_at!: DateTime<Utc>"
+            FROM audit_events
+            WHERE organization_id = $1
+              AND ($2::uuid IS NULL OR actor_user_id = $2)
+              AND ($3::audit_action IS NULL OR action = $3)
+              AND ($4::timestamptz IS NULL OR created_at >= $4)
+              AND ($5::timestamptz IS NULL OR created_at < $5)
+              -- A NULL id makes the comparison NULL for events at exactly $6
+              AND ($6::timestamptz IS NULL OR (created_at, id) < ($6, $7::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $8
+            "#,
+            organization_id,
+            filter.actor_user_id,
+            filter.action as Option<AuditAction>,
+            filter.from,
+            filter.to,
+            before.map(|cursor| cursor.created_at),
+            before.and_then(|cursor| cursor.id),
+            limit
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(events)
+    }
+
+    /// Oldest first, starting after the `after` cursor, for export
+    pub async fn list_after<'e, E>(
+        executor: E,
+        organization_id: Uuid,
+        filter: &AuditEventFilter,
+        after: Option<AuditEventCursor>,
+        limit: i64,
+    ) -> Result<Vec<AuditEvent>, AuditEventError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let events = sqlx::query_as!(
+            AuditEvent,
+            r#"
+            SELECT
+                id              AS "id!: Uuid",
+                organization_id AS "organization_id!: Uuid",
+                actor_user_id   AS "actor_user_id?: Uuid",
+                action          AS "action!: AuditAction",
+                target_id       AS "target_id?: Uuid",
+                metadata        AS "metadata!: Value",
+                created_at      AS "created_at!: DateTime<Utc>"
+            FROM audit_events
+            WHERE organization_id = $1
+              AND ($2::uuid IS NULL OR actor_user_id = $2)
+              AND ($3::audit_action IS NULL OR action = $3)
+              AND ($4::timestamptz IS NULL OR created_at >= $4)
+              AND ($5::timestamptz IS NULL OR created_at < $5)
+              AND ($6::timestamptz IS NULL OR (created_at, id) > ($6, $7::uuid))
+            ORDER BY created_at ASC, id ASC
+            LIMIT $8
+            "#,
+            organization_id,
+            filter.actor_user_id,
+            filter.action as Option<AuditAction>,
+            filter.from,
+            filter.to,
+            after.map(|cursor| cursor.created_at),
+            after.and_then(|cursor| cursor.id),
+            limit
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(events)
+    }
+}
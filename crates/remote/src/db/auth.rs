@@ -24,6 +24,8 @@ pub enum AuthSessionError {
 pub struct AuthSession {
     pub id: Uuid,
     pub user_id: Uuid,
+    /// Provider the session was signed in with
+    pub provider: Option<String>,
     pub created_at: DateTime<Utc>,
     pub last_used_at: Option<DateTime<Utc>>,
     pub revoked_at: Option<DateTime<Utc>>,
@@ -45,16 +47,18 @@ impl<'a> AuthSessionRepository<'a> {
     pub async fn create(
         &self,
         user_id: Uuid,
+        provider: &str,
         refresh_token_id: Option<Uuid>,
     ) -> Result<AuthSession, AuthSessionError> {
         query_as!(
             AuthSession,
             r#"
-            INSERT INTO auth_sessions (user_id, refresh_token_id)
-            VALUES ($1, $2)
+            INSERT INTO auth_sessions (user_id, provider, refresh_token_id)
+            VALUES ($1, $2, $3)
             RETURNING
                 id                          AS "id!",
                 user_id                     AS "user_id!: Uuid",
+                provider                    AS "provider?",
                 created_at                  AS "created_at!",
                 last_used_at                AS "last_used_at?",
                 revoked_at                  AS "revoked_at?",
@@ -62,6 +66,7 @@ impl<'a> AuthSessionRepository<'a> {
                 refresh_token_issued_at     AS "refresh_token_issued_at?"
             "#,
             user_id,
+            provider,
             refresh_token_id
         )
         .fetch_one(self.pool)
@@ -76,6 +81,7 @@ impl<'a> AuthSessionRepository<'a> {
             SELECT
                 id                          AS "id!",
                 user_id                     AS "user_id!: Uuid",
+                provider                    AS "provider?",
                 created_at                  AS "created_at!",
                 last_used_at                AS "last_used_at?",
                 revoked_at                  AS "revoked_at?",
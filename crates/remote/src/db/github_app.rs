@@ -1,5 +1,5 @@
 use chrono::{DateTime, Utc};
-use sqlx::{FromRow, PgPool};
+use sqlx::{Executor, FromRow, PgPool, Postgres};
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -54,6 +54,8 @@ pub struct GitHubAppRepository2<'a> {
     pool: &'a PgPool,
 }
 
+// Changes that are audited take an executor instead of using the pool, so
+// callers can record the audit event in the same transaction.
 impl<'a> GitHubAppRepository2<'a> {
     pub fn new(pool: &'a PgPool) -> Self {
         Self { pool }
@@ -61,15 +63,18 @@ impl<'a> GitHubAppRepository2<'a> {
 
     // ========== Installations ==========
 
-    pub async fn create_installation(
-        &self,
+    pub async fn create_installation<'e, E>(
+        executor: E,
         organization_id: Uuid,
         github_installation_id: i64,
         github_account_login: &str,
         github_account_type: &str,
         repository_selection: &str,
         installed_by_user_id: Uuid,
-    ) -> Result<GitHubAppInstallation, GitHubAppDbError> {
+    ) -> Result<GitHubAppInstallation, GitHubAppDbError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
         let installation = sqlx::query_as!(
             GitHubAppInstallation,
             r#"
@@ -109,7 +114,7 @@ impl<'a> GitHubAppRepository2<'a> {
             repository_selection,
             installed_by_user_id
         )
-        .fetch_one(self.pool)
+        .fetch_one(executor)
         .await?;
 
         Ok(installation)
@@ -203,10 +208,13 @@ impl<'a> GitHubAppRepository2<'a> {
         Ok(installation)
     }
 
-    pub async fn delete_by_github_id(
-        &self,
+    pub async fn delete_by_github_id<'e, E>(
+        executor: E,
         github_installation_id: i64,
-    ) -> Result<(), GitHubAppDbError> {
+    ) -> Result<(), GitHubAppDbError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
         sqlx::query!(
             r#"
             DELETE FROM github_app_installations
@@ -214,16 +222,19 @@ impl<'a> GitHubAppRepository2<'a> {
             "#,
             github_installation_id
         )
-        .execute(self.pool)
+        .execute(executor)
         .await?;
 
         Ok(())
     }
 
-    pub async fn delete_by_organization(
-        &self,
+    pub async fn delete_by_organization<'e, E>(
+        executor: E,
         organization_id: Uuid,
-    ) -> Result<(), GitHubAppDbError> {
+    ) -> Result<(), GitHubAppDbError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
         sqlx::query!(
             r#"
             DELETE FROM github_app_installations
@@ -231,7 +242,7 @@ impl<'a> GitHubAppRepository2<'a> {
             "#,
             organization_id
         )
-        .execute(self.pool)
+        .execute(executor)
         .await?;
 
         Ok(())
@@ -399,12 +410,15 @@ impl<'a> GitHubAppRepository2<'a> {
     }
 
     /// Update the review_enabled flag for a repository
-    pub async fn update_repository_review_enabled(
-        &self,
+    pub async fn update_repository_review_enabled<'e, E>(
+        executor: E,
         repo_id: Uuid,
         installation_id: Uuid,
         enabled: bool,
-    ) -> Result<GitHubAppRepository, GitHubAppDbError> {
+    ) -> Result<GitHubAppRepository, GitHubAppDbError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
         let repo = sqlx::query_as!(
             GitHubAppRepository,
             r#"
@@ -423,7 +437,7 @@ impl<'a> GitHubAppRepository2<'a> {
             installation_id,
             enabled
         )
-        .fetch_optional(self.pool)
+        .fetch_optional(executor)
         .await?
         .ok_or(GitHubAppDbError::NotFound)?;
 
@@ -453,11 +467,14 @@ impl<'a> GitHubAppRepository2<'a> {
     }
 
     /// Bulk update review_enabled for all repositories in an installation
-    pub async fn set_all_repositories_review_enabled(
-        &self,
+    pub async fn set_all_repositories_review_enabled<'e, E>(
+        executor: E,
         installation_id: Uuid,
         enabled: bool,
-    ) -> Result<u64, GitHubAppDbError> {
+    ) -> Result<u64, GitHubAppDbError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
         let result = sqlx::query!(
             r#"
             UPDATE github_app_repositories
@@ -467,7 +484,7 @@ impl<'a> GitHubAppRepository2<'a> {
             installation_id,
             enabled
         )
-        .execute(self.pool)
+        .execute(executor)
         .await?;
 
         Ok(result.rows_affected())
@@ -8,6 +8,8 @@ pub enum IdentityError {
     PermissionDenied,
     #[error("invitation error: {0}")]
     InvitationError(String),
+    #[error("invitation has expired")]
+    InvitationExpired,
     #[error("cannot delete organization: {0}")]
     CannotDeleteOrganization(String),
     #[error("organization conflict: {0}")]
@@ -5,6 +5,7 @@ pub use utils::api::organizations::InvitationStatus;
 use uuid::Uuid;
 
 use super::{
+    Tx,
     identity_errors::IdentityError,
     organization_members::{MemberRole, add_member, assert_admin},
     organizations::{Organization, OrganizationRepository},
@@ -34,8 +35,11 @@ impl<'a> InvitationRepository<'a> {
         Self { pool }
     }
 
+    /// Inserts through `tx` so the caller can record the audit event with it
+    #[allow(clippy::too_many_arguments)]
     pub async fn create_invitation(
         &self,
+        tx: &mut Tx<'_>,
         organization_id: Uuid,
         invited_by_user_id: Uuid,
         email: &str,
@@ -80,7 +84,7 @@ impl<'a> InvitationRepository<'a> {
             token,
             expires_at
         )
-        .fetch_one(self.pool)
+        .fetch_one(&mut **tx)
         .await
         .map_err(|e| {
             if let Some(db_err) = e.as_database_error()
@@ -165,6 +169,7 @@ impl<'a> InvitationRepository<'a> {
 
     pub async fn revoke_invitation(
         &self,
+        tx: &mut Tx<'_>,
         organization_id: Uuid,
         invitation_id: Uuid,
         requesting_user_id: Uuid,
@@ -179,7 +184,7 @@ impl<'a> InvitationRepository<'a> {
             invitation_id,
             organization_id
         )
-        .execute(self.pool)
+        .execute(&mut **tx)
         .await?;
 
         if result.rows_affected() == 0 {
@@ -189,13 +194,14 @@ impl<'a> InvitationRepository<'a> {
         Ok(())
     }
 
+    /// Runs in the caller's transaction. An expired invitation is marked as
+    /// such before `InvitationExpired` is returned; commit `tx` to keep that.
     pub async fn accept_invitation(
         &self,
+        tx: &mut Tx<'_>,
         token: &str,
         user_id: Uuid,
     ) -> Result<(Organization, MemberRole), IdentityError> {
-        let mut tx = self.pool.begin().await?;
-
         let invitation = sqlx::query_as!(
             Invitation,
             r#"
@@ -216,7 +222,7 @@ impl<'a> InvitationRepository<'a> {
             "#,
             token
         )
-        .fetch_optional(&mut *tx)
+        .fetch_optional(&mut **tx)
         .await?
         .ok_or_else(|| {
             IdentityError::InvitationError("Invitation not found or already used".to_string())
@@ -226,7 +232,6 @@ impl<'a> InvitationRepository<'a> {
             .is_personal(invitation.organization_id)
             .await?
         {
-            tx.rollback().await?;
             return Err(IdentityError::InvitationError(
                 "Cannot accept invitations for a personal organization".to_string(),
             ));
@@ -241,24 +246,20 @@ impl<'a> InvitationRepository<'a> {
                 "#,
                 invitation.id
             )
-            .execute(&mut *tx)
+            .execute(&mut **tx)
             .await?;
 
-            tx.commit().await?;
-            return Err(IdentityError::InvitationError(
-                "Invitation has expired".to_string(),
-            ));
+            return Err(IdentityError::InvitationExpired);
         }
 
-        if is_member(&mut *tx, invitation.organization_id, user_id).await? {
-            tx.rollback().await?;
+        if is_member(&mut **tx, invitation.organization_id, user_id).await? {
             return Err(IdentityError::InvitationError(
                 "You are already a member of the organization".to_string(),
             ));
         }
 
         add_member(
-            &mut *tx,
+            &mut **tx,
             invitation.organization_id,
             user_id,
             invitation.role,
@@ -273,11 +274,9 @@ impl<'a> InvitationRepository<'a> {
             "#,
             invitation.id
         )
-        .execute(&mut *tx)
+        .execute(&mut **tx)
         .await?;
 
-        tx.commit().await?;
-
         let organization = OrganizationRepository::new(self.pool)
             .fetch_organization(invitation.organization_id)
             .await?;
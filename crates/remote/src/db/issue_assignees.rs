@@ -48,4 +48,79 @@ impl IssueAssigneeRepository {
 
         Ok(record)
     }
+
+    pub async fn list_by_issue<'e, E>(
+        executor: E,
+        issue_id: Uuid,
+    ) -> Result<Vec<IssueAssignee>, IssueAssigneeError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let records = sqlx::query_as!(
+            IssueAssignee,
+            r#"
+            SELECT
+                issue_id    AS "issue_id!: Uuid",
+                user_id     AS "user_id!: Uuid",
+                assigned_at AS "assigned_at!: DateTime<Utc>"
+            FROM issue_assignees
+            WHERE issue_id = $1
+            ORDER BY assigned_at ASC
+            "#,
+            issue_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Returns `None` if the user was already assigned
+    pub async fn create<'e, E>(
+        executor: E,
+        issue_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<IssueAssignee>, IssueAssigneeError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let record = sqlx::query_as!(
+            IssueAssignee,
+            r#"
+            INSERT INTO issue_assignees (issue_id, user_id)
+            VALUES ($1, $2)
+            ON CONFLICT (issue_id, user_id) DO NOTHING
+            RETURNING
+                issue_id    AS "issue_id!: Uuid",
+                user_id     AS "user_id!: Uuid",
+                assigned_at AS "assigned_at!: DateTime<Utc>"
+            "#,
+            issue_id,
+            user_id
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Returns whether the user was assigned
+    pub async fn delete<'e, E>(
+        executor: E,
+        issue_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool, IssueAssigneeError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let result = sqlx::query!(
+            "DELETE FROM issue_assignees WHERE issue_id = $1 AND user_id = $2",
+            issue_id,
+            user_id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
 }
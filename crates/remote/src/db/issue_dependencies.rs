@@ -48,4 +48,111 @@ impl IssueDependencyRepository {
 
         Ok(record)
     }
+
+    /// Dependencies in which the issue is either the blocker or the blocked one
+    pub async fn list_by_issue<'e, E>(
+        executor: E,
+        issue_id: Uuid,
+    ) -> Result<Vec<IssueDependency>, IssueDependencyError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let records = sqlx::query_as!(
+            IssueDependency,
+            r#"
+            SELECT
+                blocking_issue_id AS "blocking_issue_id!: Uuid",
+                blocked_issue_id  AS "blocked_issue_id!: Uuid",
+                created_at        AS "created_at!: DateTime<Utc>"
+            FROM issue_dependencies
+            WHERE blocking_issue_id = $1 OR blocked_issue_id = $1
+            ORDER BY created_at ASC
+            "#,
+            issue_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Whether `blocking_issue_id` is already blocked, directly or transitively,
+    /// by `blocked_issue_id`, so adding the dependency would close a cycle
+    pub async fn would_create_cycle<'e, E>(
+        executor: E,
+        blocking_issue_id: Uuid,
+        blocked_issue_id: Uuid,
+    ) -> Result<bool, IssueDependencyError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let exists = sqlx::query_scalar!(
+            r#"
+            WITH RECURSIVE downstream(issue_id) AS (
+                SELECT blocked_issue_id
+                FROM issue_dependencies
+                WHERE blocking_issue_id = $2
+                UNION
+                SELECT d.blocked_issue_id
+                FROM issue_dependencies d
+                INNER JOIN downstream ON d.blocking_issue_id = downstream.issue_id
+            )
+            SELECT EXISTS(SELECT 1 FROM downstream WHERE issue_id = $1) AS "exists!"
+            "#,
+            blocking_issue_id,
+            blocked_issue_id
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(exists)
+    }
+
+    pub async fn create<'e, E>(
+        executor: E,
+        blocking_issue_id: Uuid,
+        blocked_issue_id: Uuid,
+    ) -> Result<IssueDependency, IssueDependencyError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let record = sqlx::query_as!(
+            IssueDependency,
+            r#"
+            INSERT INTO issue_dependencies (blocking_issue_id, blocked_issue_id)
+            VALUES ($1, $2)
+            ON CONFLICT (blocking_issue_id, blocked_issue_id)
+                DO UPDATE SET created_at = issue_dependencies.created_at
+            RETURNING
+                blocking_issue_id AS "blocking_issue_id!: Uuid",
+                blocked_issue_id  AS "blocked_issue_id!: Uuid",
+                created_at        AS "created_at!: DateTime<Utc>"
+            "#,
+            blocking_issue_id,
+            blocked_issue_id
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(record)
+    }
+
+    pub async fn delete<'e, E>(
+        executor: E,
+        blocking_issue_id: Uuid,
+        blocked_issue_id: Uuid,
+    ) -> Result<(), IssueDependencyError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            "DELETE FROM issue_dependencies WHERE blocking_issue_id = $1 AND blocked_issue_id = $2",
+            blocking_issue_id,
+            blocked_issue_id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,320 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use sqlx::{Executor, Postgres};
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::issues::Issue;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, TS)]
+#[sqlx(type_name = "issue_event_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum IssueEventType {
+    Created,
+    StatusChanged,
+    PriorityChanged,
+    AssigneeAdded,
+    AssigneeRemoved,
+    StartDateChanged,
+    TargetDateChanged,
+    CompletedAtChanged,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct IssueEvent {
+    pub id: Uuid,
+    pub issue_id: Uuid,
+    pub actor_user_id: Option<Uuid>,
+    pub event_type: IssueEventType,
+    pub old_value: Option<Value>,
+    pub new_value: Option<Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A field that differs between two versions of an issue
+#[derive(Debug, Clone, PartialEq)]
+pub struct IssueFieldChange {
+    pub event_type: IssueEventType,
+    pub old_value: Option<Value>,
+    pub new_value: Option<Value>,
+}
+
+/// Total time an issue has spent in one status
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatusDuration {
+    pub status_id: Uuid,
+    pub seconds: i64,
+}
+
+#[derive(Debug, Error)]
+pub enum IssueEventError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+/// The tracked fields that changed from `before` to `after`
+pub fn field_changes(before: &Issue, after: &Issue) -> Vec<IssueFieldChange> {
+    let candidates = [
+        (
+            IssueEventType::StatusChanged,
+            json!(before.status_id),
+            json!(after.status_id),
+        ),
+        (
+            IssueEventType::PriorityChanged,
+            json!(before.priority),
+            json!(after.priority),
+        ),
+        (
+            IssueEventType::StartDateChanged,
+            json!(before.start_date),
+            json!(after.start_date),
+        ),
+        (
+            IssueEventType::TargetDateChanged,
+            json!(before.target_date),
+            json!(after.target_date),
+        ),
+        (
+            IssueEventType::CompletedAtChanged,
+            json!(before.completed_at),
+            json!(after.completed_at),
+        ),
+    ];
+
+    candidates
+        .into_iter()
+        .filter(|(_, old_value, new_value)| old_value != new_value)
+        .map(|(event_type, old_value, new_value)| IssueFieldChange {
+            event_type,
+            old_value: Some(old_value).filter(|value| !value.is_null()),
+            new_value: Some(new_value).filter(|value| !value.is_null()),
+        })
+        .collect()
+}
+
+/// Add up how long an issue sat in each status, in the order the statuses
+/// were first entered. `events` must be oldest first; the current status is
+/// counted up to `until`.
+pub fn time_in_status(events: &[IssueEvent], until: DateTime<Utc>) -> Vec<StatusDuration> {
+    let mut durations: Vec<StatusDuration> = Vec::new();
+    let mut current: Option<(Uuid, DateTime<Utc>)> = None;
+
+    let entered = events.iter().filter_map(|event| {
+        let status = match event.event_type {
+            IssueEventType::Created => event.new_value.as_ref()?.get("status_id")?,
+            IssueEventType::StatusChanged => event.new_value.as_ref()?,
+            _ => return None,
+        };
+        let status_id = status.as_str()?.parse::<Uuid>().ok()?;
+        Some((status_id, event.created_at))
+    });
+
+    let mut add = |status_id: Uuid, from: DateTime<Utc>, to: DateTime<Utc>| {
+        let seconds = (to - from).num_seconds().max(0);
+        match durations.iter_mut().find(|d| d.status_id == status_id) {
+            Some(duration) => duration.seconds += seconds,
+            None => durations.push(StatusDuration { status_id, seconds }),
+        }
+    };
+
+    for (status_id, at) in entered {
+        if let Some((previous, since)) = current.replace((status_id, at)) {
+            add(previous, since, at);
+        }
+    }
+    if let Some((status_id, since)) = current {
+        add(status_id, since, until);
+    }
+
+    durations
+}
+
+pub struct IssueEventRepository;
+
+impl IssueEventRepository {
+    /// Oldest first
+    pub async fn list_by_issue<'e, E>(
+        executor: E,
+        issue_id: Uuid,
+    ) -> Result<Vec<IssueEvent>, IssueEventError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let records = sqlx::query_as!(
+            IssueEvent,
+            r#"
+            SELECT
+                id            AS "id!: Uuid",
+                issue_id      AS "issue_id!: Uuid",
+                actor_user_id AS "actor_user_id?: Uuid",
+                event_type    AS "event_type!: IssueEventType",
+                old_value     AS "old_value?: Value",
+                new_value     AS "new_value?: Value",
+                created_at    AS "created_at!: DateTime<Utc>"
+            FROM issue_events
+            WHERE issue_id = $1
+            ORDER BY created_at ASC, id ASC
+            "#,
+            issue_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(records)
+    }
+
+    pub async fn create<'e, E>(
+        executor: E,
+        issue_id: Uuid,
+        actor_user_id: Uuid,
+        event_type: IssueEventType,
+        old_value: Option<Value>,
+        new_value: Option<Value>,
+    ) -> Result<IssueEvent, IssueEventError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let record = sqlx::query_as!(
+            IssueEvent,
+            r#"
+            INSERT INTO issue_events (issue_id, actor_user_id, event_type, old_value, new_value)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING
+                id            AS "id!: Uuid",
+                issue_id      AS "issue_id!: Uuid",
+                actor_user_id AS "actor_user_id?: Uuid",
+                event_type    AS "event_type!: IssueEventType",
+                old_value     AS "old_value?: Value",
+                new_value     AS "new_value?: Value",
+                created_at    AS "created_at!: DateTime<Utc>"
+            "#,
+            issue_id,
+            actor_user_id,
+            event_type as IssueEventType,
+            old_value,
+            new_value
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::db::types::IssuePriority;
+
+    fn issue(status_id: Uuid) -> Issue {
+        let now = Utc::now();
+        Issue {
+            id: Uuid::nil(),
+            project_id: Uuid::nil(),
+            status_id,
+            title: "Fix login".to_string(),
+            description: None,
+            priority: IssuePriority::Medium,
+            start_date: None,
+            target_date: None,
+            completed_at: None,
+            sort_order: 0.0,
+            parent_issue_id: None,
+            extension_metadata: json!({}),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn event(
+        event_type: IssueEventType,
+        new_value: Value,
+        created_at: DateTime<Utc>,
+    ) -> IssueEvent {
+        IssueEvent {
+            id: Uuid::new_v4(),
+            issue_id: Uuid::nil(),
+            actor_user_id: None,
+            event_type,
+            old_value: None,
+            new_value: Some(new_value),
+            created_at,
+        }
+    }
+
+    #[test]
+    fn field_changes_only_reports_tracked_fields_that_changed() {
+        let before = issue(Uuid::new_v4());
+        let mut after = before.clone();
+        after.title = "Fix login flow".to_string();
+        after.status_id = Uuid::new_v4();
+        let target_date = Utc::now();
+        after.target_date = Some(target_date);
+
+        assert_eq!(
+            field_changes(&before, &after),
+            vec![
+                IssueFieldChange {
+                    event_type: IssueEventType::StatusChanged,
+                    old_value: Some(json!(before.status_id)),
+                    new_value: Some(json!(after.status_id)),
+                },
+                IssueFieldChange {
+                    event_type: IssueEventType::TargetDateChanged,
+                    old_value: None,
+                    new_value: Some(json!(target_date)),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn time_in_status_adds_up_repeat_visits() {
+        let todo = Uuid::new_v4();
+        let doing = Uuid::new_v4();
+        let start = Utc::now() - Duration::hours(10);
+        let events = vec![
+            event(
+                IssueEventType::Created,
+                json!({ "status_id": todo, "priority": "medium" }),
+                start,
+            ),
+            event(
+                IssueEventType::StatusChanged,
+                json!(doing),
+                start + Duration::hours(2),
+            ),
+            event(
+                IssueEventType::PriorityChanged,
+                json!("high"),
+                start + Duration::hours(3),
+            ),
+            event(
+                IssueEventType::StatusChanged,
+                json!(todo),
+                start + Duration::hours(5),
+            ),
+        ];
+
+        assert_eq!(
+            time_in_status(&events, start + Duration::hours(6)),
+            vec![
+                StatusDuration {
+                    status_id: todo,
+                    seconds: 3 * 3600,
+                },
+                StatusDuration {
+                    status_id: doing,
+                    seconds: 3 * 3600,
+                },
+            ]
+        );
+    }
+}
@@ -45,4 +45,70 @@ impl IssueFollowerRepository {
 
         Ok(record)
     }
+
+    pub async fn list_by_issue<'e, E>(
+        executor: E,
+        issue_id: Uuid,
+    ) -> Result<Vec<IssueFollower>, IssueFollowerError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let records = sqlx::query_as!(
+            IssueFollower,
+            r#"
+            SELECT
+                issue_id AS "issue_id!: Uuid",
+                user_id  AS "user_id!: Uuid"
+            FROM issue_followers
+            WHERE issue_id = $1
+            "#,
+            issue_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(records)
+    }
+
+    pub async fn create<'e, E>(
+        executor: E,
+        issue_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<IssueFollower, IssueFollowerError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"
+            INSERT INTO issue_followers (issue_id, user_id)
+            VALUES ($1, $2)
+            ON CONFLICT (issue_id, user_id) DO NOTHING
+            "#,
+            issue_id,
+            user_id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(IssueFollower { issue_id, user_id })
+    }
+
+    pub async fn delete<'e, E>(
+        executor: E,
+        issue_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<(), IssueFollowerError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            "DELETE FROM issue_followers WHERE issue_id = $1 AND user_id = $2",
+            issue_id,
+            user_id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,213 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, Postgres};
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueMention {
+    pub id: Uuid,
+    pub issue_id: Uuid,
+    /// `None` when the mention is in the issue description
+    pub comment_id: Option<Uuid>,
+    pub mentioned_user_id: Uuid,
+    pub author_user_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Error)]
+pub enum IssueMentionError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+/// Pull `@username` and `@name@example.com` handles out of markdown text,
+/// lowercased and without duplicates. An `@` only starts a mention at the
+/// beginning of a word, so plain email addresses are not picked up.
+pub fn extract_mention_handles(text: &str) -> Vec<String> {
+    let mut handles: Vec<String> = Vec::new();
+    let words = text.split(|c: char| c.is_whitespace() || "()[]{},;:!?\"'".contains(c));
+
+    for word in words {
+        let Some(rest) = word.strip_prefix('@') else {
+            continue;
+        };
+        let end = rest
+            .find(|c: char| !(c.is_alphanumeric() || "._-+@".contains(c)))
+            .unwrap_or(rest.len());
+        let handle = rest[..end].trim_end_matches(['.', '-', '@']).to_lowercase();
+        if !handle.is_empty() && !handles.contains(&handle) {
+            handles.push(handle);
+        }
+    }
+
+    handles
+}
+
+pub struct IssueMentionRepository;
+
+impl IssueMentionRepository {
+    /// Match handles against the usernames and emails of an organization's
+    /// members, ignoring case
+    pub async fn resolve_members<'e, E>(
+        executor: E,
+        organization_id: Uuid,
+        handles: &[String],
+    ) -> Result<Vec<Uuid>, IssueMentionError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        if handles.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let user_ids = sqlx::query_scalar!(
+            r#"
+            SELECT u.id AS "id!: Uuid"
+            FROM users u
+            JOIN organization_member_metadata om ON om.user_id = u.id
+            WHERE om.organization_id = $1
+              AND (LOWER(u.username) = ANY($2) OR LOWER(u.email) = ANY($2))
+            "#,
+            organization_id,
+            handles
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(user_ids)
+    }
+
+    pub async fn list_by_issue<'e, E>(
+        executor: E,
+        issue_id: Uuid,
+    ) -> Result<Vec<IssueMention>, IssueMentionError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let records = sqlx::query_as!(
+            IssueMention,
+            r#"
+            SELECT
+                id                AS "id!: Uuid",
+                issue_id          AS "issue_id!: Uuid",
+                comment_id        AS "comment_id?: Uuid",
+                mentioned_user_id AS "mentioned_user_id!: Uuid",
+                author_user_id    AS "author_user_id?: Uuid",
+                created_at        AS "created_at!: DateTime<Utc>"
+            FROM issue_mentions
+            WHERE issue_id = $1
+            ORDER BY created_at ASC
+            "#,
+            issue_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Drop mentions that were edited out of the description or comment
+    pub async fn remove_stale<'e, E>(
+        executor: E,
+        issue_id: Uuid,
+        comment_id: Option<Uuid>,
+        mentioned_user_ids: &[Uuid],
+    ) -> Result<(), IssueMentionError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"
+            DELETE FROM issue_mentions
+            WHERE issue_id = $1
+              AND comment_id IS NOT DISTINCT FROM $2
+              AND NOT (mentioned_user_id = ANY($3))
+            "#,
+            issue_id,
+            comment_id,
+            mentioned_user_ids
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Record mentions, returning only the users who were not already
+    /// mentioned in the same description or comment
+    pub async fn add<'e, E>(
+        executor: E,
+        issue_id: Uuid,
+        comment_id: Option<Uuid>,
+        author_user_id: Uuid,
+        mentioned_user_ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, IssueMentionError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let added = match comment_id {
+            Some(comment_id) => {
+                sqlx::query_scalar!(
+                    r#"
+                    INSERT INTO issue_mentions (issue_id, comment_id, mentioned_user_id, author_user_id)
+                    SELECT $1, $2, user_id, $3
+                    FROM UNNEST($4::uuid[]) AS user_id
+                    ON CONFLICT (comment_id, mentioned_user_id) DO NOTHING
+                    RETURNING mentioned_user_id AS "mentioned_user_id!: Uuid"
+                    "#,
+                    issue_id,
+                    comment_id,
+                    author_user_id,
+                    mentioned_user_ids
+                )
+                .fetch_all(executor)
+                .await?
+            }
+            None => {
+                sqlx::query_scalar!(
+                    r#"
+                    INSERT INTO issue_mentions (issue_id, mentioned_user_id, author_user_id)
+                    SELECT $1, user_id, $2
+                    FROM UNNEST($3::uuid[]) AS user_id
+                    ON CONFLICT (issue_id, mentioned_user_id) WHERE comment_id IS NULL DO NOTHING
+                    RETURNING mentioned_user_id AS "mentioned_user_id!: Uuid"
+                    "#,
+                    issue_id,
+                    author_user_id,
+                    mentioned_user_ids
+                )
+                .fetch_all(executor)
+                .await?
+            }
+        };
+
+        Ok(added)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_usernames_and_emails() {
+        assert_eq!(
+            extract_mention_handles("cc @Alice and @bob@Example.com, thanks @alice."),
+            vec!["alice".to_string(), "bob@example.com".to_string()]
+        );
+    }
+
+    #[test]
+    fn ignores_addresses_and_bare_at_signs() {
+        assert!(extract_mention_handles("mail me at carol@example.com or @ here").is_empty());
+    }
+
+    #[test]
+    fn mentions_can_follow_punctuation() {
+        assert_eq!(
+            extract_mention_handles("(@dave) @erin-ops: done"),
+            vec!["dave".to_string(), "erin-ops".to_string()]
+        );
+    }
+}
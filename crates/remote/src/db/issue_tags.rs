@@ -45,4 +45,70 @@ impl IssueTagRepository {
 
         Ok(record)
     }
+
+    pub async fn list_by_issue<'e, E>(
+        executor: E,
+        issue_id: Uuid,
+    ) -> Result<Vec<IssueTag>, IssueTagError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let records = sqlx::query_as!(
+            IssueTag,
+            r#"
+            SELECT
+                issue_id AS "issue_id!: Uuid",
+                tag_id   AS "tag_id!: Uuid"
+            FROM issue_tags
+            WHERE issue_id = $1
+            "#,
+            issue_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(records)
+    }
+
+    pub async fn create<'e, E>(
+        executor: E,
+        issue_id: Uuid,
+        tag_id: Uuid,
+    ) -> Result<IssueTag, IssueTagError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"
+            INSERT INTO issue_tags (issue_id, tag_id)
+            VALUES ($1, $2)
+            ON CONFLICT (issue_id, tag_id) DO NOTHING
+            "#,
+            issue_id,
+            tag_id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(IssueTag { issue_id, tag_id })
+    }
+
+    pub async fn delete<'e, E>(
+        executor: E,
+        issue_id: Uuid,
+        tag_id: Uuid,
+    ) -> Result<(), IssueTagError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            "DELETE FROM issue_tags WHERE issue_id = $1 AND tag_id = $2",
+            issue_id,
+            tag_id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
 }
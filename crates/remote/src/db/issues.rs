@@ -27,6 +27,21 @@ pub struct Issue {
     pub updated_at: DateTime<Utc>,
 }
 
+/// Writable issue fields, used for both creating and updating an issue
+#[derive(Debug, Clone)]
+pub struct IssueFields {
+    pub status_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: IssuePriority,
+    pub start_date: Option<DateTime<Utc>>,
+    pub target_date: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub sort_order: f64,
+    pub parent_issue_id: Option<Uuid>,
+    pub extension_metadata: Value,
+}
+
 #[derive(Debug, Error)]
 pub enum IssueError {
     #[error(transparent)]
@@ -69,6 +84,281 @@ impl IssueRepository {
         Ok(record)
     }
 
+    pub async fn list_by_project<'e, E>(
+        executor: E,
+        project_id: Uuid,
+    ) -> Result<Vec<Issue>, IssueError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let records = sqlx::query_as!(
+            Issue,
+            r#"
+            SELECT
+                id                  AS "id!: Uuid",
+                project_id          AS "project_id!: Uuid",
+                status_id           AS "status_id!: Uuid",
+                title               AS "title!",
+                description         AS "description?",
+                priority            AS "priority!: IssuePriority",
+                start_date          AS "start_date?: DateTime<Utc>",
+                target_date         AS "target_date?: DateTime<Utc>",
+                completed_at        AS "completed_at?: DateTime<Utc>",
+                sort_order          AS "sort_order!",
+                parent_issue_id     AS "parent_issue_id?: Uuid",
+                extension_metadata  AS "extension_metadata!: Value",
+                created_at          AS "created_at!: DateTime<Utc>",
+                updated_at          AS "updated_at!: DateTime<Utc>"
+            FROM issues
+            WHERE project_id = $1
+            ORDER BY sort_order ASC, created_at ASC
+            "#,
+            project_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Sort order that places a new issue at the bottom of its status column
+    pub async fn next_sort_order<'e, E>(executor: E, status_id: Uuid) -> Result<f64, IssueError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let next = sqlx::query_scalar!(
+            r#"
+            SELECT COALESCE(MAX(sort_order), 0) + 1 AS "next!"
+            FROM issues
+            WHERE status_id = $1
+            "#,
+            status_id
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(next)
+    }
+
+    pub async fn create<'e, E>(
+        executor: E,
+        project_id: Uuid,
+        fields: &IssueFields,
+    ) -> Result<Issue, IssueError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let id = Uuid::new_v4();
+        let record = sqlx::query_as!(
+            Issue,
+            r#"
+            INSERT INTO issues (
+                id, project_id, status_id, title, description, priority, start_date,
+                target_date, completed_at, sort_order, parent_issue_id, extension_metadata
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            RETURNING
+                id                  AS "id!: Uuid",
+                project_id          AS "project_id!: Uuid",
+                status_id           AS "status_id!: Uuid",
+                title               AS "title!",
+                description         AS "description?",
+                priority            AS "priority!: IssuePriority",
+                start_date          AS "start_date?: DateTime<Utc>",
+                target_date         AS "target_date?: DateTime<Utc>",
+                completed_at        AS "completed_at?: DateTime<Utc>",
+                sort_order          AS "sort_order!",
+                parent_issue_id     AS "parent_issue_id?: Uuid",
+                extension_metadata  AS "extension_metadata!: Value",
+                created_at          AS "created_at!: DateTime<Utc>",
+                updated_at          AS "updated_at!: DateTime<Utc>"
+            "#,
+            id,
+            project_id,
+            fields.status_id,
+            fields.title,
+            fields.description,
+            fields.priority as IssuePriority,
+            fields.start_date,
+            fields.target_date,
+            fields.completed_at,
+            fields.sort_order,
+            fields.parent_issue_id,
+            fields.extension_metadata
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(record)
+    }
+
+    pub async fn update<'e, E>(
+        executor: E,
+        id: Uuid,
+        fields: &IssueFields,
+    ) -> Result<Issue, IssueError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let record = sqlx::query_as!(
+            Issue,
+            r#"
+            UPDATE issues
+            SET
+                status_id = $1,
+                title = $2,
+                description = $3,
+                priority = $4,
+                start_date = $5,
+                target_date = $6,
+                completed_at = $7,
+                sort_order = $8,
+                parent_issue_id = $9,
+                extension_metadata = $10,
+                updated_at = NOW()
+            WHERE id = $11
+            RETURNING
+                id                  AS "id!: Uuid",
+                project_id          AS "project_id!: Uuid",
+                status_id           AS "status_id!: Uuid",
+                title               AS "title!",
+                description         AS "description?",
+                priority            AS "priority!: IssuePriority",
+                start_date          AS "start_date?: DateTime<Utc>",
+                target_date         AS "target_date?: DateTime<Utc>",
+                completed_at        AS "completed_at?: DateTime<Utc>",
+                sort_order          AS "sort_order!",
+                parent_issue_id     AS "parent_issue_id?: Uuid",
+                extension_metadata  AS "extension_metadata!: Value",
+                created_at          AS "created_at!: DateTime<Utc>",
+                updated_at          AS "updated_at!: DateTime<Utc>"
+            "#,
+            fields.status_id,
+            fields.title,
+            fields.description,
+            fields.priority as IssuePriority,
+            fields.start_date,
+            fields.target_date,
+            fields.completed_at,
+            fields.sort_order,
+            fields.parent_issue_id,
+            fields.extension_metadata,
+            id
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Move an issue within or across status columns
+    pub async fn set_position<'e, E>(
+        executor: E,
+        id: Uuid,
+        status_id: Uuid,
+        sort_order: f64,
+        completed_at: Option<DateTime<Utc>>,
+    ) -> Result<Issue, IssueError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let record = sqlx::query_as!(
+            Issue,
+            r#"
+            UPDATE issues
+            SET
+                status_id = $1,
+                sort_order = $2,
+                completed_at = $3,
+                updated_at = NOW()
+            WHERE id = $4
+            RETURNING
+                id                  AS "id!: Uuid",
+                project_id          AS "project_id!: Uuid",
+                status_id           AS "status_id!: Uuid",
+                title               AS "title!",
+                description         AS "description?",
+                priority            AS "priority!: IssuePriority",
+                start_date          AS "start_date?: DateTime<Utc>",
+                target_date         AS "target_date?: DateTime<Utc>",
+                completed_at        AS "completed_at?: DateTime<Utc>",
+                sort_order          AS "sort_order!",
+                parent_issue_id     AS "parent_issue_id?: Uuid",
+                extension_metadata  AS "extension_metadata!: Value",
+                created_at          AS "created_at!: DateTime<Utc>",
+                updated_at          AS "updated_at!: DateTime<Utc>"
+            "#,
+            status_id,
+            sort_order,
+            completed_at,
+            id
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Whether making `parent_issue_id` the parent of `issue_id` would close a
+    /// loop, i.e. `issue_id` is the new parent or one of its ancestors
+    pub async fn would_create_cycle<'e, E>(
+        executor: E,
+        issue_id: Uuid,
+        parent_issue_id: Uuid,
+    ) -> Result<bool, IssueError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        // UNION rather than UNION ALL stops at loops already in the data
+        let found = sqlx::query_scalar!(
+            r#"
+            WITH RECURSIVE ancestors(id, parent_issue_id) AS (
+                SELECT id, parent_issue_id FROM issues WHERE id = $1
+                UNION
+                SELECT i.id, i.parent_issue_id
+                FROM issues i
+                JOIN ancestors a ON i.id = a.parent_issue_id
+            )
+            SELECT EXISTS (SELECT 1 FROM ancestors WHERE id = $2) AS "found!"
+            "#,
+            parent_issue_id,
+            issue_id
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(found)
+    }
+
+    pub async fn delete<'e, E>(executor: E, id: Uuid) -> Result<(), IssueError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!("DELETE FROM issues WHERE id = $1", id)
+            .execute(executor)
+            .await?;
+        Ok(())
+    }
+
+    /// Users who hear about changes to an issue: its assignees and followers
+    pub async fn subscriber_ids<'e, E>(executor: E, issue_id: Uuid) -> Result<Vec<Uuid>, IssueError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let records = sqlx::query_scalar!(
+            r#"
+            SELECT user_id AS "user_id!: Uuid" FROM issue_assignees WHERE issue_id = $1
+            UNION
+            SELECT user_id FROM issue_followers WHERE issue_id = $1
+            "#,
+            issue_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(records)
+    }
+
     pub async fn organization_id<'e, E>(
         executor: E,
         issue_id: Uuid,
@@ -1,3 +1,5 @@
+pub mod api_tokens;
+pub mod audit_events;
 pub mod auth;
 pub mod github_app;
 pub mod identity_errors;
@@ -6,18 +8,24 @@ pub mod issue_assignees;
 pub mod issue_comment_reactions;
 pub mod issue_comments;
 pub mod issue_dependencies;
+pub mod issue_events;
 pub mod issue_followers;
+pub mod issue_mentions;
 pub mod issue_tags;
 pub mod issues;
+pub mod notification_settings;
 pub mod notifications;
 pub mod oauth;
 pub mod oauth_accounts;
 pub mod organization_members;
+pub mod organization_sso;
 pub mod organizations;
+pub mod project_members;
 pub mod project_notification_preferences;
 pub mod project_statuses;
 pub mod projects;
 pub mod reviews;
+pub mod service_accounts;
 pub mod tags;
 pub mod types;
 pub mod users;
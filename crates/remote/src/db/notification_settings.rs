@@ -0,0 +1,188 @@
+use chrono::{DateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, Postgres, Type};
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "email_digest_frequency", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum EmailDigestFrequency {
+    Off,
+    Immediate,
+    Hourly,
+    Daily,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NotificationSettings {
+    pub user_id: Uuid,
+    pub email_frequency: EmailDigestFrequency,
+    pub quiet_hours_start: Option<NaiveTime>,
+    pub quiet_hours_end: Option<NaiveTime>,
+    pub utc_offset_minutes: i32,
+    pub last_digest_sent_at: Option<DateTime<Utc>>,
+    #[serde(skip)]
+    pub unsubscribe_token: Uuid,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Error)]
+pub enum NotificationSettingsError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+pub struct NotificationSettingsRepository;
+
+impl NotificationSettingsRepository {
+    /// Load the user's settings, creating the default row on first use so
+    /// there is always an unsubscribe token to hand out
+    pub async fn get_or_create<'e, E>(
+        executor: E,
+        user_id: Uuid,
+    ) -> Result<NotificationSettings, NotificationSettingsError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let record = sqlx::query_as!(
+            NotificationSettings,
+            r#"
+            INSERT INTO user_notification_settings (user_id)
+            VALUES ($1)
+            ON CONFLICT (user_id) DO UPDATE
+            SET user_id = EXCLUDED.user_id
+            RETURNING
+                user_id             AS "user_id!: Uuid",
+                email_frequency     AS "email_frequency!: EmailDigestFrequency",
+                quiet_hours_start   AS "quiet_hours_start?: NaiveTime",
+                quiet_hours_end     AS "quiet_hours_end?: NaiveTime",
+                utc_offset_minutes  AS "utc_offset_minutes!",
+                last_digest_sent_at AS "last_digest_sent_at?: DateTime<Utc>",
+                unsubscribe_token   AS "unsubscribe_token!: Uuid",
+                updated_at          AS "updated_at!: DateTime<Utc>"
+            "#,
+            user_id
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(record)
+    }
+
+    pub async fn update<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        email_frequency: EmailDigestFrequency,
+        quiet_hours: Option<(NaiveTime, NaiveTime)>,
+        utc_offset_minutes: i32,
+    ) -> Result<NotificationSettings, NotificationSettingsError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let (quiet_hours_start, quiet_hours_end) = quiet_hours.unzip();
+        let record = sqlx::query_as!(
+            NotificationSettings,
+            r#"
+            INSERT INTO user_notification_settings (
+                user_id, email_frequency, quiet_hours_start, quiet_hours_end,
+                utc_offset_minutes, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, NOW())
+            ON CONFLICT (user_id) DO UPDATE
+            SET
+                email_frequency = EXCLUDED.email_frequency,
+                quiet_hours_start = EXCLUDED.quiet_hours_start,
+                quiet_hours_end = EXCLUDED.quiet_hours_end,
+                utc_offset_minutes = EXCLUDED.utc_offset_minutes,
+                updated_at = NOW()
+            RETURNING
+                user_id             AS "user_id!: Uuid",
+                email_frequency     AS "email_frequency!: EmailDigestFrequency",
+                quiet_hours_start   AS "quiet_hours_start?: NaiveTime",
+                quiet_hours_end     AS "quiet_hours_end?: NaiveTime",
+                utc_offset_minutes  AS "utc_offset_minutes!",
+                last_digest_sent_at AS "last_digest_sent_at?: DateTime<Utc>",
+                unsubscribe_token   AS "unsubscribe_token!: Uuid",
+                updated_at          AS "updated_at!: DateTime<Utc>"
+            "#,
+            user_id,
+            email_frequency as EmailDigestFrequency,
+            quiet_hours_start,
+            quiet_hours_end,
+            utc_offset_minutes
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Turn off all notification email for the token's owner, returning the
+    /// user id if the token was valid
+    pub async fn unsubscribe<'e, E>(
+        executor: E,
+        unsubscribe_token: Uuid,
+    ) -> Result<Option<Uuid>, NotificationSettingsError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let user_id = sqlx::query_scalar!(
+            r#"
+            UPDATE user_notification_settings
+            SET email_frequency = 'off', updated_at = NOW()
+            WHERE unsubscribe_token = $1
+            RETURNING user_id AS "user_id!: Uuid"
+            "#,
+            unsubscribe_token
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(user_id)
+    }
+
+    pub async fn find_user_by_unsubscribe_token<'e, E>(
+        executor: E,
+        unsubscribe_token: Uuid,
+    ) -> Result<Option<Uuid>, NotificationSettingsError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let user_id = sqlx::query_scalar!(
+            r#"
+            SELECT user_id AS "user_id!: Uuid"
+            FROM user_notification_settings
+            WHERE unsubscribe_token = $1
+            "#,
+            unsubscribe_token
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(user_id)
+    }
+
+    pub async fn mark_digest_sent<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        sent_at: DateTime<Utc>,
+    ) -> Result<(), NotificationSettingsError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"
+            UPDATE user_notification_settings
+            SET last_digest_sent_at = $2
+            WHERE user_id = $1
+            "#,
+            user_id,
+            sent_at
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+}
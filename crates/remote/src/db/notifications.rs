@@ -14,6 +14,7 @@ pub enum NotificationType {
     IssueStatusChanged,
     IssueAssigneeChanged,
     IssueDeleted,
+    UserMentioned,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -31,6 +32,19 @@ pub struct Notification {
     pub created_at: DateTime<Utc>,
 }
 
+/// An unread notification that has not been emailed yet, with the project it
+/// belongs to when the issue still exists
+#[derive(Debug, Clone)]
+pub struct PendingEmailNotification {
+    pub id: Uuid,
+    pub notification_type: NotificationType,
+    pub payload: Value,
+    pub project_id: Option<Uuid>,
+    pub project_name: Option<String>,
+    pub email_enabled: bool,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Error)]
 pub enum NotificationError {
     #[error(transparent)]
@@ -266,4 +280,84 @@ impl NotificationRepository {
 
         Ok(result.unwrap_or(0))
     }
+
+    /// Users with at least one notification waiting to be emailed. Service
+    /// accounts have no real mailbox and are left out.
+    pub async fn users_with_pending_email<'e, E>(
+        executor: E,
+    ) -> Result<Vec<Uuid>, NotificationError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let user_ids = sqlx::query_scalar!(
+            r#"
+            SELECT DISTINCT n.user_id AS "user_id!: Uuid"
+            FROM notifications n
+            WHERE n.emailed_at IS NULL
+              AND n.seen = FALSE
+              AND n.dismissed_at IS NULL
+              AND NOT EXISTS (SELECT 1 FROM service_accounts sa WHERE sa.user_id = n.user_id)
+            "#
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(user_ids)
+    }
+
+    pub async fn list_pending_email<'e, E>(
+        executor: E,
+        user_id: Uuid,
+    ) -> Result<Vec<PendingEmailNotification>, NotificationError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let records = sqlx::query_as!(
+            PendingEmailNotification,
+            r#"
+            SELECT
+                n.id                              AS "id!: Uuid",
+                n.notification_type               AS "notification_type!: NotificationType",
+                n.payload                         AS "payload!: Value",
+                p.id                              AS "project_id?: Uuid",
+                p.name                            AS "project_name?",
+                COALESCE(pnp.email_enabled, TRUE) AS "email_enabled!",
+                n.created_at                      AS "created_at!: DateTime<Utc>"
+            FROM notifications n
+            LEFT JOIN issues i ON i.id = n.issue_id
+            LEFT JOIN projects p ON p.id = i.project_id
+            LEFT JOIN project_notification_preferences pnp
+              ON pnp.project_id = p.id AND pnp.user_id = n.user_id
+            WHERE n.user_id = $1
+              AND n.emailed_at IS NULL
+              AND n.seen = FALSE
+              AND n.dismissed_at IS NULL
+            ORDER BY n.created_at ASC
+            "#,
+            user_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(records)
+    }
+
+    pub async fn mark_emailed<'e, E>(
+        executor: E,
+        ids: &[Uuid],
+        emailed_at: DateTime<Utc>,
+    ) -> Result<(), NotificationError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            "UPDATE notifications SET emailed_at = $2 WHERE id = ANY($1)",
+            ids,
+            emailed_at
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
 }
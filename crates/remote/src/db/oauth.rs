@@ -69,6 +69,7 @@ pub struct OAuthHandoff {
     pub user_id: Option<Uuid>,
     pub session_id: Option<Uuid>,
     pub encrypted_provider_tokens: Option<String>,
+    pub provider_code_verifier: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -85,6 +86,7 @@ pub struct CreateOAuthHandoff<'a> {
     pub state: &'a str,
     pub return_to: &'a str,
     pub app_challenge: &'a str,
+    pub provider_code_verifier: Option<&'a str>,
     pub expires_at: DateTime<Utc>,
 }
 
@@ -109,9 +111,10 @@ impl<'a> OAuthHandoffRepository<'a> {
                 state,
                 return_to,
                 app_challenge,
+                provider_code_verifier,
                 expires_at
             )
-            VALUES ($1, $2, $3, $4, $5)
+            VALUES ($1, $2, $3, $4, $5, $6)
             RETURNING
                 id                          AS "id!",
                 provider                    AS "provider!",
@@ -127,6 +130,7 @@ impl<'a> OAuthHandoffRepository<'a> {
                 user_id                     AS "user_id?",
                 session_id                  AS "session_id?",
                 encrypted_provider_tokens   AS "encrypted_provider_tokens?",
+                provider_code_verifier      AS "provider_code_verifier?",
                 created_at                  AS "created_at!",
                 updated_at                  AS "updated_at!"
             "#,
@@ -134,6 +138,7 @@ impl<'a> OAuthHandoffRepository<'a> {
             data.state,
             data.return_to,
             data.app_challenge,
+            data.provider_code_verifier,
             data.expires_at,
         )
         .fetch_one(self.pool)
@@ -160,6 +165,7 @@ impl<'a> OAuthHandoffRepository<'a> {
                 user_id         AS "user_id?",
                 session_id                  AS "session_id?",
                 encrypted_provider_tokens   AS "encrypted_provider_tokens?",
+                provider_code_verifier      AS "provider_code_verifier?",
                 created_at      AS "created_at!",
                 updated_at      AS "updated_at!"
             FROM oauth_handoffs
@@ -191,6 +197,7 @@ impl<'a> OAuthHandoffRepository<'a> {
                 user_id         AS "user_id?",
                 session_id                  AS "session_id?",
                 encrypted_provider_tokens   AS "encrypted_provider_tokens?",
+                provider_code_verifier      AS "provider_code_verifier?",
                 created_at      AS "created_at!",
                 updated_at      AS "updated_at!"
             FROM oauth_handoffs
@@ -244,6 +251,7 @@ impl<'a> OAuthHandoffRepository<'a> {
                 session_id = $3,
                 app_code_hash = $4,
                 encrypted_provider_tokens = $5,
+                provider_code_verifier = NULL,
                 authorized_at = NOW()
             WHERE id = $1
             "#,
@@ -30,7 +30,7 @@ where
     Ok(())
 }
 
-pub(super) async fn check_user_role(
+pub(crate) async fn check_user_role(
     pool: &PgPool,
     organization_id: Uuid,
     user_id: Uuid,
@@ -89,41 +89,106 @@ pub(crate) async fn assert_membership(
     }
 }
 
+/// Whether a request only reads a project's data or also changes it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectAccess {
+    Read,
+    Write,
+}
+
+/// Admins see every project. Members and viewers see the non-private ones,
+/// and anyone listed in `project_members` sees that project whatever their
+/// role. Viewers can never write.
+pub(crate) fn can_access_project(
+    role: MemberRole,
+    is_private: bool,
+    is_project_member: bool,
+    access: ProjectAccess,
+) -> bool {
+    let visible = match role {
+        MemberRole::Admin => true,
+        MemberRole::Member | MemberRole::Viewer => !is_private || is_project_member,
+        MemberRole::Guest => is_project_member,
+    };
+    visible && (access == ProjectAccess::Read || role != MemberRole::Viewer)
+}
+
+/// Projects are organization-wide resources, so only admins and full members
+/// may create them
+pub(crate) async fn assert_can_create_project(
+    pool: &PgPool,
+    organization_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), IdentityError> {
+    match check_user_role(pool, organization_id, user_id).await? {
+        Some(MemberRole::Admin | MemberRole::Member) => Ok(()),
+        Some(MemberRole::Viewer | MemberRole::Guest) => Err(IdentityError::PermissionDenied),
+        None => Err(IdentityError::NotFound),
+    }
+}
+
 pub(crate) async fn assert_issue_access(
     pool: &PgPool,
     issue_id: Uuid,
     user_id: Uuid,
+    access: ProjectAccess,
 ) -> Result<(), IdentityError> {
-    let org_id = sqlx::query_scalar!(
-        r#"
-        SELECT p.organization_id
-        FROM issues i
-        JOIN projects p ON i.project_id = p.id
-        WHERE i.id = $1
-        "#,
-        issue_id
-    )
-    .fetch_optional(pool)
-    .await?
-    .ok_or(IdentityError::NotFound)?;
+    let project_id =
+        sqlx::query_scalar!(r#"SELECT project_id FROM issues WHERE id = $1"#, issue_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or(IdentityError::NotFound)?;
 
-    assert_membership(pool, org_id, user_id).await
+    assert_project_access(pool, project_id, user_id, access).await
 }
 
+/// Hidden projects report `NotFound` so their existence is not leaked;
+/// visible projects a viewer tries to change report `PermissionDenied`
 pub(crate) async fn assert_project_access(
     pool: &PgPool,
     project_id: Uuid,
     user_id: Uuid,
+    access: ProjectAccess,
 ) -> Result<(), IdentityError> {
-    let org_id = sqlx::query_scalar!(
-        r#"SELECT organization_id FROM projects WHERE id = $1"#,
-        project_id
+    let record = sqlx::query!(
+        r#"
+        SELECT
+            m.role       AS "role!: MemberRole",
+            p.is_private AS "is_private!",
+            EXISTS(
+                SELECT 1
+                FROM project_members pm
+                WHERE pm.project_id = p.id AND pm.user_id = $2
+            ) AS "is_project_member!"
+        FROM projects p
+        JOIN organization_member_metadata m
+          ON m.organization_id = p.organization_id AND m.user_id = $2
+        WHERE p.id = $1
+        "#,
+        project_id,
+        user_id
     )
     .fetch_optional(pool)
     .await?
     .ok_or(IdentityError::NotFound)?;
 
-    assert_membership(pool, org_id, user_id).await
+    if can_access_project(
+        record.role,
+        record.is_private,
+        record.is_project_member,
+        access,
+    ) {
+        Ok(())
+    } else if can_access_project(
+        record.role,
+        record.is_private,
+        record.is_project_member,
+        ProjectAccess::Read,
+    ) {
+        Err(IdentityError::PermissionDenied)
+    } else {
+        Err(IdentityError::NotFound)
+    }
 }
 
 pub(super) async fn assert_admin(
@@ -137,3 +202,66 @@ pub(super) async fn assert_admin(
         _ => Err(IdentityError::PermissionDenied),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn admins_see_private_projects() {
+        assert!(can_access_project(
+            MemberRole::Admin,
+            true,
+            false,
+            ProjectAccess::Write
+        ));
+    }
+
+    #[test]
+    fn private_projects_require_project_membership() {
+        assert!(!can_access_project(
+            MemberRole::Member,
+            true,
+            false,
+            ProjectAccess::Read
+        ));
+        assert!(can_access_project(
+            MemberRole::Member,
+            true,
+            true,
+            ProjectAccess::Write
+        ));
+    }
+
+    #[test]
+    fn viewers_are_read_only() {
+        assert!(can_access_project(
+            MemberRole::Viewer,
+            false,
+            false,
+            ProjectAccess::Read
+        ));
+        assert!(!can_access_project(
+            MemberRole::Viewer,
+            false,
+            true,
+            ProjectAccess::Write
+        ));
+    }
+
+    #[test]
+    fn guests_only_see_projects_they_were_added_to() {
+        assert!(!can_access_project(
+            MemberRole::Guest,
+            false,
+            false,
+            ProjectAccess::Read
+        ));
+        assert!(can_access_project(
+            MemberRole::Guest,
+            true,
+            true,
+            ProjectAccess::Write
+        ));
+    }
+}
@@ -0,0 +1,354 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{Executor, FromRow, PgPool, Postgres};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::organization_members::{MemberRole, add_member};
+
+/// An email domain claimed by an organization. The claim only takes effect
+/// once `verified_at` is set, after the organization published
+/// `verification_token` in a DNS TXT record on the domain.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct OrganizationDomain {
+    pub domain: String,
+    pub organization_id: Uuid,
+    pub auto_join: bool,
+    pub verification_token: String,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub created_by_user_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Error)]
+pub enum OrganizationSsoError {
+    #[error("domain is already claimed")]
+    DomainTaken,
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+pub struct OrganizationSsoRepository;
+
+impl OrganizationSsoRepository {
+    pub async fn is_sso_required<'e, E>(
+        executor: E,
+        organization_id: Uuid,
+    ) -> Result<bool, OrganizationSsoError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let required = sqlx::query_scalar!(
+            r#"
+            SELECT sso_required AS "sso_required!"
+            FROM organization_sso_settings
+            WHERE organization_id = $1
+            "#,
+            organization_id
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(required.unwrap_or(false))
+    }
+
+    /// Whether the user belongs to any organization that requires SSO
+    pub async fn is_sso_required_for_user<'e, E>(
+        executor: E,
+        user_id: Uuid,
+    ) -> Result<bool, OrganizationSsoError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let required = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM organization_member_metadata m
+                JOIN organization_sso_settings s ON s.organization_id = m.organization_id
+                WHERE m.user_id = $1
+                  AND s.sso_required
+            ) AS "required!"
+            "#,
+            user_id
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(required)
+    }
+
+    /// Turn SSO enforcement on or off. Enabling it revokes every session of
+    /// the organization's members that was not signed in with `sso_provider`,
+    /// along with their personal API tokens; service account tokens are kept.
+    pub async fn set_sso_required(
+        pool: &PgPool,
+        organization_id: Uuid,
+        sso_required: bool,
+        sso_provider: &str,
+    ) -> Result<(), OrganizationSsoError> {
+        let mut tx = pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO organization_sso_settings (organization_id, sso_required)
+            VALUES ($1, $2)
+            ON CONFLICT (organization_id) DO UPDATE
+            SET sso_required = EXCLUDED.sso_required,
+                updated_at = NOW()
+            "#,
+            organization_id,
+            sso_required
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        if sso_required {
+            sqlx::query!(
+                r#"
+                UPDATE auth_sessions
+                SET revoked_at = NOW()
+                WHERE revoked_at IS NULL
+                  AND provider IS DISTINCT FROM $2
+                  AND user_id IN (
+                      SELECT user_id
+                      FROM organization_member_metadata
+                      WHERE organization_id = $1
+                  )
+                "#,
+                organization_id,
+                sso_provider
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            sqlx::query!(
+                r#"
+                UPDATE api_tokens
+                SET revoked_at = NOW()
+                WHERE revoked_at IS NULL
+                  AND user_id IN (
+                      SELECT user_id
+                      FROM organization_member_metadata
+                      WHERE organization_id = $1
+                  )
+                  AND user_id NOT IN (SELECT user_id FROM service_accounts)
+                "#,
+                organization_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn list_domains<'e, E>(
+        executor: E,
+        organization_id: Uuid,
+    ) -> Result<Vec<OrganizationDomain>, OrganizationSsoError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let domains = sqlx::query_as!(
+            OrganizationDomain,
+            r#"
+            SELECT
+                domain             AS "domain!",
+                organization_id    AS "organization_id!: Uuid",
+                auto_join          AS "auto_join!",
+                verification_token AS "verification_token!",
+                verified_at        AS "verified_at?: DateTime<Utc>",
+                created_by_user_id AS "created_by_user_id?: Uuid",
+                created_at         AS "created_at!: DateTime<Utc>"
+            FROM organization_domains
+            WHERE organization_id = $1
+            ORDER BY domain ASC
+            "#,
+            organization_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(domains)
+    }
+
+    pub async fn get_domain<'e, E>(
+        executor: E,
+        organization_id: Uuid,
+        domain: &str,
+    ) -> Result<Option<OrganizationDomain>, OrganizationSsoError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let domain = sqlx::query_as!(
+            OrganizationDomain,
+            r#"
+            SELECT
+                domain             AS "domain!",
+                organization_id    AS "organization_id!: Uuid",
+                auto_join          AS "auto_join!",
+                verification_token AS "verification_token!",
+                verified_at        AS "verified_at?: DateTime<Utc>",
+                created_by_user_id AS "created_by_user_id?: Uuid",
+                created_at         AS "created_at!: DateTime<Utc>"
+            FROM organization_domains
+            WHERE organization_id = $1
+              AND domain = $2
+            "#,
+            organization_id,
+            domain
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(domain)
+    }
+
+    /// Add an unverified claim. Fails with `DomainTaken` if the organization
+    /// already added the domain or another organization has verified it.
+    pub async fn add_domain<'e, E>(
+        executor: E,
+        organization_id: Uuid,
+        domain: &str,
+        auto_join: bool,
+        created_by_user_id: Uuid,
+    ) -> Result<OrganizationDomain, OrganizationSsoError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let added = sqlx::query_as!(
+            OrganizationDomain,
+            r#"
+            INSERT INTO organization_domains (domain, organization_id, auto_join, created_by_user_id)
+            SELECT $1, $2, $3, $4
+            WHERE NOT EXISTS (
+                SELECT 1
+                FROM organization_domains
+                WHERE domain = $1
+                  AND verified_at IS NOT NULL
+            )
+            RETURNING
+                domain             AS "domain!",
+                organization_id    AS "organization_id!: Uuid",
+                auto_join          AS "auto_join!",
+                verification_token AS "verification_token!",
+                verified_at        AS "verified_at?: DateTime<Utc>",
+                created_by_user_id AS "created_by_user_id?: Uuid",
+                created_at         AS "created_at!: DateTime<Utc>"
+            "#,
+            domain,
+            organization_id,
+            auto_join,
+            created_by_user_id
+        )
+        .fetch_optional(executor)
+        .await
+        .map_err(map_domain_conflict)?;
+
+        added.ok_or(OrganizationSsoError::DomainTaken)
+    }
+
+    /// Record that the organization proved control of the domain. Fails with
+    /// `DomainTaken` if another organization verified it first.
+    pub async fn mark_domain_verified<'e, E>(
+        executor: E,
+        organization_id: Uuid,
+        domain: &str,
+    ) -> Result<OrganizationDomain, OrganizationSsoError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query_as!(
+            OrganizationDomain,
+            r#"
+            UPDATE organization_domains
+            SET verified_at = COALESCE(verified_at, NOW())
+            WHERE organization_id = $1
+              AND domain = $2
+            RETURNING
+                domain             AS "domain!",
+                organization_id    AS "organization_id!: Uuid",
+                auto_join          AS "auto_join!",
+                verification_token AS "verification_token!",
+                verified_at        AS "verified_at?: DateTime<Utc>",
+                created_by_user_id AS "created_by_user_id?: Uuid",
+                created_at         AS "created_at!: DateTime<Utc>"
+            "#,
+            organization_id,
+            domain
+        )
+        .fetch_one(executor)
+        .await
+        .map_err(map_domain_conflict)
+    }
+
+    /// Returns false if the organization has not claimed the domain
+    pub async fn delete_domain<'e, E>(
+        executor: E,
+        organization_id: Uuid,
+        domain: &str,
+    ) -> Result<bool, OrganizationSsoError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM organization_domains
+            WHERE organization_id = $1
+              AND domain = $2
+            "#,
+            organization_id,
+            domain
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Add the user as a member of the organization that verified `domain` for
+    /// auto-join, unless they already belong to it. Returns the organization
+    /// joined, if any.
+    pub async fn auto_join_by_domain(
+        pool: &PgPool,
+        user_id: Uuid,
+        domain: &str,
+    ) -> Result<Option<Uuid>, OrganizationSsoError> {
+        let organization_id = sqlx::query_scalar!(
+            r#"
+            SELECT d.organization_id AS "organization_id!: Uuid"
+            FROM organization_domains d
+            WHERE d.domain = $1
+              AND d.auto_join
+              AND d.verified_at IS NOT NULL
+              AND NOT EXISTS (
+                  SELECT 1
+                  FROM organization_member_metadata m
+                  WHERE m.organization_id = d.organization_id
+                    AND m.user_id = $2
+              )
+            "#,
+            domain,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        let Some(organization_id) = organization_id else {
+            return Ok(None);
+        };
+        add_member(pool, organization_id, user_id, MemberRole::Member).await?;
+        Ok(Some(organization_id))
+    }
+}
+
+fn map_domain_conflict(error: sqlx::Error) -> OrganizationSsoError {
+    match &error {
+        sqlx::Error::Database(db_error) if db_error.is_unique_violation() => {
+            OrganizationSsoError::DomainTaken
+        }
+        _ => OrganizationSsoError::Database(error),
+    }
+}
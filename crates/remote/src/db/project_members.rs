@@ -0,0 +1,130 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, Postgres};
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ProjectMember {
+    pub project_id: Uuid,
+    pub user_id: Uuid,
+    pub added_by_user_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Error)]
+pub enum ProjectMemberError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+pub struct ProjectMemberRepository;
+
+impl ProjectMemberRepository {
+    pub async fn list_by_project<'e, E>(
+        executor: E,
+        project_id: Uuid,
+    ) -> Result<Vec<ProjectMember>, ProjectMemberError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let records = sqlx::query_as!(
+            ProjectMember,
+            r#"
+            SELECT
+                project_id       AS "project_id!: Uuid",
+                user_id          AS "user_id!: Uuid",
+                added_by_user_id AS "added_by_user_id?: Uuid",
+                created_at       AS "created_at!: DateTime<Utc>"
+            FROM project_members
+            WHERE project_id = $1
+            ORDER BY created_at ASC
+            "#,
+            project_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Adding someone who is already a member keeps the original record
+    pub async fn add<'e, E>(
+        executor: E,
+        project_id: Uuid,
+        user_id: Uuid,
+        added_by_user_id: Option<Uuid>,
+    ) -> Result<ProjectMember, ProjectMemberError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let record = sqlx::query_as!(
+            ProjectMember,
+            r#"
+            INSERT INTO project_members (project_id, user_id, added_by_user_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (project_id, user_id) DO UPDATE
+            SET project_id = EXCLUDED.project_id
+            RETURNING
+                project_id       AS "project_id!: Uuid",
+                user_id          AS "user_id!: Uuid",
+                added_by_user_id AS "added_by_user_id?: Uuid",
+                created_at       AS "created_at!: DateTime<Utc>"
+            "#,
+            project_id,
+            user_id,
+            added_by_user_id
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Returns whether a membership was removed
+    pub async fn remove<'e, E>(
+        executor: E,
+        project_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool, ProjectMemberError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let result = sqlx::query!(
+            "DELETE FROM project_members WHERE project_id = $1 AND user_id = $2",
+            project_id,
+            user_id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Drop every project membership a user holds in an organization, used
+    /// when they leave or are removed from it
+    pub async fn remove_from_organization<'e, E>(
+        executor: E,
+        organization_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<(), ProjectMemberError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"
+            DELETE FROM project_members
+            WHERE user_id = $2
+              AND project_id IN (SELECT id FROM projects WHERE organization_id = $1)
+            "#,
+            organization_id,
+            user_id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -9,6 +9,7 @@ pub struct ProjectNotificationPreference {
     pub user_id: Uuid,
     pub notify_on_issue_created: bool,
     pub notify_on_issue_assigned: bool,
+    pub email_enabled: bool,
 }
 
 #[derive(Debug, Error)]
@@ -35,7 +36,8 @@ impl ProjectNotificationPreferenceRepository {
                 project_id               AS "project_id!: Uuid",
                 user_id                  AS "user_id!: Uuid",
                 notify_on_issue_created  AS "notify_on_issue_created!",
-                notify_on_issue_assigned AS "notify_on_issue_assigned!"
+                notify_on_issue_assigned AS "notify_on_issue_assigned!",
+                email_enabled            AS "email_enabled!"
             FROM project_notification_preferences
             WHERE project_id = $1 AND user_id = $2
             "#,
@@ -47,4 +49,72 @@ impl ProjectNotificationPreferenceRepository {
 
         Ok(record)
     }
+
+    pub async fn upsert<'e, E>(
+        executor: E,
+        project_id: Uuid,
+        user_id: Uuid,
+        notify_on_issue_created: bool,
+        notify_on_issue_assigned: bool,
+        email_enabled: bool,
+    ) -> Result<ProjectNotificationPreference, ProjectNotificationPreferenceError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let record = sqlx::query_as!(
+            ProjectNotificationPreference,
+            r#"
+            INSERT INTO project_notification_preferences (
+                project_id, user_id, notify_on_issue_created,
+                notify_on_issue_assigned, email_enabled
+            )
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (project_id, user_id) DO UPDATE
+            SET
+                notify_on_issue_created = EXCLUDED.notify_on_issue_created,
+                notify_on_issue_assigned = EXCLUDED.notify_on_issue_assigned,
+                email_enabled = EXCLUDED.email_enabled
+            RETURNING
+                project_id               AS "project_id!: Uuid",
+                user_id                  AS "user_id!: Uuid",
+                notify_on_issue_created  AS "notify_on_issue_created!",
+                notify_on_issue_assigned AS "notify_on_issue_assigned!",
+                email_enabled            AS "email_enabled!"
+            "#,
+            project_id,
+            user_id,
+            notify_on_issue_created,
+            notify_on_issue_assigned,
+            email_enabled
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Stop emailing the user about a project, keeping in-app notifications
+    pub async fn disable_email<'e, E>(
+        executor: E,
+        project_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<(), ProjectNotificationPreferenceError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"
+            INSERT INTO project_notification_preferences (project_id, user_id, email_enabled)
+            VALUES ($1, $2, FALSE)
+            ON CONFLICT (project_id, user_id) DO UPDATE
+            SET email_enabled = FALSE
+            "#,
+            project_id,
+            user_id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
 }
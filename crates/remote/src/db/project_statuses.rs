@@ -5,14 +5,16 @@ use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
 
-/// Default statuses that are created for each new project (name, color, sort_order)
-pub const DEFAULT_STATUSES: &[(&str, &str, i32)] = &[
-    ("Backlog", "#6b7280", 0),
-    ("To do", "#3b82f6", 1),
-    ("In progress", "#f59e0b", 2),
-    ("In review", "#8b5cf6", 3),
-    ("Done", "#22c55e", 4),
-    ("Cancelled", "#ef4444", 5),
+use super::types::StatusCategory;
+
+/// Default statuses that are created for each new project (name, color, sort_order, category)
+pub const DEFAULT_STATUSES: &[(&str, &str, i32, StatusCategory)] = &[
+    ("Backlog", "#6b7280", 0, StatusCategory::Backlog),
+    ("To do", "#3b82f6", 1, StatusCategory::Unstarted),
+    ("In progress", "#f59e0b", 2, StatusCategory::Started),
+    ("In review", "#8b5cf6", 3, StatusCategory::Started),
+    ("Done", "#22c55e", 4, StatusCategory::Completed),
+    ("Cancelled", "#ef4444", 5, StatusCategory::Cancelled),
 ];
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -23,6 +25,7 @@ pub struct ProjectStatus {
     pub name: String,
     pub color: String,
     pub sort_order: i32,
+    pub category: StatusCategory,
     pub created_at: DateTime<Utc>,
 }
 
@@ -51,6 +54,7 @@ impl ProjectStatusRepository {
                 name            AS "name!",
                 color           AS "color!",
                 sort_order      AS "sort_order!",
+                category        AS "category!: StatusCategory",
                 created_at      AS "created_at!: DateTime<Utc>"
             FROM project_statuses
             WHERE id = $1
@@ -69,6 +73,7 @@ impl ProjectStatusRepository {
         name: String,
         color: String,
         sort_order: i32,
+        category: StatusCategory,
     ) -> Result<ProjectStatus, ProjectStatusError>
     where
         E: Executor<'e, Database = Postgres>,
@@ -78,14 +83,15 @@ impl ProjectStatusRepository {
         let record = sqlx::query_as!(
             ProjectStatus,
             r#"
-            INSERT INTO project_statuses (id, project_id, name, color, sort_order, created_at)
-            VALUES ($1, $2, $3, $4, $5, $6)
+            INSERT INTO project_statuses (id, project_id, name, color, sort_order, category, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
             RETURNING
                 id              AS "id!: Uuid",
                 project_id      AS "project_id!: Uuid",
                 name            AS "name!",
                 color           AS "color!",
                 sort_order      AS "sort_order!",
+                category        AS "category!: StatusCategory",
                 created_at      AS "created_at!: DateTime<Utc>"
             "#,
             id,
@@ -93,6 +99,7 @@ impl ProjectStatusRepository {
             name,
             color,
             sort_order,
+            category as StatusCategory,
             created_at
         )
         .fetch_one(executor)
@@ -107,6 +114,7 @@ impl ProjectStatusRepository {
         name: String,
         color: String,
         sort_order: i32,
+        category: StatusCategory,
     ) -> Result<ProjectStatus, ProjectStatusError>
     where
         E: Executor<'e, Database = Postgres>,
@@ -118,19 +126,22 @@ impl ProjectStatusRepository {
             SET
                 name = $1,
                 color = $2,
-                sort_order = $3
-            WHERE id = $4
+                sort_order = $3,
+                category = $4
+            WHERE id = $5
             RETURNING
                 id              AS "id!: Uuid",
                 project_id      AS "project_id!: Uuid",
                 name            AS "name!",
                 color           AS "color!",
                 sort_order      AS "sort_order!",
+                category        AS "category!: StatusCategory",
                 created_at      AS "created_at!: DateTime<Utc>"
             "#,
             name,
             color,
             sort_order,
+            category as StatusCategory,
             id
         )
         .fetch_one(executor)
@@ -165,6 +176,7 @@ impl ProjectStatusRepository {
                 name            AS "name!",
                 color           AS "color!",
                 sort_order      AS "sort_order!",
+                category        AS "category!: StatusCategory",
                 created_at      AS "created_at!: DateTime<Utc>"
             FROM project_statuses
             WHERE project_id = $1
@@ -186,32 +198,37 @@ impl ProjectStatusRepository {
     {
         let names: Vec<String> = DEFAULT_STATUSES
             .iter()
-            .map(|(n, _, _)| (*n).to_string())
+            .map(|(n, _, _, _)| (*n).to_string())
             .collect();
         let colors: Vec<String> = DEFAULT_STATUSES
             .iter()
-            .map(|(_, c, _)| (*c).to_string())
+            .map(|(_, c, _, _)| (*c).to_string())
             .collect();
-        let sort_orders: Vec<i32> = DEFAULT_STATUSES.iter().map(|(_, _, s)| *s).collect();
+        let sort_orders: Vec<i32> = DEFAULT_STATUSES.iter().map(|(_, _, s, _)| *s).collect();
+        let categories: Vec<StatusCategory> =
+            DEFAULT_STATUSES.iter().map(|(_, _, _, c)| *c).collect();
 
         let statuses = sqlx::query_as!(
             ProjectStatus,
             r#"
-            INSERT INTO project_statuses (id, project_id, name, color, sort_order, created_at)
-            SELECT gen_random_uuid(), $1, name, color, sort_order, NOW()
-            FROM UNNEST($2::text[], $3::text[], $4::int[]) AS t(name, color, sort_order)
+            INSERT INTO project_statuses (id, project_id, name, color, sort_order, category, created_at)
+            SELECT gen_random_uuid(), $1, name, color, sort_order, category, NOW()
+            FROM UNNEST($2::text[], $3::text[], $4::int[], $5::status_category[])
+                AS t(name, color, sort_order, category)
             RETURNING
                 id              AS "id!: Uuid",
                 project_id      AS "project_id!: Uuid",
                 name            AS "name!",
                 color           AS "color!",
                 sort_order      AS "sort_order!",
+                category        AS "category!: StatusCategory",
                 created_at      AS "created_at!: DateTime<Utc>"
             "#,
             project_id,
             &names,
             &colors,
-            &sort_orders
+            &sort_orders,
+            &categories as &[StatusCategory]
         )
         .fetch_all(executor)
         .await?;
@@ -12,6 +12,7 @@ pub struct Project {
     pub organization_id: Uuid,
     pub name: String,
     pub color: String,
+    pub is_private: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -39,6 +40,7 @@ impl ProjectRepository {
                 organization_id  AS "organization_id!: Uuid",
                 name             AS "name!",
                 color            AS "color!",
+                is_private       AS "is_private!",
                 created_at       AS "created_at!: DateTime<Utc>",
                 updated_at       AS "updated_at!: DateTime<Utc>"
             FROM projects
@@ -57,6 +59,7 @@ impl ProjectRepository {
         organization_id: Uuid,
         name: String,
         color: String,
+        is_private: bool,
     ) -> Result<Project, ProjectError>
     where
         E: Executor<'e, Database = Postgres>,
@@ -67,15 +70,16 @@ impl ProjectRepository {
             Project,
             r#"
             INSERT INTO projects (
-                id, organization_id, name, color,
+                id, organization_id, name, color, is_private,
                 created_at, updated_at
             )
-            VALUES ($1, $2, $3, $4, $5, $6)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
             RETURNING
                 id               AS "id!: Uuid",
                 organization_id  AS "organization_id!: Uuid",
                 name             AS "name!",
                 color            AS "color!",
+                is_private       AS "is_private!",
                 created_at       AS "created_at!: DateTime<Utc>",
                 updated_at       AS "updated_at!: DateTime<Utc>"
             "#,
@@ -83,6 +87,7 @@ impl ProjectRepository {
             organization_id,
             name,
             color,
+            is_private,
             now,
             now
         )
@@ -92,9 +97,12 @@ impl ProjectRepository {
         Ok(record)
     }
 
-    pub async fn list_by_organization<'e, E>(
+    /// Projects in the organization that the user may see, following the
+    /// same rules as `organization_members::can_access_project`
+    pub async fn list_visible_to_user<'e, E>(
         executor: E,
         organization_id: Uuid,
+        user_id: Uuid,
     ) -> Result<Vec<Project>, ProjectError>
     where
         E: Executor<'e, Database = Postgres>,
@@ -103,17 +111,30 @@ impl ProjectRepository {
             Project,
             r#"
             SELECT
-                id               AS "id!: Uuid",
-                organization_id  AS "organization_id!: Uuid",
-                name             AS "name!",
-                color            AS "color!",
-                created_at       AS "created_at!: DateTime<Utc>",
-                updated_at       AS "updated_at!: DateTime<Utc>"
-            FROM projects
-            WHERE organization_id = $1
-            ORDER BY created_at DESC
+                p.id               AS "id!: Uuid",
+                p.organization_id  AS "organization_id!: Uuid",
+                p.name             AS "name!",
+                p.color            AS "color!",
+                p.is_private       AS "is_private!",
+                p.created_at       AS "created_at!: DateTime<Utc>",
+                p.updated_at       AS "updated_at!: DateTime<Utc>"
+            FROM projects p
+            JOIN organization_member_metadata m
+              ON m.organization_id = p.organization_id AND m.user_id = $2
+            WHERE p.organization_id = $1
+              AND (
+                  m.role = 'admin'
+                  OR (m.role IN ('member', 'viewer') AND NOT p.is_private)
+                  OR EXISTS (
+                      SELECT 1
+                      FROM project_members pm
+                      WHERE pm.project_id = p.id AND pm.user_id = $2
+                  )
+              )
+            ORDER BY p.created_at DESC
             "#,
-            organization_id
+            organization_id,
+            user_id
         )
         .fetch_all(executor)
         .await?;
@@ -145,6 +166,7 @@ impl ProjectRepository {
                 organization_id  AS "organization_id!: Uuid",
                 name             AS "name!",
                 color            AS "color!",
+                is_private       AS "is_private!",
                 created_at       AS "created_at!: DateTime<Utc>",
                 updated_at       AS "updated_at!: DateTime<Utc>"
             "#,
@@ -159,6 +181,42 @@ impl ProjectRepository {
         Ok(record)
     }
 
+    pub async fn set_private<'e, E>(
+        executor: E,
+        id: Uuid,
+        is_private: bool,
+    ) -> Result<Project, ProjectError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let updated_at = Utc::now();
+        let record = sqlx::query_as!(
+            Project,
+            r#"
+            UPDATE projects
+            SET
+                is_private = $1,
+                updated_at = $2
+            WHERE id = $3
+            RETURNING
+                id               AS "id!: Uuid",
+                organization_id  AS "organization_id!: Uuid",
+                name             AS "name!",
+                color            AS "color!",
+                is_private       AS "is_private!",
+                created_at       AS "created_at!: DateTime<Utc>",
+                updated_at       AS "updated_at!: DateTime<Utc>"
+            "#,
+            is_private,
+            updated_at,
+            id
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(record)
+    }
+
     pub async fn delete<'e, E>(executor: E, id: Uuid) -> Result<(), ProjectError>
     where
         E: Executor<'e, Database = Postgres>,
@@ -0,0 +1,168 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{Executor, FromRow, PgPool, Postgres};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::{
+    api_tokens::{ApiTokenError, ApiTokenRepository},
+    organization_members::{MemberRole, add_member},
+};
+
+/// Bot user owned by an organization, e.g. for CI jobs. Service accounts are
+/// regular members of their organization and authenticate with API tokens.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ServiceAccount {
+    pub user_id: Uuid,
+    pub organization_id: Uuid,
+    pub name: String,
+    pub created_by_user_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub disabled_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Error)]
+pub enum ServiceAccountError {
+    #[error(transparent)]
+    ApiToken(#[from] ApiTokenError),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+pub struct ServiceAccountRepository;
+
+impl ServiceAccountRepository {
+    /// Create the backing user, add it to the organization as a member and
+    /// record it as a service account
+    pub async fn create(
+        pool: &PgPool,
+        organization_id: Uuid,
+        name: &str,
+        created_by_user_id: Uuid,
+    ) -> Result<ServiceAccount, ServiceAccountError> {
+        let mut tx = pool.begin().await?;
+
+        let user_id = Uuid::new_v4();
+        // Service accounts never receive mail, so use an address that cannot exist
+        let email = format!("{user_id}@service-accounts.invalid");
+        sqlx::query!(
+            r#"
+            INSERT INTO users (id, email, username)
+            VALUES ($1, $2, $3)
+            "#,
+            user_id,
+            email,
+            name
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        add_member(&mut *tx, organization_id, user_id, MemberRole::Member).await?;
+
+        let account = sqlx::query_as!(
+            ServiceAccount,
+            r#"
+            INSERT INTO service_accounts (user_id, organization_id, name, created_by_user_id)
+            VALUES ($1, $2, $3, $4)
+            RETURNING
+                user_id            AS "user_id!: Uuid",
+                organization_id    AS "organization_id!: Uuid",
+                name               AS "name!",
+                created_by_user_id AS "created_by_user_id?: Uuid",
+                created_at         AS "created_at!: DateTime<Utc>",
+                disabled_at        AS "disabled_at?: DateTime<Utc>"
+            "#,
+            user_id,
+            organization_id,
+            name,
+            created_by_user_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(account)
+    }
+
+    pub async fn find_by_user_id<'e, E>(
+        executor: E,
+        user_id: Uuid,
+    ) -> Result<Option<ServiceAccount>, ServiceAccountError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let account = sqlx::query_as!(
+            ServiceAccount,
+            r#"
+            SELECT
+                user_id            AS "user_id!: Uuid",
+                organization_id    AS "organization_id!: Uuid",
+                name               AS "name!",
+                created_by_user_id AS "created_by_user_id?: Uuid",
+                created_at         AS "created_at!: DateTime<Utc>",
+                disabled_at        AS "disabled_at?: DateTime<Utc>"
+            FROM service_accounts
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(account)
+    }
+
+    /// Active service accounts of an organization, oldest first
+    pub async fn list_by_organization<'e, E>(
+        executor: E,
+        organization_id: Uuid,
+    ) -> Result<Vec<ServiceAccount>, ServiceAccountError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let accounts = sqlx::query_as!(
+            ServiceAccount,
+            r#"
+            SELECT
+                user_id            AS "user_id!: Uuid",
+                organization_id    AS "organization_id!: Uuid",
+                name               AS "name!",
+                created_by_user_id AS "created_by_user_id?: Uuid",
+                created_at         AS "created_at!: DateTime<Utc>",
+                disabled_at        AS "disabled_at?: DateTime<Utc>"
+            FROM service_accounts
+            WHERE organization_id = $1
+              AND disabled_at IS NULL
+            ORDER BY created_at ASC
+            "#,
+            organization_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(accounts)
+    }
+
+    /// Disable a service account and revoke all of its tokens. The backing
+    /// user is kept so issues and comments it authored stay attributed.
+    pub async fn disable(pool: &PgPool, user_id: Uuid) -> Result<(), ServiceAccountError> {
+        let mut tx = pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE service_accounts
+            SET disabled_at = NOW()
+            WHERE user_id = $1
+              AND disabled_at IS NULL
+            "#,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        ApiTokenRepository::revoke_all_for_user(&mut *tx, user_id).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
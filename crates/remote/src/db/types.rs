@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+pub use utils::api::workspaces::WorkspacePrStatus;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, TS)]
 #[sqlx(type_name = "issue_priority", rename_all = "snake_case")]
@@ -12,12 +13,15 @@ pub enum IssuePriority {
     Low,
 }
 
+/// What a project status means, independent of its name
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, TS)]
-#[sqlx(type_name = "workspace_pr_status", rename_all = "lowercase")]
-#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "status_category", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
 #[ts(export)]
-pub enum WorkspacePrStatus {
-    Open,
-    Merged,
-    Closed,
+pub enum StatusCategory {
+    Backlog,
+    Unstarted,
+    Started,
+    Completed,
+    Cancelled,
 }
@@ -1,6 +1,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::{Executor, FromRow, Postgres};
+use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
 
@@ -46,3 +47,242 @@ pub struct WorkspacePr {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
+
+#[derive(Debug, Error)]
+pub enum WorkspaceError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+pub struct WorkspaceRepository;
+
+impl WorkspaceRepository {
+    pub async fn find_by_local_id<'e, E>(
+        executor: E,
+        owner_user_id: Uuid,
+        local_workspace_id: Uuid,
+    ) -> Result<Option<Workspace>, WorkspaceError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let record = sqlx::query_as!(
+            Workspace,
+            r#"
+            SELECT
+                id                 AS "id!: Uuid",
+                project_id         AS "project_id!: Uuid",
+                owner_user_id      AS "owner_user_id!: Uuid",
+                issue_id           AS "issue_id?: Uuid",
+                local_workspace_id AS "local_workspace_id!: Uuid",
+                archived           AS "archived!",
+                files_changed,
+                lines_added,
+                lines_removed,
+                created_at         AS "created_at!: DateTime<Utc>",
+                updated_at         AS "updated_at!: DateTime<Utc>"
+            FROM workspaces
+            WHERE owner_user_id = $1 AND local_workspace_id = $2
+            "#,
+            owner_user_id,
+            local_workspace_id
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Insert or overwrite the workspace a user pushed from a local client
+    #[allow(clippy::too_many_arguments)]
+    pub async fn upsert<'e, E>(
+        executor: E,
+        owner_user_id: Uuid,
+        local_workspace_id: Uuid,
+        project_id: Uuid,
+        issue_id: Option<Uuid>,
+        archived: bool,
+        files_changed: Option<i32>,
+        lines_added: Option<i32>,
+        lines_removed: Option<i32>,
+    ) -> Result<Workspace, WorkspaceError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let record = sqlx::query_as!(
+            Workspace,
+            r#"
+            INSERT INTO workspaces (
+                project_id, owner_user_id, issue_id, local_workspace_id,
+                archived, files_changed, lines_added, lines_removed
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (owner_user_id, local_workspace_id) DO UPDATE SET
+                project_id    = EXCLUDED.project_id,
+                issue_id      = EXCLUDED.issue_id,
+                archived      = EXCLUDED.archived,
+                files_changed = EXCLUDED.files_changed,
+                lines_added   = EXCLUDED.lines_added,
+                lines_removed = EXCLUDED.lines_removed,
+                updated_at    = NOW()
+            RETURNING
+                id                 AS "id!: Uuid",
+                project_id         AS "project_id!: Uuid",
+                owner_user_id      AS "owner_user_id!: Uuid",
+                issue_id           AS "issue_id?: Uuid",
+                local_workspace_id AS "local_workspace_id!: Uuid",
+                archived           AS "archived!",
+                files_changed,
+                lines_added,
+                lines_removed,
+                created_at         AS "created_at!: DateTime<Utc>",
+                updated_at         AS "updated_at!: DateTime<Utc>"
+            "#,
+            project_id,
+            owner_user_id,
+            issue_id,
+            local_workspace_id,
+            archived,
+            files_changed,
+            lines_added,
+            lines_removed
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(record)
+    }
+
+    pub async fn delete<'e, E>(executor: E, id: Uuid) -> Result<(), WorkspaceError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!("DELETE FROM workspaces WHERE id = $1", id)
+            .execute(executor)
+            .await?;
+        Ok(())
+    }
+}
+
+pub struct WorkspaceRepoRepository;
+
+impl WorkspaceRepoRepository {
+    pub async fn upsert<'e, E>(
+        executor: E,
+        workspace_id: Uuid,
+        repo_name: &str,
+    ) -> Result<WorkspaceRepo, WorkspaceError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let record = sqlx::query_as!(
+            WorkspaceRepo,
+            r#"
+            INSERT INTO workspace_repos (workspace_id, repo_name)
+            VALUES ($1, $2)
+            ON CONFLICT (workspace_id, repo_name) DO UPDATE SET
+                updated_at = workspace_repos.updated_at
+            RETURNING
+                id           AS "id!: Uuid",
+                workspace_id AS "workspace_id!: Uuid",
+                repo_name    AS "repo_name!",
+                created_at   AS "created_at!: DateTime<Utc>",
+                updated_at   AS "updated_at!: DateTime<Utc>"
+            "#,
+            workspace_id,
+            repo_name
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Remove the workspace's repos whose names are not in `keep`
+    pub async fn delete_except<'e, E>(
+        executor: E,
+        workspace_id: Uuid,
+        keep: &[String],
+    ) -> Result<(), WorkspaceError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            "DELETE FROM workspace_repos WHERE workspace_id = $1 AND NOT (repo_name = ANY($2))",
+            workspace_id,
+            keep
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+}
+
+pub struct WorkspacePrRepository;
+
+impl WorkspacePrRepository {
+    pub async fn upsert<'e, E>(
+        executor: E,
+        workspace_repo_id: Uuid,
+        pr_url: &str,
+        pr_number: i32,
+        pr_status: WorkspacePrStatus,
+        merged_at: Option<DateTime<Utc>>,
+        closed_at: Option<DateTime<Utc>>,
+    ) -> Result<WorkspacePr, WorkspaceError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let record = sqlx::query_as!(
+            WorkspacePr,
+            r#"
+            INSERT INTO workspace_prs (
+                workspace_repo_id, pr_url, pr_number, pr_status, merged_at, closed_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (workspace_repo_id) DO UPDATE SET
+                pr_url     = EXCLUDED.pr_url,
+                pr_number  = EXCLUDED.pr_number,
+                pr_status  = EXCLUDED.pr_status,
+                merged_at  = EXCLUDED.merged_at,
+                closed_at  = EXCLUDED.closed_at,
+                updated_at = NOW()
+            RETURNING
+                id                AS "id!: Uuid",
+                workspace_repo_id AS "workspace_repo_id!: Uuid",
+                pr_url            AS "pr_url!",
+                pr_number         AS "pr_number!",
+                pr_status         AS "pr_status!: WorkspacePrStatus",
+                merged_at         AS "merged_at?: DateTime<Utc>",
+                closed_at         AS "closed_at?: DateTime<Utc>",
+                created_at        AS "created_at!: DateTime<Utc>",
+                updated_at        AS "updated_at!: DateTime<Utc>"
+            "#,
+            workspace_repo_id,
+            pr_url,
+            pr_number,
+            pr_status as WorkspacePrStatus,
+            merged_at,
+            closed_at
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(record)
+    }
+
+    pub async fn delete_by_workspace_repo<'e, E>(
+        executor: E,
+        workspace_repo_id: Uuid,
+    ) -> Result<(), WorkspaceError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            "DELETE FROM workspace_prs WHERE workspace_repo_id = $1",
+            workspace_repo_id
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+}
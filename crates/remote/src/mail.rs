@@ -9,6 +9,21 @@ const LOOPS_INVITE_TEMPLATE_ID: &str = "cmhvy2wgs3s13z70i1pxakij9";
 const LOOPS_REVIEW_READY_TEMPLATE_ID: &str = "cmj47k5ge16990iylued9by17";
 const LOOPS_REVIEW_FAILED_TEMPLATE_ID: &str = "cmj49ougk1c8s0iznavijdqpo";
 
+/// Unread notifications collected for one user, oldest first
+#[derive(Debug, Clone)]
+pub struct NotificationDigest {
+    pub items: Vec<NotificationDigestItem>,
+    pub unsubscribe_url: String,
+    /// One link per project in the digest that stops email for that project only
+    pub project_unsubscribe_urls: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NotificationDigestItem {
+    pub project_name: Option<String>,
+    pub summary: String,
+}
+
 #[async_trait]
 pub trait Mailer: Send + Sync {
     async fn send_org_invitation(
@@ -23,21 +38,32 @@ pub trait Mailer: Send + Sync {
     async fn send_review_ready(&self, email: &str, review_url: &str, pr_name: &str);
 
     async fn send_review_failed(&self, email: &str, pr_name: &str, review_id: &str);
+
+    async fn send_notification_digest(&self, email: &str, digest: &NotificationDigest);
 }
 
 pub struct LoopsMailer {
     client: reqwest::Client,
     api_key: String,
+    digest_template_id: Option<String>,
 }
 
 impl LoopsMailer {
-    pub fn new(api_key: String) -> Self {
+    pub fn new(api_key: String, digest_template_id: Option<String>) -> Self {
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(5))
             .build()
             .expect("failed to build reqwest client");
 
-        Self { client, api_key }
+        Self {
+            client,
+            api_key,
+            digest_template_id,
+        }
+    }
+
+    pub fn supports_digests(&self) -> bool {
+        self.digest_template_id.is_some()
     }
 }
 
@@ -54,6 +80,8 @@ impl Mailer for LoopsMailer {
         let role_str = match role {
             MemberRole::Admin => "admin",
             MemberRole::Member => "member",
+            MemberRole::Viewer => "viewer",
+            MemberRole::Guest => "guest",
         };
         let inviter = invited_by.unwrap_or("someone");
 
@@ -181,4 +209,69 @@ impl Mailer for LoopsMailer {
             }
         }
     }
+
+    async fn send_notification_digest(&self, email: &str, digest: &NotificationDigest) {
+        let Some(template_id) = &self.digest_template_id else {
+            tracing::warn!("notification digest requested but no Loops template is configured");
+            return;
+        };
+
+        let items_text = digest
+            .items
+            .iter()
+            .map(|item| match &item.project_name {
+                Some(project_name) => format!("[{project_name}] {}", item.summary),
+                None => item.summary.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let project_unsubscribe_text = digest
+            .project_unsubscribe_urls
+            .iter()
+            .map(|(project_name, url)| format!("{project_name}: {url}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        if cfg!(debug_assertions) {
+            tracing::info!(
+                "Sending notification digest to {email}\n\
+                 {items_text}\n\
+                 Unsubscribe URL: {}",
+                digest.unsubscribe_url
+            );
+        }
+
+        let payload = json!({
+            "transactionalId": template_id,
+            "email": email,
+            "dataVariables": {
+                "notification_count": digest.items.len(),
+                "notifications": items_text,
+                "unsubscribe_url": digest.unsubscribe_url,
+                "project_unsubscribe_links": project_unsubscribe_text,
+            }
+        });
+
+        let res = self
+            .client
+            .post("https://app.loops.so/api/v1/transactional")
+            .bearer_auth(&self.api_key)
+            .json(&payload)
+            .send()
+            .await;
+
+        match res {
+            Ok(resp) if resp.status().is_success() => {
+                tracing::debug!("Notification digest sent via Loops to {email}");
+            }
+            Ok(resp) => {
+                let status = resp.status();
+                let body = resp.text().await.unwrap_or_default();
+                tracing::warn!(status = %status, body = %body, "Loops send failed for notification digest");
+            }
+            Err(err) => {
+                tracing::error!(error = ?err, "Loops request error for notification digest");
+            }
+        }
+    }
 }
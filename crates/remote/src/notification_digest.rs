@@ -0,0 +1,405 @@
+//! Background job that emails users their unread notifications.
+//!
+//! Each tick looks at every user with notifications that have not been
+//! emailed yet and sends one digest per user whose delivery frequency is due
+//! and who is outside their quiet hours. Notifications for projects the user
+//! muted by email are marked as handled without being sent.
+
+use std::{collections::BTreeMap, sync::Arc, time::Duration as StdDuration};
+
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use serde_json::Value;
+use sqlx::PgPool;
+use thiserror::Error;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        identity_errors::IdentityError,
+        notification_settings::{
+            EmailDigestFrequency, NotificationSettings, NotificationSettingsError,
+            NotificationSettingsRepository,
+        },
+        notifications::{
+            NotificationError, NotificationRepository, NotificationType, PendingEmailNotification,
+        },
+        users::UserRepository,
+    },
+    mail::{Mailer, NotificationDigest, NotificationDigestItem},
+};
+
+const TICK_INTERVAL: StdDuration = StdDuration::from_secs(60);
+
+/// Arbitrary key for the advisory lock that keeps several server replicas
+/// from sending the same digest
+const DIGEST_LOCK_KEY: i64 = 0x6469_6765_7374;
+
+#[derive(Debug, Error)]
+pub enum NotificationDigestError {
+    #[error(transparent)]
+    Notification(#[from] NotificationError),
+    #[error(transparent)]
+    Settings(#[from] NotificationSettingsError),
+    #[error(transparent)]
+    Identity(#[from] IdentityError),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+pub struct NotificationDigestJob {
+    pool: PgPool,
+    mailer: Arc<dyn Mailer>,
+    public_base_url: String,
+}
+
+impl NotificationDigestJob {
+    pub fn new(pool: PgPool, mailer: Arc<dyn Mailer>, public_base_url: String) -> Self {
+        Self {
+            pool,
+            mailer,
+            public_base_url: public_base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(TICK_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                match self.run_once(Utc::now()).await {
+                    Ok(0) => {}
+                    Ok(sent) => tracing::info!(sent, "sent notification digests"),
+                    Err(error) => tracing::error!(?error, "notification digest run failed"),
+                }
+            }
+        })
+    }
+
+    /// Send every digest that is due at `now`, returning how many were sent
+    pub async fn run_once(&self, now: DateTime<Utc>) -> Result<usize, NotificationDigestError> {
+        // Held until `lock_tx` is dropped at the end of the run
+        let mut lock_tx = self.pool.begin().await?;
+        let acquired = sqlx::query_scalar!(
+            r#"SELECT pg_try_advisory_xact_lock($1) AS "acquired!""#,
+            DIGEST_LOCK_KEY
+        )
+        .fetch_one(&mut *lock_tx)
+        .await?;
+        if !acquired {
+            return Ok(0);
+        }
+
+        let mut sent = 0;
+        for user_id in NotificationRepository::users_with_pending_email(&self.pool).await? {
+            match self.send_user_digest(user_id, now).await {
+                Ok(true) => sent += 1,
+                Ok(false) => {}
+                Err(error) => {
+                    tracing::error!(?error, %user_id, "failed to send notification digest");
+                }
+            }
+        }
+
+        lock_tx.rollback().await?;
+        Ok(sent)
+    }
+
+    async fn send_user_digest(
+        &self,
+        user_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<bool, NotificationDigestError> {
+        let settings = NotificationSettingsRepository::get_or_create(&self.pool, user_id).await?;
+        if !is_digest_due(&settings, now) {
+            return Ok(false);
+        }
+
+        let pending = NotificationRepository::list_pending_email(&self.pool, user_id).await?;
+        let (to_send, muted): (Vec<_>, Vec<_>) = pending
+            .into_iter()
+            .partition(|notification| notification.email_enabled);
+
+        let muted_ids: Vec<Uuid> = muted.iter().map(|notification| notification.id).collect();
+        if !muted_ids.is_empty() {
+            NotificationRepository::mark_emailed(&self.pool, &muted_ids, now).await?;
+        }
+        if to_send.is_empty() {
+            return Ok(false);
+        }
+
+        let user = UserRepository::new(&self.pool).fetch_user(user_id).await?;
+        let digest = build_digest(&self.public_base_url, settings.unsubscribe_token, &to_send);
+
+        // Mark first so a crash mid-send results in a missed email rather
+        // than the same digest arriving every minute
+        let ids: Vec<Uuid> = to_send.iter().map(|notification| notification.id).collect();
+        NotificationRepository::mark_emailed(&self.pool, &ids, now).await?;
+        NotificationSettingsRepository::mark_digest_sent(&self.pool, user_id, now).await?;
+
+        deliver_digest(self.mailer.as_ref(), &user.email, &digest).await;
+        Ok(true)
+    }
+}
+
+async fn deliver_digest(mailer: &dyn Mailer, email: &str, digest: &NotificationDigest) {
+    if digest.items.is_empty() {
+        return;
+    }
+    mailer.send_notification_digest(email, digest).await;
+}
+
+/// Whether the user should get an email now, given how often they want one
+/// and their quiet hours
+fn is_digest_due(settings: &NotificationSettings, now: DateTime<Utc>) -> bool {
+    let interval = match settings.email_frequency {
+        EmailDigestFrequency::Off => return false,
+        EmailDigestFrequency::Immediate => Duration::zero(),
+        EmailDigestFrequency::Hourly => Duration::hours(1),
+        EmailDigestFrequency::Daily => Duration::days(1),
+    };
+
+    if let (Some(start), Some(end)) = (settings.quiet_hours_start, settings.quiet_hours_end) {
+        let local_time = (now + Duration::minutes(settings.utc_offset_minutes.into())).time();
+        if in_quiet_hours(start, end, local_time) {
+            return false;
+        }
+    }
+
+    settings
+        .last_digest_sent_at
+        .is_none_or(|last_sent| now - last_sent >= interval)
+}
+
+/// Quiet hours may wrap midnight, e.g. 22:00 to 07:00
+fn in_quiet_hours(start: NaiveTime, end: NaiveTime, local_time: NaiveTime) -> bool {
+    if start <= end {
+        start <= local_time && local_time < end
+    } else {
+        local_time >= start || local_time < end
+    }
+}
+
+fn unsubscribe_url(
+    public_base_url: &str,
+    unsubscribe_token: Uuid,
+    project_id: Option<Uuid>,
+) -> String {
+    let url = format!("{public_base_url}/v1/notifications/unsubscribe/{unsubscribe_token}");
+    match project_id {
+        Some(project_id) => format!("{url}?project_id={project_id}"),
+        None => url,
+    }
+}
+
+fn build_digest(
+    public_base_url: &str,
+    unsubscribe_token: Uuid,
+    notifications: &[PendingEmailNotification],
+) -> NotificationDigest {
+    let items = notifications
+        .iter()
+        .map(|notification| NotificationDigestItem {
+            project_name: notification.project_name.clone(),
+            summary: describe(notification.notification_type, &notification.payload),
+        })
+        .collect();
+
+    let projects: BTreeMap<String, Uuid> = notifications
+        .iter()
+        .filter_map(|notification| {
+            Some((notification.project_name.clone()?, notification.project_id?))
+        })
+        .collect();
+    let project_unsubscribe_urls = projects
+        .into_iter()
+        .map(|(project_name, project_id)| {
+            let url = unsubscribe_url(public_base_url, unsubscribe_token, Some(project_id));
+            (project_name, url)
+        })
+        .collect();
+
+    NotificationDigest {
+        items,
+        unsubscribe_url: unsubscribe_url(public_base_url, unsubscribe_token, None),
+        project_unsubscribe_urls,
+    }
+}
+
+fn describe(notification_type: NotificationType, payload: &Value) -> String {
+    let title = payload
+        .get("issue_title")
+        .and_then(Value::as_str)
+        .unwrap_or("an issue");
+    match notification_type {
+        NotificationType::IssueCommentAdded => format!("New comment on \"{title}\""),
+        NotificationType::IssueStatusChanged => format!("Status changed on \"{title}\""),
+        NotificationType::IssueAssigneeChanged => {
+            let assigned = payload
+                .get("assigned")
+                .and_then(Value::as_bool)
+                .unwrap_or(true);
+            if assigned {
+                format!("Assignee added to \"{title}\"")
+            } else {
+                format!("Assignee removed from \"{title}\"")
+            }
+        }
+        NotificationType::IssueDeleted => format!("\"{title}\" was deleted"),
+        NotificationType::UserMentioned => format!("You were mentioned in \"{title}\""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use serde_json::json;
+
+    use super::*;
+    use crate::db::organization_members::MemberRole;
+
+    #[derive(Default)]
+    struct FakeMailer {
+        digests: Mutex<Vec<(String, NotificationDigest)>>,
+    }
+
+    #[async_trait]
+    impl Mailer for FakeMailer {
+        async fn send_org_invitation(
+            &self,
+            _org_name: &str,
+            _email: &str,
+            _accept_url: &str,
+            _role: MemberRole,
+            _invited_by: Option<&str>,
+        ) {
+        }
+
+        async fn send_review_ready(&self, _email: &str, _review_url: &str, _pr_name: &str) {}
+
+        async fn send_review_failed(&self, _email: &str, _pr_name: &str, _review_id: &str) {}
+
+        async fn send_notification_digest(&self, email: &str, digest: &NotificationDigest) {
+            self.digests
+                .lock()
+                .unwrap()
+                .push((email.to_string(), digest.clone()));
+        }
+    }
+
+    fn settings(frequency: EmailDigestFrequency) -> NotificationSettings {
+        NotificationSettings {
+            user_id: Uuid::new_v4(),
+            email_frequency: frequency,
+            quiet_hours_start: None,
+            quiet_hours_end: None,
+            utc_offset_minutes: 0,
+            last_digest_sent_at: None,
+            unsubscribe_token: Uuid::new_v4(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(&format!("2026-01-15T{hour:02}:{minute:02}:00Z"))
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn pending(project: Option<(&str, Uuid)>, title: &str) -> PendingEmailNotification {
+        PendingEmailNotification {
+            id: Uuid::new_v4(),
+            notification_type: NotificationType::IssueStatusChanged,
+            payload: json!({ "issue_title": title }),
+            project_id: project.map(|(_, id)| id),
+            project_name: project.map(|(name, _)| name.to_string()),
+            email_enabled: true,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn off_is_never_due() {
+        assert!(!is_digest_due(
+            &settings(EmailDigestFrequency::Off),
+            at(12, 0)
+        ));
+    }
+
+    #[test]
+    fn hourly_waits_an_hour_after_the_last_digest() {
+        let mut hourly = settings(EmailDigestFrequency::Hourly);
+        hourly.last_digest_sent_at = Some(at(11, 30));
+        assert!(!is_digest_due(&hourly, at(12, 0)));
+        assert!(is_digest_due(&hourly, at(12, 30)));
+    }
+
+    #[test]
+    fn quiet_hours_wrap_midnight_in_local_time() {
+        let mut immediate = settings(EmailDigestFrequency::Immediate);
+        immediate.quiet_hours_start = NaiveTime::from_hms_opt(22, 0, 0);
+        immediate.quiet_hours_end = NaiveTime::from_hms_opt(7, 0, 0);
+        // UTC+2, so 21:30 UTC is 23:30 local
+        immediate.utc_offset_minutes = 120;
+        assert!(!is_digest_due(&immediate, at(21, 30)));
+        assert!(!is_digest_due(&immediate, at(4, 0)));
+        assert!(is_digest_due(&immediate, at(5, 0)));
+    }
+
+    #[test]
+    fn digest_links_unsubscribe_per_project() {
+        let token = Uuid::new_v4();
+        let project_id = Uuid::new_v4();
+        let digest = build_digest(
+            "https://kanban.example.com",
+            token,
+            &[
+                pending(Some(("Backend", project_id)), "Fix login"),
+                pending(Some(("Backend", project_id)), "Add SSO"),
+                pending(None, "Old issue"),
+            ],
+        );
+
+        assert_eq!(digest.items.len(), 3);
+        assert_eq!(digest.items[0].summary, "Status changed on \"Fix login\"");
+        assert_eq!(
+            digest.unsubscribe_url,
+            format!("https://kanban.example.com/v1/notifications/unsubscribe/{token}")
+        );
+        assert_eq!(
+            digest.project_unsubscribe_urls,
+            vec![(
+                "Backend".to_string(),
+                format!(
+                    "https://kanban.example.com/v1/notifications/unsubscribe/{token}?project_id={project_id}"
+                )
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn delivers_digest_through_mailer() {
+        let mailer = FakeMailer::default();
+        let digest = build_digest(
+            "https://kanban.example.com",
+            Uuid::new_v4(),
+            &[pending(None, "Fix login")],
+        );
+
+        deliver_digest(&mailer, "dev@example.com", &digest).await;
+        deliver_digest(
+            &mailer,
+            "dev@example.com",
+            &build_digest("https://kanban.example.com", Uuid::new_v4(), &[]),
+        )
+        .await;
+
+        let digests = mailer.digests.lock().unwrap();
+        assert_eq!(digests.len(), 1);
+        assert_eq!(digests[0].0, "dev@example.com");
+        assert_eq!(digests[0].1.items.len(), 1);
+    }
+}
@@ -0,0 +1,333 @@
+use axum::{
+    Json, Router,
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    routing::{delete, get},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{error::ErrorResponse, organization_members::ensure_admin_access};
+use crate::{
+    AppState,
+    auth::{RequestContext, generate_api_token},
+    db::{
+        api_tokens::{ApiToken, ApiTokenRepository, ApiTokenScope},
+        service_accounts::{ServiceAccount, ServiceAccountRepository},
+    },
+};
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api-tokens",
+            get(list_personal_tokens).post(create_personal_token),
+        )
+        .route("/api-tokens/{token_id}", delete(revoke_personal_token))
+        .route(
+            "/organizations/{org_id}/service-accounts",
+            get(list_service_accounts).post(create_service_account),
+        )
+        .route(
+            "/organizations/{org_id}/service-accounts/{user_id}",
+            delete(delete_service_account),
+        )
+        .route(
+            "/organizations/{org_id}/service-accounts/{user_id}/tokens",
+            get(list_service_account_tokens).post(create_service_account_token),
+        )
+        .route(
+            "/organizations/{org_id}/service-accounts/{user_id}/tokens/{token_id}",
+            delete(revoke_service_account_token),
+        )
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateApiTokenRequest {
+    pub name: String,
+    pub scopes: Vec<ApiTokenScope>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreateApiTokenResponse {
+    pub token: ApiToken,
+    /// The token secret. It is not stored and cannot be retrieved again.
+    pub secret: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListApiTokensResponse {
+    pub tokens: Vec<ApiToken>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateServiceAccountRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListServiceAccountsResponse {
+    pub service_accounts: Vec<ServiceAccount>,
+}
+
+fn internal_error<E: std::fmt::Debug>(message: &'static str) -> impl FnOnce(E) -> ErrorResponse {
+    move |error| {
+        tracing::error!(?error, "{message}");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, message)
+    }
+}
+
+fn validate_name(name: &str) -> Result<&str, ErrorResponse> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "name must not be empty",
+        ));
+    }
+    Ok(name)
+}
+
+async fn create_token(
+    state: &AppState,
+    user_id: Uuid,
+    created_by_user_id: Uuid,
+    payload: CreateApiTokenRequest,
+) -> Result<CreateApiTokenResponse, ErrorResponse> {
+    let name = validate_name(&payload.name)?;
+    let mut scopes = payload.scopes;
+    scopes.sort();
+    scopes.dedup();
+    if scopes.is_empty() {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "at least one scope is required",
+        ));
+    }
+    if payload
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= Utc::now())
+    {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "expires_at must be in the future",
+        ));
+    }
+
+    let generated = generate_api_token();
+    let token = ApiTokenRepository::create(
+        state.pool(),
+        user_id,
+        name,
+        &generated.hash,
+        &generated.display_prefix,
+        &scopes,
+        payload.expires_at,
+        created_by_user_id,
+    )
+    .await
+    .map_err(internal_error("failed to create api token"))?;
+
+    Ok(CreateApiTokenResponse {
+        token,
+        secret: generated.secret,
+    })
+}
+
+/// Load a service account and check that it belongs to the organization
+async fn load_service_account(
+    state: &AppState,
+    org_id: Uuid,
+    user_id: Uuid,
+) -> Result<ServiceAccount, ErrorResponse> {
+    ServiceAccountRepository::find_by_user_id(state.pool(), user_id)
+        .await
+        .map_err(internal_error("failed to load service account"))?
+        .filter(|account| account.organization_id == org_id && account.disabled_at.is_none())
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "service account not found"))
+}
+
+#[instrument(
+    name = "api_tokens.list_personal_tokens",
+    skip(state, ctx),
+    fields(user_id = %ctx.user.id)
+)]
+async fn list_personal_tokens(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+) -> Result<Json<ListApiTokensResponse>, ErrorResponse> {
+    let tokens = ApiTokenRepository::list_by_user(state.pool(), ctx.user.id)
+        .await
+        .map_err(internal_error("failed to list api tokens"))?;
+
+    Ok(Json(ListApiTokensResponse { tokens }))
+}
+
+#[instrument(
+    name = "api_tokens.create_personal_token",
+    skip(state, ctx, payload),
+    fields(user_id = %ctx.user.id)
+)]
+async fn create_personal_token(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Json(payload): Json<CreateApiTokenRequest>,
+) -> Result<Json<CreateApiTokenResponse>, ErrorResponse> {
+    let response = create_token(&state, ctx.user.id, ctx.user.id, payload).await?;
+    Ok(Json(response))
+}
+
+#[instrument(
+    name = "api_tokens.revoke_personal_token",
+    skip(state, ctx),
+    fields(token_id = %token_id, user_id = %ctx.user.id)
+)]
+async fn revoke_personal_token(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(token_id): Path<Uuid>,
+) -> Result<StatusCode, ErrorResponse> {
+    let revoked = ApiTokenRepository::revoke(state.pool(), ctx.user.id, token_id)
+        .await
+        .map_err(internal_error("failed to revoke api token"))?;
+    if !revoked {
+        return Err(ErrorResponse::new(
+            StatusCode::NOT_FOUND,
+            "api token not found",
+        ));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(
+    name = "api_tokens.list_service_accounts",
+    skip(state, ctx),
+    fields(org_id = %org_id, user_id = %ctx.user.id)
+)]
+async fn list_service_accounts(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(org_id): Path<Uuid>,
+) -> Result<Json<ListServiceAccountsResponse>, ErrorResponse> {
+    ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
+
+    let service_accounts = ServiceAccountRepository::list_by_organization(state.pool(), org_id)
+        .await
+        .map_err(internal_error("failed to list service accounts"))?;
+
+    Ok(Json(ListServiceAccountsResponse { service_accounts }))
+}
+
+#[instrument(
+    name = "api_tokens.create_service_account",
+    skip(state, ctx, payload),
+    fields(org_id = %org_id, user_id = %ctx.user.id)
+)]
+async fn create_service_account(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(org_id): Path<Uuid>,
+    Json(payload): Json<CreateServiceAccountRequest>,
+) -> Result<Json<ServiceAccount>, ErrorResponse> {
+    ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
+    let name = validate_name(&payload.name)?;
+
+    let account = ServiceAccountRepository::create(state.pool(), org_id, name, ctx.user.id)
+        .await
+        .map_err(internal_error("failed to create service account"))?;
+
+    Ok(Json(account))
+}
+
+#[instrument(
+    name = "api_tokens.delete_service_account",
+    skip(state, ctx),
+    fields(org_id = %org_id, service_account_id = %user_id, user_id = %ctx.user.id)
+)]
+async fn delete_service_account(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((org_id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, ErrorResponse> {
+    ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
+    load_service_account(&state, org_id, user_id).await?;
+
+    ServiceAccountRepository::disable(state.pool(), user_id)
+        .await
+        .map_err(internal_error("failed to delete service account"))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(
+    name = "api_tokens.list_service_account_tokens",
+    skip(state, ctx),
+    fields(org_id = %org_id, service_account_id = %user_id, user_id = %ctx.user.id)
+)]
+async fn list_service_account_tokens(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((org_id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ListApiTokensResponse>, ErrorResponse> {
+    ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
+    load_service_account(&state, org_id, user_id).await?;
+
+    let tokens = ApiTokenRepository::list_by_user(state.pool(), user_id)
+        .await
+        .map_err(internal_error("failed to list api tokens"))?;
+
+    Ok(Json(ListApiTokensResponse { tokens }))
+}
+
+#[instrument(
+    name = "api_tokens.create_service_account_token",
+    skip(state, ctx, payload),
+    fields(org_id = %org_id, service_account_id = %user_id, user_id = %ctx.user.id)
+)]
+async fn create_service_account_token(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((org_id, user_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<CreateApiTokenRequest>,
+) -> Result<Json<CreateApiTokenResponse>, ErrorResponse> {
+    ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
+    load_service_account(&state, org_id, user_id).await?;
+
+    let response = create_token(&state, user_id, ctx.user.id, payload).await?;
+    Ok(Json(response))
+}
+
+#[instrument(
+    name = "api_tokens.revoke_service_account_token",
+    skip(state, ctx),
+    fields(
+        org_id = %org_id,
+        service_account_id = %user_id,
+        token_id = %token_id,
+        user_id = %ctx.user.id
+    )
+)]
+async fn revoke_service_account_token(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((org_id, user_id, token_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<StatusCode, ErrorResponse> {
+    ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
+    load_service_account(&state, org_id, user_id).await?;
+
+    let revoked = ApiTokenRepository::revoke(state.pool(), user_id, token_id)
+        .await
+        .map_err(internal_error("failed to revoke api token"))?;
+    if !revoked {
+        return Err(ErrorResponse::new(
+            StatusCode::NOT_FOUND,
+            "api token not found",
+        ));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,334 @@
+use std::future::Future;
+
+use axum::{
+    Json, Router,
+    body::{Body, Bytes},
+    extract::{Extension, Path, Query, State},
+    http::{StatusCode, header},
+    response::IntoResponse,
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+use futures::{Stream, stream};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{error::ErrorResponse, organization_members::ensure_admin_access};
+use crate::{
+    AppState,
+    auth::RequestContext,
+    db::{
+        Tx,
+        audit_events::{
+            AuditAction, AuditEvent, AuditEventCursor, AuditEventError, AuditEventFilter,
+            AuditEventRepository,
+        },
+    },
+};
+
+const DEFAULT_PAGE_SIZE: i64 = 100;
+const MAX_PAGE_SIZE: i64 = 500;
+/// Events fetched per query while streaming an export
+const EXPORT_BATCH_SIZE: i64 = 500;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/organizations/{org_id}/audit-events", get(list_events))
+        .route(
+            "/organizations/{org_id}/audit-events/export",
+            get(export_events),
+        )
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuditEventsQuery {
+    pub actor_user_id: Option<Uuid>,
+    pub action: Option<AuditAction>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    /// Only return events older than this, for paging. Pass the `created_at`
+    /// and `id` of the last event of the previous page.
+    pub before: Option<DateTime<Utc>>,
+    pub before_id: Option<Uuid>,
+    pub limit: Option<i64>,
+}
+
+impl AuditEventsQuery {
+    fn filter(&self) -> Result<AuditEventFilter, ErrorResponse> {
+        if let (Some(from), Some(to)) = (self.from, self.to)
+            && from >= to
+        {
+            return Err(ErrorResponse::new(
+                StatusCode::BAD_REQUEST,
+                "`from` must be before `to`",
+            ));
+        }
+        Ok(AuditEventFilter {
+            actor_user_id: self.actor_user_id,
+            action: self.action,
+            from: self.from,
+            to: self.to,
+        })
+    }
+
+    fn before(&self) -> Result<Option<AuditEventCursor>, ErrorResponse> {
+        match (self.before, self.before_id) {
+            (None, Some(_)) => Err(ErrorResponse::new(
+                StatusCode::BAD_REQUEST,
+                "`before_id` requires `before`",
+            )),
+            (before, id) => Ok(before.map(|created_at| AuditEventCursor { created_at, id })),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListAuditEventsResponse {
+    pub events: Vec<AuditEvent>,
+}
+
+fn internal_error<E: std::fmt::Debug>(message: &'static str) -> impl FnOnce(E) -> ErrorResponse {
+    move |error| {
+        tracing::error!(?error, "{message}");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, message)
+    }
+}
+
+/// Record an event in the transaction that applies the audited change, so the
+/// event is stored exactly when the change commits.
+pub(crate) async fn record_event(
+    tx: &mut Tx<'_>,
+    organization_id: Uuid,
+    actor_user_id: Option<Uuid>,
+    action: AuditAction,
+    target_id: Option<Uuid>,
+    metadata: Value,
+) -> Result<(), ErrorResponse> {
+    AuditEventRepository::record(
+        &mut **tx,
+        organization_id,
+        actor_user_id,
+        action,
+        target_id,
+        metadata,
+    )
+    .await
+    .map_err(internal_error("failed to record audit event"))
+}
+
+#[instrument(
+    name = "audit_events.list_events",
+    skip(state, ctx, params),
+    fields(org_id = %org_id, user_id = %ctx.user.id)
+)]
+async fn list_events(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(org_id): Path<Uuid>,
+    Query(params): Query<AuditEventsQuery>,
+) -> Result<Json<ListAuditEventsResponse>, ErrorResponse> {
+    ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
+
+    let filter = params.filter()?;
+    let before = params.before()?;
+    let limit = params
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    let events = AuditEventRepository::list(state.pool(), org_id, &filter, before, limit)
+        .await
+        .map_err(internal_error("failed to list audit events"))?;
+
+    Ok(Json(ListAuditEventsResponse { events }))
+}
+
+/// One JSON object per line, oldest first. The body is streamed in batches so
+/// large exports are never held in memory.
+#[instrument(
+    name = "audit_events.export_events",
+    skip(state, ctx, params),
+    fields(org_id = %org_id, user_id = %ctx.user.id)
+)]
+async fn export_events(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(org_id): Path<Uuid>,
+    Query(params): Query<AuditEventsQuery>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
+
+    let filter = params.filter()?;
+    let pool = state.pool().clone();
+    let body = jsonl_stream(EXPORT_BATCH_SIZE, move |after| {
+        let pool = pool.clone();
+        let filter = filter.clone();
+        async move {
+            AuditEventRepository::list_after(&pool, org_id, &filter, after, EXPORT_BATCH_SIZE).await
+        }
+    });
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/x-ndjson".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"audit-events-{org_id}.jsonl\""),
+            ),
+        ],
+        Body::from_stream(body),
+    ))
+}
+
+/// Render events as JSON lines, fetching them in batches of `batch_size` that
+/// each resume after the last event of the previous batch. A batch shorter
+/// than `batch_size` ends the stream.
+fn jsonl_stream<F, Fut>(
+    batch_size: i64,
+    fetch: F,
+) -> impl Stream<Item = Result<Bytes, std::io::Error>>
+where
+    F: FnMut(Option<AuditEventCursor>) -> Fut,
+    Fut: Future<Output = Result<Vec<AuditEvent>, AuditEventError>>,
+{
+    stream::try_unfold(
+        (fetch, None, false),
+        move |(mut fetch, after, done)| async move {
+            if done {
+                return Ok(None);
+            }
+            let events = fetch(after).await.map_err(|error| {
+                tracing::error!(?error, "failed to export audit events");
+                std::io::Error::other(error)
+            })?;
+            let Some(last) = events.last() else {
+                return Ok(None);
+            };
+            let next = Some(AuditEventCursor::from(last));
+            let done = (events.len() as i64) < batch_size;
+
+            let mut chunk = Vec::new();
+            for event in &events {
+                serde_json::to_writer(&mut chunk, event)?;
+                chunk.push(b'\n');
+            }
+            Ok(Some((Bytes::from(chunk), (fetch, next, done))))
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::Uri;
+    use chrono::Duration;
+    use futures::TryStreamExt;
+    use serde_json::json;
+
+    use super::*;
+
+    fn query(uri: &str) -> AuditEventsQuery {
+        let uri: Uri = uri.parse().unwrap();
+        Query::<AuditEventsQuery>::try_from_uri(&uri).unwrap().0
+    }
+
+    fn event(id: u128, created_at: DateTime<Utc>) -> AuditEvent {
+        AuditEvent {
+            id: Uuid::from_u128(id),
+            organization_id: Uuid::nil(),
+            actor_user_id: None,
+            action: AuditAction::MemberRemoved,
+            target_id: None,
+            metadata: json!({}),
+            created_at,
+        }
+    }
+
+    #[test]
+    fn paging_cursor_combines_timestamp_and_id() {
+        let id = Uuid::new_v4();
+        let params = query(&format!(
+            "/audit-events?before=2026-01-01T00:00:00Z&before_id={id}"
+        ));
+        let cursor = params.before().unwrap().unwrap();
+        assert_eq!(cursor.created_at.to_rfc3339(), "2026-01-01T00:00:00+00:00");
+        assert_eq!(cursor.id, Some(id));
+
+        let params = query("/audit-events?before=2026-01-01T00:00:00Z");
+        assert_eq!(params.before().unwrap().unwrap().id, None);
+        assert!(query("/audit-events").before().unwrap().is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_queries() {
+        let params = query(&format!("/audit-events?before_id={}", Uuid::new_v4()));
+        assert_eq!(
+            params.before().unwrap_err().into_response().status(),
+            StatusCode::BAD_REQUEST
+        );
+
+        let params = query("/audit-events?from=2026-02-01T00:00:00Z&to=2026-01-01T00:00:00Z");
+        assert_eq!(
+            params.filter().unwrap_err().into_response().status(),
+            StatusCode::BAD_REQUEST
+        );
+
+        let params = query("/audit-events?action=member_role_changed");
+        assert_eq!(
+            params.filter().unwrap().action,
+            Some(AuditAction::MemberRoleChanged)
+        );
+    }
+
+    #[tokio::test]
+    async fn export_streams_every_event_once_across_batches() {
+        // Three events share a timestamp and straddle a batch boundary
+        let start = Utc::now();
+        let tied = start + Duration::seconds(1);
+        let events = [
+            event(1, start),
+            event(2, tied),
+            event(3, tied),
+            event(4, tied),
+            event(5, start + Duration::seconds(2)),
+        ];
+
+        let mut fetches = 0;
+        let chunks: Vec<Bytes> = jsonl_stream(2, |after: Option<AuditEventCursor>| {
+            fetches += 1;
+            let batch: Vec<AuditEvent> = events
+                .iter()
+                .filter(|event| {
+                    after.is_none_or(|cursor| {
+                        (event.created_at, Some(event.id)) > (cursor.created_at, cursor.id)
+                    })
+                })
+                .take(2)
+                .cloned()
+                .collect();
+            async move { Ok(batch) }
+        })
+        .try_collect()
+        .await
+        .unwrap();
+
+        let body = String::from_utf8(chunks.concat()).unwrap();
+        let ids: Vec<Uuid> = body
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap()["id"].clone())
+            .map(|id| serde_json::from_value(id).unwrap())
+            .collect();
+        assert_eq!(ids, (1..=5).map(Uuid::from_u128).collect::<Vec<_>>());
+        // Two full batches, one short one that ends the stream
+        assert_eq!(fetches, 3);
+    }
+
+    #[tokio::test]
+    async fn export_of_no_events_is_empty() {
+        let chunks: Vec<Bytes> = jsonl_stream(2, |_| async { Ok(Vec::new()) })
+            .try_collect()
+            .await
+            .unwrap();
+        assert!(chunks.is_empty());
+    }
+}
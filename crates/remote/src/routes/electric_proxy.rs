@@ -15,7 +15,12 @@ use tracing::error;
 use ts_rs::TS;
 use uuid::Uuid;
 
-use crate::{AppState, auth::RequestContext, db::organization_members, shapes};
+use crate::{
+    AppState,
+    auth::RequestContext,
+    db::organization_members::{self, MemberRole, ProjectAccess},
+    shapes,
+};
 
 #[derive(Deserialize)]
 struct OrgShapeQuery {
@@ -55,6 +60,7 @@ pub fn router() -> Router<AppState> {
             shapes::ISSUE_COMMENT_REACTIONS.url,
             get(proxy_issue_comment_reactions),
         )
+        .route(shapes::ISSUE_EVENTS.url, get(proxy_issue_events))
 }
 
 async fn proxy_projects(
@@ -62,17 +68,23 @@ async fn proxy_projects(
     Extension(ctx): Extension<RequestContext>,
     Query(query): Query<OrgShapeQuery>,
 ) -> Result<Response, ProxyError> {
-    organization_members::assert_membership(state.pool(), query.organization_id, ctx.user.id)
-        .await
-        .map_err(|e| ProxyError::Authorization(e.to_string()))?;
+    let role =
+        organization_members::check_user_role(state.pool(), query.organization_id, ctx.user.id)
+            .await
+            .map_err(|e| ProxyError::Authorization(e.to_string()))?
+            .ok_or_else(|| ProxyError::Authorization("not a member of organization".into()))?;
+
+    let organization_id = query.organization_id.to_string();
+    let user_id = ctx.user.id.to_string();
+    let (shape, params) = match role {
+        MemberRole::Admin => (&shapes::ADMIN_PROJECTS, vec![organization_id]),
+        MemberRole::Member | MemberRole::Viewer => {
+            (&shapes::PROJECTS, vec![organization_id, user_id])
+        }
+        MemberRole::Guest => (&shapes::GUEST_PROJECTS, vec![organization_id, user_id]),
+    };
 
-    proxy_table(
-        &state,
-        &shapes::PROJECTS,
-        &query.params,
-        &[query.organization_id.to_string()],
-    )
-    .await
+    proxy_table(&state, shape, &query.params, &params).await
 }
 
 async fn proxy_notifications(
@@ -99,9 +111,14 @@ async fn proxy_workspaces(
     Path(project_id): Path<Uuid>,
     Query(query): Query<ShapeQuery>,
 ) -> Result<Response, ProxyError> {
-    organization_members::assert_project_access(state.pool(), project_id, ctx.user.id)
-        .await
-        .map_err(|e| ProxyError::Authorization(e.to_string()))?;
+    organization_members::assert_project_access(
+        state.pool(),
+        project_id,
+        ctx.user.id,
+        ProjectAccess::Read,
+    )
+    .await
+    .map_err(|e| ProxyError::Authorization(e.to_string()))?;
 
     proxy_table(
         &state,
@@ -118,9 +135,14 @@ async fn proxy_project_statuses(
     Path(project_id): Path<Uuid>,
     Query(query): Query<ShapeQuery>,
 ) -> Result<Response, ProxyError> {
-    organization_members::assert_project_access(state.pool(), project_id, ctx.user.id)
-        .await
-        .map_err(|e| ProxyError::Authorization(e.to_string()))?;
+    organization_members::assert_project_access(
+        state.pool(),
+        project_id,
+        ctx.user.id,
+        ProjectAccess::Read,
+    )
+    .await
+    .map_err(|e| ProxyError::Authorization(e.to_string()))?;
 
     proxy_table(
         &state,
@@ -137,9 +159,14 @@ async fn proxy_tags(
     Path(project_id): Path<Uuid>,
     Query(query): Query<ShapeQuery>,
 ) -> Result<Response, ProxyError> {
-    organization_members::assert_project_access(state.pool(), project_id, ctx.user.id)
-        .await
-        .map_err(|e| ProxyError::Authorization(e.to_string()))?;
+    organization_members::assert_project_access(
+        state.pool(),
+        project_id,
+        ctx.user.id,
+        ProjectAccess::Read,
+    )
+    .await
+    .map_err(|e| ProxyError::Authorization(e.to_string()))?;
 
     proxy_table(
         &state,
@@ -156,9 +183,14 @@ async fn proxy_issues(
     Path(project_id): Path<Uuid>,
     Query(query): Query<ShapeQuery>,
 ) -> Result<Response, ProxyError> {
-    organization_members::assert_project_access(state.pool(), project_id, ctx.user.id)
-        .await
-        .map_err(|e| ProxyError::Authorization(e.to_string()))?;
+    organization_members::assert_project_access(
+        state.pool(),
+        project_id,
+        ctx.user.id,
+        ProjectAccess::Read,
+    )
+    .await
+    .map_err(|e| ProxyError::Authorization(e.to_string()))?;
 
     proxy_table(
         &state,
@@ -175,9 +207,14 @@ async fn proxy_issue_assignees(
     Path(project_id): Path<Uuid>,
     Query(query): Query<ShapeQuery>,
 ) -> Result<Response, ProxyError> {
-    organization_members::assert_project_access(state.pool(), project_id, ctx.user.id)
-        .await
-        .map_err(|e| ProxyError::Authorization(e.to_string()))?;
+    organization_members::assert_project_access(
+        state.pool(),
+        project_id,
+        ctx.user.id,
+        ProjectAccess::Read,
+    )
+    .await
+    .map_err(|e| ProxyError::Authorization(e.to_string()))?;
 
     proxy_table(
         &state,
@@ -194,9 +231,14 @@ async fn proxy_issue_followers(
     Path(project_id): Path<Uuid>,
     Query(query): Query<ShapeQuery>,
 ) -> Result<Response, ProxyError> {
-    organization_members::assert_project_access(state.pool(), project_id, ctx.user.id)
-        .await
-        .map_err(|e| ProxyError::Authorization(e.to_string()))?;
+    organization_members::assert_project_access(
+        state.pool(),
+        project_id,
+        ctx.user.id,
+        ProjectAccess::Read,
+    )
+    .await
+    .map_err(|e| ProxyError::Authorization(e.to_string()))?;
 
     proxy_table(
         &state,
@@ -213,9 +255,14 @@ async fn proxy_issue_tags(
     Path(project_id): Path<Uuid>,
     Query(query): Query<ShapeQuery>,
 ) -> Result<Response, ProxyError> {
-    organization_members::assert_project_access(state.pool(), project_id, ctx.user.id)
-        .await
-        .map_err(|e| ProxyError::Authorization(e.to_string()))?;
+    organization_members::assert_project_access(
+        state.pool(),
+        project_id,
+        ctx.user.id,
+        ProjectAccess::Read,
+    )
+    .await
+    .map_err(|e| ProxyError::Authorization(e.to_string()))?;
 
     proxy_table(
         &state,
@@ -232,9 +279,14 @@ async fn proxy_issue_comments(
     Path(issue_id): Path<Uuid>,
     Query(query): Query<ShapeQuery>,
 ) -> Result<Response, ProxyError> {
-    organization_members::assert_issue_access(state.pool(), issue_id, ctx.user.id)
-        .await
-        .map_err(|e| ProxyError::Authorization(e.to_string()))?;
+    organization_members::assert_issue_access(
+        state.pool(),
+        issue_id,
+        ctx.user.id,
+        ProjectAccess::Read,
+    )
+    .await
+    .map_err(|e| ProxyError::Authorization(e.to_string()))?;
 
     proxy_table(
         &state,
@@ -251,9 +303,14 @@ async fn proxy_issue_dependencies(
     Path(project_id): Path<Uuid>,
     Query(query): Query<ShapeQuery>,
 ) -> Result<Response, ProxyError> {
-    organization_members::assert_project_access(state.pool(), project_id, ctx.user.id)
-        .await
-        .map_err(|e| ProxyError::Authorization(e.to_string()))?;
+    organization_members::assert_project_access(
+        state.pool(),
+        project_id,
+        ctx.user.id,
+        ProjectAccess::Read,
+    )
+    .await
+    .map_err(|e| ProxyError::Authorization(e.to_string()))?;
 
     proxy_table(
         &state,
@@ -270,9 +327,14 @@ async fn proxy_issue_comment_reactions(
     Path(issue_id): Path<Uuid>,
     Query(query): Query<ShapeQuery>,
 ) -> Result<Response, ProxyError> {
-    organization_members::assert_issue_access(state.pool(), issue_id, ctx.user.id)
-        .await
-        .map_err(|e| ProxyError::Authorization(e.to_string()))?;
+    organization_members::assert_issue_access(
+        state.pool(),
+        issue_id,
+        ctx.user.id,
+        ProjectAccess::Read,
+    )
+    .await
+    .map_err(|e| ProxyError::Authorization(e.to_string()))?;
 
     proxy_table(
         &state,
@@ -283,6 +345,30 @@ async fn proxy_issue_comment_reactions(
     .await
 }
 
+async fn proxy_issue_events(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(issue_id): Path<Uuid>,
+    Query(query): Query<ShapeQuery>,
+) -> Result<Response, ProxyError> {
+    organization_members::assert_issue_access(
+        state.pool(),
+        issue_id,
+        ctx.user.id,
+        ProjectAccess::Read,
+    )
+    .await
+    .map_err(|e| ProxyError::Authorization(e.to_string()))?;
+
+    proxy_table(
+        &state,
+        &shapes::ISSUE_EVENTS,
+        &query.params,
+        &[issue_id.to_string()],
+    )
+    .await
+}
+
 /// Proxy a Shape request to Electric for a specific table.
 ///
 /// The table and where clause are set server-side (not from client params)
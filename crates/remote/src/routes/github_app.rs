@@ -9,18 +9,20 @@ use axum::{
 use chrono::{Duration, Utc};
 use secrecy::ExposeSecret;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
-use super::error::ErrorResponse;
+use super::{audit_events::record_event, error::ErrorResponse};
 use crate::{
     AppState,
     auth::RequestContext,
     db::{
-        github_app::GitHubAppRepository2, identity_errors::IdentityError,
-        organizations::OrganizationRepository, reviews::ReviewRepository,
+        audit_events::AuditAction, github_app::GitHubAppRepository2,
+        identity_errors::IdentityError, organizations::OrganizationRepository,
+        reviews::ReviewRepository,
     },
-    github_app::{PrReviewParams, PrReviewService, verify_webhook_signature},
+    github_app::{InstallationInfo, PrReviewParams, PrReviewService, verify_webhook_signature},
 };
 
 // ========== Public Routes ==========
@@ -53,6 +55,10 @@ pub fn protected_router() -> Router<AppState> {
             "/organizations/{org_id}/github-app/repositories/{repo_id}/review-enabled",
             patch(update_repo_review_enabled),
         )
+        .route(
+            "/organizations/{org_id}/github-app/reviews",
+            post(trigger_org_pr_review),
+        )
         .route("/debug/pr-review/trigger", post(trigger_pr_review))
 }
 
@@ -271,10 +277,39 @@ pub async fn uninstall(
         })?;
 
     let gh_repo = GitHubAppRepository2::new(state.pool());
-    gh_repo.delete_by_organization(org_id).await.map_err(|e| {
-        error!(?e, "Failed to delete GitHub App installation");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
-    })?;
+    let installation = gh_repo
+        .get_by_organization(org_id)
+        .await
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
+    let mut tx = state
+        .pool()
+        .begin()
+        .await
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
+    GitHubAppRepository2::delete_by_organization(&mut *tx, org_id)
+        .await
+        .map_err(|e| {
+            error!(?e, "Failed to delete GitHub App installation");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
+        })?;
+
+    if let Some(installation) = installation {
+        record_event(
+            &mut tx,
+            org_id,
+            Some(ctx.user.id),
+            AuditAction::GithubAppUninstalled,
+            Some(installation.id),
+            json!({
+                "github_installation_id": installation.github_installation_id,
+                "github_account_login": installation.github_account_login,
+            }),
+        )
+        .await?;
+    }
+    tx.commit()
+        .await
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
 
     info!(org_id = %org_id, user_id = %ctx.user.id, "GitHub App installation removed");
     Ok(StatusCode::NO_CONTENT)
@@ -311,19 +346,29 @@ pub async fn update_repo_review_enabled(
         .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "GitHub App not installed"))?;
 
-    // Update the repository
-    let updated = gh_repo
-        .update_repository_review_enabled(repo_id, installation.id, payload.enabled)
+    let mut tx = state
+        .pool()
+        .begin()
         .await
-        .map_err(|e| {
-            error!(?e, "Failed to update repository review_enabled");
-            match e {
-                crate::db::github_app::GitHubAppDbError::NotFound => {
-                    ErrorResponse::new(StatusCode::NOT_FOUND, "Repository not found")
-                }
-                _ => ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"),
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
+
+    // Update the repository
+    let updated = GitHubAppRepository2::update_repository_review_enabled(
+        &mut *tx,
+        repo_id,
+        installation.id,
+        payload.enabled,
+    )
+    .await
+    .map_err(|e| {
+        error!(?e, "Failed to update repository review_enabled");
+        match e {
+            crate::db::github_app::GitHubAppDbError::NotFound => {
+                ErrorResponse::new(StatusCode::NOT_FOUND, "Repository not found")
             }
-        })?;
+            _ => ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"),
+        }
+    })?;
 
     info!(
         org_id = %org_id,
@@ -332,6 +377,22 @@ pub async fn update_repo_review_enabled(
         "Repository review_enabled updated"
     );
 
+    record_event(
+        &mut tx,
+        org_id,
+        Some(ctx.user.id),
+        AuditAction::RepositoryReviewEnabledChanged,
+        Some(updated.id),
+        json!({
+            "repo_full_name": updated.repo_full_name,
+            "review_enabled": updated.review_enabled,
+        }),
+    )
+    .await?;
+    tx.commit()
+        .await
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
+
     Ok(Json(RepositoryDetails {
         id: updated.id.to_string(),
         github_repo_id: updated.github_repo_id,
@@ -441,13 +502,21 @@ pub async fn bulk_update_review_enabled(
         .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "GitHub App not installed"))?;
 
-    let updated_count = gh_repo
-        .set_all_repositories_review_enabled(installation.id, payload.enabled)
+    let mut tx = state
+        .pool()
+        .begin()
         .await
-        .map_err(|e| {
-            error!(?e, "Failed to bulk update review_enabled");
-            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
-        })?;
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
+    let updated_count = GitHubAppRepository2::set_all_repositories_review_enabled(
+        &mut *tx,
+        installation.id,
+        payload.enabled,
+    )
+    .await
+    .map_err(|e| {
+        error!(?e, "Failed to bulk update review_enabled");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
+    })?;
 
     info!(
         org_id = %org_id,
@@ -456,6 +525,23 @@ pub async fn bulk_update_review_enabled(
         "Bulk updated repository review_enabled"
     );
 
+    record_event(
+        &mut tx,
+        org_id,
+        Some(ctx.user.id),
+        AuditAction::RepositoryReviewEnabledChanged,
+        None,
+        json!({
+            "all_repositories": true,
+            "review_enabled": payload.enabled,
+            "updated_count": updated_count,
+        }),
+    )
+    .await?;
+    tx.commit()
+        .await
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
+
     Ok(Json(BulkUpdateReviewEnabledResponse { updated_count }))
 }
 
@@ -551,16 +637,14 @@ pub async fn handle_callback(
     };
 
     // Create installation record
-    if let Err(e) = gh_repo
-        .create_installation(
-            pending.organization_id,
-            installation_id,
-            &installation_info.account.login,
-            &installation_info.account.account_type,
-            &installation_info.repository_selection,
-            user_id,
-        )
-        .await
+    if let Err(e) = save_installation(
+        &state,
+        pending.organization_id,
+        user_id,
+        installation_id,
+        &installation_info,
+    )
+    .await
     {
         error!(?e, "Failed to create installation record");
         return redirect_error(Some(org_id), "Failed to save installation");
@@ -662,6 +746,94 @@ pub async fn handle_webhook(
     }
 }
 
+/// Store an installation completed through our install flow along with its
+/// audit event
+async fn save_installation(
+    state: &AppState,
+    organization_id: Uuid,
+    user_id: Uuid,
+    installation_id: i64,
+    installation_info: &InstallationInfo,
+) -> Result<(), ErrorResponse> {
+    let mut tx = state
+        .pool()
+        .begin()
+        .await
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
+
+    let installation = GitHubAppRepository2::create_installation(
+        &mut *tx,
+        organization_id,
+        installation_id,
+        &installation_info.account.login,
+        &installation_info.account.account_type,
+        &installation_info.repository_selection,
+        user_id,
+    )
+    .await
+    .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
+
+    record_event(
+        &mut tx,
+        organization_id,
+        Some(user_id),
+        AuditAction::GithubAppInstalled,
+        Some(installation.id),
+        json!({
+            "github_installation_id": installation_id,
+            "github_account_login": installation_info.account.login,
+            "repository_selection": installation_info.repository_selection,
+        }),
+    )
+    .await?;
+
+    tx.commit()
+        .await
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))
+}
+
+/// Remove an installation that was uninstalled on GitHub along with its audit
+/// event. There is no actor on our side.
+async fn delete_uninstalled_installation(
+    state: &AppState,
+    installation_id: i64,
+) -> Result<(), ErrorResponse> {
+    let installation = GitHubAppRepository2::new(state.pool())
+        .get_by_github_id(installation_id)
+        .await
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
+
+    let mut tx = state
+        .pool()
+        .begin()
+        .await
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
+
+    GitHubAppRepository2::delete_by_github_id(&mut *tx, installation_id)
+        .await
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
+
+    if let Some(installation) = installation {
+        record_event(
+            &mut tx,
+            installation.organization_id,
+            None,
+            AuditAction::GithubAppUninstalled,
+            Some(installation.id),
+            json!({
+                "github_installation_id": installation_id,
+                "github_account_login": installation.github_account_login,
+                "source": "github",
+            }),
+        )
+        .await?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))
+}
+
 // ========== Webhook Event Handlers ==========
 
 async fn handle_installation_event(state: &AppState, payload: &serde_json::Value) -> Response {
@@ -674,7 +846,7 @@ async fn handle_installation_event(state: &AppState, payload: &serde_json::Value
 
     match action {
         "deleted" => {
-            if let Err(e) = gh_repo.delete_by_github_id(installation_id).await {
+            if let Err(e) = delete_uninstalled_installation(state, installation_id).await {
                 error!(?e, "Failed to delete installation");
             } else {
                 info!(installation_id, "Installation deleted");
@@ -1070,17 +1242,138 @@ fn parse_pr_url(url: &str) -> Option<(String, String, u64)> {
     Some((owner, repo, pr_number))
 }
 
+/// POST /v1/organizations/:org_id/github-app/reviews
+/// Trigger a review of a PR in one of the organization's review-enabled
+/// repositories. This is the route API tokens with the `reviews_trigger` scope
+/// use; the debug endpoint below stays limited to signed-in users.
+pub async fn trigger_org_pr_review(
+    State(state): State<AppState>,
+    axum::extract::Extension(ctx): axum::extract::Extension<RequestContext>,
+    Path(org_id): Path<Uuid>,
+    Json(payload): Json<TriggerPrReviewRequest>,
+) -> Result<Json<TriggerPrReviewResponse>, ErrorResponse> {
+    let org_repo = OrganizationRepository::new(state.pool());
+    org_repo
+        .assert_membership(org_id, ctx.user.id)
+        .await
+        .map_err(|e| match e {
+            IdentityError::PermissionDenied | IdentityError::NotFound => {
+                ErrorResponse::new(StatusCode::FORBIDDEN, "Access denied")
+            }
+            _ => ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"),
+        })?;
+
+    let (owner, repo, pr_number) = parse_pr_url(&payload.pr_url)
+        .ok_or_else(|| ErrorResponse::new(StatusCode::BAD_REQUEST, "Invalid PR URL format"))?;
+
+    let gh_repo = GitHubAppRepository2::new(state.pool());
+    let installation = gh_repo
+        .get_by_organization(org_id)
+        .await
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "GitHub App not installed"))?;
+
+    if installation.suspended_at.is_some() {
+        return Err(ErrorResponse::new(
+            StatusCode::CONFLICT,
+            "GitHub App installation is suspended",
+        ));
+    }
+    if !installation
+        .github_account_login
+        .eq_ignore_ascii_case(&owner)
+    {
+        return Err(ErrorResponse::new(
+            StatusCode::FORBIDDEN,
+            "PR does not belong to this organization's GitHub App installation",
+        ));
+    }
+
+    // Repositories missing from the cache are reviewable in "all repositories"
+    // mode, matching the webhook; GitHub rejects the PR lookup if the
+    // installation can't see the repository.
+    let full_name = format!("{owner}/{repo}");
+    let repositories = gh_repo
+        .get_repositories(installation.id)
+        .await
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
+    if repositories
+        .iter()
+        .any(|r| r.repo_full_name.eq_ignore_ascii_case(&full_name) && !r.review_enabled)
+    {
+        return Err(ErrorResponse::new(
+            StatusCode::FORBIDDEN,
+            "Reviews are not enabled for this repository",
+        ));
+    }
+
+    let review_id = run_pr_review(
+        &state,
+        installation.github_installation_id,
+        owner,
+        repo,
+        pr_number,
+    )
+    .await?;
+
+    info!(
+        review_id = %review_id,
+        pr_url = %payload.pr_url,
+        org_id = %org_id,
+        api_token_id = ?ctx.api_token.as_ref().map(|token| token.id),
+        "PR review triggered"
+    );
+
+    Ok(Json(TriggerPrReviewResponse { review_id }))
+}
+
 /// POST /v1/debug/pr-review/trigger
 /// Manually trigger a PR review for debugging purposes
 pub async fn trigger_pr_review(
     State(state): State<AppState>,
     Json(payload): Json<TriggerPrReviewRequest>,
 ) -> Result<Json<TriggerPrReviewResponse>, ErrorResponse> {
-    // 1. Parse PR URL
     let (owner, repo, pr_number) = parse_pr_url(&payload.pr_url)
         .ok_or_else(|| ErrorResponse::new(StatusCode::BAD_REQUEST, "Invalid PR URL format"))?;
 
-    // 2. Validate services are configured
+    let gh_repo = GitHubAppRepository2::new(state.pool());
+    let installation = gh_repo
+        .get_by_account_login(&owner)
+        .await
+        .map_err(|e| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| {
+            ErrorResponse::new(
+                StatusCode::NOT_FOUND,
+                format!("No installation found for {}", owner),
+            )
+        })?;
+
+    let review_id = run_pr_review(
+        &state,
+        installation.github_installation_id,
+        owner,
+        repo,
+        pr_number,
+    )
+    .await?;
+
+    info!(
+        review_id = %review_id,
+        pr_url = %payload.pr_url,
+        "Manual PR review triggered"
+    );
+
+    Ok(Json(TriggerPrReviewResponse { review_id }))
+}
+
+/// Fetch the PR from GitHub and start a review of it
+async fn run_pr_review(
+    state: &AppState,
+    installation_id: i64,
+    owner: String,
+    repo: String,
+    pr_number: u64,
+) -> Result<Uuid, ErrorResponse> {
     let github_app = state.github_app().ok_or_else(|| {
         ErrorResponse::new(StatusCode::SERVICE_UNAVAILABLE, "GitHub App not configured")
     })?;
@@ -1098,31 +1391,11 @@ pub async fn trigger_pr_review(
             )
         })?;
 
-    // 3. Look up installation by owner
-    let gh_repo = GitHubAppRepository2::new(state.pool());
-    let installation = gh_repo
-        .get_by_account_login(&owner)
-        .await
-        .map_err(|e| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-        .ok_or_else(|| {
-            ErrorResponse::new(
-                StatusCode::NOT_FOUND,
-                format!("No installation found for {}", owner),
-            )
-        })?;
-
-    // 4. Fetch PR details from GitHub API
     let pr_details = github_app
-        .get_pr_details(
-            installation.github_installation_id,
-            &owner,
-            &repo,
-            pr_number,
-        )
+        .get_pr_details(installation_id, &owner, &repo, pr_number)
         .await
         .map_err(|e| ErrorResponse::new(StatusCode::BAD_GATEWAY, e.to_string()))?;
 
-    // 5. Create service and process review
     let service = PrReviewService::new(
         github_app.clone(),
         r2.clone(),
@@ -1132,7 +1405,7 @@ pub async fn trigger_pr_review(
     );
 
     let params = PrReviewParams {
-        installation_id: installation.github_installation_id,
+        installation_id,
         owner,
         repo,
         pr_number,
@@ -1142,16 +1415,8 @@ pub async fn trigger_pr_review(
         base_ref: pr_details.base.ref_name,
     };
 
-    let review_id = service
+    service
         .process_pr_review(state.pool(), params)
         .await
-        .map_err(|e| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
-
-    info!(
-        review_id = %review_id,
-        pr_url = %payload.pr_url,
-        "Manual PR review triggered"
-    );
-
-    Ok(Json(TriggerPrReviewResponse { review_id }))
+        .map_err(|e| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
 }
@@ -3,13 +3,22 @@ use serde::{Deserialize, Serialize};
 use tracing::instrument;
 use uuid::Uuid;
 
-use crate::{AppState, auth::RequestContext};
+use crate::{AppState, auth::RequestContext, db::api_tokens::ApiTokenScope};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct IdentityResponse {
     pub user_id: Uuid,
     pub username: Option<String>,
     pub email: String,
+    /// Present when the request was authenticated with an API token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_token: Option<IdentityApiToken>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IdentityApiToken {
+    pub id: Uuid,
+    pub scopes: Vec<ApiTokenScope>,
 }
 
 pub fn router() -> Router<AppState> {
@@ -23,5 +32,9 @@ pub async fn get_identity(Extension(ctx): Extension<RequestContext>) -> Json<Ide
         user_id: user.id,
         username: user.username,
         email: user.email,
+        api_token: ctx.api_token.map(|token| IdentityApiToken {
+            id: token.id,
+            scopes: token.scopes,
+        }),
     })
 }
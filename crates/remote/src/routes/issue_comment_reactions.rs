@@ -8,7 +8,10 @@ use serde::{Deserialize, Serialize};
 use tracing::instrument;
 use uuid::Uuid;
 
-use super::{error::ErrorResponse, organization_members::ensure_issue_access};
+use super::{
+    error::ErrorResponse,
+    organization_members::{ensure_issue_access, ensure_issue_write_access},
+};
 use crate::{
     AppState,
     auth::RequestContext,
@@ -89,7 +92,7 @@ async fn create_reaction(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "comment not found"))?;
 
-    ensure_issue_access(state.pool(), ctx.user.id, comment.issue_id).await?;
+    ensure_issue_write_access(state.pool(), ctx.user.id, comment.issue_id).await?;
 
     let reaction = IssueCommentReactionRepository::create(
         state.pool(),
@@ -139,7 +142,7 @@ async fn delete_reaction(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "comment not found"))?;
 
-    ensure_issue_access(state.pool(), ctx.user.id, comment.issue_id).await?;
+    ensure_issue_write_access(state.pool(), ctx.user.id, comment.issue_id).await?;
 
     IssueCommentReactionRepository::delete(state.pool(), reaction_id)
         .await
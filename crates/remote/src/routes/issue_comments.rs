@@ -8,11 +8,19 @@ use serde::{Deserialize, Serialize};
 use tracing::instrument;
 use uuid::Uuid;
 
-use super::{error::ErrorResponse, organization_members::ensure_issue_access};
+use super::{
+    error::ErrorResponse,
+    issues::record_mentions,
+    organization_members::{ensure_issue_access, ensure_issue_write_access},
+};
 use crate::{
     AppState,
     auth::RequestContext,
-    db::issue_comments::{IssueComment, IssueCommentRepository},
+    db::{
+        Tx,
+        issue_comments::{IssueComment, IssueCommentRepository},
+        issues::IssueRepository,
+    },
 };
 
 #[derive(Debug, Serialize)]
@@ -78,15 +86,25 @@ async fn create_comment(
     Path(issue_id): Path<Uuid>,
     Json(payload): Json<CreateCommentRequest>,
 ) -> Result<Json<IssueComment>, ErrorResponse> {
-    ensure_issue_access(state.pool(), ctx.user.id, issue_id).await?;
+    let organization_id = ensure_issue_write_access(state.pool(), ctx.user.id, issue_id).await?;
+
+    let mut tx = state.pool().begin().await.map_err(|error| {
+        tracing::error!(?error, "failed to begin transaction");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+
+    let comment = IssueCommentRepository::create(&mut *tx, issue_id, ctx.user.id, payload.message)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to create issue comment");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+    mention_users(&state, &mut tx, organization_id, ctx.user.id, &comment).await?;
 
-    let comment =
-        IssueCommentRepository::create(state.pool(), issue_id, ctx.user.id, payload.message)
-            .await
-            .map_err(|error| {
-                tracing::error!(?error, "failed to create issue comment");
-                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
-            })?;
+    tx.commit().await.map_err(|error| {
+        tracing::error!(?error, "failed to commit transaction");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
 
     Ok(Json(comment))
 }
@@ -120,14 +138,33 @@ async fn update_comment(
         ));
     }
 
-    ensure_issue_access(state.pool(), ctx.user.id, comment.issue_id).await?;
+    let organization_id =
+        ensure_issue_write_access(state.pool(), ctx.user.id, comment.issue_id).await?;
 
-    let updated_comment = IssueCommentRepository::update(state.pool(), comment_id, payload.message)
+    let mut tx = state.pool().begin().await.map_err(|error| {
+        tracing::error!(?error, "failed to begin transaction");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+
+    let updated_comment = IssueCommentRepository::update(&mut *tx, comment_id, payload.message)
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to update issue comment");
             ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
         })?;
+    mention_users(
+        &state,
+        &mut tx,
+        organization_id,
+        ctx.user.id,
+        &updated_comment,
+    )
+    .await?;
+
+    tx.commit().await.map_err(|error| {
+        tracing::error!(?error, "failed to commit transaction");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
 
     Ok(Json(updated_comment))
 }
@@ -160,7 +197,7 @@ async fn delete_comment(
         ));
     }
 
-    ensure_issue_access(state.pool(), ctx.user.id, comment.issue_id).await?;
+    ensure_issue_write_access(state.pool(), ctx.user.id, comment.issue_id).await?;
 
     IssueCommentRepository::delete(state.pool(), comment_id)
         .await
@@ -171,3 +208,30 @@ async fn delete_comment(
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+async fn mention_users(
+    state: &AppState,
+    tx: &mut Tx<'_>,
+    organization_id: Uuid,
+    actor_id: Uuid,
+    comment: &IssueComment,
+) -> Result<(), ErrorResponse> {
+    let issue = IssueRepository::find_by_id(&mut **tx, comment.issue_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, issue_id = %comment.issue_id, "failed to load issue");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue not found"))?;
+
+    record_mentions(
+        state.pool(),
+        tx,
+        organization_id,
+        actor_id,
+        &issue,
+        Some(comment.id),
+        &comment.message,
+    )
+    .await
+}
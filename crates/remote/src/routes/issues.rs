@@ -0,0 +1,1384 @@
+use std::collections::BTreeSet;
+
+use axum::{
+    Json, Router,
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    routing::{get, post, put},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use sqlx::PgPool;
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{
+    error::ErrorResponse,
+    organization_members::{ensure_project_access, ensure_project_write_access},
+};
+use crate::{
+    AppState,
+    auth::RequestContext,
+    db::{
+        Tx,
+        audit_events::{AuditAction, AuditEventRepository},
+        identity_errors::IdentityError,
+        issue_assignees::{IssueAssignee, IssueAssigneeRepository},
+        issue_dependencies::{IssueDependency, IssueDependencyRepository},
+        issue_events::{
+            IssueEvent, IssueEventRepository, IssueEventType, StatusDuration, field_changes,
+            time_in_status,
+        },
+        issue_followers::{IssueFollower, IssueFollowerRepository},
+        issue_mentions::{IssueMention, IssueMentionRepository, extract_mention_handles},
+        issue_tags::{IssueTag, IssueTagRepository},
+        issues::{Issue, IssueFields, IssueRepository},
+        notifications::{NotificationError, NotificationRepository, NotificationType},
+        organization_members::{self, ProjectAccess},
+        project_notification_preferences::ProjectNotificationPreferenceRepository,
+        project_statuses::{ProjectStatus, ProjectStatusRepository},
+        tags::TagRepository,
+        types::{IssuePriority, StatusCategory},
+    },
+};
+
+#[derive(Debug, Serialize)]
+pub struct ListIssuesResponse {
+    pub issues: Vec<Issue>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListIssueAssigneesResponse {
+    pub assignees: Vec<IssueAssignee>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListIssueTagsResponse {
+    pub tags: Vec<IssueTag>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListIssueFollowersResponse {
+    pub followers: Vec<IssueFollower>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListIssueMentionsResponse {
+    pub mentions: Vec<IssueMention>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListIssueEventsResponse {
+    pub events: Vec<IssueEvent>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IssueCycleTimeResponse {
+    pub statuses: Vec<StatusDuration>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListIssueDependenciesResponse {
+    pub dependencies: Vec<IssueDependency>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateIssueRequest {
+    pub status_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: Option<IssuePriority>,
+    pub start_date: Option<DateTime<Utc>>,
+    pub target_date: Option<DateTime<Utc>>,
+    /// Defaults to the bottom of the status column
+    pub sort_order: Option<f64>,
+    pub parent_issue_id: Option<Uuid>,
+    pub extension_metadata: Option<Value>,
+    #[serde(default)]
+    pub assignee_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub tag_ids: Vec<Uuid>,
+}
+
+/// Omitted fields are left unchanged; nullable fields are cleared with `null`
+#[derive(Debug, Deserialize)]
+pub struct UpdateIssueRequest {
+    pub status_id: Option<Uuid>,
+    pub title: Option<String>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub description: Option<Option<String>>,
+    pub priority: Option<IssuePriority>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub start_date: Option<Option<DateTime<Utc>>>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub target_date: Option<Option<DateTime<Utc>>>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub completed_at: Option<Option<DateTime<Utc>>>,
+    pub sort_order: Option<f64>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub parent_issue_id: Option<Option<Uuid>>,
+    pub extension_metadata: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReorderIssuesRequest {
+    pub issues: Vec<IssuePosition>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IssuePosition {
+    pub id: Uuid,
+    /// Moves the issue to another status column when set
+    pub status_id: Option<Uuid>,
+    pub sort_order: f64,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/projects/{project_id}/issues",
+            get(list_issues).post(create_issue),
+        )
+        .route(
+            "/projects/{project_id}/issues/reorder",
+            post(reorder_issues),
+        )
+        .route(
+            "/projects/{project_id}/issues/{issue_id}",
+            get(get_issue).patch(update_issue).delete(delete_issue),
+        )
+        .route(
+            "/projects/{project_id}/issues/{issue_id}/assignees",
+            get(list_assignees),
+        )
+        .route(
+            "/projects/{project_id}/issues/{issue_id}/assignees/{user_id}",
+            put(add_assignee).delete(remove_assignee),
+        )
+        .route(
+            "/projects/{project_id}/issues/{issue_id}/tags",
+            get(list_tags),
+        )
+        .route(
+            "/projects/{project_id}/issues/{issue_id}/tags/{tag_id}",
+            put(add_tag).delete(remove_tag),
+        )
+        .route(
+            "/projects/{project_id}/issues/{issue_id}/followers",
+            get(list_followers),
+        )
+        .route(
+            "/projects/{project_id}/issues/{issue_id}/mentions",
+            get(list_mentions),
+        )
+        .route(
+            "/projects/{project_id}/issues/{issue_id}/events",
+            get(list_events),
+        )
+        .route(
+            "/projects/{project_id}/issues/{issue_id}/cycle-time",
+            get(cycle_time),
+        )
+        .route(
+            "/projects/{project_id}/issues/{issue_id}/followers/{user_id}",
+            put(add_follower).delete(remove_follower),
+        )
+        .route(
+            "/projects/{project_id}/issues/{issue_id}/dependencies",
+            get(list_dependencies),
+        )
+        .route(
+            "/projects/{project_id}/issues/{issue_id}/dependencies/{blocking_issue_id}",
+            put(add_dependency).delete(remove_dependency),
+        )
+}
+
+fn internal_error<E: std::fmt::Debug>(message: &'static str) -> impl FnOnce(E) -> ErrorResponse {
+    move |error| {
+        tracing::error!(?error, "{message}");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, message)
+    }
+}
+
+/// Check the caller can see the project and load an issue that belongs to
+/// it, returning it with the organization id
+async fn load_issue(
+    pool: &PgPool,
+    user_id: Uuid,
+    project_id: Uuid,
+    issue_id: Uuid,
+) -> Result<(Issue, Uuid), ErrorResponse> {
+    let organization_id = ensure_project_access(pool, user_id, project_id).await?;
+    find_issue_in_project(pool, project_id, issue_id)
+        .await
+        .map(|issue| (issue, organization_id))
+}
+
+/// Like [`load_issue`], but the caller must also be allowed to change the
+/// project
+async fn load_issue_for_write(
+    pool: &PgPool,
+    user_id: Uuid,
+    project_id: Uuid,
+    issue_id: Uuid,
+) -> Result<(Issue, Uuid), ErrorResponse> {
+    let organization_id = ensure_project_write_access(pool, user_id, project_id).await?;
+    find_issue_in_project(pool, project_id, issue_id)
+        .await
+        .map(|issue| (issue, organization_id))
+}
+
+async fn find_issue_in_project(
+    pool: &PgPool,
+    project_id: Uuid,
+    issue_id: Uuid,
+) -> Result<Issue, ErrorResponse> {
+    IssueRepository::find_by_id(pool, issue_id)
+        .await
+        .map_err(internal_error("failed to load issue"))?
+        .filter(|issue| issue.project_id == project_id)
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue not found"))
+}
+
+async fn ensure_status_in_project(
+    pool: &PgPool,
+    project_id: Uuid,
+    status_id: Uuid,
+) -> Result<ProjectStatus, ErrorResponse> {
+    ProjectStatusRepository::find_by_id(pool, status_id)
+        .await
+        .map_err(internal_error("failed to load project status"))?
+        .filter(|status| status.project_id == project_id)
+        .ok_or_else(|| {
+            ErrorResponse::new(
+                StatusCode::BAD_REQUEST,
+                "status does not belong to this project",
+            )
+        })
+}
+
+async fn ensure_issue_in_project(
+    pool: &PgPool,
+    project_id: Uuid,
+    issue_id: Uuid,
+) -> Result<(), ErrorResponse> {
+    IssueRepository::find_by_id(pool, issue_id)
+        .await
+        .map_err(internal_error("failed to load issue"))?
+        .filter(|issue| issue.project_id == project_id)
+        .map(|_| ())
+        .ok_or_else(|| {
+            ErrorResponse::new(
+                StatusCode::BAD_REQUEST,
+                "issue does not belong to this project",
+            )
+        })
+}
+
+/// Reject a parent that is the issue itself or one of its descendants
+async fn ensure_no_parent_cycle(
+    pool: &PgPool,
+    issue_id: Uuid,
+    parent_issue_id: Uuid,
+) -> Result<(), ErrorResponse> {
+    let cycle = IssueRepository::would_create_cycle(pool, issue_id, parent_issue_id)
+        .await
+        .map_err(internal_error("failed to check issue hierarchy"))?;
+    if cycle {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "an issue cannot be nested under itself or one of its sub-issues",
+        ));
+    }
+    Ok(())
+}
+
+/// `completed_at` once an issue is moved to `status`: stamped when it enters
+/// a completed status, kept when it moves between completed statuses and
+/// cleared when it leaves them
+fn completed_at_for_status(issue: &Issue, status: &ProjectStatus) -> Option<DateTime<Utc>> {
+    if status.category == StatusCategory::Completed {
+        issue.completed_at.or_else(|| Some(Utc::now()))
+    } else {
+        None
+    }
+}
+
+async fn ensure_tag_in_project(
+    pool: &PgPool,
+    project_id: Uuid,
+    tag_id: Uuid,
+) -> Result<(), ErrorResponse> {
+    TagRepository::find_by_id(pool, tag_id)
+        .await
+        .map_err(internal_error("failed to load tag"))?
+        .filter(|tag| tag.project_id == project_id)
+        .map(|_| ())
+        .ok_or_else(|| {
+            ErrorResponse::new(
+                StatusCode::BAD_REQUEST,
+                "tag does not belong to this project",
+            )
+        })
+}
+
+/// Assignees and followers must be able to see the project
+async fn ensure_can_see_project(
+    pool: &PgPool,
+    project_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), ErrorResponse> {
+    match organization_members::assert_project_access(
+        pool,
+        project_id,
+        user_id,
+        ProjectAccess::Read,
+    )
+    .await
+    {
+        Ok(()) => Ok(()),
+        Err(IdentityError::Database(error)) => {
+            Err(internal_error("failed to check project access")(error))
+        }
+        Err(_) => Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "user does not have access to this project",
+        )),
+    }
+}
+
+/// Notify each recipient once, never the user who made the change
+async fn notify_users(
+    tx: &mut Tx<'_>,
+    organization_id: Uuid,
+    recipients: impl IntoIterator<Item = Uuid>,
+    actor_id: Uuid,
+    notification_type: NotificationType,
+    payload: Value,
+    issue_id: Option<Uuid>,
+) -> Result<(), NotificationError> {
+    let recipients: BTreeSet<Uuid> = recipients
+        .into_iter()
+        .filter(|user_id| *user_id != actor_id)
+        .collect();
+    for user_id in recipients {
+        NotificationRepository::create(
+            &mut **tx,
+            organization_id,
+            user_id,
+            notification_type,
+            payload.clone(),
+            issue_id,
+            None,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+async fn notify_status_changed(
+    tx: &mut Tx<'_>,
+    organization_id: Uuid,
+    actor_id: Uuid,
+    previous_status_id: Uuid,
+    issue: &Issue,
+) -> Result<(), ErrorResponse> {
+    let subscribers = IssueRepository::subscriber_ids(&mut **tx, issue.id)
+        .await
+        .map_err(internal_error("failed to load issue subscribers"))?;
+    notify_users(
+        tx,
+        organization_id,
+        subscribers,
+        actor_id,
+        NotificationType::IssueStatusChanged,
+        json!({
+            "issue_id": issue.id,
+            "issue_title": issue.title,
+            "actor_user_id": actor_id,
+            "old_status_id": previous_status_id,
+            "new_status_id": issue.status_id,
+        }),
+        Some(issue.id),
+    )
+    .await
+    .map_err(internal_error("failed to create notifications"))
+}
+
+/// Tell the affected user, unless they opted out of assignment notifications
+/// for the project, and the issue's followers
+async fn notify_assignee_changed(
+    tx: &mut Tx<'_>,
+    organization_id: Uuid,
+    actor_id: Uuid,
+    issue: &Issue,
+    assignee_id: Uuid,
+    assigned: bool,
+) -> Result<(), ErrorResponse> {
+    let notify_assignee =
+        ProjectNotificationPreferenceRepository::find(&mut **tx, issue.project_id, assignee_id)
+            .await
+            .map_err(internal_error("failed to load notification preferences"))?
+            .is_none_or(|preference| preference.notify_on_issue_assigned);
+    let mut recipients: Vec<Uuid> = IssueFollowerRepository::list_by_issue(&mut **tx, issue.id)
+        .await
+        .map_err(internal_error("failed to load issue followers"))?
+        .into_iter()
+        .map(|follower| follower.user_id)
+        .collect();
+    if notify_assignee {
+        recipients.push(assignee_id);
+    }
+
+    notify_users(
+        tx,
+        organization_id,
+        recipients,
+        actor_id,
+        NotificationType::IssueAssigneeChanged,
+        json!({
+            "issue_id": issue.id,
+            "issue_title": issue.title,
+            "actor_user_id": actor_id,
+            "assignee_user_id": assignee_id,
+            "assigned": assigned,
+        }),
+        Some(issue.id),
+    )
+    .await
+    .map_err(internal_error("failed to create notifications"))
+}
+
+async fn record_event(
+    tx: &mut Tx<'_>,
+    issue_id: Uuid,
+    actor_id: Uuid,
+    event_type: IssueEventType,
+    old_value: Option<Value>,
+    new_value: Option<Value>,
+) -> Result<(), ErrorResponse> {
+    IssueEventRepository::create(
+        &mut **tx, issue_id, actor_id, event_type, old_value, new_value,
+    )
+    .await
+    .map(|_| ())
+    .map_err(internal_error("failed to record issue history"))
+}
+
+/// Append every tracked field that changed to the issue's history
+async fn record_changes(
+    tx: &mut Tx<'_>,
+    actor_id: Uuid,
+    before: &Issue,
+    after: &Issue,
+) -> Result<(), ErrorResponse> {
+    for change in field_changes(before, after) {
+        record_event(
+            tx,
+            after.id,
+            actor_id,
+            change.event_type,
+            change.old_value,
+            change.new_value,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Record who a description or comment mentions. Newly mentioned users
+/// follow the issue and are notified even if they were not following it.
+pub(super) async fn record_mentions(
+    pool: &PgPool,
+    tx: &mut Tx<'_>,
+    organization_id: Uuid,
+    actor_id: Uuid,
+    issue: &Issue,
+    comment_id: Option<Uuid>,
+    text: &str,
+) -> Result<(), ErrorResponse> {
+    let handles = extract_mention_handles(text);
+    let candidates = IssueMentionRepository::resolve_members(&mut **tx, organization_id, &handles)
+        .await
+        .map_err(internal_error("failed to resolve mentions"))?;
+
+    // Mentioning someone must not leak a private project to them
+    let mut mentioned = Vec::with_capacity(candidates.len());
+    for user_id in candidates {
+        match organization_members::assert_project_access(
+            pool,
+            issue.project_id,
+            user_id,
+            ProjectAccess::Read,
+        )
+        .await
+        {
+            Ok(()) => mentioned.push(user_id),
+            Err(IdentityError::Database(error)) => {
+                return Err(internal_error("failed to check project access")(error));
+            }
+            Err(_) => {}
+        }
+    }
+
+    IssueMentionRepository::remove_stale(&mut **tx, issue.id, comment_id, &mentioned)
+        .await
+        .map_err(internal_error("failed to update mentions"))?;
+    let added = IssueMentionRepository::add(&mut **tx, issue.id, comment_id, actor_id, &mentioned)
+        .await
+        .map_err(internal_error("failed to record mentions"))?;
+
+    for user_id in added.into_iter().filter(|user_id| *user_id != actor_id) {
+        IssueFollowerRepository::create(&mut **tx, issue.id, user_id)
+            .await
+            .map_err(internal_error("failed to follow issue"))?;
+        NotificationRepository::create(
+            &mut **tx,
+            organization_id,
+            user_id,
+            NotificationType::UserMentioned,
+            json!({
+                "issue_id": issue.id,
+                "issue_title": issue.title,
+                "actor_user_id": actor_id,
+                "comment_id": comment_id,
+            }),
+            Some(issue.id),
+            comment_id,
+        )
+        .await
+        .map_err(internal_error("failed to create notifications"))?;
+    }
+
+    Ok(())
+}
+
+#[instrument(
+    name = "issues.list_issues",
+    skip(state, ctx),
+    fields(project_id = %project_id, user_id = %ctx.user.id)
+)]
+async fn list_issues(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<ListIssuesResponse>, ErrorResponse> {
+    ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+
+    let issues = IssueRepository::list_by_project(state.pool(), project_id)
+        .await
+        .map_err(internal_error("failed to list issues"))?;
+
+    Ok(Json(ListIssuesResponse { issues }))
+}
+
+#[instrument(
+    name = "issues.create_issue",
+    skip(state, ctx, payload),
+    fields(project_id = %project_id, user_id = %ctx.user.id)
+)]
+async fn create_issue(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<CreateIssueRequest>,
+) -> Result<Json<Issue>, ErrorResponse> {
+    let organization_id =
+        ensure_project_write_access(state.pool(), ctx.user.id, project_id).await?;
+
+    let title = payload.title.trim().to_string();
+    if title.is_empty() {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "title must not be empty",
+        ));
+    }
+    let status = ensure_status_in_project(state.pool(), project_id, payload.status_id).await?;
+    if let Some(parent_issue_id) = payload.parent_issue_id {
+        ensure_issue_in_project(state.pool(), project_id, parent_issue_id).await?;
+    }
+    for tag_id in &payload.tag_ids {
+        ensure_tag_in_project(state.pool(), project_id, *tag_id).await?;
+    }
+    for assignee_id in &payload.assignee_ids {
+        ensure_can_see_project(state.pool(), project_id, *assignee_id).await?;
+    }
+
+    let mut tx = state
+        .pool()
+        .begin()
+        .await
+        .map_err(internal_error("failed to begin transaction"))?;
+
+    let sort_order = match payload.sort_order {
+        Some(sort_order) => sort_order,
+        None => IssueRepository::next_sort_order(&mut *tx, payload.status_id)
+            .await
+            .map_err(internal_error("failed to compute sort order"))?,
+    };
+    let fields = IssueFields {
+        status_id: payload.status_id,
+        title,
+        description: payload.description,
+        priority: payload.priority.unwrap_or(IssuePriority::Medium),
+        start_date: payload.start_date,
+        target_date: payload.target_date,
+        completed_at: (status.category == StatusCategory::Completed).then(Utc::now),
+        sort_order,
+        parent_issue_id: payload.parent_issue_id,
+        extension_metadata: payload.extension_metadata.unwrap_or_else(|| json!({})),
+    };
+    let issue = IssueRepository::create(&mut *tx, project_id, &fields)
+        .await
+        .map_err(internal_error("failed to create issue"))?;
+    record_event(
+        &mut tx,
+        issue.id,
+        ctx.user.id,
+        IssueEventType::Created,
+        None,
+        Some(json!({ "status_id": issue.status_id, "priority": issue.priority })),
+    )
+    .await?;
+
+    for tag_id in &payload.tag_ids {
+        IssueTagRepository::create(&mut *tx, issue.id, *tag_id)
+            .await
+            .map_err(internal_error("failed to tag issue"))?;
+    }
+    for assignee_id in &payload.assignee_ids {
+        if IssueAssigneeRepository::create(&mut *tx, issue.id, *assignee_id)
+            .await
+            .map_err(internal_error("failed to assign issue"))?
+            .is_some()
+        {
+            record_event(
+                &mut tx,
+                issue.id,
+                ctx.user.id,
+                IssueEventType::AssigneeAdded,
+                None,
+                Some(json!(assignee_id)),
+            )
+            .await?;
+            notify_assignee_changed(
+                &mut tx,
+                organization_id,
+                ctx.user.id,
+                &issue,
+                *assignee_id,
+                true,
+            )
+            .await?;
+        }
+    }
+    if let Some(description) = &issue.description {
+        record_mentions(
+            state.pool(),
+            &mut tx,
+            organization_id,
+            ctx.user.id,
+            &issue,
+            None,
+            description,
+        )
+        .await?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(internal_error("failed to commit transaction"))?;
+
+    Ok(Json(issue))
+}
+
+#[instrument(
+    name = "issues.get_issue",
+    skip(state, ctx),
+    fields(project_id = %project_id, issue_id = %issue_id, user_id = %ctx.user.id)
+)]
+async fn get_issue(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((project_id, issue_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Issue>, ErrorResponse> {
+    let (issue, _) = load_issue(state.pool(), ctx.user.id, project_id, issue_id).await?;
+    Ok(Json(issue))
+}
+
+#[instrument(
+    name = "issues.update_issue",
+    skip(state, ctx, payload),
+    fields(project_id = %project_id, issue_id = %issue_id, user_id = %ctx.user.id)
+)]
+async fn update_issue(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((project_id, issue_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateIssueRequest>,
+) -> Result<Json<Issue>, ErrorResponse> {
+    let (existing, organization_id) =
+        load_issue_for_write(state.pool(), ctx.user.id, project_id, issue_id).await?;
+
+    let title = match payload.title {
+        Some(title) if title.trim().is_empty() => {
+            return Err(ErrorResponse::new(
+                StatusCode::BAD_REQUEST,
+                "title must not be empty",
+            ));
+        }
+        Some(title) => title.trim().to_string(),
+        None => existing.title.clone(),
+    };
+    let status_id = payload.status_id.unwrap_or(existing.status_id);
+    let new_status = if status_id != existing.status_id {
+        Some(ensure_status_in_project(state.pool(), project_id, status_id).await?)
+    } else {
+        None
+    };
+    let completed_at = match (payload.completed_at, &new_status) {
+        (Some(completed_at), _) => completed_at,
+        (None, Some(status)) => completed_at_for_status(&existing, status),
+        (None, None) => existing.completed_at,
+    };
+    let parent_issue_id = payload.parent_issue_id.unwrap_or(existing.parent_issue_id);
+    if let Some(parent_issue_id) = parent_issue_id
+        && Some(parent_issue_id) != existing.parent_issue_id
+    {
+        ensure_issue_in_project(state.pool(), project_id, parent_issue_id).await?;
+        ensure_no_parent_cycle(state.pool(), issue_id, parent_issue_id).await?;
+    }
+
+    let description_changed = payload.description.is_some();
+    let fields = IssueFields {
+        status_id,
+        title,
+        description: payload
+            .description
+            .unwrap_or_else(|| existing.description.clone()),
+        priority: payload.priority.unwrap_or(existing.priority),
+        start_date: payload.start_date.unwrap_or(existing.start_date),
+        target_date: payload.target_date.unwrap_or(existing.target_date),
+        completed_at,
+        sort_order: payload.sort_order.unwrap_or(existing.sort_order),
+        parent_issue_id,
+        extension_metadata: payload
+            .extension_metadata
+            .unwrap_or_else(|| existing.extension_metadata.clone()),
+    };
+
+    let mut tx = state
+        .pool()
+        .begin()
+        .await
+        .map_err(internal_error("failed to begin transaction"))?;
+
+    let issue = IssueRepository::update(&mut *tx, issue_id, &fields)
+        .await
+        .map_err(internal_error("failed to update issue"))?;
+    record_changes(&mut tx, ctx.user.id, &existing, &issue).await?;
+
+    if issue.status_id != existing.status_id {
+        notify_status_changed(
+            &mut tx,
+            organization_id,
+            ctx.user.id,
+            existing.status_id,
+            &issue,
+        )
+        .await?;
+    }
+    if description_changed {
+        record_mentions(
+            state.pool(),
+            &mut tx,
+            organization_id,
+            ctx.user.id,
+            &issue,
+            None,
+            issue.description.as_deref().unwrap_or_default(),
+        )
+        .await?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(internal_error("failed to commit transaction"))?;
+
+    Ok(Json(issue))
+}
+
+#[instrument(
+    name = "issues.delete_issue",
+    skip(state, ctx),
+    fields(project_id = %project_id, issue_id = %issue_id, user_id = %ctx.user.id)
+)]
+async fn delete_issue(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((project_id, issue_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, ErrorResponse> {
+    let (issue, organization_id) =
+        load_issue_for_write(state.pool(), ctx.user.id, project_id, issue_id).await?;
+
+    let mut tx = state
+        .pool()
+        .begin()
+        .await
+        .map_err(internal_error("failed to begin transaction"))?;
+
+    let subscribers = IssueRepository::subscriber_ids(&mut *tx, issue_id)
+        .await
+        .map_err(internal_error("failed to load issue subscribers"))?;
+    // The issue is gone afterwards, so the payload carries what the
+    // notification needs to render
+    notify_users(
+        &mut tx,
+        organization_id,
+        subscribers,
+        ctx.user.id,
+        NotificationType::IssueDeleted,
+        json!({
+            "issue_id": issue.id,
+            "issue_title": issue.title,
+            "project_id": issue.project_id,
+            "actor_user_id": ctx.user.id,
+        }),
+        None,
+    )
+    .await
+    .map_err(internal_error("failed to create notifications"))?;
+
+    IssueRepository::delete(&mut *tx, issue_id)
+        .await
+        .map_err(internal_error("failed to delete issue"))?;
+
+    AuditEventRepository::record(
+        &mut *tx,
+        organization_id,
+        Some(ctx.user.id),
+        AuditAction::IssueDeleted,
+        Some(issue.id),
+        json!({ "title": issue.title, "project_id": issue.project_id }),
+    )
+    .await
+    .map_err(internal_error("failed to record audit event"))?;
+
+    tx.commit()
+        .await
+        .map_err(internal_error("failed to commit transaction"))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(
+    name = "issues.reorder_issues",
+    skip(state, ctx, payload),
+    fields(project_id = %project_id, user_id = %ctx.user.id)
+)]
+async fn reorder_issues(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<ReorderIssuesRequest>,
+) -> Result<Json<ListIssuesResponse>, ErrorResponse> {
+    let organization_id =
+        ensure_project_write_access(state.pool(), ctx.user.id, project_id).await?;
+
+    let mut tx = state
+        .pool()
+        .begin()
+        .await
+        .map_err(internal_error("failed to begin transaction"))?;
+
+    let mut issues = Vec::with_capacity(payload.issues.len());
+    for position in payload.issues {
+        let existing = IssueRepository::find_by_id(&mut *tx, position.id)
+            .await
+            .map_err(internal_error("failed to load issue"))?
+            .filter(|issue| issue.project_id == project_id)
+            .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue not found"))?;
+
+        let status_id = position.status_id.unwrap_or(existing.status_id);
+        let completed_at = if status_id != existing.status_id {
+            let status = ensure_status_in_project(state.pool(), project_id, status_id).await?;
+            completed_at_for_status(&existing, &status)
+        } else {
+            existing.completed_at
+        };
+
+        let issue = IssueRepository::set_position(
+            &mut *tx,
+            existing.id,
+            status_id,
+            position.sort_order,
+            completed_at,
+        )
+        .await
+        .map_err(internal_error("failed to reorder issue"))?;
+        record_changes(&mut tx, ctx.user.id, &existing, &issue).await?;
+
+        if issue.status_id != existing.status_id {
+            notify_status_changed(
+                &mut tx,
+                organization_id,
+                ctx.user.id,
+                existing.status_id,
+                &issue,
+            )
+            .await?;
+        }
+        issues.push(issue);
+    }
+
+    tx.commit()
+        .await
+        .map_err(internal_error("failed to commit transaction"))?;
+
+    Ok(Json(ListIssuesResponse { issues }))
+}
+
+#[instrument(
+    name = "issues.list_assignees",
+    skip(state, ctx),
+    fields(project_id = %project_id, issue_id = %issue_id, user_id = %ctx.user.id)
+)]
+async fn list_assignees(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((project_id, issue_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ListIssueAssigneesResponse>, ErrorResponse> {
+    load_issue(state.pool(), ctx.user.id, project_id, issue_id).await?;
+
+    let assignees = IssueAssigneeRepository::list_by_issue(state.pool(), issue_id)
+        .await
+        .map_err(internal_error("failed to list issue assignees"))?;
+
+    Ok(Json(ListIssueAssigneesResponse { assignees }))
+}
+
+#[instrument(
+    name = "issues.add_assignee",
+    skip(state, ctx),
+    fields(project_id = %project_id, issue_id = %issue_id, assignee_id = %assignee_id, user_id = %ctx.user.id)
+)]
+async fn add_assignee(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((project_id, issue_id, assignee_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<Json<IssueAssignee>, ErrorResponse> {
+    let (issue, organization_id) =
+        load_issue_for_write(state.pool(), ctx.user.id, project_id, issue_id).await?;
+    ensure_can_see_project(state.pool(), project_id, assignee_id).await?;
+
+    let mut tx = state
+        .pool()
+        .begin()
+        .await
+        .map_err(internal_error("failed to begin transaction"))?;
+
+    let assignee = match IssueAssigneeRepository::create(&mut *tx, issue_id, assignee_id)
+        .await
+        .map_err(internal_error("failed to assign issue"))?
+    {
+        Some(assignee) => {
+            record_event(
+                &mut tx,
+                issue_id,
+                ctx.user.id,
+                IssueEventType::AssigneeAdded,
+                None,
+                Some(json!(assignee_id)),
+            )
+            .await?;
+            notify_assignee_changed(
+                &mut tx,
+                organization_id,
+                ctx.user.id,
+                &issue,
+                assignee_id,
+                true,
+            )
+            .await?;
+            assignee
+        }
+        // Already assigned; nothing changed
+        None => IssueAssigneeRepository::find(&mut *tx, issue_id, assignee_id)
+            .await
+            .map_err(internal_error("failed to load issue assignee"))?
+            .ok_or_else(|| ErrorResponse::new(StatusCode::CONFLICT, "assignee was removed"))?,
+    };
+
+    tx.commit()
+        .await
+        .map_err(internal_error("failed to commit transaction"))?;
+
+    Ok(Json(assignee))
+}
+
+#[instrument(
+    name = "issues.remove_assignee",
+    skip(state, ctx),
+    fields(project_id = %project_id, issue_id = %issue_id, assignee_id = %assignee_id, user_id = %ctx.user.id)
+)]
+async fn remove_assignee(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((project_id, issue_id, assignee_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<StatusCode, ErrorResponse> {
+    let (issue, organization_id) =
+        load_issue_for_write(state.pool(), ctx.user.id, project_id, issue_id).await?;
+
+    let mut tx = state
+        .pool()
+        .begin()
+        .await
+        .map_err(internal_error("failed to begin transaction"))?;
+
+    if IssueAssigneeRepository::delete(&mut *tx, issue_id, assignee_id)
+        .await
+        .map_err(internal_error("failed to unassign issue"))?
+    {
+        record_event(
+            &mut tx,
+            issue_id,
+            ctx.user.id,
+            IssueEventType::AssigneeRemoved,
+            Some(json!(assignee_id)),
+            None,
+        )
+        .await?;
+        notify_assignee_changed(
+            &mut tx,
+            organization_id,
+            ctx.user.id,
+            &issue,
+            assignee_id,
+            false,
+        )
+        .await?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(internal_error("failed to commit transaction"))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(
+    name = "issues.list_tags",
+    skip(state, ctx),
+    fields(project_id = %project_id, issue_id = %issue_id, user_id = %ctx.user.id)
+)]
+async fn list_tags(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((project_id, issue_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ListIssueTagsResponse>, ErrorResponse> {
+    load_issue(state.pool(), ctx.user.id, project_id, issue_id).await?;
+
+    let tags = IssueTagRepository::list_by_issue(state.pool(), issue_id)
+        .await
+        .map_err(internal_error("failed to list issue tags"))?;
+
+    Ok(Json(ListIssueTagsResponse { tags }))
+}
+
+#[instrument(
+    name = "issues.add_tag",
+    skip(state, ctx),
+    fields(project_id = %project_id, issue_id = %issue_id, tag_id = %tag_id, user_id = %ctx.user.id)
+)]
+async fn add_tag(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((project_id, issue_id, tag_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<Json<IssueTag>, ErrorResponse> {
+    load_issue_for_write(state.pool(), ctx.user.id, project_id, issue_id).await?;
+    ensure_tag_in_project(state.pool(), project_id, tag_id).await?;
+
+    let tag = IssueTagRepository::create(state.pool(), issue_id, tag_id)
+        .await
+        .map_err(internal_error("failed to tag issue"))?;
+
+    Ok(Json(tag))
+}
+
+#[instrument(
+    name = "issues.remove_tag",
+    skip(state, ctx),
+    fields(project_id = %project_id, issue_id = %issue_id, tag_id = %tag_id, user_id = %ctx.user.id)
+)]
+async fn remove_tag(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((project_id, issue_id, tag_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<StatusCode, ErrorResponse> {
+    load_issue_for_write(state.pool(), ctx.user.id, project_id, issue_id).await?;
+
+    IssueTagRepository::delete(state.pool(), issue_id, tag_id)
+        .await
+        .map_err(internal_error("failed to untag issue"))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(
+    name = "issues.list_followers",
+    skip(state, ctx),
+    fields(project_id = %project_id, issue_id = %issue_id, user_id = %ctx.user.id)
+)]
+async fn list_followers(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((project_id, issue_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ListIssueFollowersResponse>, ErrorResponse> {
+    load_issue(state.pool(), ctx.user.id, project_id, issue_id).await?;
+
+    let followers = IssueFollowerRepository::list_by_issue(state.pool(), issue_id)
+        .await
+        .map_err(internal_error("failed to list issue followers"))?;
+
+    Ok(Json(ListIssueFollowersResponse { followers }))
+}
+
+#[instrument(
+    name = "issues.list_mentions",
+    skip(state, ctx),
+    fields(project_id = %project_id, issue_id = %issue_id, user_id = %ctx.user.id)
+)]
+async fn list_mentions(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((project_id, issue_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ListIssueMentionsResponse>, ErrorResponse> {
+    load_issue(state.pool(), ctx.user.id, project_id, issue_id).await?;
+
+    let mentions = IssueMentionRepository::list_by_issue(state.pool(), issue_id)
+        .await
+        .map_err(internal_error("failed to list issue mentions"))?;
+
+    Ok(Json(ListIssueMentionsResponse { mentions }))
+}
+
+#[instrument(
+    name = "issues.list_events",
+    skip(state, ctx),
+    fields(project_id = %project_id, issue_id = %issue_id, user_id = %ctx.user.id)
+)]
+async fn list_events(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((project_id, issue_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ListIssueEventsResponse>, ErrorResponse> {
+    load_issue(state.pool(), ctx.user.id, project_id, issue_id).await?;
+
+    let events = IssueEventRepository::list_by_issue(state.pool(), issue_id)
+        .await
+        .map_err(internal_error("failed to list issue history"))?;
+
+    Ok(Json(ListIssueEventsResponse { events }))
+}
+
+/// Time the issue has spent in each status so far
+#[instrument(
+    name = "issues.cycle_time",
+    skip(state, ctx),
+    fields(project_id = %project_id, issue_id = %issue_id, user_id = %ctx.user.id)
+)]
+async fn cycle_time(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((project_id, issue_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<IssueCycleTimeResponse>, ErrorResponse> {
+    load_issue(state.pool(), ctx.user.id, project_id, issue_id).await?;
+
+    let events = IssueEventRepository::list_by_issue(state.pool(), issue_id)
+        .await
+        .map_err(internal_error("failed to list issue history"))?;
+
+    Ok(Json(IssueCycleTimeResponse {
+        statuses: time_in_status(&events, Utc::now()),
+    }))
+}
+
+#[instrument(
+    name = "issues.add_follower",
+    skip(state, ctx),
+    fields(project_id = %project_id, issue_id = %issue_id, follower_id = %follower_id, user_id = %ctx.user.id)
+)]
+async fn add_follower(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((project_id, issue_id, follower_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<Json<IssueFollower>, ErrorResponse> {
+    load_issue_for_write(state.pool(), ctx.user.id, project_id, issue_id).await?;
+    ensure_can_see_project(state.pool(), project_id, follower_id).await?;
+
+    let follower = IssueFollowerRepository::create(state.pool(), issue_id, follower_id)
+        .await
+        .map_err(internal_error("failed to follow issue"))?;
+
+    Ok(Json(follower))
+}
+
+#[instrument(
+    name = "issues.remove_follower",
+    skip(state, ctx),
+    fields(project_id = %project_id, issue_id = %issue_id, follower_id = %follower_id, user_id = %ctx.user.id)
+)]
+async fn remove_follower(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((project_id, issue_id, follower_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<StatusCode, ErrorResponse> {
+    load_issue_for_write(state.pool(), ctx.user.id, project_id, issue_id).await?;
+
+    IssueFollowerRepository::delete(state.pool(), issue_id, follower_id)
+        .await
+        .map_err(internal_error("failed to unfollow issue"))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(
+    name = "issues.list_dependencies",
+    skip(state, ctx),
+    fields(project_id = %project_id, issue_id = %issue_id, user_id = %ctx.user.id)
+)]
+async fn list_dependencies(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((project_id, issue_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ListIssueDependenciesResponse>, ErrorResponse> {
+    load_issue(state.pool(), ctx.user.id, project_id, issue_id).await?;
+
+    let dependencies = IssueDependencyRepository::list_by_issue(state.pool(), issue_id)
+        .await
+        .map_err(internal_error("failed to list issue dependencies"))?;
+
+    Ok(Json(ListIssueDependenciesResponse { dependencies }))
+}
+
+/// Mark the issue as blocked by `blocking_issue_id`
+#[instrument(
+    name = "issues.add_dependency",
+    skip(state, ctx),
+    fields(project_id = %project_id, issue_id = %issue_id, blocking_issue_id = %blocking_issue_id, user_id = %ctx.user.id)
+)]
+async fn add_dependency(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((project_id, issue_id, blocking_issue_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<Json<IssueDependency>, ErrorResponse> {
+    load_issue_for_write(state.pool(), ctx.user.id, project_id, issue_id).await?;
+    if blocking_issue_id == issue_id {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "an issue cannot block itself",
+        ));
+    }
+    ensure_issue_in_project(state.pool(), project_id, blocking_issue_id).await?;
+
+    let mut tx = state
+        .pool()
+        .begin()
+        .await
+        .map_err(internal_error("failed to begin transaction"))?;
+
+    if IssueDependencyRepository::would_create_cycle(&mut *tx, blocking_issue_id, issue_id)
+        .await
+        .map_err(internal_error("failed to check issue dependencies"))?
+    {
+        return Err(ErrorResponse::new(
+            StatusCode::CONFLICT,
+            "dependency would create a cycle",
+        ));
+    }
+
+    let dependency = IssueDependencyRepository::create(&mut *tx, blocking_issue_id, issue_id)
+        .await
+        .map_err(internal_error("failed to add issue dependency"))?;
+
+    tx.commit()
+        .await
+        .map_err(internal_error("failed to commit transaction"))?;
+
+    Ok(Json(dependency))
+}
+
+#[instrument(
+    name = "issues.remove_dependency",
+    skip(state, ctx),
+    fields(project_id = %project_id, issue_id = %issue_id, blocking_issue_id = %blocking_issue_id, user_id = %ctx.user.id)
+)]
+async fn remove_dependency(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((project_id, issue_id, blocking_issue_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<StatusCode, ErrorResponse> {
+    load_issue_for_write(state.pool(), ctx.user.id, project_id, issue_id).await?;
+
+    IssueDependencyRepository::delete(state.pool(), blocking_issue_id, issue_id)
+        .await
+        .map_err(internal_error("failed to remove issue dependency"))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(completed_at: Option<DateTime<Utc>>) -> Issue {
+        let now = Utc::now();
+        Issue {
+            id: Uuid::nil(),
+            project_id: Uuid::nil(),
+            status_id: Uuid::nil(),
+            title: "Fix login".to_string(),
+            description: None,
+            priority: IssuePriority::Medium,
+            start_date: None,
+            target_date: None,
+            completed_at,
+            sort_order: 0.0,
+            parent_issue_id: None,
+            extension_metadata: json!({}),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn status(category: StatusCategory) -> ProjectStatus {
+        ProjectStatus {
+            id: Uuid::new_v4(),
+            project_id: Uuid::nil(),
+            name: "Shipped".to_string(),
+            color: "#22c55e".to_string(),
+            sort_order: 4,
+            category,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn completed_at_follows_the_status_category() {
+        let done = status(StatusCategory::Completed);
+        assert!(completed_at_for_status(&issue(None), &done).is_some());
+
+        let earlier = Utc::now() - chrono::Duration::days(3);
+        assert_eq!(
+            completed_at_for_status(&issue(Some(earlier)), &done),
+            Some(earlier)
+        );
+
+        let started = status(StatusCategory::Started);
+        assert_eq!(
+            completed_at_for_status(&issue(Some(earlier)), &started),
+            None
+        );
+    }
+}
@@ -14,21 +14,26 @@ use tracing::{Level, field};
 
 use crate::{AppState, auth::require_session};
 
+mod api_tokens;
+mod audit_events;
 mod electric_proxy;
 mod error;
 mod github_app;
 mod identity;
 mod issue_comment_reactions;
 mod issue_comments;
+mod issues;
 mod notifications;
 mod oauth;
 pub(crate) mod organization_members;
+mod organization_sso;
 mod organizations;
 mod project_statuses;
 mod projects;
 mod review;
 mod tags;
 mod tokens;
+mod workspaces;
 
 pub fn router(state: AppState) -> Router {
     let trace_layer = TraceLayer::new_for_http()
@@ -57,6 +62,7 @@ pub fn router(state: AppState) -> Router {
         .merge(organization_members::public_router())
         .merge(tokens::public_router())
         .merge(review::public_router())
+        .merge(notifications::public_router())
         .merge(github_app::public_router());
 
     let v1_protected = Router::<AppState>::new()
@@ -64,11 +70,16 @@ pub fn router(state: AppState) -> Router {
         .merge(projects::router())
         .merge(organizations::router())
         .merge(organization_members::protected_router())
+        .merge(organization_sso::router())
         .merge(oauth::protected_router())
+        .merge(api_tokens::router())
+        .merge(audit_events::router())
         .merge(electric_proxy::router())
         .merge(github_app::protected_router())
         .merge(project_statuses::router())
         .merge(tags::router())
+        .merge(issues::router())
+        .merge(workspaces::router())
         .merge(issue_comments::router())
         .merge(issue_comment_reactions::router())
         .merge(notifications::router())
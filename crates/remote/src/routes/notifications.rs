@@ -2,17 +2,27 @@ use axum::{
     Json, Router,
     extract::{Extension, Path, Query, State},
     http::StatusCode,
-    routing::{get, post},
+    routing::{get, post, put},
 };
+use chrono::NaiveTime;
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
 use uuid::Uuid;
 
-use super::error::ErrorResponse;
+use super::{error::ErrorResponse, organization_members::ensure_project_access};
 use crate::{
     AppState,
     auth::RequestContext,
-    db::notifications::{Notification, NotificationRepository},
+    db::{
+        notification_settings::{
+            EmailDigestFrequency, NotificationSettings, NotificationSettingsError,
+            NotificationSettingsRepository,
+        },
+        notifications::{Notification, NotificationRepository},
+        project_notification_preferences::{
+            ProjectNotificationPreference, ProjectNotificationPreferenceRepository,
+        },
+    },
 };
 
 #[derive(Debug, Serialize)]
@@ -42,11 +52,45 @@ pub struct UpdateNotificationRequest {
     pub dismissed: Option<bool>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct UpdateEmailSettingsRequest {
+    pub email_frequency: EmailDigestFrequency,
+    pub quiet_hours_start: Option<NaiveTime>,
+    pub quiet_hours_end: Option<NaiveTime>,
+    #[serde(default)]
+    pub utc_offset_minutes: i32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateProjectPreferencesRequest {
+    pub notify_on_issue_created: bool,
+    pub notify_on_issue_assigned: bool,
+    pub email_enabled: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UnsubscribeQuery {
+    pub project_id: Option<Uuid>,
+}
+
+/// Unsubscribe links in emails are followed without a session
+pub fn public_router() -> Router<AppState> {
+    Router::new().route("/notifications/unsubscribe/{token}", get(unsubscribe))
+}
+
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/notifications", get(list_notifications))
         .route("/notifications/unread-count", get(unread_count))
         .route("/notifications/mark-all-seen", post(mark_all_seen))
+        .route(
+            "/notifications/email-settings",
+            get(get_email_settings).put(update_email_settings),
+        )
+        .route(
+            "/notifications/projects/{project_id}/preferences",
+            put(update_project_preferences),
+        )
         .route(
             "/notifications/{notification_id}",
             get(get_notification).patch(update_notification),
@@ -197,3 +241,138 @@ async fn unread_count(
 
     Ok(Json(UnreadCountResponse { count }))
 }
+
+#[instrument(
+    name = "notifications.get_email_settings",
+    skip(state, ctx),
+    fields(user_id = %ctx.user.id)
+)]
+async fn get_email_settings(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+) -> Result<Json<NotificationSettings>, ErrorResponse> {
+    let settings = NotificationSettingsRepository::get_or_create(state.pool(), ctx.user.id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to load notification settings");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+
+    Ok(Json(settings))
+}
+
+#[instrument(
+    name = "notifications.update_email_settings",
+    skip(state, ctx, payload),
+    fields(user_id = %ctx.user.id)
+)]
+async fn update_email_settings(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Json(payload): Json<UpdateEmailSettingsRequest>,
+) -> Result<Json<NotificationSettings>, ErrorResponse> {
+    let quiet_hours = match (payload.quiet_hours_start, payload.quiet_hours_end) {
+        (Some(start), Some(end)) => Some((start, end)),
+        (None, None) => None,
+        _ => {
+            return Err(ErrorResponse::new(
+                StatusCode::BAD_REQUEST,
+                "quiet hours need both a start and an end",
+            ));
+        }
+    };
+    if !(-720..=840).contains(&payload.utc_offset_minutes) {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "utc_offset_minutes must be between -720 and 840",
+        ));
+    }
+
+    let settings = NotificationSettingsRepository::update(
+        state.pool(),
+        ctx.user.id,
+        payload.email_frequency,
+        quiet_hours,
+        payload.utc_offset_minutes,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, "failed to update notification settings");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+
+    Ok(Json(settings))
+}
+
+#[instrument(
+    name = "notifications.update_project_preferences",
+    skip(state, ctx, payload),
+    fields(project_id = %project_id, user_id = %ctx.user.id)
+)]
+async fn update_project_preferences(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<UpdateProjectPreferencesRequest>,
+) -> Result<Json<ProjectNotificationPreference>, ErrorResponse> {
+    ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+
+    let preference = ProjectNotificationPreferenceRepository::upsert(
+        state.pool(),
+        project_id,
+        ctx.user.id,
+        payload.notify_on_issue_created,
+        payload.notify_on_issue_assigned,
+        payload.email_enabled,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, "failed to update project notification preferences");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+
+    Ok(Json(preference))
+}
+
+/// Turn off digest emails entirely, or only for one project when the link
+/// carries a `project_id`
+#[instrument(name = "notifications.unsubscribe", skip(state, token, query))]
+async fn unsubscribe(
+    State(state): State<AppState>,
+    Path(token): Path<Uuid>,
+    Query(query): Query<UnsubscribeQuery>,
+) -> Result<&'static str, ErrorResponse> {
+    let internal_error = |error: NotificationSettingsError| {
+        tracing::error!(?error, "failed to unsubscribe from notification emails");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    };
+    let not_found = || ErrorResponse::new(StatusCode::NOT_FOUND, "unsubscribe link is not valid");
+
+    match query.project_id {
+        Some(project_id) => {
+            let user_id =
+                NotificationSettingsRepository::find_user_by_unsubscribe_token(state.pool(), token)
+                    .await
+                    .map_err(internal_error)?
+                    .ok_or_else(not_found)?;
+            ProjectNotificationPreferenceRepository::disable_email(
+                state.pool(),
+                project_id,
+                user_id,
+            )
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, %project_id, "failed to mute project emails");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            })?;
+            Ok("You will no longer receive emails about this project.")
+        }
+        None => {
+            NotificationSettingsRepository::unsubscribe(state.pool(), token)
+                .await
+                .map_err(internal_error)?
+                .ok_or_else(not_found)?;
+            Ok("You have been unsubscribed from notification emails.")
+        }
+    }
+}
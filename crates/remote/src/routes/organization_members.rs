@@ -7,6 +7,7 @@ use axum::{
 };
 use chrono::{Duration, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use sqlx::PgPool;
 use tracing::warn;
 use utils::api::organizations::{
@@ -15,16 +16,22 @@ use utils::api::organizations::{
 };
 use uuid::Uuid;
 
-use super::error::{ErrorResponse, membership_error};
+use super::{
+    audit_events::record_event,
+    error::{ErrorResponse, membership_error},
+};
 use crate::{
     AppState,
-    auth::RequestContext,
+    auth::{OIDC_PROVIDER_NAME, RequestContext},
     db::{
+        audit_events::{AuditAction, AuditEventRepository},
         identity_errors::IdentityError,
         invitations::{Invitation, InvitationRepository},
         issues::IssueRepository,
-        organization_members::{self, MemberRole},
+        organization_members::{self, MemberRole, ProjectAccess},
+        organization_sso::OrganizationSsoRepository,
         organizations::OrganizationRepository,
+        project_members::ProjectMemberRepository,
         projects::ProjectRepository,
     },
 };
@@ -103,8 +110,15 @@ pub async fn create_invitation(
     let token = Uuid::new_v4().to_string();
     let expires_at = Utc::now() + Duration::days(7);
 
+    let mut tx = state
+        .pool
+        .begin()
+        .await
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
+
     let invitation = invitation_repo
         .create_invitation(
+            &mut tx,
             org_id,
             user.id,
             &payload.email,
@@ -121,6 +135,20 @@ pub async fn create_invitation(
             _ => ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"),
         })?;
 
+    record_event(
+        &mut tx,
+        org_id,
+        Some(user.id),
+        AuditAction::InvitationCreated,
+        Some(invitation.id),
+        json!({ "email": invitation.email, "role": invitation.role }),
+    )
+    .await?;
+
+    tx.commit()
+        .await
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
+
     let organization = org_repo.fetch_organization(org_id).await.map_err(|_| {
         ErrorResponse::new(
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -215,8 +243,14 @@ pub async fn revoke_invitation(
 
     ensure_admin_access(&state.pool, org_id, user.id).await?;
 
+    let mut tx = state
+        .pool
+        .begin()
+        .await
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
+
     invitation_repo
-        .revoke_invitation(org_id, payload.invitation_id, user.id)
+        .revoke_invitation(&mut tx, org_id, payload.invitation_id, user.id)
         .await
         .map_err(|e| match e {
             IdentityError::PermissionDenied => {
@@ -228,6 +262,20 @@ pub async fn revoke_invitation(
             _ => ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"),
         })?;
 
+    record_event(
+        &mut tx,
+        org_id,
+        Some(user.id),
+        AuditAction::InvitationRevoked,
+        Some(payload.invitation_id),
+        json!({}),
+    )
+    .await?;
+
+    tx.commit()
+        .await
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
+
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -239,16 +287,74 @@ pub async fn accept_invitation(
     let user = ctx.user;
     let invitation_repo = InvitationRepository::new(&state.pool);
 
-    let (org, role) = invitation_repo
-        .accept_invitation(&token, user.id)
+    // Joining an organization that requires SSO needs an SSO session, or the
+    // new member would keep working from a session the policy does not allow
+    if let Ok(invitation) = invitation_repo.get_invitation_by_token(&token).await {
+        let sso_required =
+            OrganizationSsoRepository::is_sso_required(&state.pool, invitation.organization_id)
+                .await
+                .map_err(|_| {
+                    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
+                })?;
+        if sso_required && ctx.session_provider.as_deref() != Some(OIDC_PROVIDER_NAME) {
+            return Err(ErrorResponse::new(
+                StatusCode::FORBIDDEN,
+                "Sign in with SSO to join this organization",
+            ));
+        }
+    }
+
+    let mut tx = state
+        .pool
+        .begin()
         .await
-        .map_err(|e| match e {
-            IdentityError::InvitationError(msg) => ErrorResponse::new(StatusCode::BAD_REQUEST, msg),
-            IdentityError::NotFound => {
-                ErrorResponse::new(StatusCode::NOT_FOUND, "Invitation not found")
-            }
-            _ => ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"),
-        })?;
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
+
+    let (org, role) = match invitation_repo
+        .accept_invitation(&mut tx, &token, user.id)
+        .await
+    {
+        Ok(accepted) => accepted,
+        Err(IdentityError::InvitationExpired) => {
+            // Keep the invitation marked as expired
+            tx.commit().await.map_err(|_| {
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
+            })?;
+            return Err(ErrorResponse::new(
+                StatusCode::BAD_REQUEST,
+                "Invitation has expired",
+            ));
+        }
+        Err(IdentityError::InvitationError(msg)) => {
+            return Err(ErrorResponse::new(StatusCode::BAD_REQUEST, msg));
+        }
+        Err(IdentityError::NotFound) => {
+            return Err(ErrorResponse::new(
+                StatusCode::NOT_FOUND,
+                "Invitation not found",
+            ));
+        }
+        Err(_) => {
+            return Err(ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Database error",
+            ));
+        }
+    };
+
+    record_event(
+        &mut tx,
+        org.id,
+        Some(user.id),
+        AuditAction::InvitationAccepted,
+        Some(user.id),
+        json!({ "role": role }),
+    )
+    .await?;
+
+    tx.commit()
+        .await
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
 
     Ok(Json(AcceptInvitationResponse {
         organization_id: org.id.to_string(),
@@ -380,6 +486,21 @@ pub async fn remove_member(
     .await
     .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
 
+    ProjectMemberRepository::remove_from_organization(&mut *tx, org_id, user_id)
+        .await
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
+
+    AuditEventRepository::record(
+        &mut *tx,
+        org_id,
+        Some(user.id),
+        AuditAction::MemberRemoved,
+        Some(user_id),
+        json!({ "role": target.role }),
+    )
+    .await
+    .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
+
     tx.commit()
         .await
         .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
@@ -394,7 +515,7 @@ pub async fn update_member_role(
     Json(payload): Json<UpdateMemberRoleRequest>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
     let user = ctx.user;
-    if user.id == user_id && payload.role == MemberRole::Member {
+    if user.id == user_id && payload.role != MemberRole::Admin {
         return Err(ErrorResponse::new(
             StatusCode::BAD_REQUEST,
             "Cannot demote yourself",
@@ -443,7 +564,7 @@ pub async fn update_member_role(
         }));
     }
 
-    if target.role == MemberRole::Admin && payload.role == MemberRole::Member {
+    if target.role == MemberRole::Admin && payload.role != MemberRole::Admin {
         let admin_ids = sqlx::query_scalar!(
             r#"
             SELECT user_id
@@ -479,6 +600,17 @@ pub async fn update_member_role(
     .await
     .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
 
+    AuditEventRepository::record(
+        &mut *tx,
+        org_id,
+        Some(user.id),
+        AuditAction::MemberRoleChanged,
+        Some(user_id),
+        json!({ "from": target.role, "to": payload.role }),
+    )
+    .await
+    .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
+
     tx.commit()
         .await
         .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
@@ -510,10 +642,53 @@ pub(crate) async fn ensure_admin_access(
         .map_err(|err| membership_error(err, "Admin access required"))
 }
 
+pub(crate) async fn ensure_can_create_project(
+    pool: &PgPool,
+    organization_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), ErrorResponse> {
+    organization_members::assert_can_create_project(pool, organization_id, user_id)
+        .await
+        .map_err(|err| membership_error(err, "Not allowed to create projects"))
+}
+
 pub(crate) async fn ensure_project_access(
     pool: &PgPool,
     user_id: Uuid,
     project_id: Uuid,
+) -> Result<Uuid, ErrorResponse> {
+    authorize_project(pool, user_id, project_id, ProjectAccess::Read).await
+}
+
+pub(crate) async fn ensure_project_write_access(
+    pool: &PgPool,
+    user_id: Uuid,
+    project_id: Uuid,
+) -> Result<Uuid, ErrorResponse> {
+    authorize_project(pool, user_id, project_id, ProjectAccess::Write).await
+}
+
+pub(crate) async fn ensure_issue_access(
+    pool: &PgPool,
+    user_id: Uuid,
+    issue_id: Uuid,
+) -> Result<Uuid, ErrorResponse> {
+    authorize_issue(pool, user_id, issue_id, ProjectAccess::Read).await
+}
+
+pub(crate) async fn ensure_issue_write_access(
+    pool: &PgPool,
+    user_id: Uuid,
+    issue_id: Uuid,
+) -> Result<Uuid, ErrorResponse> {
+    authorize_issue(pool, user_id, issue_id, ProjectAccess::Write).await
+}
+
+async fn authorize_project(
+    pool: &PgPool,
+    user_id: Uuid,
+    project_id: Uuid,
+    access: ProjectAccess,
 ) -> Result<Uuid, ErrorResponse> {
     let organization_id = ProjectRepository::organization_id(pool, project_id)
         .await
@@ -530,7 +705,7 @@ pub(crate) async fn ensure_project_access(
             ErrorResponse::new(StatusCode::NOT_FOUND, "project not found")
         })?;
 
-    organization_members::assert_membership(pool, organization_id, user_id)
+    organization_members::assert_project_access(pool, project_id, user_id, access)
         .await
         .map_err(|err| {
             if let IdentityError::Database(error) = &err {
@@ -543,6 +718,7 @@ pub(crate) async fn ensure_project_access(
             } else {
                 warn!(
                     ?err,
+                    ?access,
                     %organization_id,
                     %project_id,
                     %user_id,
@@ -555,10 +731,11 @@ pub(crate) async fn ensure_project_access(
     Ok(organization_id)
 }
 
-pub(crate) async fn ensure_issue_access(
+async fn authorize_issue(
     pool: &PgPool,
     user_id: Uuid,
     issue_id: Uuid,
+    access: ProjectAccess,
 ) -> Result<Uuid, ErrorResponse> {
     let organization_id = IssueRepository::organization_id(pool, issue_id)
         .await
@@ -575,7 +752,7 @@ pub(crate) async fn ensure_issue_access(
             ErrorResponse::new(StatusCode::NOT_FOUND, "issue not found")
         })?;
 
-    organization_members::assert_membership(pool, organization_id, user_id)
+    organization_members::assert_issue_access(pool, issue_id, user_id, access)
         .await
         .map_err(|err| {
             if let IdentityError::Database(error) = &err {
@@ -588,6 +765,7 @@ pub(crate) async fn ensure_issue_access(
             } else {
                 warn!(
                     ?err,
+                    ?access,
                     %organization_id,
                     %issue_id,
                     %user_id,
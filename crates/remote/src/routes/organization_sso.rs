@@ -0,0 +1,301 @@
+use axum::{
+    Json, Router,
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    routing::{delete, get, post},
+};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{error::ErrorResponse, organization_members::ensure_admin_access};
+use crate::{
+    AppState,
+    auth::{
+        OIDC_PROVIDER_NAME, RequestContext,
+        sso::{
+            lookup_txt_records, normalize_domain, verification_record_name,
+            verification_record_value,
+        },
+    },
+    db::{
+        organization_sso::{OrganizationDomain, OrganizationSsoError, OrganizationSsoRepository},
+        organizations::OrganizationRepository,
+    },
+};
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/organizations/{org_id}/sso",
+            get(get_sso_settings).put(update_sso_settings),
+        )
+        .route("/organizations/{org_id}/sso/domains", post(add_domain))
+        .route(
+            "/organizations/{org_id}/sso/domains/{domain}",
+            delete(delete_domain),
+        )
+        .route(
+            "/organizations/{org_id}/sso/domains/{domain}/verify",
+            post(verify_domain),
+        )
+}
+
+#[derive(Debug, Serialize)]
+pub struct SsoSettingsResponse {
+    /// Whether an OIDC provider is configured on this server
+    pub sso_available: bool,
+    pub sso_required: bool,
+    pub domains: Vec<OrganizationDomainResponse>,
+}
+
+/// A claimed domain along with the DNS TXT record that verifies it
+#[derive(Debug, Serialize)]
+pub struct OrganizationDomainResponse {
+    #[serde(flatten)]
+    pub domain: OrganizationDomain,
+    pub verification_record_name: String,
+    pub verification_record_value: String,
+}
+
+impl From<OrganizationDomain> for OrganizationDomainResponse {
+    fn from(domain: OrganizationDomain) -> Self {
+        Self {
+            verification_record_name: verification_record_name(&domain.domain),
+            verification_record_value: verification_record_value(&domain.verification_token),
+            domain,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateSsoSettingsRequest {
+    pub sso_required: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddDomainRequest {
+    pub domain: String,
+    #[serde(default = "default_auto_join")]
+    pub auto_join: bool,
+}
+
+fn default_auto_join() -> bool {
+    true
+}
+
+fn internal_error<E: std::fmt::Debug>(message: &'static str) -> impl FnOnce(E) -> ErrorResponse {
+    move |error| {
+        tracing::error!(?error, "{message}");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, message)
+    }
+}
+
+/// SSO applies to team organizations only; personal organizations have a
+/// single member who picks their own sign-in method
+async fn ensure_team_organization(state: &AppState, org_id: Uuid) -> Result<(), ErrorResponse> {
+    let organization = OrganizationRepository::new(state.pool())
+        .fetch_organization(org_id)
+        .await
+        .map_err(internal_error("failed to load organization"))?;
+    if organization.is_personal {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "SSO is not available for personal organizations",
+        ));
+    }
+    Ok(())
+}
+
+async fn load_settings(
+    state: &AppState,
+    org_id: Uuid,
+) -> Result<SsoSettingsResponse, ErrorResponse> {
+    let sso_required = OrganizationSsoRepository::is_sso_required(state.pool(), org_id)
+        .await
+        .map_err(internal_error("failed to load sso settings"))?;
+    let domains = OrganizationSsoRepository::list_domains(state.pool(), org_id)
+        .await
+        .map_err(internal_error("failed to load organization domains"))?;
+
+    Ok(SsoSettingsResponse {
+        sso_available: state.providers().get(OIDC_PROVIDER_NAME).is_some(),
+        sso_required,
+        domains: domains.into_iter().map(Into::into).collect(),
+    })
+}
+
+#[instrument(
+    name = "organization_sso.get_sso_settings",
+    skip(state, ctx),
+    fields(org_id = %org_id, user_id = %ctx.user.id)
+)]
+async fn get_sso_settings(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(org_id): Path<Uuid>,
+) -> Result<Json<SsoSettingsResponse>, ErrorResponse> {
+    ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
+
+    Ok(Json(load_settings(&state, org_id).await?))
+}
+
+#[instrument(
+    name = "organization_sso.update_sso_settings",
+    skip(state, ctx, payload),
+    fields(org_id = %org_id, user_id = %ctx.user.id, sso_required = payload.sso_required)
+)]
+async fn update_sso_settings(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(org_id): Path<Uuid>,
+    Json(payload): Json<UpdateSsoSettingsRequest>,
+) -> Result<Json<SsoSettingsResponse>, ErrorResponse> {
+    ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
+    ensure_team_organization(&state, org_id).await?;
+
+    if payload.sso_required {
+        if state.providers().get(OIDC_PROVIDER_NAME).is_none() {
+            return Err(ErrorResponse::new(
+                StatusCode::BAD_REQUEST,
+                "no SSO provider is configured on this server",
+            ));
+        }
+        // Enabling revokes every non-SSO session of the members, so make sure
+        // the admin is not about to lock themselves out
+        if ctx.session_provider.as_deref() != Some(OIDC_PROVIDER_NAME) {
+            return Err(ErrorResponse::new(
+                StatusCode::FORBIDDEN,
+                "sign in with SSO before requiring it",
+            ));
+        }
+    }
+
+    OrganizationSsoRepository::set_sso_required(
+        state.pool(),
+        org_id,
+        payload.sso_required,
+        OIDC_PROVIDER_NAME,
+    )
+    .await
+    .map_err(internal_error("failed to update sso settings"))?;
+
+    Ok(Json(load_settings(&state, org_id).await?))
+}
+
+#[instrument(
+    name = "organization_sso.add_domain",
+    skip(state, ctx, payload),
+    fields(org_id = %org_id, user_id = %ctx.user.id)
+)]
+async fn add_domain(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(org_id): Path<Uuid>,
+    Json(payload): Json<AddDomainRequest>,
+) -> Result<Json<OrganizationDomainResponse>, ErrorResponse> {
+    ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
+    ensure_team_organization(&state, org_id).await?;
+
+    let domain = normalize_domain(&payload.domain)
+        .ok_or_else(|| ErrorResponse::new(StatusCode::BAD_REQUEST, "invalid domain"))?;
+
+    let domain = OrganizationSsoRepository::add_domain(
+        state.pool(),
+        org_id,
+        &domain,
+        payload.auto_join,
+        ctx.user.id,
+    )
+    .await
+    .map_err(|error| match error {
+        OrganizationSsoError::DomainTaken => {
+            ErrorResponse::new(StatusCode::CONFLICT, "domain is already claimed")
+        }
+        error => internal_error("failed to add domain")(error),
+    })?;
+
+    Ok(Json(domain.into()))
+}
+
+/// Check the domain's verification TXT record and, if it holds the
+/// organization's token, mark the claim verified so auto-join applies to it
+#[instrument(
+    name = "organization_sso.verify_domain",
+    skip(state, ctx),
+    fields(org_id = %org_id, domain = %domain, user_id = %ctx.user.id)
+)]
+async fn verify_domain(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((org_id, domain)): Path<(Uuid, String)>,
+) -> Result<Json<OrganizationDomainResponse>, ErrorResponse> {
+    ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
+
+    let domain = normalize_domain(&domain)
+        .ok_or_else(|| ErrorResponse::new(StatusCode::BAD_REQUEST, "invalid domain"))?;
+    let claim = OrganizationSsoRepository::get_domain(state.pool(), org_id, &domain)
+        .await
+        .map_err(internal_error("failed to load domain"))?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "domain not found"))?;
+    if claim.verified_at.is_some() {
+        return Ok(Json(claim.into()));
+    }
+
+    let records = lookup_txt_records(
+        &state.http_client,
+        &state.config.dns_over_https_url,
+        &verification_record_name(&domain),
+    )
+    .await
+    .map_err(|error| {
+        tracing::warn!(?error, "failed to look up domain verification record");
+        ErrorResponse::new(StatusCode::BAD_GATEWAY, "failed to look up DNS records")
+    })?;
+    let expected = verification_record_value(&claim.verification_token);
+    if !records.iter().any(|record| record.trim() == expected) {
+        return Err(ErrorResponse::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "verification TXT record not found",
+        ));
+    }
+
+    let domain = OrganizationSsoRepository::mark_domain_verified(state.pool(), org_id, &domain)
+        .await
+        .map_err(|error| match error {
+            OrganizationSsoError::DomainTaken => ErrorResponse::new(
+                StatusCode::CONFLICT,
+                "domain is verified by another organization",
+            ),
+            error => internal_error("failed to verify domain")(error),
+        })?;
+
+    Ok(Json(domain.into()))
+}
+
+#[instrument(
+    name = "organization_sso.delete_domain",
+    skip(state, ctx),
+    fields(org_id = %org_id, domain = %domain, user_id = %ctx.user.id)
+)]
+async fn delete_domain(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((org_id, domain)): Path<(Uuid, String)>,
+) -> Result<StatusCode, ErrorResponse> {
+    ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
+
+    let domain = normalize_domain(&domain)
+        .ok_or_else(|| ErrorResponse::new(StatusCode::BAD_REQUEST, "invalid domain"))?;
+    let deleted = OrganizationSsoRepository::delete_domain(state.pool(), org_id, &domain)
+        .await
+        .map_err(internal_error("failed to delete domain"))?;
+    if !deleted {
+        return Err(ErrorResponse::new(
+            StatusCode::NOT_FOUND,
+            "domain not found",
+        ));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
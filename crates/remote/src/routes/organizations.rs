@@ -125,6 +125,8 @@ pub async fn get_organization(
     let user_role = match role {
         MemberRole::Admin => "ADMIN",
         MemberRole::Member => "MEMBER",
+        MemberRole::Viewer => "VIEWER",
+        MemberRole::Guest => "GUEST",
     }
     .to_string();
 
@@ -8,11 +8,17 @@ use serde::{Deserialize, Serialize};
 use tracing::instrument;
 use uuid::Uuid;
 
-use super::{error::ErrorResponse, organization_members::ensure_project_access};
+use super::{
+    error::ErrorResponse,
+    organization_members::{ensure_project_access, ensure_project_write_access},
+};
 use crate::{
     AppState,
     auth::RequestContext,
-    db::project_statuses::{ProjectStatus, ProjectStatusRepository},
+    db::{
+        project_statuses::{ProjectStatus, ProjectStatusRepository},
+        types::StatusCategory,
+    },
 };
 
 #[derive(Debug, Serialize)]
@@ -25,6 +31,8 @@ pub struct CreateProjectStatusRequest {
     pub name: String,
     pub color: String,
     pub sort_order: i32,
+    /// Defaults to unstarted
+    pub category: Option<StatusCategory>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -32,6 +40,8 @@ pub struct UpdateProjectStatusRequest {
     pub name: String,
     pub color: String,
     pub sort_order: i32,
+    /// Omitted to keep the current category
+    pub category: Option<StatusCategory>,
 }
 
 pub fn router() -> Router<AppState> {
@@ -82,7 +92,7 @@ async fn create_status(
     Path(project_id): Path<Uuid>,
     Json(payload): Json<CreateProjectStatusRequest>,
 ) -> Result<Json<ProjectStatus>, ErrorResponse> {
-    ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+    ensure_project_write_access(state.pool(), ctx.user.id, project_id).await?;
 
     let status = ProjectStatusRepository::create(
         state.pool(),
@@ -90,6 +100,7 @@ async fn create_status(
         payload.name,
         payload.color,
         payload.sort_order,
+        payload.category.unwrap_or(StatusCategory::Unstarted),
     )
     .await
     .map_err(|error| {
@@ -122,7 +133,7 @@ async fn update_status(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "project status not found"))?;
 
-    ensure_project_access(state.pool(), ctx.user.id, status.project_id).await?;
+    ensure_project_write_access(state.pool(), ctx.user.id, status.project_id).await?;
 
     let updated_status = ProjectStatusRepository::update(
         state.pool(),
@@ -130,6 +141,7 @@ async fn update_status(
         payload.name,
         payload.color,
         payload.sort_order,
+        payload.category.unwrap_or(status.category),
     )
     .await
     .map_err(|error| {
@@ -161,7 +173,7 @@ async fn delete_status(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "project status not found"))?;
 
-    ensure_project_access(state.pool(), ctx.user.id, status.project_id).await?;
+    ensure_project_write_access(state.pool(), ctx.user.id, status.project_id).await?;
 
     ProjectStatusRepository::delete(state.pool(), status_id)
         .await
@@ -2,17 +2,27 @@ use axum::{
     Json, Router,
     extract::{Extension, Path, Query, State},
     http::StatusCode,
-    routing::get,
+    routing::{delete, get},
 };
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use tracing::instrument;
 use uuid::Uuid;
 
-use super::{error::ErrorResponse, organization_members::ensure_member_access};
+use super::{
+    error::ErrorResponse,
+    organization_members::{
+        ensure_admin_access, ensure_can_create_project, ensure_member_access,
+        ensure_project_access, ensure_project_write_access,
+    },
+};
 use crate::{
     AppState,
     auth::RequestContext,
     db::{
+        audit_events::{AuditAction, AuditEventRepository},
+        organization_members,
+        project_members::{ProjectMember, ProjectMemberRepository},
         project_statuses::ProjectStatusRepository,
         projects::{Project, ProjectRepository},
         tags::TagRepository,
@@ -24,6 +34,11 @@ pub struct ListProjectsResponse {
     pub projects: Vec<Project>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct ListProjectMembersResponse {
+    pub members: Vec<ProjectMember>,
+}
+
 #[derive(Debug, Deserialize)]
 struct ProjectsQuery {
     organization_id: Uuid,
@@ -34,12 +49,22 @@ struct CreateProjectRequest {
     organization_id: Uuid,
     name: String,
     color: String,
+    #[serde(default)]
+    is_private: bool,
 }
 
 #[derive(Debug, Deserialize)]
 struct UpdateProjectRequest {
     name: String,
     color: String,
+    /// Only organization admins may change a project's visibility
+    #[serde(default)]
+    is_private: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AddProjectMemberRequest {
+    user_id: Uuid,
 }
 
 pub fn router() -> Router<AppState> {
@@ -51,6 +76,14 @@ pub fn router() -> Router<AppState> {
                 .patch(update_project)
                 .delete(delete_project),
         )
+        .route(
+            "/projects/{project_id}/members",
+            get(list_project_members).post(add_project_member),
+        )
+        .route(
+            "/projects/{project_id}/members/{user_id}",
+            delete(remove_project_member),
+        )
 }
 
 #[instrument(
@@ -66,7 +99,7 @@ async fn list_projects(
     let target_org = params.organization_id;
     ensure_member_access(state.pool(), target_org, ctx.user.id).await?;
 
-    let projects = ProjectRepository::list_by_organization(state.pool(), target_org)
+    let projects = ProjectRepository::list_visible_to_user(state.pool(), target_org, ctx.user.id)
         .await
         .map_err(|error| {
             tracing::error!(?error, org_id = %target_org, "failed to list remote projects");
@@ -94,7 +127,7 @@ async fn get_project(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "project not found"))?;
 
-    ensure_member_access(state.pool(), project.organization_id, ctx.user.id).await?;
+    ensure_project_access(state.pool(), ctx.user.id, project.id).await?;
 
     Ok(Json(project))
 }
@@ -113,22 +146,35 @@ async fn create_project(
         organization_id,
         name,
         color,
+        is_private,
     } = payload;
 
-    ensure_member_access(state.pool(), organization_id, ctx.user.id).await?;
+    ensure_can_create_project(state.pool(), organization_id, ctx.user.id).await?;
 
     let mut tx = state.pool().begin().await.map_err(|error| {
         tracing::error!(?error, "failed to begin transaction");
         ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
     })?;
 
-    let project = ProjectRepository::create(&mut *tx, organization_id, name, color)
+    let project = ProjectRepository::create(&mut *tx, organization_id, name, color, is_private)
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to create remote project");
             ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
         })?;
 
+    // Keep a private project visible to the member who created it
+    if is_private
+        && let Err(error) =
+            ProjectMemberRepository::add(&mut *tx, project.id, ctx.user.id, Some(ctx.user.id)).await
+    {
+        tracing::error!(?error, project_id = %project.id, "failed to add project creator");
+        return Err(ErrorResponse::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "internal server error",
+        ));
+    }
+
     if let Err(error) = TagRepository::create_default_tags(&mut *tx, project.id).await {
         tracing::error!(?error, project_id = %project.id, "failed to create default tags");
         return Err(ErrorResponse::new(
@@ -173,15 +219,55 @@ async fn update_project(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "project not found"))?;
 
-    ensure_member_access(state.pool(), existing.organization_id, ctx.user.id).await?;
+    ensure_project_write_access(state.pool(), ctx.user.id, project_id).await?;
+
+    let visibility_change = payload
+        .is_private
+        .filter(|is_private| *is_private != existing.is_private);
+    if visibility_change.is_some() {
+        ensure_admin_access(state.pool(), existing.organization_id, ctx.user.id).await?;
+    }
 
-    let project = ProjectRepository::update(state.pool(), project_id, payload.name, payload.color)
+    let mut tx = state.pool().begin().await.map_err(|error| {
+        tracing::error!(?error, "failed to begin transaction");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+
+    let mut project = ProjectRepository::update(&mut *tx, project_id, payload.name, payload.color)
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to update remote project");
             ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
         })?;
 
+    if let Some(is_private) = visibility_change {
+        project = ProjectRepository::set_private(&mut *tx, project_id, is_private)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, "failed to change project visibility");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            })?;
+
+        AuditEventRepository::record(
+            &mut *tx,
+            existing.organization_id,
+            Some(ctx.user.id),
+            AuditAction::ProjectVisibilityChanged,
+            Some(project_id),
+            json!({ "name": project.name, "is_private": is_private }),
+        )
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to record audit event");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+    }
+
+    tx.commit().await.map_err(|error| {
+        tracing::error!(?error, "failed to commit transaction");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+
     Ok(Json(project))
 }
 
@@ -203,14 +289,186 @@ async fn delete_project(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "project not found"))?;
 
-    ensure_member_access(state.pool(), record.organization_id, ctx.user.id).await?;
+    ensure_project_write_access(state.pool(), ctx.user.id, project_id).await?;
 
-    ProjectRepository::delete(state.pool(), project_id)
+    let mut tx = state.pool().begin().await.map_err(|error| {
+        tracing::error!(?error, "failed to begin transaction");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+
+    ProjectRepository::delete(&mut *tx, project_id)
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to delete remote project");
             ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
         })?;
 
+    AuditEventRepository::record(
+        &mut *tx,
+        record.organization_id,
+        Some(ctx.user.id),
+        AuditAction::ProjectDeleted,
+        Some(project_id),
+        json!({ "name": record.name }),
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, "failed to record audit event");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+
+    tx.commit().await.map_err(|error| {
+        tracing::error!(?error, "failed to commit transaction");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(
+    name = "projects.list_project_members",
+    skip(state, ctx),
+    fields(user_id = %ctx.user.id, project_id = %project_id)
+)]
+async fn list_project_members(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<ListProjectMembersResponse>, ErrorResponse> {
+    ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+
+    let members = ProjectMemberRepository::list_by_project(state.pool(), project_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %project_id, "failed to list project members");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+
+    Ok(Json(ListProjectMembersResponse { members }))
+}
+
+#[instrument(
+    name = "projects.add_project_member",
+    skip(state, ctx, payload),
+    fields(user_id = %ctx.user.id, project_id = %project_id, member_id = %payload.user_id)
+)]
+async fn add_project_member(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<AddProjectMemberRequest>,
+) -> Result<Json<ProjectMember>, ErrorResponse> {
+    let project = ProjectRepository::find_by_id(state.pool(), project_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %project_id, "failed to load project");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load project")
+        })?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "project not found"))?;
+
+    ensure_admin_access(state.pool(), project.organization_id, ctx.user.id).await?;
+
+    let is_member =
+        organization_members::is_member(state.pool(), project.organization_id, payload.user_id)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, "failed to check organization membership");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            })?;
+    if !is_member {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "user is not a member of this organization",
+        ));
+    }
+
+    let mut tx = state.pool().begin().await.map_err(|error| {
+        tracing::error!(?error, "failed to begin transaction");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+
+    let member =
+        ProjectMemberRepository::add(&mut *tx, project_id, payload.user_id, Some(ctx.user.id))
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, "failed to add project member");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            })?;
+
+    AuditEventRepository::record(
+        &mut *tx,
+        project.organization_id,
+        Some(ctx.user.id),
+        AuditAction::ProjectMemberAdded,
+        Some(payload.user_id),
+        json!({ "project_id": project_id, "project_name": project.name }),
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, "failed to record audit event");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+
+    tx.commit().await.map_err(|error| {
+        tracing::error!(?error, "failed to commit transaction");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+
+    Ok(Json(member))
+}
+
+#[instrument(
+    name = "projects.remove_project_member",
+    skip(state, ctx),
+    fields(user_id = %ctx.user.id, project_id = %project_id, member_id = %member_id)
+)]
+async fn remove_project_member(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((project_id, member_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, ErrorResponse> {
+    let project = ProjectRepository::find_by_id(state.pool(), project_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %project_id, "failed to load project");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load project")
+        })?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "project not found"))?;
+
+    ensure_admin_access(state.pool(), project.organization_id, ctx.user.id).await?;
+
+    let mut tx = state.pool().begin().await.map_err(|error| {
+        tracing::error!(?error, "failed to begin transaction");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+
+    let removed = ProjectMemberRepository::remove(&mut *tx, project_id, member_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to remove project member");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+
+    if removed {
+        AuditEventRepository::record(
+            &mut *tx,
+            project.organization_id,
+            Some(ctx.user.id),
+            AuditAction::ProjectMemberRemoved,
+            Some(member_id),
+            json!({ "project_id": project_id, "project_name": project.name }),
+        )
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to record audit event");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+    }
+
+    tx.commit().await.map_err(|error| {
+        tracing::error!(?error, "failed to commit transaction");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+
     Ok(StatusCode::NO_CONTENT)
 }
@@ -8,7 +8,10 @@ use serde::{Deserialize, Serialize};
 use tracing::instrument;
 use uuid::Uuid;
 
-use super::{error::ErrorResponse, organization_members::ensure_project_access};
+use super::{
+    error::ErrorResponse,
+    organization_members::{ensure_project_access, ensure_project_write_access},
+};
 use crate::{
     AppState,
     auth::RequestContext,
@@ -74,7 +77,7 @@ async fn create_tag(
     Path(project_id): Path<Uuid>,
     Json(payload): Json<CreateTagRequest>,
 ) -> Result<Json<Tag>, ErrorResponse> {
-    ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+    ensure_project_write_access(state.pool(), ctx.user.id, project_id).await?;
 
     let tag = TagRepository::create(state.pool(), project_id, payload.name, payload.color)
         .await
@@ -105,7 +108,7 @@ async fn update_tag(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "tag not found"))?;
 
-    ensure_project_access(state.pool(), ctx.user.id, tag.project_id).await?;
+    ensure_project_write_access(state.pool(), ctx.user.id, tag.project_id).await?;
 
     let updated_tag = TagRepository::update(state.pool(), tag_id, payload.name, payload.color)
         .await
@@ -135,7 +138,7 @@ async fn delete_tag(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "tag not found"))?;
 
-    ensure_project_access(state.pool(), ctx.user.id, tag.project_id).await?;
+    ensure_project_write_access(state.pool(), ctx.user.id, tag.project_id).await?;
 
     TagRepository::delete(state.pool(), tag_id)
         .await
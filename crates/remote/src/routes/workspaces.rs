@@ -0,0 +1,151 @@
+use axum::{
+    Json, Router,
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    routing::put,
+};
+use tracing::instrument;
+use utils::api::workspaces::{UpsertWorkspaceRequest, UpsertWorkspaceResponse};
+use uuid::Uuid;
+
+use super::{error::ErrorResponse, organization_members::ensure_project_write_access};
+use crate::{
+    AppState,
+    auth::RequestContext,
+    db::{
+        issues::IssueRepository,
+        workspaces::{WorkspacePrRepository, WorkspaceRepoRepository, WorkspaceRepository},
+    },
+};
+
+pub fn router() -> Router<AppState> {
+    Router::new().route(
+        "/workspaces/{local_workspace_id}",
+        put(upsert_workspace).delete(delete_workspace),
+    )
+}
+
+fn internal_error<E: std::fmt::Debug>(message: &'static str) -> impl FnOnce(E) -> ErrorResponse {
+    move |error| {
+        tracing::error!(?error, "{message}");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, message)
+    }
+}
+
+/// Store the latest snapshot of one of the caller's local workspaces
+#[instrument(
+    name = "workspaces.upsert_workspace",
+    skip(state, ctx, payload),
+    fields(
+        local_workspace_id = %local_workspace_id,
+        project_id = %payload.project_id,
+        user_id = %ctx.user.id
+    )
+)]
+async fn upsert_workspace(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(local_workspace_id): Path<Uuid>,
+    Json(payload): Json<UpsertWorkspaceRequest>,
+) -> Result<Json<UpsertWorkspaceResponse>, ErrorResponse> {
+    ensure_project_write_access(state.pool(), ctx.user.id, payload.project_id).await?;
+
+    if let Some(issue_id) = payload.issue_id {
+        let issue = IssueRepository::find_by_id(state.pool(), issue_id)
+            .await
+            .map_err(internal_error("failed to load issue"))?;
+        if issue.is_none_or(|issue| issue.project_id != payload.project_id) {
+            return Err(ErrorResponse::new(
+                StatusCode::BAD_REQUEST,
+                "issue does not belong to this project",
+            ));
+        }
+    }
+
+    let mut tx = state
+        .pool()
+        .begin()
+        .await
+        .map_err(internal_error("failed to begin transaction"))?;
+
+    let workspace = WorkspaceRepository::upsert(
+        &mut *tx,
+        ctx.user.id,
+        local_workspace_id,
+        payload.project_id,
+        payload.issue_id,
+        payload.archived,
+        payload.files_changed,
+        payload.lines_added,
+        payload.lines_removed,
+    )
+    .await
+    .map_err(internal_error("failed to save workspace"))?;
+
+    for repo in &payload.repos {
+        let workspace_repo =
+            WorkspaceRepoRepository::upsert(&mut *tx, workspace.id, &repo.repo_name)
+                .await
+                .map_err(internal_error("failed to save workspace repo"))?;
+        match &repo.pr {
+            Some(pr) => {
+                WorkspacePrRepository::upsert(
+                    &mut *tx,
+                    workspace_repo.id,
+                    &pr.pr_url,
+                    pr.pr_number,
+                    pr.pr_status,
+                    pr.merged_at,
+                    pr.closed_at,
+                )
+                .await
+                .map_err(internal_error("failed to save workspace pull request"))?;
+            }
+            None => {
+                WorkspacePrRepository::delete_by_workspace_repo(&mut *tx, workspace_repo.id)
+                    .await
+                    .map_err(internal_error("failed to save workspace pull request"))?;
+            }
+        }
+    }
+
+    let repo_names: Vec<String> = payload
+        .repos
+        .iter()
+        .map(|repo| repo.repo_name.clone())
+        .collect();
+    WorkspaceRepoRepository::delete_except(&mut *tx, workspace.id, &repo_names)
+        .await
+        .map_err(internal_error("failed to save workspace repos"))?;
+
+    tx.commit()
+        .await
+        .map_err(internal_error("failed to commit transaction"))?;
+
+    Ok(Json(UpsertWorkspaceResponse { id: workspace.id }))
+}
+
+#[instrument(
+    name = "workspaces.delete_workspace",
+    skip(state, ctx),
+    fields(local_workspace_id = %local_workspace_id, user_id = %ctx.user.id)
+)]
+async fn delete_workspace(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(local_workspace_id): Path<Uuid>,
+) -> Result<StatusCode, ErrorResponse> {
+    let workspace =
+        WorkspaceRepository::find_by_local_id(state.pool(), ctx.user.id, local_workspace_id)
+            .await
+            .map_err(internal_error("failed to load workspace"))?;
+
+    // Deleting is idempotent so a client can retry after a lost response
+    if let Some(workspace) = workspace {
+        WorkspaceRepository::delete(state.pool(), workspace.id)
+            .await
+            .map_err(internal_error("failed to delete workspace"))?;
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -4,7 +4,7 @@ use ts_rs::TS;
 
 use crate::db::{
     issue_assignees::IssueAssignee, issue_comment_reactions::IssueCommentReaction,
-    issue_comments::IssueComment, issue_dependencies::IssueDependency,
+    issue_comments::IssueComment, issue_dependencies::IssueDependency, issue_events::IssueEvent,
     issue_followers::IssueFollower, issue_tags::IssueTag, issues::Issue,
     notifications::Notification, project_statuses::ProjectStatus, projects::Project, tags::Tag,
     workspaces::Workspace,
@@ -96,14 +96,33 @@ macro_rules! define_shape {
 }
 
 // Organization-scoped shapes
+//
+// Which projects a user may sync depends on their role, so the proxy picks
+// one of these three. They share a URL and only `PROJECTS` is exported.
 define_shape!(
     PROJECTS, Project,
     table: "projects",
+    where_clause: r#""organization_id" = $1 AND ("is_private" = false OR "id" IN (SELECT project_id FROM project_members WHERE "user_id" = $2))"#,
+    url: "/shape/projects",
+    params: ["organization_id", "user_id"]
+);
+
+define_shape!(
+    ADMIN_PROJECTS, Project,
+    table: "projects",
     where_clause: r#""organization_id" = $1"#,
     url: "/shape/projects",
     params: ["organization_id"]
 );
 
+define_shape!(
+    GUEST_PROJECTS, Project,
+    table: "projects",
+    where_clause: r#""organization_id" = $1 AND "id" IN (SELECT project_id FROM project_members WHERE "user_id" = $2)"#,
+    url: "/shape/projects",
+    params: ["organization_id", "user_id"]
+);
+
 define_shape!(
     NOTIFICATIONS, Notification,
     table: "notifications",
@@ -194,6 +213,14 @@ define_shape!(
     params: ["issue_id"]
 );
 
+define_shape!(
+    ISSUE_EVENTS, IssueEvent,
+    table: "issue_events",
+    where_clause: r#""issue_id" = $1"#,
+    url: "/shape/issue/{issue_id}/events",
+    params: ["issue_id"]
+);
+
 /// All shape definitions for export - uses trait objects for heterogeneous collection
 pub fn all_shapes() -> Vec<&'static dyn ShapeExport> {
     vec![
@@ -209,5 +236,6 @@ pub fn all_shapes() -> Vec<&'static dyn ShapeExport> {
         &ISSUE_DEPENDENCIES,
         &ISSUE_COMMENTS,
         &ISSUE_COMMENT_REACTIONS,
+        &ISSUE_EVENTS,
     ]
 }
@@ -20,6 +20,9 @@ fn generate_types_content() -> String {
         db::models::repo::UpdateRepo::decl(),
         db::models::project_repo::ProjectRepo::decl(),
         db::models::project_repo::CreateProjectRepo::decl(),
+        db::models::project_secret::ProjectSecret::decl(),
+        db::models::project_secret::CreateProjectSecret::decl(),
+        db::models::project_secret::UpdateProjectSecret::decl(),
         db::models::workspace_repo::WorkspaceRepo::decl(),
         db::models::workspace_repo::CreateWorkspaceRepo::decl(),
         db::models::workspace_repo::RepoWithTargetBranch::decl(),
@@ -46,19 +49,40 @@ fn generate_types_content() -> String {
         db::models::image::CreateImage::decl(),
         db::models::workspace::Workspace::decl(),
         db::models::workspace::WorkspaceWithStatus::decl(),
+        db::models::workspace_disk_usage::WorkspaceDiskUsage::decl(),
+        db::models::workspace_disk_usage::WorkspaceDiskUsageEntry::decl(),
         db::models::session::Session::decl(),
         db::models::execution_process::ExecutionProcess::decl(),
         db::models::execution_process::ExecutionProcessStatus::decl(),
         db::models::execution_process::ExecutionProcessRunReason::decl(),
         db::models::execution_process_repo_state::ExecutionProcessRepoState::decl(),
+        db::models::execution_process_verification::ExecutionProcessVerification::decl(),
+        db::models::fan_out::FanOutGroup::decl(),
+        db::models::fan_out::FanOutAttempt::decl(),
         db::models::merge::Merge::decl(),
         db::models::merge::DirectMerge::decl(),
         db::models::merge::PrMerge::decl(),
         db::models::merge::MergeStatus::decl(),
         db::models::merge::PullRequestInfo::decl(),
+        db::models::pr_comment_watch::PrCommentWatch::decl(),
+        db::models::pr_comment_watch::PrCommentWatchMode::decl(),
+        db::models::pr_comment_watch::PrCommentFollowUp::decl(),
+        db::models::pr_comment_watch::PrCommentFollowUpStatus::decl(),
+        db::models::task_schedule::TaskScheduleRepo::decl(),
+        db::models::task_schedule::TaskSchedule::decl(),
+        db::models::task_schedule::CreateTaskSchedule::decl(),
+        db::models::task_schedule::UpdateTaskSchedule::decl(),
+        db::models::task_schedule::TaskScheduleRunStatus::decl(),
+        db::models::task_schedule::TaskScheduleRun::decl(),
+        db::models::github_issue_sync::GitHubIssueSyncSettings::decl(),
+        db::models::github_issue_sync::UpsertGitHubIssueSyncSettings::decl(),
+        db::models::github_issue_sync::TaskGitHubIssue::decl(),
+        db::models::github_issue_sync::GitHubIssueSyncDirection::decl(),
+        db::models::github_issue_sync::GitHubIssueSyncEvent::decl(),
         utils::approvals::ApprovalStatus::decl(),
         utils::approvals::CreateApprovalRequest::decl(),
         utils::approvals::ApprovalResponse::decl(),
+        services::services::approvals::PendingApprovalInfo::decl(),
         utils::diff::Diff::decl(),
         utils::diff::DiffChangeKind::decl(),
         utils::response::ApiResponse::<()>::decl(),
@@ -106,6 +130,8 @@ fn generate_types_content() -> String {
         server::routes::config::CheckAgentAvailabilityQuery::decl(),
         server::routes::oauth::CurrentUserResponse::decl(),
         server::routes::sessions::CreateFollowUpAttempt::decl(),
+        server::routes::sessions::SessionConversationEntry::decl(),
+        server::routes::sessions::SessionConversationPage::decl(),
         server::routes::task_attempts::ChangeTargetBranchRequest::decl(),
         server::routes::task_attempts::ChangeTargetBranchResponse::decl(),
         server::routes::task_attempts::MergeTaskAttemptRequest::decl(),
@@ -117,6 +143,7 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::OpenEditorRequest::decl(),
         server::routes::task_attempts::OpenEditorResponse::decl(),
         server::routes::tasks::CreateAndStartTaskRequest::decl(),
+        server::routes::remote_issues::StartRemoteIssueRequest::decl(),
         server::routes::task_attempts::pr::CreatePrApiRequest::decl(),
         server::routes::images::ImageResponse::decl(),
         server::routes::images::ImageMetadata::decl(),
@@ -137,14 +164,30 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::pr::PrCommentsResponse::decl(),
         server::routes::task_attempts::pr::GetPrCommentsError::decl(),
         server::routes::task_attempts::pr::GetPrCommentsQuery::decl(),
+        server::routes::task_attempts::pr::UpdatePrCommentWatchRequest::decl(),
+        server::routes::task_attempts::pr::PrCommentFollowUpActionRequest::decl(),
         services::services::git_host::UnifiedPrComment::decl(),
         services::services::git_host::ProviderKind::decl(),
+        services::services::git_host::GitHubIssueFilter::decl(),
+        services::services::task_import::TaskImportItem::decl(),
+        services::services::task_import::TaskImportResult::decl(),
         server::routes::task_attempts::RepoBranchStatus::decl(),
         server::routes::task_attempts::UpdateWorkspace::decl(),
         server::routes::task_attempts::workspace_summary::WorkspaceSummaryRequest::decl(),
         server::routes::task_attempts::workspace_summary::WorkspaceSummary::decl(),
         server::routes::task_attempts::workspace_summary::WorkspaceSummaryResponse::decl(),
         server::routes::task_attempts::workspace_summary::DiffStats::decl(),
+        server::routes::task_attempts::fan_out::CreateFanOutBody::decl(),
+        server::routes::task_attempts::fan_out::FanOutGroupWithAttempts::decl(),
+        server::routes::task_attempts::fan_out::FanOutAttemptComparison::decl(),
+        server::routes::task_attempts::fan_out::FanOutComparison::decl(),
+        server::routes::task_attempts::fan_out::PromoteFanOutWinnerRequest::decl(),
+        server::routes::task_attempts::fan_out::PromoteFanOutWinnerResponse::decl(),
+        server::routes::task_attempts::fork::ForkWorkspaceRequest::decl(),
+        server::routes::task_import::TaskImportSource::decl(),
+        server::routes::task_import::TaskImportPreviewRequest::decl(),
+        server::routes::task_import::TaskImportRequest::decl(),
+        server::routes::github_issue_sync::LinkTaskGitHubIssueRequest::decl(),
         services::services::filesystem::DirectoryEntry::decl(),
         services::services::filesystem::DirectoryListResponse::decl(),
         services::services::file_search::SearchMode::decl(),
@@ -158,9 +201,15 @@ fn generate_types_content() -> String {
         services::services::config::SoundFile::decl(),
         services::services::config::UiLanguage::decl(),
         services::services::config::ShowcaseState::decl(),
+        services::services::config::WorkspaceDiskPolicy::decl(),
         services::services::git::GitBranch::decl(),
         services::services::queued_message::QueuedMessage::decl(),
         services::services::queued_message::QueueStatus::decl(),
+        services::services::preview::WorkspacePreview::decl(),
+        services::services::disk_usage::DiskEvictionAction::decl(),
+        services::services::disk_usage::DiskEviction::decl(),
+        services::services::disk_usage::ProjectDiskUsage::decl(),
+        services::services::disk_usage::DiskUsageSummary::decl(),
         services::services::git::ConflictOp::decl(),
         executors::actions::ExecutorAction::decl(),
         executors::mcp_config::McpConfig::decl(),
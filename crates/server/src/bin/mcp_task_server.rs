@@ -62,15 +62,17 @@ fn main() -> anyhow::Result<()> {
                 url
             };
 
-            let service = TaskServer::new(&base_url)
-                .init()
-                .await
-                .serve(stdio())
-                .await
-                .map_err(|e| {
-                    tracing::error!("serving error: {:?}", e);
-                    e
-                })?;
+            // Launched by a workspace's coding agent, so act only on that workspace
+            let server = TaskServer::new(&base_url);
+            let server = match std::env::var("VK_MCP_TOKEN") {
+                Ok(token) => server.init_with_token(&token).await,
+                Err(_) => server.init().await,
+            };
+
+            let service = server.serve(stdio()).await.map_err(|e| {
+                tracing::error!("serving error: {:?}", e);
+                e
+            })?;
 
             service.waiting().await?;
             Ok(())
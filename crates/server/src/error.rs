@@ -18,10 +18,13 @@ use services::services::{
     container::ContainerError,
     git::GitServiceError,
     git_host::GitHostError,
+    github_issue_sync::GitHubIssueSyncError,
     image::ImageError,
     project::ProjectServiceError,
     remote_client::RemoteClientError,
     repo::RepoError as RepoServiceError,
+    secrets::SecretsError,
+    task_import::TaskImportError,
     worktree_manager::WorktreeError,
 };
 use thiserror::Error;
@@ -72,6 +75,8 @@ pub enum ApiError {
     Unauthorized,
     #[error("Bad request: {0}")]
     BadRequest(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
     #[error("Conflict: {0}")]
     Conflict(String),
     #[error("Forbidden: {0}")]
@@ -180,6 +185,7 @@ impl IntoResponse for ApiError {
             },
             ApiError::Unauthorized => (StatusCode::UNAUTHORIZED, "Unauthorized"),
             ApiError::BadRequest(_) => (StatusCode::BAD_REQUEST, "BadRequest"),
+            ApiError::NotFound(_) => (StatusCode::NOT_FOUND, "NotFound"),
             ApiError::Conflict(_) => (StatusCode::CONFLICT, "ConflictError"),
             ApiError::Forbidden(_) => (StatusCode::FORBIDDEN, "ForbiddenError"),
             ApiError::Pty(err) => match err {
@@ -261,6 +267,7 @@ impl IntoResponse for ApiError {
             },
             ApiError::Unauthorized => "Unauthorized. Please sign in again.".to_string(),
             ApiError::BadRequest(msg) => msg.clone(),
+            ApiError::NotFound(msg) => msg.clone(),
             ApiError::Conflict(msg) => msg.clone(),
             ApiError::Forbidden(msg) => msg.clone(),
             _ => format!("{}: {}", error_type, self),
@@ -345,3 +352,40 @@ impl From<ProjectRepoError> for ApiError {
         }
     }
 }
+
+impl From<TaskImportError> for ApiError {
+    fn from(err: TaskImportError) -> Self {
+        match err {
+            TaskImportError::Csv(msg) => ApiError::BadRequest(format!("Invalid CSV: {msg}")),
+            TaskImportError::GitHost(host_err) => ApiError::GitHost(host_err),
+            TaskImportError::Database(db_err) => ApiError::Database(db_err),
+        }
+    }
+}
+
+impl From<GitHubIssueSyncError> for ApiError {
+    fn from(err: GitHubIssueSyncError) -> Self {
+        match err {
+            GitHubIssueSyncError::GitHost(host_err) => ApiError::GitHost(host_err),
+            GitHubIssueSyncError::GitService(git_err) => ApiError::GitService(git_err),
+            GitHubIssueSyncError::Workspace(ws_err) => ApiError::Workspace(ws_err),
+            GitHubIssueSyncError::Database(db_err) => ApiError::Database(db_err),
+            GitHubIssueSyncError::RepoNotFound(_) | GitHubIssueSyncError::NotGitHub => {
+                ApiError::BadRequest(err.to_string())
+            }
+        }
+    }
+}
+
+impl From<SecretsError> for ApiError {
+    fn from(err: SecretsError) -> Self {
+        match err {
+            SecretsError::Database(db_err) => ApiError::Database(db_err),
+            SecretsError::Io(io_err) => ApiError::Io(io_err),
+            SecretsError::Crypto => ApiError::Deployment(DeploymentError::Secrets(err)),
+            SecretsError::InvalidName(_) | SecretsError::NoRunReasons => {
+                ApiError::BadRequest(err.to_string())
+            }
+        }
+    }
+}
@@ -68,6 +68,9 @@ async fn main() -> Result<(), VibeKanbanError> {
         .await
         .map_err(DeploymentError::from)?;
     deployment.spawn_pr_monitor_service().await;
+    deployment.spawn_github_issue_sync_service().await;
+    deployment.spawn_task_scheduler().await;
+    deployment.spawn_workspace_publisher().await;
     deployment
         .track_if_analytics_allowed("session_start", serde_json::json!({}))
         .await;
@@ -101,7 +104,9 @@ async fn main() -> Result<(), VibeKanbanError> {
 
     let host = std::env::var("HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
     let listener = tokio::net::TcpListener::bind(format!("{host}:{port}")).await?;
-    let actual_port = listener.local_addr()?.port(); // get → 53427 (example)
+    let local_addr = listener.local_addr()?;
+    let actual_port = local_addr.port(); // get → 53427 (example)
+    deployment.mcp_tokens().set_server_addr(local_addr);
 
     // Write port file for discovery if prod, warn on fail
     if let Err(e) = write_port_file(actual_port).await {
@@ -1,13 +1,20 @@
 use std::{future::Future, str::FromStr};
 
 use db::models::{
+    execution_process::ExecutionProcess,
+    merge::Merge,
     project::Project,
     repo::Repo,
+    session::Session,
     tag::Tag,
     task::{CreateTask, Task, TaskStatus, TaskWithAttemptStatus, UpdateTask},
     workspace::{Workspace, WorkspaceContext},
 };
-use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
+use executors::{
+    executors::BaseCodingAgent,
+    logs::{NormalizedEntryType, ToolStatus},
+    profile::ExecutorProfileId,
+};
 use regex::Regex;
 use rmcp::{
     ErrorData, ServerHandler,
@@ -19,11 +26,21 @@ use rmcp::{
 };
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use serde_json;
+use services::services::approvals::PendingApprovalInfo;
+use utils::{
+    approvals::{ApprovalResponse, ApprovalStatus},
+    diff::{Diff, compute_line_change_counts, create_unified_diff},
+};
 use uuid::Uuid;
 
 use crate::routes::{
     containers::ContainerQuery,
-    task_attempts::{CreateTaskAttemptBody, WorkspaceRepoInput},
+    remote_issues::StartRemoteIssueRequest,
+    sessions::{CreateFollowUpAttempt, SessionConversationEntry, SessionConversationPage},
+    task_attempts::{
+        CreateTaskAttemptBody, DiffStreamQuery, MergeTaskAttemptRequest, RepoBranchStatus,
+        WorkspaceRepoInput, pr::CreatePrApiRequest,
+    },
 };
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -291,6 +308,28 @@ pub struct StartWorkspaceSessionResponse {
     pub workspace_id: String,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct StartRemoteIssueToolRequest {
+    #[schemars(description = "The ID of the local project linked to the issue's remote project")]
+    pub project_id: Uuid,
+    #[schemars(description = "The ID of the issue on the remote server")]
+    pub remote_issue_id: Uuid,
+    #[schemars(
+        description = "The coding agent executor to run ('CLAUDE_CODE', 'AMP', 'GEMINI', 'CODEX', 'OPENCODE', 'CURSOR_AGENT', 'QWEN_CODE', 'COPILOT', 'DROID')"
+    )]
+    pub executor: String,
+    #[schemars(description = "Optional executor variant, if needed")]
+    pub variant: Option<String>,
+    #[schemars(description = "Base branch for each repository in the project")]
+    pub repos: Vec<McpWorkspaceRepoInput>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct StartRemoteIssueToolResponse {
+    pub task_id: String,
+    pub remote_issue_id: String,
+}
+
 #[derive(Debug, Serialize, schemars::JsonSchema)]
 pub struct DeleteTaskResponse {
     pub deleted_task_id: Option<String>,
@@ -307,12 +346,341 @@ pub struct GetTaskResponse {
     pub task: TaskDetails,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct SendFollowUpRequest {
+    #[schemars(description = "The ID of the workspace running the coding agent")]
+    pub workspace_id: Uuid,
+    #[schemars(
+        description = "Optional session to continue. Defaults to the workspace's most recently used session"
+    )]
+    pub session_id: Option<Uuid>,
+    #[schemars(description = "The follow-up prompt for the coding agent")]
+    pub prompt: String,
+    #[schemars(description = "Optional executor variant, if needed")]
+    pub variant: Option<String>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct SendFollowUpResponse {
+    pub session_id: String,
+    pub execution_process_id: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetSessionConversationRequest {
+    #[schemars(description = "The ID of the workspace running the coding agent")]
+    pub workspace_id: Uuid,
+    #[schemars(
+        description = "Optional session to read. Defaults to the workspace's most recently used session"
+    )]
+    pub session_id: Option<Uuid>,
+    #[schemars(
+        description = "Index of the first entry to return. Omit to get the most recent entries"
+    )]
+    pub offset: Option<usize>,
+    #[schemars(description = "Maximum number of entries to return (default: 50, max: 200)")]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct McpConversationEntry {
+    #[schemars(description = "Position of the entry in the whole conversation")]
+    pub index: usize,
+    pub execution_process_id: String,
+    #[schemars(
+        description = "One of 'user_message', 'assistant_message', 'tool_use', 'thinking', 'system_message', 'error_message', ..."
+    )]
+    pub entry_type: String,
+    pub tool_name: Option<String>,
+    pub tool_status: Option<String>,
+    #[schemars(description = "Set while the tool call is waiting for `respond_to_approval`")]
+    pub approval_id: Option<String>,
+    pub content: String,
+    pub timestamp: Option<String>,
+}
+
+impl McpConversationEntry {
+    fn from_entry(index: usize, entry: SessionConversationEntry) -> Self {
+        fn serde_tag<T: Serialize>(value: &T, tag: &str) -> Option<String> {
+            serde_json::to_value(value)
+                .ok()?
+                .get(tag)?
+                .as_str()
+                .map(str::to_string)
+        }
+
+        let entry_type = serde_tag(&entry.entry.entry_type, "type").unwrap_or_default();
+        let (tool_name, tool_status, approval_id) = match &entry.entry.entry_type {
+            NormalizedEntryType::ToolUse {
+                tool_name, status, ..
+            } => {
+                let approval_id = match status {
+                    ToolStatus::PendingApproval { approval_id, .. } => Some(approval_id.clone()),
+                    _ => None,
+                };
+                (
+                    Some(tool_name.clone()),
+                    serde_tag(status, "status"),
+                    approval_id,
+                )
+            }
+            _ => (None, None, None),
+        };
+
+        Self {
+            index,
+            execution_process_id: entry.execution_process_id.to_string(),
+            entry_type,
+            tool_name,
+            tool_status,
+            approval_id,
+            content: entry.entry.content,
+            timestamp: entry.entry.timestamp,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct GetSessionConversationResponse {
+    pub session_id: String,
+    pub entries: Vec<McpConversationEntry>,
+    #[schemars(description = "Total number of entries in the conversation")]
+    pub total: usize,
+    #[schemars(description = "Whether the coding agent is still running")]
+    pub running: bool,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetWorkspaceDiffRequest {
+    #[schemars(description = "The ID of the workspace to diff")]
+    pub workspace_id: Uuid,
+    #[schemars(description = "Only return per-file line counts, without patches")]
+    pub stats_only: Option<bool>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct McpFileDiff {
+    #[schemars(description = "Path of the file, prefixed with the repository name")]
+    pub path: String,
+    #[schemars(description = "Previous path, for renamed files")]
+    pub old_path: Option<String>,
+    pub change: String,
+    pub additions: Option<usize>,
+    pub deletions: Option<usize>,
+    #[schemars(
+        description = "Unified diff, omitted for large changes or when `stats_only` is set"
+    )]
+    pub patch: Option<String>,
+}
+
+impl McpFileDiff {
+    fn from_diff(diff: Diff) -> Self {
+        let path = diff
+            .new_path
+            .clone()
+            .or_else(|| diff.old_path.clone())
+            .unwrap_or_default();
+        let old_path = diff.old_path.filter(|old| *old != path);
+        let change = serde_json::to_value(&diff.change)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+
+        let (patch, additions, deletions) = if diff.content_omitted {
+            (None, diff.additions, diff.deletions)
+        } else {
+            let old = diff.old_content.as_deref().unwrap_or("");
+            let new = diff.new_content.as_deref().unwrap_or("");
+            let (additions, deletions) = compute_line_change_counts(old, new);
+            (
+                Some(create_unified_diff(&path, old, new)),
+                Some(additions),
+                Some(deletions),
+            )
+        };
+
+        Self {
+            path,
+            old_path,
+            change,
+            additions,
+            deletions,
+            patch,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct GetWorkspaceDiffResponse {
+    pub workspace_id: String,
+    pub files: Vec<McpFileDiff>,
+    pub files_changed: usize,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetBranchStatusRequest {
+    #[schemars(description = "The ID of the workspace to check")]
+    pub workspace_id: Uuid,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct McpBranchStatus {
+    pub repo_id: String,
+    pub repo_name: String,
+    pub target_branch: String,
+    #[schemars(description = "Commits on the workspace branch that the target branch lacks")]
+    pub commits_ahead: Option<usize>,
+    #[schemars(description = "Commits on the target branch that the workspace branch lacks")]
+    pub commits_behind: Option<usize>,
+    pub has_uncommitted_changes: Option<bool>,
+    pub is_rebase_in_progress: bool,
+    pub conflicted_files: Vec<String>,
+    #[schemars(description = "Commit the workspace was merged as, if merged directly")]
+    pub merge_commit: Option<String>,
+    pub pr_url: Option<String>,
+    #[schemars(description = "'open', 'merged', 'closed' or 'unknown'")]
+    pub pr_status: Option<String>,
+}
+
+impl McpBranchStatus {
+    fn from_repo_status(repo_status: RepoBranchStatus) -> Self {
+        let status = repo_status.status;
+        let merge_commit = status.merges.iter().find_map(|merge| match merge {
+            Merge::Direct(direct) => Some(direct.merge_commit.clone()),
+            Merge::Pr(_) => None,
+        });
+        let pr_info = status.merges.iter().find_map(|merge| match merge {
+            Merge::Pr(pr) => Some(&pr.pr_info),
+            Merge::Direct(_) => None,
+        });
+
+        Self {
+            repo_id: repo_status.repo_id.to_string(),
+            repo_name: repo_status.repo_name,
+            target_branch: status.target_branch_name.clone(),
+            commits_ahead: status.commits_ahead,
+            commits_behind: status.commits_behind,
+            has_uncommitted_changes: status.has_uncommitted_changes,
+            is_rebase_in_progress: status.is_rebase_in_progress,
+            conflicted_files: status.conflicted_files.clone(),
+            merge_commit,
+            pr_url: pr_info.map(|info| info.url.clone()),
+            pr_status: pr_info.and_then(|info| {
+                serde_json::to_value(&info.status)
+                    .ok()
+                    .and_then(|v| v.as_str().map(str::to_string))
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct GetBranchStatusResponse {
+    pub workspace_id: String,
+    pub repos: Vec<McpBranchStatus>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ListPendingApprovalsRequest {
+    #[schemars(description = "The ID of the workspace to check")]
+    pub workspace_id: Uuid,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct McpPendingApproval {
+    pub approval_id: String,
+    pub execution_process_id: String,
+    pub tool_name: String,
+    #[schemars(description = "What the agent is asking to do")]
+    pub content: String,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct ListPendingApprovalsResponse {
+    pub approvals: Vec<McpPendingApproval>,
+    pub count: usize,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct RespondToApprovalRequest {
+    #[schemars(description = "The approval to respond to, from `list_pending_approvals`")]
+    pub approval_id: String,
+    #[schemars(description = "The execution process that requested the approval")]
+    pub execution_process_id: Uuid,
+    #[schemars(description = "Whether to let the agent run the tool")]
+    pub approve: bool,
+    #[schemars(description = "Optional reason passed back to the agent when denying")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct RespondToApprovalResponse {
+    pub approval_id: String,
+    #[schemars(description = "'approved', 'denied', 'timed_out' or 'pending'")]
+    pub status: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct StopExecutionRequest {
+    #[schemars(description = "The ID of the workspace to stop")]
+    pub workspace_id: Uuid,
+    #[schemars(
+        description = "Optional execution process to stop. Defaults to everything running in the workspace except the dev server"
+    )]
+    pub execution_process_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct StopExecutionResponse {
+    pub workspace_id: String,
+    pub execution_process_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct MergeWorkspaceRequest {
+    #[schemars(description = "The ID of the workspace to merge")]
+    pub workspace_id: Uuid,
+    #[schemars(description = "The repository whose branch is merged into its target branch")]
+    pub repo_id: Uuid,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct MergeWorkspaceResponse {
+    pub workspace_id: String,
+    pub repo_id: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct CreatePrRequest {
+    #[schemars(description = "The ID of the workspace to open a pull request for")]
+    pub workspace_id: Uuid,
+    #[schemars(description = "The repository to open the pull request in")]
+    pub repo_id: Uuid,
+    #[schemars(description = "The pull request title")]
+    pub title: String,
+    #[schemars(description = "Optional pull request body")]
+    pub body: Option<String>,
+    #[schemars(description = "Optional base branch. Defaults to the workspace's target branch")]
+    pub target_branch: Option<String>,
+    #[schemars(description = "Open the pull request as a draft")]
+    pub draft: Option<bool>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct CreatePrResponse {
+    pub workspace_id: String,
+    pub pr_url: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct TaskServer {
     client: reqwest::Client,
     base_url: String,
     tool_router: ToolRouter<TaskServer>,
     context: Option<McpContext>,
+    /// Set when serving a workspace's MCP token: calls may only touch that
+    /// workspace and the task, project and repos it belongs to
+    scoped: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
@@ -345,12 +713,31 @@ impl TaskServer {
             base_url: base_url.to_string(),
             tool_router: Self::tool_router(),
             context: None,
+            scoped: false,
         }
     }
 
-    pub async fn init(mut self) -> Self {
+    pub async fn init(self) -> Self {
         let context = self.fetch_context_at_startup().await;
+        self.with_context(context)
+    }
 
+    /// Resolve the context from the workspace token the coding agent was given
+    /// and restrict every tool call to that workspace. Falls back to the working
+    /// directory lookup when the token can't be resolved.
+    pub async fn init_with_token(self, token: &str) -> Self {
+        match self.fetch_context_for_token(token).await {
+            Some(context) => self.with_context(Some(context)).scoped_to_context(),
+            None => {
+                tracing::warn!("Could not resolve the workspace for VK_MCP_TOKEN");
+                self.init().await.scoped_to_context()
+            }
+        }
+    }
+
+    /// Use a context the caller already knows, e.g. from the token an HTTP client
+    /// authenticated with, instead of looking it up from the working directory
+    pub fn with_context(mut self, context: Option<McpContext>) -> Self {
         if context.is_none() {
             self.tool_router.map.remove("get_context");
             tracing::debug!("VK context not available, get_context tool will not be registered");
@@ -362,6 +749,108 @@ impl TaskServer {
         self
     }
 
+    /// Restrict every tool call to the workspace in the context. Tools that only
+    /// make sense across projects are removed.
+    pub fn scoped_to_context(mut self) -> Self {
+        self.tool_router.map.remove("list_projects");
+        self.scoped = true;
+        self
+    }
+
+    fn scope(&self) -> Option<&McpContext> {
+        self.context.as_ref().filter(|_| self.scoped)
+    }
+
+    fn out_of_scope(what: &str) -> CallToolResult {
+        Self::err(
+            format!("This {what} is outside the workspace this MCP session belongs to"),
+            None::<String>,
+        )
+        .unwrap()
+    }
+
+    fn check_project(&self, project_id: Uuid) -> Result<(), CallToolResult> {
+        match self.scope() {
+            Some(scope) if scope.project_id != project_id => Err(Self::out_of_scope("project")),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_task(&self, task_id: Uuid) -> Result<(), CallToolResult> {
+        match self.scope() {
+            Some(scope) if scope.task_id != task_id => Err(Self::out_of_scope("task")),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_workspace(&self, workspace_id: Uuid) -> Result<(), CallToolResult> {
+        match self.scope() {
+            Some(scope) if scope.workspace_id != workspace_id => {
+                Err(Self::out_of_scope("workspace"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn check_repo(&self, repo_id: Uuid) -> Result<(), CallToolResult> {
+        match self.scope() {
+            Some(scope) if !scope.workspace_repos.iter().any(|r| r.repo_id == repo_id) => {
+                Err(Self::out_of_scope("repository"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    async fn check_session(&self, session_id: Uuid) -> Result<(), CallToolResult> {
+        let Some(scope) = self.scope() else {
+            return Ok(());
+        };
+        let url = self.url(&format!(
+            "/api/sessions?workspace_id={}",
+            scope.workspace_id
+        ));
+        let sessions: Vec<Session> = self.send_json(self.client.get(&url)).await?;
+        if sessions.iter().any(|session| session.id == session_id) {
+            Ok(())
+        } else {
+            Err(Self::out_of_scope("session"))
+        }
+    }
+
+    async fn check_execution_process(&self, id: Uuid) -> Result<(), CallToolResult> {
+        if self.scope().is_none() {
+            return Ok(());
+        }
+        let url = self.url(&format!("/api/execution-processes/{}", id));
+        let process: ExecutionProcess = self.send_json(self.client.get(&url)).await?;
+        self.check_session(process.session_id).await
+    }
+
+    async fn fetch_context_for_token(&self, token: &str) -> Option<McpContext> {
+        let response = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            self.client
+                .get(self.url("/api/mcp/context"))
+                .bearer_auth(token)
+                .send(),
+        )
+        .await
+        .ok()?
+        .ok()?;
+
+        if !response.status().is_success() {
+            return None;
+        }
+
+        let api_response: ApiResponseEnvelope<WorkspaceContext> = response.json().await.ok()?;
+
+        if !api_response.success {
+            return None;
+        }
+
+        api_response.data.map(McpContext::from_workspace_context)
+    }
+
     async fn fetch_context_at_startup(&self) -> Option<McpContext> {
         let current_dir = std::env::current_dir().ok()?;
         let canonical_path = current_dir.canonicalize().unwrap_or(current_dir);
@@ -390,8 +879,12 @@ impl TaskServer {
             return None;
         }
 
-        let ctx = api_response.data?;
+        api_response.data.map(McpContext::from_workspace_context)
+    }
+}
 
+impl McpContext {
+    pub fn from_workspace_context(ctx: WorkspaceContext) -> Self {
         // Map RepoWithTargetBranch to McpRepoContext
         let workspace_repos: Vec<McpRepoContext> = ctx
             .workspace_repos
@@ -403,14 +896,14 @@ impl TaskServer {
             })
             .collect();
 
-        Some(McpContext {
+        Self {
             project_id: ctx.project.id,
             task_id: ctx.task.id,
             task_title: ctx.task.title,
             workspace_id: ctx.workspace.id,
             workspace_branch: ctx.workspace.branch,
             workspace_repos,
-        })
+        }
     }
 }
 
@@ -418,6 +911,7 @@ impl TaskServer {
 struct ApiResponseEnvelope<T> {
     success: bool,
     data: Option<T>,
+    error_data: Option<serde_json::Value>,
     message: Option<String>,
 }
 
@@ -454,10 +948,7 @@ impl TaskServer {
             .map_err(|e| Self::err("Failed to connect to VK API", Some(&e.to_string())).unwrap())?;
 
         if !resp.status().is_success() {
-            let status = resp.status();
-            return Err(
-                Self::err(format!("VK API returned error status: {}", status), None).unwrap(),
-            );
+            return Err(Self::error_status(resp).await);
         }
 
         let api_response = resp.json::<ApiResponseEnvelope<T>>().await.map_err(|e| {
@@ -465,8 +956,12 @@ impl TaskServer {
         })?;
 
         if !api_response.success {
-            let msg = api_response.message.as_deref().unwrap_or("Unknown error");
-            return Err(Self::err("VK API returned error", Some(msg)).unwrap());
+            // Some endpoints report failures as structured `error_data` instead of a message
+            let msg = api_response
+                .message
+                .or_else(|| api_response.error_data.map(|d| d.to_string()))
+                .unwrap_or_else(|| "Unknown error".to_string());
+            return Err(Self::err("VK API returned error", Some(msg.as_str())).unwrap());
         }
 
         api_response
@@ -474,6 +969,17 @@ impl TaskServer {
             .ok_or_else(|| Self::err("VK API response missing data field", None).unwrap())
     }
 
+    /// Error for a non-2xx response, carrying the API's message when the body has one
+    async fn error_status(resp: reqwest::Response) -> CallToolResult {
+        let status = resp.status();
+        let message = resp
+            .json::<ApiResponseEnvelope<serde_json::Value>>()
+            .await
+            .ok()
+            .and_then(|r| r.message);
+        Self::err(format!("VK API returned error status: {}", status), message).unwrap()
+    }
+
     async fn send_empty_json(&self, rb: reqwest::RequestBuilder) -> Result<(), CallToolResult> {
         let resp = rb
             .send()
@@ -481,10 +987,7 @@ impl TaskServer {
             .map_err(|e| Self::err("Failed to connect to VK API", Some(&e.to_string())).unwrap())?;
 
         if !resp.status().is_success() {
-            let status = resp.status();
-            return Err(
-                Self::err(format!("VK API returned error status: {}", status), None).unwrap(),
-            );
+            return Err(Self::error_status(resp).await);
         }
 
         #[derive(Deserialize)]
@@ -505,6 +1008,25 @@ impl TaskServer {
         Ok(())
     }
 
+    /// The given session, or the workspace's most recently used one
+    async fn resolve_session_id(
+        &self,
+        workspace_id: Uuid,
+        session_id: Option<Uuid>,
+    ) -> Result<Uuid, CallToolResult> {
+        if let Some(session_id) = session_id {
+            self.check_session(session_id).await?;
+            return Ok(session_id);
+        }
+
+        let url = self.url(&format!("/api/sessions?workspace_id={}", workspace_id));
+        let sessions: Vec<Session> = self.send_json(self.client.get(&url)).await?;
+        sessions
+            .first()
+            .map(|session| session.id)
+            .ok_or_else(|| Self::err("Workspace has no sessions", None).unwrap())
+    }
+
     fn url(&self, path: &str) -> String {
         format!(
             "{}/{}",
@@ -566,6 +1088,35 @@ impl TaskServer {
     }
 }
 
+/// Resolve an executor name such as `claude-code` and an optional variant
+fn parse_executor_profile_id(
+    executor: &str,
+    variant: Option<String>,
+) -> Result<ExecutorProfileId, String> {
+    let executor_trimmed = executor.trim();
+    if executor_trimmed.is_empty() {
+        return Err("Executor must not be empty.".to_string());
+    }
+
+    let normalized_executor = executor_trimmed.replace('-', "_").to_ascii_uppercase();
+    let base_executor = BaseCodingAgent::from_str(&normalized_executor)
+        .map_err(|_| format!("Unknown executor '{executor_trimmed}'."))?;
+
+    let variant = variant.and_then(|v| {
+        let trimmed = v.trim();
+        if trimmed.is_empty() {
+            None
+        } else {
+            Some(trimmed.to_string())
+        }
+    });
+
+    Ok(ExecutorProfileId {
+        executor: base_executor,
+        variant,
+    })
+}
+
 #[tool_router]
 impl TaskServer {
     #[tool(
@@ -589,6 +1140,9 @@ impl TaskServer {
             description,
         }): Parameters<CreateTaskRequest>,
     ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.check_project(project_id) {
+            return Ok(e);
+        }
         // Expand @tagname references in description
         let expanded_description = match description {
             Some(desc) => Some(self.expand_tags(&desc).await),
@@ -644,6 +1198,9 @@ impl TaskServer {
         &self,
         Parameters(ListReposRequest { project_id }): Parameters<ListReposRequest>,
     ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.check_project(project_id) {
+            return Ok(e);
+        }
         let url = self.url(&format!("/api/projects/{}/repositories", project_id));
         let repos: Vec<Repo> = match self.send_json(self.client.get(&url)).await {
             Ok(rs) => rs,
@@ -674,6 +1231,9 @@ impl TaskServer {
         &self,
         Parameters(GetRepoRequest { repo_id }): Parameters<GetRepoRequest>,
     ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.check_repo(repo_id) {
+            return Ok(e);
+        }
         let url = self.url(&format!("/api/repos/{}", repo_id));
         let repo: Repo = match self.send_json(self.client.get(&url)).await {
             Ok(r) => r,
@@ -698,6 +1258,9 @@ impl TaskServer {
             UpdateSetupScriptRequest,
         >,
     ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.check_repo(repo_id) {
+            return Ok(e);
+        }
         let url = self.url(&format!("/api/repos/{}", repo_id));
         let script_value = if script.is_empty() {
             None
@@ -727,6 +1290,9 @@ impl TaskServer {
             UpdateCleanupScriptRequest,
         >,
     ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.check_repo(repo_id) {
+            return Ok(e);
+        }
         let url = self.url(&format!("/api/repos/{}", repo_id));
         let script_value = if script.is_empty() {
             None
@@ -756,6 +1322,9 @@ impl TaskServer {
             UpdateDevServerScriptRequest,
         >,
     ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.check_repo(repo_id) {
+            return Ok(e);
+        }
         let url = self.url(&format!("/api/repos/{}", repo_id));
         let script_value = if script.is_empty() {
             None
@@ -787,6 +1356,9 @@ impl TaskServer {
             limit,
         }): Parameters<ListTasksRequest>,
     ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.check_project(project_id) {
+            return Ok(e);
+        }
         let status_filter = if let Some(ref status_str) = status {
             match TaskStatus::from_str(status_str) {
                 Ok(s) => Some(s),
@@ -848,6 +1420,14 @@ impl TaskServer {
             repos,
         }): Parameters<StartWorkspaceSessionRequest>,
     ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.check_task(task_id) {
+            return Ok(e);
+        }
+        for repo in &repos {
+            if let Err(e) = self.check_repo(repo.repo_id) {
+                return Ok(e);
+            }
+        }
         if repos.is_empty() {
             return Self::err(
                 "At least one repository must be specified.".to_string(),
@@ -855,34 +1435,9 @@ impl TaskServer {
             );
         }
 
-        let executor_trimmed = executor.trim();
-        if executor_trimmed.is_empty() {
-            return Self::err("Executor must not be empty.".to_string(), None::<String>);
-        }
-
-        let normalized_executor = executor_trimmed.replace('-', "_").to_ascii_uppercase();
-        let base_executor = match BaseCodingAgent::from_str(&normalized_executor) {
-            Ok(exec) => exec,
-            Err(_) => {
-                return Self::err(
-                    format!("Unknown executor '{executor_trimmed}'."),
-                    None::<String>,
-                );
-            }
-        };
-
-        let variant = variant.and_then(|v| {
-            let trimmed = v.trim();
-            if trimmed.is_empty() {
-                None
-            } else {
-                Some(trimmed.to_string())
-            }
-        });
-
-        let executor_profile_id = ExecutorProfileId {
-            executor: base_executor,
-            variant,
+        let executor_profile_id = match parse_executor_profile_id(&executor, variant) {
+            Ok(executor_profile_id) => executor_profile_id,
+            Err(msg) => return Self::err(msg, None::<String>),
         };
 
         let workspace_repos: Vec<WorkspaceRepoInput> = repos
@@ -914,6 +1469,59 @@ impl TaskServer {
         TaskServer::success(&response)
     }
 
+    #[tool(
+        description = "Start working on an issue from the remote project a local project is linked to. Creates a task from the issue's title, description and tags, launches a workspace session for it and moves the issue to 'In progress'."
+    )]
+    async fn start_remote_issue(
+        &self,
+        Parameters(StartRemoteIssueToolRequest {
+            project_id,
+            remote_issue_id,
+            executor,
+            variant,
+            repos,
+        }): Parameters<StartRemoteIssueToolRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.check_project(project_id) {
+            return Ok(e);
+        }
+        if repos.is_empty() {
+            return Self::err(
+                "At least one repository must be specified.".to_string(),
+                None::<String>,
+            );
+        }
+
+        let executor_profile_id = match parse_executor_profile_id(&executor, variant) {
+            Ok(executor_profile_id) => executor_profile_id,
+            Err(msg) => return Self::err(msg, None::<String>),
+        };
+
+        let payload = StartRemoteIssueRequest {
+            remote_issue_id,
+            executor_profile_id,
+            repos: repos
+                .into_iter()
+                .map(|r| WorkspaceRepoInput {
+                    repo_id: r.repo_id,
+                    target_branch: r.base_branch,
+                })
+                .collect(),
+        };
+
+        let url = self.url(&format!("/api/projects/{}/remote-issues/start", project_id));
+        let task: TaskWithAttemptStatus =
+            match self.send_json(self.client.post(&url).json(&payload)).await {
+                Ok(task) => task,
+                Err(e) => return Ok(e),
+            };
+
+        TaskServer::success(&StartRemoteIssueToolResponse {
+            task_id: task.id.to_string(),
+            remote_issue_id: remote_issue_id.to_string(),
+        })
+    }
+
     #[tool(
         description = "Update an existing task/ticket's title, description, or status. `task_id` is required. `title`, `description`, and `status` are optional."
     )]
@@ -926,6 +1534,9 @@ impl TaskServer {
             status,
         }): Parameters<UpdateTaskRequest>,
     ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.check_task(task_id) {
+            return Ok(e);
+        }
         let status = if let Some(ref status_str) = status {
             match TaskStatus::from_str(status_str) {
                 Ok(s) => Some(s),
@@ -969,6 +1580,9 @@ impl TaskServer {
         &self,
         Parameters(DeleteTaskRequest { task_id }): Parameters<DeleteTaskRequest>,
     ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.check_task(task_id) {
+            return Ok(e);
+        }
         let url = self.url(&format!("/api/tasks/{}", task_id));
         if let Err(e) = self.send_empty_json(self.client.delete(&url)).await {
             return Ok(e);
@@ -988,6 +1602,9 @@ impl TaskServer {
         &self,
         Parameters(GetTaskRequest { task_id }): Parameters<GetTaskRequest>,
     ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.check_task(task_id) {
+            return Ok(e);
+        }
         let url = self.url(&format!("/api/tasks/{}", task_id));
         let task: Task = match self.send_json(self.client.get(&url)).await {
             Ok(t) => t,
@@ -999,12 +1616,345 @@ impl TaskServer {
 
         TaskServer::success(&response)
     }
+
+    #[tool(
+        description = "Send a follow-up prompt to the coding agent in a workspace, continuing its session. `workspace_id` and `prompt` are required."
+    )]
+    async fn send_follow_up(
+        &self,
+        Parameters(SendFollowUpRequest {
+            workspace_id,
+            session_id,
+            prompt,
+            variant,
+        }): Parameters<SendFollowUpRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.check_workspace(workspace_id) {
+            return Ok(e);
+        }
+        if prompt.trim().is_empty() {
+            return Self::err("Prompt must not be empty.".to_string(), None::<String>);
+        }
+
+        let session_id = match self.resolve_session_id(workspace_id, session_id).await {
+            Ok(id) => id,
+            Err(e) => return Ok(e),
+        };
+
+        let payload = CreateFollowUpAttempt {
+            prompt: self.expand_tags(&prompt).await,
+            variant,
+            retry_process_id: None,
+            force_when_dirty: None,
+            perform_git_reset: None,
+        };
+        let url = self.url(&format!("/api/sessions/{}/follow-up", session_id));
+        let process: ExecutionProcess =
+            match self.send_json(self.client.post(&url).json(&payload)).await {
+                Ok(p) => p,
+                Err(e) => return Ok(e),
+            };
+
+        TaskServer::success(&SendFollowUpResponse {
+            session_id: session_id.to_string(),
+            execution_process_id: process.id.to_string(),
+        })
+    }
+
+    #[tool(
+        description = "Read the coding agent conversation of a workspace session, a page at a time. Returns the most recent entries unless `offset` is given. Tool calls waiting for approval carry an `approval_id`. `workspace_id` is required."
+    )]
+    async fn get_session_conversation(
+        &self,
+        Parameters(GetSessionConversationRequest {
+            workspace_id,
+            session_id,
+            offset,
+            limit,
+        }): Parameters<GetSessionConversationRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.check_workspace(workspace_id) {
+            return Ok(e);
+        }
+        let session_id = match self.resolve_session_id(workspace_id, session_id).await {
+            Ok(id) => id,
+            Err(e) => return Ok(e),
+        };
+
+        let mut query = Vec::new();
+        if let Some(offset) = offset {
+            query.push(("offset", offset));
+        }
+        if let Some(limit) = limit {
+            query.push(("limit", limit));
+        }
+        let url = self.url(&format!("/api/sessions/{}/conversation", session_id));
+        let page: SessionConversationPage =
+            match self.send_json(self.client.get(&url).query(&query)).await {
+                Ok(p) => p,
+                Err(e) => return Ok(e),
+            };
+
+        let entries = page
+            .entries
+            .into_iter()
+            .enumerate()
+            .map(|(i, entry)| McpConversationEntry::from_entry(page.offset + i, entry))
+            .collect();
+
+        TaskServer::success(&GetSessionConversationResponse {
+            session_id: session_id.to_string(),
+            entries,
+            total: page.total,
+            running: page.running,
+        })
+    }
+
+    #[tool(
+        description = "Get the changes a workspace has made against its target branches, as unified diffs. `workspace_id` is required."
+    )]
+    async fn get_workspace_diff(
+        &self,
+        Parameters(GetWorkspaceDiffRequest {
+            workspace_id,
+            stats_only,
+        }): Parameters<GetWorkspaceDiffRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.check_workspace(workspace_id) {
+            return Ok(e);
+        }
+        let url = self.url(&format!("/api/task-attempts/{}/diff", workspace_id));
+        let query = DiffStreamQuery {
+            stats_only: stats_only.unwrap_or(false),
+        };
+        let diffs: Vec<Diff> = match self.send_json(self.client.get(&url).query(&query)).await {
+            Ok(d) => d,
+            Err(e) => return Ok(e),
+        };
+
+        let files: Vec<McpFileDiff> = diffs.into_iter().map(McpFileDiff::from_diff).collect();
+        TaskServer::success(&GetWorkspaceDiffResponse {
+            workspace_id: workspace_id.to_string(),
+            files_changed: files.len(),
+            files,
+        })
+    }
+
+    #[tool(
+        description = "Get the branch status of each repository in a workspace: commits ahead/behind the target branch, uncommitted changes, conflicts, and any merge or pull request. `workspace_id` is required."
+    )]
+    async fn get_branch_status(
+        &self,
+        Parameters(GetBranchStatusRequest { workspace_id }): Parameters<GetBranchStatusRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.check_workspace(workspace_id) {
+            return Ok(e);
+        }
+        let url = self.url(&format!(
+            "/api/task-attempts/{}/branch-status",
+            workspace_id
+        ));
+        let statuses: Vec<RepoBranchStatus> = match self.send_json(self.client.get(&url)).await {
+            Ok(s) => s,
+            Err(e) => return Ok(e),
+        };
+
+        TaskServer::success(&GetBranchStatusResponse {
+            workspace_id: workspace_id.to_string(),
+            repos: statuses
+                .into_iter()
+                .map(McpBranchStatus::from_repo_status)
+                .collect(),
+        })
+    }
+
+    #[tool(
+        description = "List the tool calls the coding agent in a workspace is waiting for approval on. `workspace_id` is required."
+    )]
+    async fn list_pending_approvals(
+        &self,
+        Parameters(ListPendingApprovalsRequest { workspace_id }): Parameters<
+            ListPendingApprovalsRequest,
+        >,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.check_workspace(workspace_id) {
+            return Ok(e);
+        }
+        let url = self.url(&format!(
+            "/api/approvals/pending?workspace_id={}",
+            workspace_id
+        ));
+        let pending: Vec<PendingApprovalInfo> = match self.send_json(self.client.get(&url)).await {
+            Ok(p) => p,
+            Err(e) => return Ok(e),
+        };
+
+        let approvals: Vec<McpPendingApproval> = pending
+            .into_iter()
+            .map(|p| McpPendingApproval {
+                approval_id: p.approval_id,
+                execution_process_id: p.execution_process_id.to_string(),
+                tool_name: p.tool_name,
+                content: p.entry.content,
+            })
+            .collect();
+
+        TaskServer::success(&ListPendingApprovalsResponse {
+            count: approvals.len(),
+            approvals,
+        })
+    }
+
+    #[tool(
+        description = "Approve or deny a tool call the coding agent is waiting on. Use `list_pending_approvals` to find `approval_id` and `execution_process_id`."
+    )]
+    async fn respond_to_approval(
+        &self,
+        Parameters(RespondToApprovalRequest {
+            approval_id,
+            execution_process_id,
+            approve,
+            reason,
+        }): Parameters<RespondToApprovalRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.check_execution_process(execution_process_id).await {
+            return Ok(e);
+        }
+        let payload = ApprovalResponse {
+            execution_process_id,
+            status: if approve {
+                ApprovalStatus::Approved
+            } else {
+                ApprovalStatus::Denied { reason }
+            },
+        };
+        let url = self.url(&format!("/api/approvals/{}/respond", approval_id));
+        let status: ApprovalStatus =
+            match self.send_json(self.client.post(&url).json(&payload)).await {
+                Ok(s) => s,
+                Err(e) => return Ok(e),
+            };
+
+        let status = match status {
+            ApprovalStatus::Pending => "pending",
+            ApprovalStatus::Approved => "approved",
+            ApprovalStatus::Denied { .. } => "denied",
+            ApprovalStatus::TimedOut => "timed_out",
+        };
+        TaskServer::success(&RespondToApprovalResponse {
+            approval_id,
+            status: status.to_string(),
+        })
+    }
+
+    #[tool(
+        description = "Stop a running coding agent or script in a workspace. `workspace_id` is required."
+    )]
+    async fn stop_execution(
+        &self,
+        Parameters(StopExecutionRequest {
+            workspace_id,
+            execution_process_id,
+        }): Parameters<StopExecutionRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.check_workspace(workspace_id) {
+            return Ok(e);
+        }
+        if let Some(execution_process_id) = execution_process_id
+            && let Err(e) = self.check_execution_process(execution_process_id).await
+        {
+            return Ok(e);
+        }
+        let url = match execution_process_id {
+            Some(id) => self.url(&format!("/api/execution-processes/{}/stop", id)),
+            None => self.url(&format!("/api/task-attempts/{}/stop", workspace_id)),
+        };
+        if let Err(e) = self.send_empty_json(self.client.post(&url)).await {
+            return Ok(e);
+        }
+
+        TaskServer::success(&StopExecutionResponse {
+            workspace_id: workspace_id.to_string(),
+            execution_process_id: execution_process_id.map(|id| id.to_string()),
+        })
+    }
+
+    #[tool(
+        description = "Merge a workspace's branch into the target branch of one of its repositories. `workspace_id` and `repo_id` are required."
+    )]
+    async fn merge_workspace(
+        &self,
+        Parameters(MergeWorkspaceRequest {
+            workspace_id,
+            repo_id,
+        }): Parameters<MergeWorkspaceRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.check_workspace(workspace_id) {
+            return Ok(e);
+        }
+        if let Err(e) = self.check_repo(repo_id) {
+            return Ok(e);
+        }
+        let url = self.url(&format!("/api/task-attempts/{}/merge", workspace_id));
+        let payload = MergeTaskAttemptRequest { repo_id };
+        if let Err(e) = self
+            .send_empty_json(self.client.post(&url).json(&payload))
+            .await
+        {
+            return Ok(e);
+        }
+
+        TaskServer::success(&MergeWorkspaceResponse {
+            workspace_id: workspace_id.to_string(),
+            repo_id: repo_id.to_string(),
+        })
+    }
+
+    #[tool(
+        description = "Push a workspace's branch and open a pull request for one of its repositories. `workspace_id`, `repo_id` and `title` are required."
+    )]
+    async fn create_pr(
+        &self,
+        Parameters(CreatePrRequest {
+            workspace_id,
+            repo_id,
+            title,
+            body,
+            target_branch,
+            draft,
+        }): Parameters<CreatePrRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.check_workspace(workspace_id) {
+            return Ok(e);
+        }
+        if let Err(e) = self.check_repo(repo_id) {
+            return Ok(e);
+        }
+        let url = self.url(&format!("/api/task-attempts/{}/pr", workspace_id));
+        let payload = CreatePrApiRequest {
+            title,
+            body,
+            target_branch,
+            draft,
+            repo_id,
+            auto_generate_description: false,
+        };
+        let pr_url: String = match self.send_json(self.client.post(&url).json(&payload)).await {
+            Ok(u) => u,
+            Err(e) => return Ok(e),
+        };
+
+        TaskServer::success(&CreatePrResponse {
+            workspace_id: workspace_id.to_string(),
+            pr_url,
+        })
+    }
 }
 
 #[tool_handler]
 impl ServerHandler for TaskServer {
     fn get_info(&self) -> ServerInfo {
-        let mut instruction = "A task and project management server. If you need to create or update tickets or tasks then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. You can get project ids by using `list projects`. Call `list_tasks` to fetch the `task_ids` of all the tasks in a project. TOOLS: 'list_projects', 'list_tasks', 'create_task', 'start_workspace_session', 'get_task', 'update_task', 'delete_task', 'list_repos', 'get_repo', 'update_setup_script', 'update_cleanup_script', 'update_dev_server_script'. Make sure to pass `project_id`, `task_id`, or `repo_id` where required. You can use list tools to get the available ids.".to_string();
+        let mut instruction = "A task and project management server. If you need to create or update tickets or tasks then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. You can get project ids by using `list projects`. Call `list_tasks` to fetch the `task_ids` of all the tasks in a project. TOOLS: 'list_projects', 'list_tasks', 'create_task', 'start_workspace_session', 'start_remote_issue', 'get_task', 'update_task', 'delete_task', 'list_repos', 'get_repo', 'update_setup_script', 'update_cleanup_script', 'update_dev_server_script', 'send_follow_up', 'get_session_conversation', 'get_workspace_diff', 'get_branch_status', 'list_pending_approvals', 'respond_to_approval', 'stop_execution', 'merge_workspace', 'create_pr'. Make sure to pass `project_id`, `task_id`, `workspace_id`, or `repo_id` where required. You can use list tools to get the available ids. After `start_workspace_session`, use its `workspace_id` to supervise the coding agent: read its conversation, answer its approvals, send follow-ups, review the diff, and finally merge or open a pull request.".to_string();
         if self.context.is_some() {
             let context_instruction = "Use 'get_context' to fetch project/task/workspace metadata for the active Vibe Kanban workspace session when available.";
             instruction = format!("{} {}", context_instruction, instruction);
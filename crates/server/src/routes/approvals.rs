@@ -1,17 +1,53 @@
 use axum::{
     Router,
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::Json as ResponseJson,
-    routing::post,
+    routing::{get, post},
+};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessStatus},
+    session::Session,
 };
 use deployment::Deployment;
+use serde::Deserialize;
+use services::services::approvals::PendingApprovalInfo;
 use utils::{
     approvals::{ApprovalResponse, ApprovalStatus},
     response::ApiResponse,
 };
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
 
-use crate::DeploymentImpl;
+#[derive(Debug, Deserialize)]
+pub struct PendingApprovalsQuery {
+    pub workspace_id: Uuid,
+}
+
+/// Approvals that running processes in the workspace are waiting on
+pub async fn get_pending_approvals(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<PendingApprovalsQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<PendingApprovalInfo>>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let mut running_ids = Vec::new();
+    for session in Session::find_by_workspace_id(pool, query.workspace_id).await? {
+        running_ids.extend(
+            ExecutionProcess::find_by_session_id(pool, session.id, false)
+                .await?
+                .into_iter()
+                .filter(|process| process.status == ExecutionProcessStatus::Running)
+                .map(|process| process.id),
+        );
+    }
+
+    let pending = deployment
+        .approvals()
+        .pending_for_execution_processes(&running_ids);
+    Ok(ResponseJson(ApiResponse::success(pending)))
+}
 
 pub async fn respond_to_approval(
     State(deployment): State<DeploymentImpl>,
@@ -44,5 +80,7 @@ pub async fn respond_to_approval(
 }
 
 pub fn router() -> Router<DeploymentImpl> {
-    Router::new().route("/approvals/{id}/respond", post(respond_to_approval))
+    Router::new()
+        .route("/approvals/pending", get(get_pending_approvals))
+        .route("/approvals/{id}/respond", post(respond_to_approval))
 }
@@ -0,0 +1,41 @@
+use axum::{
+    Router,
+    extract::State,
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::workspace_disk_usage::WorkspaceDiskUsage;
+use deployment::Deployment;
+use services::services::{
+    container::ContainerService,
+    disk_usage::{self, DiskEviction, DiskUsageSummary},
+};
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Usage as of each workspace's last measurement, grouped by project
+pub async fn get_disk_usage(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<DiskUsageSummary>>, ApiError> {
+    let entries = WorkspaceDiskUsage::find_entries(&deployment.db().pool).await?;
+    let policy = deployment.config().read().await.workspace_disk.clone();
+    Ok(ResponseJson(ApiResponse::success(disk_usage::summarize(
+        entries, &policy,
+    ))))
+}
+
+/// Apply the disk policy now instead of waiting for the periodic cleanup
+pub async fn evict_disk_usage(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<DiskEviction>>>, ApiError> {
+    let policy = deployment.config().read().await.workspace_disk.clone();
+    let evictions = deployment.container().enforce_disk_policy(&policy).await?;
+    Ok(ResponseJson(ApiResponse::success(evictions)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/disk-usage", get(get_disk_usage))
+        .route("/disk-usage/evict", post(evict_disk_usage))
+}
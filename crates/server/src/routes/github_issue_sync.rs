@@ -0,0 +1,140 @@
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    github_issue_sync::{
+        GitHubIssueSyncEvent, GitHubIssueSyncSettings, TaskGitHubIssue,
+        UpsertGitHubIssueSyncSettings,
+    },
+    project::Project,
+    project_repo::ProjectRepo,
+    task::Task,
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::github_issue_sync::GitHubIssueSyncService;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Number of audit events returned for a task
+const SYNC_EVENT_LIMIT: i64 = 200;
+
+#[derive(Debug, Deserialize, TS)]
+pub struct LinkTaskGitHubIssueRequest {
+    #[ts(type = "number")]
+    pub issue_number: i64,
+}
+
+fn sync_service(deployment: &DeploymentImpl) -> Result<GitHubIssueSyncService, ApiError> {
+    Ok(GitHubIssueSyncService::new(
+        deployment.db().clone(),
+        deployment.git().clone(),
+    )?)
+}
+
+pub async fn get_sync_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<GitHubIssueSyncSettings>>>, ApiError> {
+    let settings =
+        GitHubIssueSyncSettings::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+pub async fn upsert_sync_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpsertGitHubIssueSyncSettings>,
+) -> Result<ResponseJson<ApiResponse<GitHubIssueSyncSettings>>, ApiError> {
+    let pool = &deployment.db().pool;
+    ProjectRepo::find_by_project_and_repo(pool, project.id, payload.repo_id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("Repository not found in project".to_string()))?;
+
+    let settings = GitHubIssueSyncSettings::upsert(pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "github_issue_sync_configured",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "enabled": settings.enabled,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+pub async fn delete_sync_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    GitHubIssueSyncSettings::delete(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Sync the project's linked issues now instead of waiting for the next poll
+pub async fn run_sync(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let settings = GitHubIssueSyncSettings::find_by_project_id(&deployment.db().pool, project.id)
+        .await?
+        .filter(|s| s.enabled)
+        .ok_or_else(|| {
+            ApiError::BadRequest("GitHub issue sync is not enabled for this project".to_string())
+        })?;
+    sync_service(&deployment)?.sync_project(&settings).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub async fn get_task_issue_link(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<TaskGitHubIssue>>>, ApiError> {
+    let link = TaskGitHubIssue::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(link)))
+}
+
+pub async fn link_task_issue(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<LinkTaskGitHubIssueRequest>,
+) -> Result<ResponseJson<ApiResponse<TaskGitHubIssue>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if let Some(link) = TaskGitHubIssue::find_by_task_id(pool, task.id).await? {
+        return Err(ApiError::Conflict(format!(
+            "Task is already linked to issue #{}",
+            link.issue_number
+        )));
+    }
+    let settings = GitHubIssueSyncSettings::find_by_project_id(pool, task.project_id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::BadRequest("Set up GitHub issue sync for this project first".to_string())
+        })?;
+
+    let link = sync_service(&deployment)?
+        .link_task_in_project(&task, &settings, payload.issue_number)
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(link)))
+}
+
+pub async fn unlink_task_issue(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    TaskGitHubIssue::delete_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub async fn get_task_issue_sync_events(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<GitHubIssueSyncEvent>>>, ApiError> {
+    let events =
+        GitHubIssueSyncEvent::find_by_task_id(&deployment.db().pool, task.id, SYNC_EVENT_LIMIT)
+            .await?;
+    Ok(ResponseJson(ApiResponse::success(events)))
+}
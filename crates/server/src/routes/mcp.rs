@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension, Router,
+    body::Body,
+    extract::{Request, State},
+    http::header,
+    response::{Json as ResponseJson, Response},
+    routing::{any, get},
+};
+use dashmap::DashMap;
+use db::models::workspace::{Workspace, WorkspaceContext};
+use deployment::Deployment;
+use rmcp::transport::streamable_http_server::{
+    StreamableHttpServerConfig, StreamableHttpService, session::local::LocalSessionManager,
+};
+use tokio::sync::broadcast::error::RecvError;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    mcp::task_server::{McpContext, TaskServer},
+};
+
+type WorkspaceMcpService = StreamableHttpService<TaskServer, LocalSessionManager>;
+
+/// One MCP service per workspace, so sessions persist across requests and each
+/// server is bound to the workspace its token belongs to
+#[derive(Clone, Default)]
+pub struct McpServices(Arc<DashMap<Uuid, WorkspaceMcpService>>);
+
+fn bearer_token(request: &Request) -> Option<&str> {
+    request
+        .headers()
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+}
+
+fn authenticated_workspace(
+    deployment: &DeploymentImpl,
+    request: &Request,
+) -> Result<Uuid, ApiError> {
+    bearer_token(request)
+        .and_then(|token| deployment.mcp_tokens().workspace_for_token(token))
+        .ok_or(ApiError::Unauthorized)
+}
+
+/// Streamable HTTP transport for the task MCP server, authenticated with the
+/// token handed to the workspace's coding agent in `VK_MCP_TOKEN`
+pub async fn handle_mcp(
+    State(deployment): State<DeploymentImpl>,
+    Extension(services): Extension<McpServices>,
+    request: Request,
+) -> Result<Response, ApiError> {
+    let workspace_id = authenticated_workspace(&deployment, &request)?;
+
+    let existing = services.0.get(&workspace_id).map(|service| service.clone());
+    let service = match existing {
+        Some(service) => service,
+        None => {
+            let service = create_service(&deployment, workspace_id).await?;
+            services.0.entry(workspace_id).or_insert(service).clone()
+        }
+    };
+
+    Ok(service.handle(request).await.map(Body::new))
+}
+
+/// Context of the workspace a token belongs to, so the stdio MCP server an
+/// agent launches can scope itself the same way the HTTP endpoint does
+pub async fn get_mcp_context(
+    State(deployment): State<DeploymentImpl>,
+    request: Request,
+) -> Result<ResponseJson<ApiResponse<WorkspaceContext>>, ApiError> {
+    let workspace_id = authenticated_workspace(&deployment, &request)?;
+    let context = load_context(&deployment, workspace_id).await?;
+    Ok(ResponseJson(ApiResponse::success(context)))
+}
+
+async fn load_context(
+    deployment: &DeploymentImpl,
+    workspace_id: Uuid,
+) -> Result<WorkspaceContext, ApiError> {
+    let pool = &deployment.db().pool;
+    let workspace = Workspace::find_by_id(pool, workspace_id)
+        .await?
+        .ok_or(ApiError::Unauthorized)?;
+    let task = workspace
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::Unauthorized)?;
+    Ok(Workspace::load_context(pool, workspace.id, task.id, task.project_id).await?)
+}
+
+async fn create_service(
+    deployment: &DeploymentImpl,
+    workspace_id: Uuid,
+) -> Result<WorkspaceMcpService, ApiError> {
+    let context = load_context(deployment, workspace_id).await?;
+
+    let base_url = deployment
+        .mcp_tokens()
+        .server_url()
+        .ok_or_else(|| ApiError::BadRequest("MCP server URL is not set".to_string()))?
+        .to_string();
+    let server = TaskServer::new(&base_url)
+        .with_context(Some(McpContext::from_workspace_context(context)))
+        .scoped_to_context();
+
+    Ok(StreamableHttpService::new(
+        move || Ok(server.clone()),
+        Arc::new(LocalSessionManager::default()),
+        StreamableHttpServerConfig::default(),
+    ))
+}
+
+/// Drop a workspace's MCP service, and the sessions it holds, once its token is
+/// revoked
+fn evict_revoked(deployment: &DeploymentImpl, services: McpServices) {
+    let mut revocations = deployment.mcp_tokens().subscribe_revocations();
+    tokio::spawn(async move {
+        loop {
+            match revocations.recv().await {
+                Ok(workspace_id) => {
+                    services.0.remove(&workspace_id);
+                }
+                // Missed revocations can't be replayed, so start over with an empty cache
+                Err(RecvError::Lagged(_)) => services.0.clear(),
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let services = McpServices::default();
+    evict_revoked(deployment, services.clone());
+
+    Router::new()
+        .route("/mcp", any(handle_mcp))
+        .route("/mcp/context", get(get_mcp_context))
+        .layer(Extension(services))
+}
@@ -9,21 +9,29 @@ use crate::{DeploymentImpl, middleware};
 pub mod approvals;
 pub mod config;
 pub mod containers;
+pub mod disk_usage;
 pub mod filesystem;
 // pub mod github;
 pub mod events;
 pub mod execution_processes;
 pub mod frontend;
+pub mod github_issue_sync;
 pub mod health;
 pub mod images;
+pub mod mcp;
 pub mod oauth;
 pub mod organizations;
+pub mod preview;
+pub mod project_secrets;
 pub mod projects;
+pub mod remote_issues;
 pub mod repo;
 pub mod scratch;
 pub mod sessions;
 pub mod tags;
 pub mod task_attempts;
+pub mod task_import;
+pub mod task_schedules;
 pub mod tasks;
 pub mod terminal;
 
@@ -33,9 +41,11 @@ pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
         .route("/health", get(health::health_check))
         .merge(config::router())
         .merge(containers::router(&deployment))
+        .merge(disk_usage::router())
         .merge(projects::router(&deployment))
         .merge(tasks::router(&deployment))
         .merge(task_attempts::router(&deployment))
+        .merge(task_schedules::router(&deployment))
         .merge(execution_processes::router(&deployment))
         .merge(tags::router(&deployment))
         .merge(oauth::router())
@@ -47,6 +57,7 @@ pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
         .merge(scratch::router(&deployment))
         .merge(sessions::router(&deployment))
         .merge(terminal::router())
+        .merge(mcp::router(&deployment))
         .nest("/images", images::routes())
         .layer(ValidateRequestHeaderLayer::custom(
             middleware::validate_origin,
@@ -0,0 +1,223 @@
+//! Reverse proxy that gives each workspace's dev server a stable URL
+//! regardless of the port it was started on. Every workspace gets its own
+//! loopback listener, so previews are served from the root of an origin that
+//! is separate from the app and its unauthenticated `/api`. The listener's port
+//! is saved per workspace and reused whenever the proxy starts, so preview URLs
+//! keep working across restarts.
+
+use std::{
+    net::{Ipv4Addr, TcpListener},
+    sync::LazyLock,
+};
+
+use axum::{
+    Router,
+    body::Body,
+    extract::{Request, State},
+    http::{HeaderName, HeaderValue, header},
+    response::{IntoResponse, Response},
+};
+use db::models::workspace_preview_port::WorkspacePreviewPort;
+use deployment::Deployment;
+use reqwest::StatusCode;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        // Let the browser follow redirects so they go back through the proxy
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .expect("failed to build preview proxy client")
+});
+
+/// Headers that only apply to a single connection and must not be forwarded
+fn is_hop_by_hop(name: &HeaderName) -> bool {
+    matches!(
+        name.as_str(),
+        "connection"
+            | "keep-alive"
+            | "proxy-authenticate"
+            | "proxy-authorization"
+            | "te"
+            | "trailer"
+            | "transfer-encoding"
+            | "upgrade"
+            | "host"
+    )
+}
+
+/// Start the workspace's preview proxy if it is not running yet and return
+/// the port it listens on
+pub async fn ensure_proxy(
+    deployment: &DeploymentImpl,
+    workspace_id: Uuid,
+) -> Result<u16, ApiError> {
+    if let Some(port) = deployment.preview().proxy_port(workspace_id) {
+        return Ok(port);
+    }
+
+    let pool = &deployment.db().pool;
+    let saved = WorkspacePreviewPort::find_all(pool).await?;
+    let saved_port = saved
+        .iter()
+        .find(|s| s.workspace_id == workspace_id)
+        .and_then(|s| u16::try_from(s.port).ok());
+    let taken: Vec<u16> = saved
+        .iter()
+        .filter(|s| s.workspace_id != workspace_id)
+        .filter_map(|s| u16::try_from(s.port).ok())
+        .collect();
+
+    let port = deployment.preview().ensure_proxy(workspace_id, || {
+        let listener = bind_listener(workspace_id, saved_port, &taken)?;
+        listener.set_nonblocking(true)?;
+        let port = listener.local_addr()?.port();
+        let listener = tokio::net::TcpListener::from_std(listener)?;
+
+        let app = Router::new()
+            .fallback(proxy_request)
+            .with_state((deployment.clone(), workspace_id));
+        let handle = tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, app).await {
+                tracing::warn!(
+                    "Preview proxy for workspace {} stopped: {}",
+                    workspace_id,
+                    e
+                );
+            }
+        });
+        Ok((port, handle))
+    })?;
+
+    if saved_port != Some(port)
+        && let Err(e) = WorkspacePreviewPort::upsert(pool, workspace_id, port).await
+    {
+        tracing::warn!(
+            "Failed to save preview proxy port for workspace {}: {}",
+            workspace_id,
+            e
+        );
+    }
+    Ok(port)
+}
+
+/// Bind the workspace's saved port, or a new one no other workspace has saved
+/// when there is none or something else holds it
+fn bind_listener(
+    workspace_id: Uuid,
+    saved_port: Option<u16>,
+    taken: &[u16],
+) -> std::io::Result<TcpListener> {
+    if let Some(port) = saved_port {
+        match TcpListener::bind((Ipv4Addr::LOCALHOST, port)) {
+            Ok(listener) => return Ok(listener),
+            Err(e) => tracing::warn!(
+                "Preview proxy port {} for workspace {} is unavailable, moving its preview URL: {}",
+                port,
+                workspace_id,
+                e
+            ),
+        }
+    }
+    loop {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+        if !taken.contains(&listener.local_addr()?.port()) {
+            return Ok(listener);
+        }
+    }
+}
+
+async fn proxy_request(
+    State((deployment, workspace_id)): State<(DeploymentImpl, Uuid)>,
+    request: Request,
+) -> Response {
+    let path = request.uri().path().trim_start_matches('/').to_string();
+    proxy(&deployment, workspace_id, &path, request).await
+}
+
+async fn proxy(
+    deployment: &DeploymentImpl,
+    workspace_id: Uuid,
+    path: &str,
+    request: Request,
+) -> Response {
+    let Some(target) = deployment.preview().get(workspace_id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            "No dev server has been started for this workspace",
+        )
+            .into_response();
+    };
+
+    let upstream_origin = target.upstream_origin();
+    let mut url = format!("{upstream_origin}/{path}");
+    if let Some(query) = request.uri().query() {
+        url.push('?');
+        url.push_str(query);
+    }
+
+    let (parts, body) = request.into_parts();
+    let mut upstream = CLIENT
+        .request(parts.method, &url)
+        .body(reqwest::Body::wrap_stream(body.into_data_stream()));
+    for (name, value) in parts.headers.iter() {
+        if !is_hop_by_hop(name) {
+            upstream = upstream.header(name, value);
+        }
+    }
+
+    let response = match upstream.send().await {
+        Ok(response) => response,
+        Err(e) => {
+            tracing::debug!("Preview proxy request to {} failed: {}", url, e);
+            return (
+                StatusCode::BAD_GATEWAY,
+                format!("Dev server at {upstream_origin} is not reachable"),
+            )
+                .into_response();
+        }
+    };
+
+    let mut builder = Response::builder().status(response.status());
+    for (name, value) in response.headers() {
+        if is_hop_by_hop(name) {
+            continue;
+        }
+        // Keep absolute redirects to the dev server inside the proxy
+        if name == header::LOCATION
+            && let Ok(location) = value.to_str()
+            && let Some(rest) = location.strip_prefix(&upstream_origin)
+            && let Ok(rewritten) = HeaderValue::from_str(if rest.is_empty() { "/" } else { rest })
+        {
+            builder = builder.header(name, rewritten);
+            continue;
+        }
+        builder = builder.header(name, value);
+    }
+
+    builder
+        .body(Body::from_stream(response.bytes_stream()))
+        .unwrap_or_else(|_| StatusCode::BAD_GATEWAY.into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bind_listener_reuses_saved_port() {
+        let workspace_id = Uuid::new_v4();
+        let first = bind_listener(workspace_id, None, &[]).unwrap();
+        let port = first.local_addr().unwrap().port();
+        drop(first);
+
+        let again = bind_listener(workspace_id, Some(port), &[]).unwrap();
+        assert_eq!(again.local_addr().unwrap().port(), port);
+
+        // The saved port is held by something else, so a new one is picked
+        let moved = bind_listener(workspace_id, Some(port), &[port]).unwrap();
+        assert_ne!(moved.local_addr().unwrap().port(), port);
+    }
+}
@@ -0,0 +1,116 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    project::Project,
+    project_repo::ProjectRepo,
+    project_secret::{CreateProjectSecret, ProjectSecret, UpdateProjectSecret},
+};
+use deployment::Deployment;
+use services::services::secrets::{SecretsError, SecretsService};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+pub async fn get_project_secrets(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectSecret>>>, ApiError> {
+    let secrets = ProjectSecret::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(secrets)))
+}
+
+pub async fn create_project_secret(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateProjectSecret>,
+) -> Result<ResponseJson<ApiResponse<ProjectSecret>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let name = payload.name.trim();
+    SecretsService::validate_name(name)?;
+    if payload.run_reasons.is_empty() {
+        return Err(SecretsError::NoRunReasons.into());
+    }
+    if let Some(repo_id) = payload.repo_id {
+        ProjectRepo::find_by_project_and_repo(pool, project.id, repo_id)
+            .await?
+            .ok_or_else(|| ApiError::BadRequest("Repository not found in project".to_string()))?;
+    }
+
+    let exists = ProjectSecret::find_by_project_id(pool, project.id)
+        .await?
+        .iter()
+        .any(|secret| secret.name == name && secret.repo_id == payload.repo_id);
+    if exists {
+        return Err(ApiError::Conflict(format!(
+            "A secret named {name} already exists for this scope"
+        )));
+    }
+
+    let ciphertext = deployment.secrets().encrypt(&payload.value)?;
+    let secret = ProjectSecret::create(
+        pool,
+        project.id,
+        payload.repo_id,
+        name,
+        &ciphertext,
+        &payload.run_reasons,
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_secret_created",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "repo_scoped": secret.repo_id.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(secret)))
+}
+
+async fn find_project_secret(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    secret_id: Uuid,
+) -> Result<ProjectSecret, ApiError> {
+    ProjectSecret::find_by_id(&deployment.db().pool, secret_id)
+        .await?
+        .filter(|secret| secret.project_id == project_id)
+        .ok_or_else(|| ApiError::NotFound("Secret not found".to_string()))
+}
+
+pub async fn update_project_secret(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, secret_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateProjectSecret>,
+) -> Result<ResponseJson<ApiResponse<ProjectSecret>>, ApiError> {
+    let existing = find_project_secret(&deployment, project_id, secret_id).await?;
+
+    let ciphertext = match &payload.value {
+        Some(value) => deployment.secrets().encrypt(value)?,
+        None => existing.ciphertext,
+    };
+    let run_reasons = payload.run_reasons.unwrap_or(existing.run_reasons.0);
+    if run_reasons.is_empty() {
+        return Err(SecretsError::NoRunReasons.into());
+    }
+
+    let secret =
+        ProjectSecret::update(&deployment.db().pool, secret_id, &ciphertext, &run_reasons).await?;
+    Ok(ResponseJson(ApiResponse::success(secret)))
+}
+
+pub async fn delete_project_secret(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, secret_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    find_project_secret(&deployment, project_id, secret_id).await?;
+    ProjectSecret::delete(&deployment.db().pool, secret_id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
@@ -10,7 +10,7 @@ use axum::{
     http::StatusCode,
     middleware::from_fn_with_state,
     response::{IntoResponse, Json as ResponseJson},
-    routing::{get, post},
+    routing::{get, post, put},
 };
 use db::models::{
     project::{CreateProject, Project, ProjectError, SearchResult, UpdateProject},
@@ -31,7 +31,12 @@ use utils::{
 };
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::load_project_middleware,
+    routes::{github_issue_sync, project_secrets, remote_issues, task_import},
+};
 
 #[derive(Deserialize, TS)]
 pub struct LinkToExistingRequest {
@@ -586,6 +591,26 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
             "/repositories",
             get(get_project_repositories).post(add_project_repository),
         )
+        .route(
+            "/github-issue-sync",
+            get(github_issue_sync::get_sync_settings)
+                .put(github_issue_sync::upsert_sync_settings)
+                .delete(github_issue_sync::delete_sync_settings),
+        )
+        .route("/github-issue-sync/run", post(github_issue_sync::run_sync))
+        .route(
+            "/secrets",
+            get(project_secrets::get_project_secrets).post(project_secrets::create_project_secret),
+        )
+        .route(
+            "/remote-issues/start",
+            post(remote_issues::start_remote_issue),
+        )
+        .route("/task-import", post(task_import::import_tasks))
+        .route(
+            "/task-import/preview",
+            post(task_import::preview_task_import),
+        )
         .layer(from_fn_with_state(
             deployment.clone(),
             load_project_middleware,
@@ -597,6 +622,11 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
             "/{project_id}/repositories/{repo_id}",
             get(get_project_repository).delete(delete_project_repository),
         )
+        .route(
+            "/{project_id}/secrets/{secret_id}",
+            put(project_secrets::update_project_secret)
+                .delete(project_secrets::delete_project_secret),
+        )
         .route("/stream/ws", get(stream_projects_ws))
         .nest("/{id}", project_id_router);
 
@@ -0,0 +1,102 @@
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    project::Project,
+    task::{CreateTask, TaskWithAttemptStatus},
+    task_remote_issue::TaskRemoteIssue,
+};
+use deployment::Deployment;
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use services::services::remote_issue::{RemoteIssueDetails, move_issue_to_status};
+use ts_rs::TS;
+use utils::{api::issues::RemoteStatusCategory, response::ApiResponse};
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::{
+        task_attempts::WorkspaceRepoInput,
+        tasks::{CreateAndStartTaskRequest, create_and_start_task},
+    },
+};
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct StartRemoteIssueRequest {
+    /// Issue in the remote project this project is linked to
+    pub remote_issue_id: Uuid,
+    pub executor_profile_id: ExecutorProfileId,
+    pub repos: Vec<WorkspaceRepoInput>,
+}
+
+/// Create a task from a remote issue's title, description and tags and start
+/// a workspace for it. The issue is moved to the "In progress" status, and the
+/// workspace is published with the issue set once the next publish runs.
+pub async fn start_remote_issue(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<StartRemoteIssueRequest>,
+) -> Result<ResponseJson<ApiResponse<TaskWithAttemptStatus>>, ApiError> {
+    let remote_project_id = project.remote_project_id.ok_or_else(|| {
+        ApiError::Conflict("Project is not linked to a remote project".to_string())
+    })?;
+    if payload.repos.is_empty() {
+        return Err(ApiError::BadRequest(
+            "At least one repository is required".to_string(),
+        ));
+    }
+
+    let client = deployment.remote_client()?;
+    let details =
+        RemoteIssueDetails::fetch(&client, remote_project_id, payload.remote_issue_id).await?;
+
+    let task = create_and_start_task(
+        &deployment,
+        CreateAndStartTaskRequest {
+            task: CreateTask::from_title_description(
+                project.id,
+                details.issue.title.clone(),
+                details.task_description(),
+            ),
+            executor_profile_id: payload.executor_profile_id,
+            repos: payload.repos,
+        },
+    )
+    .await?;
+
+    TaskRemoteIssue::create(
+        &deployment.db().pool,
+        task.id,
+        remote_project_id,
+        details.issue.id,
+    )
+    .await?;
+
+    // The task is already running, so a failure here is not worth failing the request
+    if let Err(e) = move_issue_to_status(
+        &client,
+        remote_project_id,
+        details.issue.id,
+        RemoteStatusCategory::Started,
+    )
+    .await
+    {
+        tracing::warn!(
+            "Failed to move remote issue {} to a started status: {}",
+            details.issue.id,
+            e
+        );
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "remote_issue_started",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": project.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
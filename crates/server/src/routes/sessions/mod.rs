@@ -11,7 +11,7 @@ use axum::{
     routing::{get, post},
 };
 use db::models::{
-    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
     scratch::{Scratch, ScratchType},
     session::{CreateSession, Session},
     workspace::{Workspace, WorkspaceError},
@@ -23,9 +23,10 @@ use executors::{
         ExecutorAction, ExecutorActionType, coding_agent_follow_up::CodingAgentFollowUpRequest,
     },
     executors::BaseCodingAgent,
+    logs::NormalizedEntry,
     profile::ExecutorProfileId,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use services::services::container::ContainerService;
 use ts_rs::TS;
 use utils::response::ApiResponse;
@@ -88,7 +89,7 @@ pub async fn create_session(
     Ok(ResponseJson(ApiResponse::success(session)))
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS)]
 pub struct CreateFollowUpAttempt {
     pub prompt: String,
     pub variant: Option<String>,
@@ -199,6 +200,7 @@ pub async fn follow_up(
             session_id: agent_session_id,
             executor_profile_id: executor_profile_id.clone(),
             working_dir: working_dir.clone(),
+            instructions: None,
         })
     } else {
         ExecutorActionType::CodingAgentInitialRequest(
@@ -206,6 +208,7 @@ pub async fn follow_up(
                 prompt,
                 executor_profile_id: executor_profile_id.clone(),
                 working_dir,
+                instructions: None,
             },
         )
     };
@@ -236,10 +239,103 @@ pub async fn follow_up(
     Ok(ResponseJson(ApiResponse::success(execution_process)))
 }
 
+const DEFAULT_CONVERSATION_PAGE_SIZE: usize = 50;
+const MAX_CONVERSATION_PAGE_SIZE: usize = 200;
+
+#[derive(Debug, Deserialize)]
+pub struct SessionConversationQuery {
+    /// Index of the first entry to return; omit to get the most recent entries
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct SessionConversationEntry {
+    pub execution_process_id: Uuid,
+    pub entry: NormalizedEntry,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct SessionConversationPage {
+    pub entries: Vec<SessionConversationEntry>,
+    /// Index of the first returned entry within the whole conversation
+    pub offset: usize,
+    pub total: usize,
+    /// True while a coding agent process in the session is still running
+    pub running: bool,
+}
+
+/// Offset and length of the requested page. Without an offset the page ends at
+/// the latest entry; an offset past the end yields an empty page.
+fn conversation_page_bounds(
+    total: usize,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> (usize, usize) {
+    let limit = limit
+        .unwrap_or(DEFAULT_CONVERSATION_PAGE_SIZE)
+        .clamp(1, MAX_CONVERSATION_PAGE_SIZE);
+    let offset = offset
+        .unwrap_or_else(|| total.saturating_sub(limit))
+        .min(total);
+    (offset, limit)
+}
+
+/// Normalized conversation across all coding agent turns of a session, one page at a time
+pub async fn get_session_conversation(
+    Extension(session): Extension<Session>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<SessionConversationQuery>,
+) -> Result<ResponseJson<ApiResponse<SessionConversationPage>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let processes: Vec<ExecutionProcess> =
+        ExecutionProcess::find_by_session_id(pool, session.id, false)
+            .await?
+            .into_iter()
+            .filter(|process| process.run_reason == ExecutionProcessRunReason::CodingAgent)
+            .collect();
+    let running = processes
+        .iter()
+        .any(|process| process.status == ExecutionProcessStatus::Running);
+
+    let mut turns = Vec::with_capacity(processes.len());
+    for process in &processes {
+        let entries = deployment
+            .container()
+            .normalized_entries(&process.id)
+            .await
+            .unwrap_or_default();
+        turns.push((process.id, entries));
+    }
+
+    let total = turns.iter().map(|(_, entries)| entries.len()).sum();
+    let (offset, limit) = conversation_page_bounds(total, query.offset, query.limit);
+    let entries = turns
+        .iter()
+        .flat_map(|(process_id, entries)| entries.iter().map(move |entry| (*process_id, entry)))
+        .skip(offset)
+        .take(limit)
+        .map(|(execution_process_id, entry)| SessionConversationEntry {
+            execution_process_id,
+            entry: entry.clone(),
+        })
+        .collect();
+
+    Ok(ResponseJson(ApiResponse::success(
+        SessionConversationPage {
+            entries,
+            offset,
+            total,
+            running,
+        },
+    )))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let session_id_router = Router::new()
         .route("/", get(get_session))
         .route("/follow-up", post(follow_up))
+        .route("/conversation", get(get_session_conversation))
         .route("/review", post(review::start_review))
         .layer(from_fn_with_state(
             deployment.clone(),
@@ -253,3 +349,41 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     Router::new().nest("/sessions", sessions_router)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latest_page_by_default() {
+        assert_eq!(conversation_page_bounds(120, None, None), (70, 50));
+        assert_eq!(conversation_page_bounds(10, None, None), (0, 50));
+        assert_eq!(conversation_page_bounds(0, None, None), (0, 50));
+    }
+
+    #[test]
+    fn offset_pages_forward_and_stops_at_the_end() {
+        assert_eq!(conversation_page_bounds(120, Some(0), Some(20)), (0, 20));
+        assert_eq!(
+            conversation_page_bounds(120, Some(100), Some(50)),
+            (100, 50)
+        );
+        assert_eq!(conversation_page_bounds(120, Some(500), None), (120, 50));
+    }
+
+    #[test]
+    fn limit_is_clamped() {
+        assert_eq!(conversation_page_bounds(1000, Some(0), Some(0)), (0, 1));
+        assert_eq!(
+            conversation_page_bounds(1000, Some(0), Some(10_000)),
+            (0, MAX_CONVERSATION_PAGE_SIZE)
+        );
+        assert_eq!(
+            conversation_page_bounds(1000, None, Some(10_000)),
+            (
+                1000 - MAX_CONVERSATION_PAGE_SIZE,
+                MAX_CONVERSATION_PAGE_SIZE
+            )
+        );
+    }
+}
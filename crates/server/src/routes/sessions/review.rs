@@ -107,6 +107,7 @@ pub async fn start_review(
             prompt,
             session_id: agent_session_id,
             working_dir: workspace.agent_working_dir.clone(),
+            instructions: None,
         }),
         None,
     );
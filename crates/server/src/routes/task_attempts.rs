@@ -1,5 +1,7 @@
 pub mod codex_setup;
 pub mod cursor_setup;
+pub mod fan_out;
+pub mod fork;
 pub mod gh_cli_setup;
 pub mod images;
 pub mod pr;
@@ -9,6 +11,7 @@ pub mod workspace_summary;
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
+    sync::{Arc, atomic::AtomicUsize},
 };
 
 use axum::{
@@ -30,7 +33,7 @@ use db::models::{
     repo::{Repo, RepoError},
     session::{CreateSession, Session},
     task::{Task, TaskRelationships, TaskStatus},
-    workspace::{CreateWorkspace, Workspace, WorkspaceError},
+    workspace::{Workspace, WorkspaceError},
     workspace_repo::{CreateWorkspaceRepo, RepoWithTargetBranch, WorkspaceRepo},
 };
 use deployment::Deployment;
@@ -46,18 +49,22 @@ use git2::BranchType;
 use serde::{Deserialize, Serialize};
 use services::services::{
     container::ContainerService,
+    diff_stream::apply_stream_omit_policy,
     file_search::SearchQuery,
-    git::{ConflictOp, GitCliError, GitServiceError},
+    git::{ConflictOp, DiffTarget, GitCliError, GitServiceError},
+    preview::WorkspacePreview,
     workspace_manager::WorkspaceManager,
 };
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
-use utils::response::ApiResponse;
+use utils::{diff::Diff, response::ApiResponse};
 use uuid::Uuid;
 
 use crate::{
-    DeploymentImpl, error::ApiError, middleware::load_workspace_middleware,
-    routes::task_attempts::gh_cli_setup::GhCliSetupError,
+    DeploymentImpl,
+    error::ApiError,
+    middleware::load_workspace_middleware,
+    routes::{preview, task_attempts::gh_cli_setup::GhCliSetupError},
 };
 
 #[derive(Debug, Deserialize, Serialize, TS)]
@@ -85,7 +92,7 @@ pub struct TaskAttemptQuery {
     pub task_id: Option<Uuid>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct DiffStreamQuery {
     #[serde(default)]
     pub stats_only: bool,
@@ -173,58 +180,38 @@ pub async fn create_task_attempt(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateTaskAttemptBody>,
 ) -> Result<ResponseJson<ApiResponse<Workspace>>, ApiError> {
-    let executor_profile_id = payload.executor_profile_id.clone();
-
     if payload.repos.is_empty() {
         return Err(ApiError::BadRequest(
             "At least one repository is required".to_string(),
         ));
     }
 
-    let pool = &deployment.db().pool;
     let task = Task::find_by_id(&deployment.db().pool, payload.task_id)
         .await?
         .ok_or(SqlxError::RowNotFound)?;
 
-    // Compute agent_working_dir based on repo count:
-    // - Single repo: use repo name as working dir (agent runs in repo directory)
-    // - Multiple repos: use None (agent runs in workspace root)
-    let agent_working_dir = if payload.repos.len() == 1 {
-        let repo = Repo::find_by_id(pool, payload.repos[0].repo_id)
-            .await?
-            .ok_or(RepoError::NotFound)?;
-        Some(repo.name)
-    } else {
-        None
-    };
-
-    let attempt_id = Uuid::new_v4();
-    let git_branch_name = deployment
-        .container()
-        .git_branch_from_workspace(&attempt_id, &task.title)
-        .await;
-
-    let workspace = Workspace::create(
-        pool,
-        &CreateWorkspace {
-            branch: git_branch_name.clone(),
-            agent_working_dir,
-        },
-        attempt_id,
-        payload.task_id,
+    let workspace = create_and_start_workspace(
+        &deployment,
+        &task,
+        &payload.repos,
+        &payload.executor_profile_id,
     )
     .await?;
 
-    let workspace_repos: Vec<CreateWorkspaceRepo> = payload
-        .repos
-        .iter()
-        .map(|r| CreateWorkspaceRepo {
-            repo_id: r.repo_id,
-            target_branch: r.target_branch.clone(),
-        })
-        .collect();
+    tracing::info!("Created attempt for task {}", task.id);
+
+    Ok(ResponseJson(ApiResponse::success(workspace)))
+}
 
-    WorkspaceRepo::create_many(pool, workspace.id, &workspace_repos).await?;
+/// Create a workspace for the task on the given repos and start the coding agent in it.
+/// Start failures are logged rather than returned, matching the single-attempt flow.
+pub(crate) async fn create_and_start_workspace(
+    deployment: &DeploymentImpl,
+    task: &Task,
+    repos: &[WorkspaceRepoInput],
+    executor_profile_id: &ExecutorProfileId,
+) -> Result<Workspace, ApiError> {
+    let workspace = create_workspace(deployment, task, repos).await?;
     if let Err(err) = deployment
         .container()
         .start_workspace(&workspace, executor_profile_id.clone())
@@ -232,7 +219,33 @@ pub async fn create_task_attempt(
     {
         tracing::error!("Failed to start task attempt: {}", err);
     }
+    track_attempt_started(deployment, &workspace, repos.len(), executor_profile_id).await;
+
+    Ok(workspace)
+}
 
+/// Create the workspace and its repo rows without starting anything in it
+pub(crate) async fn create_workspace(
+    deployment: &DeploymentImpl,
+    task: &Task,
+    repos: &[WorkspaceRepoInput],
+) -> Result<Workspace, ApiError> {
+    let workspace_repos: Vec<CreateWorkspaceRepo> = repos
+        .iter()
+        .map(|r| CreateWorkspaceRepo {
+            repo_id: r.repo_id,
+            target_branch: r.target_branch.clone(),
+        })
+        .collect();
+    Ok(deployment.create_workspace(task, &workspace_repos).await?)
+}
+
+pub(crate) async fn track_attempt_started(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    repository_count: usize,
+    executor_profile_id: &ExecutorProfileId,
+) {
     deployment
         .track_if_analytics_allowed(
             "task_attempt_started",
@@ -241,14 +254,10 @@ pub async fn create_task_attempt(
                 "variant": &executor_profile_id.variant,
                 "executor": &executor_profile_id.executor,
                 "workspace_id": workspace.id.to_string(),
-                "repository_count": payload.repos.len(),
+                "repository_count": repository_count,
             }),
         )
         .await;
-
-    tracing::info!("Created attempt for task {}", task.id);
-
-    Ok(ResponseJson(ApiResponse::success(workspace)))
 }
 
 #[axum::debug_handler]
@@ -347,6 +356,60 @@ async fn handle_task_attempt_diff_ws(
     Ok(())
 }
 
+/// Current diff of every repo in the workspace against its target branch
+pub async fn get_task_attempt_diff(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Query(params): Query<DiffStreamQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<Diff>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let container_ref = deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+    let workspace_dir = PathBuf::from(&container_ref);
+
+    let sent_bytes = Arc::new(AtomicUsize::new(0));
+    let mut diffs = Vec::new();
+    for repo_with_branch in
+        WorkspaceRepo::find_repos_with_target_branch_for_workspace(pool, workspace.id).await?
+    {
+        let repo = repo_with_branch.repo;
+        let base_commit = match deployment.git().get_base_commit(
+            &repo.path,
+            &workspace.branch,
+            &repo_with_branch.target_branch,
+        ) {
+            Ok(commit) => commit,
+            Err(e) => {
+                tracing::warn!(
+                    "Skipping diff for repo {}: failed to get base commit: {}",
+                    repo.name,
+                    e
+                );
+                continue;
+            }
+        };
+
+        let worktree_path = workspace_dir.join(&repo.name);
+        for mut diff in deployment.git().get_diffs(
+            DiffTarget::Worktree {
+                worktree_path: &worktree_path,
+                base_commit: &base_commit,
+            },
+            None,
+        )? {
+            apply_stream_omit_policy(&mut diff, &sent_bytes, params.stats_only);
+            diff.old_path = diff.old_path.map(|p| format!("{}/{}", repo.name, p));
+            diff.new_path = diff.new_path.map(|p| format!("{}/{}", repo.name, p));
+            diff.repo_id = Some(repo.id);
+            diffs.push(diff);
+        }
+    }
+
+    Ok(ResponseJson(ApiResponse::success(diffs)))
+}
+
 pub async fn stream_workspaces_ws(
     ws: WebSocketUpgrade,
     Query(query): Query<WorkspaceStreamQuery>,
@@ -685,7 +748,7 @@ pub struct BranchStatus {
     pub conflicted_files: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct RepoBranchStatus {
     pub repo_id: Uuid,
     pub repo_name: String,
@@ -1303,6 +1366,21 @@ pub async fn start_dev_server(
     Ok(ResponseJson(ApiResponse::success(execution_processes)))
 }
 
+/// Port and proxy origin of the workspace's dev server, if one has been
+/// started. The proxy is started on first request.
+pub async fn get_dev_server_preview(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<WorkspacePreview>>>, ApiError> {
+    if deployment.preview().get(workspace.id).is_none() {
+        return Ok(ResponseJson(ApiResponse::success(None)));
+    }
+    preview::ensure_proxy(&deployment, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(
+        deployment.preview().get(workspace.id),
+    )))
+}
+
 pub async fn get_task_attempt_children(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -1659,6 +1737,30 @@ pub async fn delete_workspace(
         }
     }
 
+    remove_workspace(&deployment, &workspace).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "workspace_deleted",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "task_id": workspace.task_id.to_string(),
+            }),
+        )
+        .await;
+
+    // Return 202 Accepted to indicate deletion was scheduled
+    Ok((StatusCode::ACCEPTED, ResponseJson(ApiResponse::success(()))))
+}
+
+/// Delete the workspace row and clean up its worktrees in the background.
+/// Callers are responsible for stopping its processes first.
+pub(crate) async fn remove_workspace(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+) -> Result<(), ApiError> {
+    let pool = &deployment.db().pool;
+
     // Gather data needed for background cleanup
     let workspace_dir = workspace.container_ref.clone().map(PathBuf::from);
     let repositories = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
@@ -1680,16 +1782,6 @@ pub async fn delete_workspace(
         return Err(ApiError::Database(SqlxError::RowNotFound));
     }
 
-    deployment
-        .track_if_analytics_allowed(
-            "workspace_deleted",
-            serde_json::json!({
-                "workspace_id": workspace.id.to_string(),
-                "task_id": workspace.task_id.to_string(),
-            }),
-        )
-        .await;
-
     // Spawn background cleanup task for filesystem resources
     if let Some(workspace_dir) = workspace_dir {
         let workspace_id = workspace.id;
@@ -1717,8 +1809,7 @@ pub async fn delete_workspace(
         });
     }
 
-    // Return 202 Accepted to indicate deletion was scheduled
-    Ok((StatusCode::ACCEPTED, ResponseJson(ApiResponse::success(()))))
+    Ok(())
 }
 
 /// Mark all coding agent turns for a workspace as seen
@@ -1745,9 +1836,11 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/run-agent-setup", post(run_agent_setup))
         .route("/gh-cli-setup", post(gh_cli_setup_handler))
         .route("/start-dev-server", post(start_dev_server))
+        .route("/dev-server/preview", get(get_dev_server_preview))
         .route("/run-setup-script", post(run_setup_script))
         .route("/run-cleanup-script", post(run_cleanup_script))
         .route("/branch-status", get(get_task_attempt_branch_status))
+        .route("/diff", get(get_task_attempt_diff))
         .route("/diff/ws", get(stream_task_attempt_diff_ws))
         .route("/merge", post(merge_task_attempt))
         .route("/push", post(push_task_attempt_branch))
@@ -1757,8 +1850,24 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/pr", post(pr::create_pr))
         .route("/pr/attach", post(pr::attach_existing_pr))
         .route("/pr/comments", get(pr::get_pr_comments))
+        .route(
+            "/pr/watch",
+            get(pr::get_pr_comment_watch)
+                .put(pr::update_pr_comment_watch)
+                .delete(pr::delete_pr_comment_watch),
+        )
+        .route("/pr/comment-follow-ups", get(pr::get_pr_comment_follow_ups))
+        .route(
+            "/pr/comment-follow-ups/confirm",
+            post(pr::confirm_pr_comment_follow_up),
+        )
+        .route(
+            "/pr/comment-follow-ups/dismiss",
+            post(pr::dismiss_pr_comment_follow_up),
+        )
         .route("/open-editor", post(open_task_attempt_in_editor))
         .route("/children", get(get_task_attempt_children))
+        .route("/fork", post(fork::fork_workspace))
         .route("/stop", post(stop_task_attempt_execution))
         .route("/change-target-branch", post(change_target_branch))
         .route("/rename-branch", post(rename_branch))
@@ -1776,6 +1885,18 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/count", get(get_workspace_count))
         .route("/stream/ws", get(stream_workspaces_ws))
         .route("/summary", post(workspace_summary::get_workspace_summaries))
+        .route(
+            "/fan-out",
+            get(fan_out::get_task_fan_outs).post(fan_out::create_fan_out),
+        )
+        .route(
+            "/fan-out/{group_id}/comparison",
+            get(fan_out::get_fan_out_comparison),
+        )
+        .route(
+            "/fan-out/{group_id}/promote",
+            post(fan_out::promote_fan_out_winner),
+        )
         .nest("/{id}", task_attempt_id_router)
         .nest("/{id}/images", images::router(deployment));
 
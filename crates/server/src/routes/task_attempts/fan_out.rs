@@ -0,0 +1,419 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    coding_agent_turn::CodingAgentTurn,
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    execution_process_verification::ExecutionProcessVerification,
+    fan_out::{FanOutAttempt, FanOutGroup},
+    task::Task,
+    workspace::Workspace,
+};
+use deployment::Deployment;
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use services::services::container::ContainerService;
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use super::{
+    WorkspaceRepoInput, create_workspace, remove_workspace, track_attempt_started,
+    workspace_summary::{DiffStats, compute_workspace_diff_stats},
+};
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Each attempt is a full worktree plus an agent process, so N is kept small
+pub const MAX_FAN_OUT_ATTEMPTS: usize = 6;
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct CreateFanOutBody {
+    pub task_id: Uuid,
+    /// One workspace is started per distinct profile
+    pub executor_profile_ids: Vec<ExecutorProfileId>,
+    pub repos: Vec<WorkspaceRepoInput>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct FanOutGroupWithAttempts {
+    pub group: FanOutGroup,
+    pub attempts: Vec<FanOutAttempt>,
+}
+
+/// One attempt's results, for side-by-side comparison
+#[derive(Debug, Serialize, TS)]
+pub struct FanOutAttemptComparison {
+    pub workspace: Workspace,
+    pub executor_profile_id: ExecutorProfileId,
+    /// None when the worktree no longer exists
+    pub diff_stats: Option<DiffStats>,
+    /// Verify script results for the latest coding agent turn
+    pub verifications: Vec<ExecutionProcessVerification>,
+    pub latest_status: Option<ExecutionProcessStatus>,
+    /// Time spent in finished coding agent turns
+    pub duration_ms: i64,
+    /// Context window usage at the end of the latest turn that reported it.
+    /// None when the executor does not report usage
+    pub context_tokens: Option<i64>,
+    /// Input tokens consumed across all coding agent turns. None when no turn
+    /// reported them
+    pub input_tokens: Option<i64>,
+    /// Output tokens generated across all coding agent turns
+    pub output_tokens: Option<i64>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct FanOutComparison {
+    pub group: FanOutGroup,
+    pub attempts: Vec<FanOutAttemptComparison>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FanOutQuery {
+    pub task_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct PromoteFanOutWinnerRequest {
+    pub workspace_id: Uuid,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct PromoteFanOutWinnerResponse {
+    pub group: FanOutGroup,
+    /// Losing workspaces left unarchived because they are pinned
+    pub pinned_workspace_ids: Vec<Uuid>,
+}
+
+/// Drop repeated profiles, keeping the first occurrence, and check the
+/// remaining count is within bounds
+fn distinct_fan_out_profiles(
+    executor_profile_ids: &[ExecutorProfileId],
+) -> Result<Vec<ExecutorProfileId>, ApiError> {
+    let mut profiles: Vec<ExecutorProfileId> = Vec::with_capacity(executor_profile_ids.len());
+    for profile in executor_profile_ids {
+        if !profiles.contains(profile) {
+            profiles.push(profile.clone());
+        }
+    }
+    if profiles.len() < 2 {
+        return Err(ApiError::BadRequest(
+            "A fan-out needs at least two distinct executor profiles".to_string(),
+        ));
+    }
+    if profiles.len() > MAX_FAN_OUT_ATTEMPTS {
+        return Err(ApiError::BadRequest(format!(
+            "A fan-out can run at most {MAX_FAN_OUT_ATTEMPTS} executor profiles"
+        )));
+    }
+    Ok(profiles)
+}
+
+/// Stop and delete workspaces started for a fan-out that failed part way,
+/// then the group itself
+async fn roll_back_fan_out(deployment: &DeploymentImpl, group: &FanOutGroup, created: &[Uuid]) {
+    let pool = &deployment.db().pool;
+    for workspace_id in created {
+        // Re-read so the worktree path set during start is cleaned up too
+        let workspace = match Workspace::find_by_id(pool, *workspace_id).await {
+            Ok(Some(workspace)) => workspace,
+            Ok(None) => continue,
+            Err(e) => {
+                tracing::error!("Failed to load fan-out workspace {}: {}", workspace_id, e);
+                continue;
+            }
+        };
+        deployment.container().try_stop(&workspace, true).await;
+        if let Err(e) = remove_workspace(deployment, &workspace).await {
+            tracing::error!(
+                "Failed to roll back fan-out workspace {}: {}",
+                workspace_id,
+                e
+            );
+        }
+    }
+    if let Err(e) = FanOutGroup::delete(pool, group.id).await {
+        tracing::error!("Failed to delete fan-out group {}: {}", group.id, e);
+    }
+}
+
+pub async fn create_fan_out(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateFanOutBody>,
+) -> Result<ResponseJson<ApiResponse<FanOutGroupWithAttempts>>, ApiError> {
+    if payload.repos.is_empty() {
+        return Err(ApiError::BadRequest(
+            "At least one repository is required".to_string(),
+        ));
+    }
+    let executor_profile_ids = distinct_fan_out_profiles(&payload.executor_profile_ids)?;
+
+    let pool = &deployment.db().pool;
+    let task = Task::find_by_id(pool, payload.task_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    // Unlike a single attempt, a start failure fails the whole fan-out: a
+    // comparison with a missing contender isn't what was asked for
+    let group = FanOutGroup::create(pool, Uuid::new_v4(), task.id).await?;
+    let mut created = Vec::with_capacity(executor_profile_ids.len());
+    let mut attempts = Vec::with_capacity(executor_profile_ids.len());
+    for executor_profile_id in &executor_profile_ids {
+        let result = async {
+            let workspace = create_workspace(&deployment, &task, &payload.repos).await?;
+            created.push(workspace.id);
+            let attempt =
+                FanOutAttempt::create(pool, group.id, workspace.id, executor_profile_id).await?;
+            deployment
+                .container()
+                .start_workspace(&workspace, executor_profile_id.clone())
+                .await?;
+            track_attempt_started(
+                &deployment,
+                &workspace,
+                payload.repos.len(),
+                executor_profile_id,
+            )
+            .await;
+            Ok::<_, ApiError>(attempt)
+        }
+        .await;
+
+        match result {
+            Ok(attempt) => attempts.push(attempt),
+            Err(err) => {
+                tracing::error!(
+                    "Fan-out {} failed to start {}, rolling back: {}",
+                    group.id,
+                    executor_profile_id,
+                    err
+                );
+                roll_back_fan_out(&deployment, &group, &created).await;
+                return Err(err);
+            }
+        }
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "fan_out_started",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "fan_out_group_id": group.id.to_string(),
+                "executors": executor_profile_ids
+                    .iter()
+                    .map(|p| p.executor.to_string())
+                    .collect::<Vec<_>>(),
+            }),
+        )
+        .await;
+
+    tracing::info!(
+        "Started fan-out {} with {} attempts for task {}",
+        group.id,
+        attempts.len(),
+        task.id
+    );
+
+    Ok(ResponseJson(ApiResponse::success(
+        FanOutGroupWithAttempts { group, attempts },
+    )))
+}
+
+pub async fn get_task_fan_outs(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<FanOutQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<FanOutGroupWithAttempts>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let mut groups = Vec::new();
+    for group in FanOutGroup::find_by_task_id(pool, query.task_id).await? {
+        let attempts = FanOutAttempt::find_by_group_id(pool, group.id).await?;
+        groups.push(FanOutGroupWithAttempts { group, attempts });
+    }
+    Ok(ResponseJson(ApiResponse::success(groups)))
+}
+
+pub async fn get_fan_out_comparison(
+    State(deployment): State<DeploymentImpl>,
+    Path(group_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<FanOutComparison>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let group = FanOutGroup::find_by_id(pool, group_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    let mut attempts = Vec::new();
+    for attempt in FanOutAttempt::find_by_group_id(pool, group.id).await? {
+        let Some(workspace) = Workspace::find_by_id(pool, attempt.workspace_id).await? else {
+            continue;
+        };
+
+        let diff_stats = if workspace.container_ref.is_some() {
+            compute_workspace_diff_stats(&deployment, &workspace)
+                .await
+                .ok()
+        } else {
+            None
+        };
+
+        let latest_agent = ExecutionProcess::find_latest_by_workspace_and_run_reason(
+            pool,
+            workspace.id,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?;
+        let verifications = match &latest_agent {
+            Some(process) => {
+                ExecutionProcessVerification::find_by_execution_process_id(pool, process.id).await?
+            }
+            None => Vec::new(),
+        };
+
+        let (input_tokens, output_tokens) =
+            CodingAgentTurn::consumed_tokens_for_workspace(pool, workspace.id).await?;
+
+        attempts.push(FanOutAttemptComparison {
+            executor_profile_id: attempt.executor_profile_id.0,
+            diff_stats,
+            verifications,
+            latest_status: latest_agent.map(|p| p.status),
+            duration_ms: ExecutionProcess::coding_agent_duration_ms_for_workspace(
+                pool,
+                workspace.id,
+            )
+            .await?,
+            context_tokens: CodingAgentTurn::latest_context_tokens_for_workspace(
+                pool,
+                workspace.id,
+            )
+            .await?,
+            input_tokens,
+            output_tokens,
+            workspace,
+        });
+    }
+
+    Ok(ResponseJson(ApiResponse::success(FanOutComparison {
+        group,
+        attempts,
+    })))
+}
+
+/// Keep the chosen workspace and archive the others, stopping anything still running in them.
+/// Pinned losers are stopped but not archived, and are listed in the response.
+pub async fn promote_fan_out_winner(
+    State(deployment): State<DeploymentImpl>,
+    Path(group_id): Path<Uuid>,
+    Json(payload): Json<PromoteFanOutWinnerRequest>,
+) -> Result<ResponseJson<ApiResponse<PromoteFanOutWinnerResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let group = FanOutGroup::find_by_id(pool, group_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let attempts = FanOutAttempt::find_by_group_id(pool, group.id).await?;
+    if !attempts
+        .iter()
+        .any(|a| a.workspace_id == payload.workspace_id)
+    {
+        return Err(ApiError::BadRequest(
+            "Workspace is not part of this fan-out".to_string(),
+        ));
+    }
+
+    let mut pinned_workspace_ids = Vec::new();
+    for attempt in attempts
+        .iter()
+        .filter(|a| a.workspace_id != payload.workspace_id)
+    {
+        let Some(workspace) = Workspace::find_by_id(pool, attempt.workspace_id).await? else {
+            continue;
+        };
+        deployment.container().try_stop(&workspace, true).await;
+        if workspace.pinned {
+            pinned_workspace_ids.push(workspace.id);
+        } else {
+            Workspace::set_archived(pool, workspace.id, true).await?;
+        }
+    }
+    Workspace::set_archived(pool, payload.workspace_id, false).await?;
+    FanOutGroup::set_winner(pool, group.id, payload.workspace_id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "fan_out_winner_promoted",
+            serde_json::json!({
+                "task_id": group.task_id.to_string(),
+                "fan_out_group_id": group.id.to_string(),
+                "workspace_id": payload.workspace_id.to_string(),
+                "pinned_losers": pinned_workspace_ids.len(),
+            }),
+        )
+        .await;
+
+    let group = FanOutGroup::find_by_id(pool, group.id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    Ok(ResponseJson(ApiResponse::success(
+        PromoteFanOutWinnerResponse {
+            group,
+            pinned_workspace_ids,
+        },
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::executors::BaseCodingAgent;
+
+    use super::*;
+
+    fn profile(executor: BaseCodingAgent, variant: Option<&str>) -> ExecutorProfileId {
+        ExecutorProfileId {
+            executor,
+            variant: variant.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn repeated_profiles_are_dropped_in_order() {
+        let profiles = distinct_fan_out_profiles(&[
+            profile(BaseCodingAgent::ClaudeCode, None),
+            profile(BaseCodingAgent::Codex, None),
+            profile(BaseCodingAgent::ClaudeCode, None),
+            profile(BaseCodingAgent::ClaudeCode, Some("PLAN")),
+        ])
+        .unwrap();
+        assert_eq!(
+            profiles,
+            vec![
+                profile(BaseCodingAgent::ClaudeCode, None),
+                profile(BaseCodingAgent::Codex, None),
+                profile(BaseCodingAgent::ClaudeCode, Some("PLAN")),
+            ]
+        );
+    }
+
+    #[test]
+    fn duplicates_do_not_count_towards_the_minimum() {
+        let result = distinct_fan_out_profiles(&[
+            profile(BaseCodingAgent::Codex, None),
+            profile(BaseCodingAgent::Codex, None),
+        ]);
+        assert!(matches!(result, Err(ApiError::BadRequest(_))));
+    }
+
+    #[test]
+    fn too_many_profiles_are_rejected() {
+        let profiles: Vec<_> = (0..=MAX_FAN_OUT_ATTEMPTS)
+            .map(|i| profile(BaseCodingAgent::ClaudeCode, Some(&format!("V{i}"))))
+            .collect();
+        assert!(matches!(
+            distinct_fan_out_profiles(&profiles),
+            Err(ApiError::BadRequest(_))
+        ));
+        assert!(distinct_fan_out_profiles(&profiles[..MAX_FAN_OUT_ATTEMPTS]).is_ok());
+    }
+}
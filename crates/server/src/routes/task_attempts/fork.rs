@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    coding_agent_turn::CodingAgentTurn,
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    execution_process_repo_state::ExecutionProcessRepoState,
+    session::Session,
+    task::Task,
+    workspace::{CreateWorkspace, Workspace},
+    workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
+};
+use deployment::Deployment;
+use executors::{
+    actions::ExecutorActionType,
+    executors::BaseAgentCapability,
+    profile::{ExecutorConfigs, ExecutorProfileId},
+};
+use serde::{Deserialize, Serialize};
+use services::services::container::{ContainerService, WorkspaceForkPoint};
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use super::remove_workspace;
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct ForkWorkspaceRequest {
+    /// Coding agent turn to fork after; its changes are included in the new workspace
+    pub execution_process_id: Uuid,
+    /// First message sent to the forked agent session
+    pub prompt: String,
+    pub variant: Option<String>,
+}
+
+/// Create a new workspace from a point in this workspace's conversation. The new
+/// worktrees are checked out at the commits recorded after the chosen turn and the
+/// agent session is forked there, leaving the original workspace untouched.
+pub async fn fork_workspace(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ForkWorkspaceRequest>,
+) -> Result<ResponseJson<ApiResponse<Workspace>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let process = ExecutionProcess::find_by_id(pool, payload.execution_process_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let session = Session::find_by_id(pool, process.session_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    if session.workspace_id != workspace.id {
+        return Err(ApiError::BadRequest(
+            "Process does not belong to this workspace".to_string(),
+        ));
+    }
+    if process.run_reason != ExecutionProcessRunReason::CodingAgent {
+        return Err(ApiError::BadRequest(
+            "Only coding agent turns can be forked".to_string(),
+        ));
+    }
+    if process.status == ExecutionProcessStatus::Running {
+        return Err(ApiError::BadRequest(
+            "Wait for the turn to finish before forking it".to_string(),
+        ));
+    }
+
+    let executor_profile_id = match process.executor_action()?.typ() {
+        ExecutorActionType::CodingAgentInitialRequest(req) => req.executor_profile_id.clone(),
+        ExecutorActionType::CodingAgentFollowUpRequest(req) => req.executor_profile_id.clone(),
+        ExecutorActionType::ReviewRequest(req) => req.executor_profile_id.clone(),
+        ExecutorActionType::ScriptRequest(_) => {
+            return Err(ApiError::BadRequest(
+                "Only coding agent turns can be forked".to_string(),
+            ));
+        }
+    };
+    let executor_profile_id = ExecutorProfileId {
+        executor: executor_profile_id.executor,
+        variant: payload.variant.or(executor_profile_id.variant),
+    };
+
+    let supports_fork = ExecutorConfigs::get_cached()
+        .get_coding_agent(&executor_profile_id)
+        .is_some_and(|agent| {
+            agent
+                .capabilities()
+                .contains(&BaseAgentCapability::SessionFork)
+        });
+    if !supports_fork {
+        return Err(ApiError::BadRequest(format!(
+            "{} does not support forking sessions",
+            executor_profile_id.executor
+        )));
+    }
+
+    // The fork runs in a new worktree, where agents that keep their sessions
+    // per directory can't find the session to fork
+    if !executor_profile_id.executor.resumes_across_directories() {
+        return Err(ApiError::BadRequest(format!(
+            "{} sessions are tied to the directory they ran in and can't be forked into a new workspace",
+            executor_profile_id.executor
+        )));
+    }
+
+    let agent_session_id = CodingAgentTurn::find_by_execution_process_id(pool, process.id)
+        .await?
+        .and_then(|turn| turn.agent_session_id)
+        .ok_or_else(|| {
+            ApiError::BadRequest("This turn has no agent session to fork".to_string())
+        })?;
+
+    let commits: HashMap<Uuid, String> =
+        ExecutionProcessRepoState::find_by_execution_process_id(pool, process.id)
+            .await?
+            .into_iter()
+            .filter_map(|state| {
+                state
+                    .after_head_commit
+                    .or(state.before_head_commit)
+                    .map(|commit| (state.repo_id, commit))
+            })
+            .collect();
+
+    let task = Task::find_by_id(pool, workspace.task_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    let fork_id = Uuid::new_v4();
+    let git_branch_name = deployment
+        .container()
+        .git_branch_from_workspace(&fork_id, &task.title)
+        .await;
+
+    let fork = Workspace::create(
+        pool,
+        &CreateWorkspace {
+            branch: git_branch_name,
+            agent_working_dir: workspace.agent_working_dir.clone(),
+        },
+        fork_id,
+        task.id,
+    )
+    .await?;
+
+    let workspace_repos: Vec<CreateWorkspaceRepo> =
+        WorkspaceRepo::find_by_workspace_id(pool, workspace.id)
+            .await?
+            .into_iter()
+            .map(|r| CreateWorkspaceRepo {
+                repo_id: r.repo_id,
+                target_branch: r.target_branch,
+            })
+            .collect();
+    let started = async {
+        WorkspaceRepo::create_many(pool, fork.id, &workspace_repos).await?;
+        deployment
+            .container()
+            .fork_workspace(
+                &fork,
+                executor_profile_id.clone(),
+                &WorkspaceForkPoint {
+                    agent_session_id: Some(agent_session_id),
+                    commits,
+                },
+                payload.prompt,
+            )
+            .await?;
+        Ok::<_, ApiError>(())
+    }
+    .await;
+    if let Err(e) = started {
+        roll_back_fork(&deployment, fork.id).await;
+        return Err(e);
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "workspace_forked",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "source_workspace_id": workspace.id.to_string(),
+                "workspace_id": fork.id.to_string(),
+                "executor": &executor_profile_id.executor,
+            }),
+        )
+        .await;
+
+    tracing::info!(
+        "Forked workspace {} from process {} into {}",
+        workspace.id,
+        process.id,
+        fork.id
+    );
+
+    Ok(ResponseJson(ApiResponse::success(fork)))
+}
+
+/// Stop and delete a forked workspace whose start failed, along with its repos
+async fn roll_back_fork(deployment: &DeploymentImpl, fork_id: Uuid) {
+    // Re-read so the worktree path set during start is cleaned up too
+    let fork = match Workspace::find_by_id(&deployment.db().pool, fork_id).await {
+        Ok(Some(fork)) => fork,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::error!("Failed to load forked workspace {}: {}", fork_id, e);
+            return;
+        }
+    };
+    deployment.container().try_stop(&fork, true).await;
+    if let Err(e) = remove_workspace(deployment, &fork).await {
+        tracing::error!("Failed to roll back forked workspace {}: {}", fork_id, e);
+    }
+}
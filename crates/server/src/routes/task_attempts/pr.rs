@@ -8,6 +8,9 @@ use axum::{
 use db::models::{
     execution_process::{ExecutionProcess, ExecutionProcessRunReason},
     merge::{Merge, MergeStatus},
+    pr_comment_watch::{
+        PrCommentFollowUp, PrCommentFollowUpStatus, PrCommentWatch, PrCommentWatchMode,
+    },
     repo::{Repo, RepoError},
     session::{CreateSession, Session},
     task::{Task, TaskStatus},
@@ -88,6 +91,16 @@ pub struct GetPrCommentsQuery {
     pub repo_id: Uuid,
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdatePrCommentWatchRequest {
+    pub mode: PrCommentWatchMode,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct PrCommentFollowUpActionRequest {
+    pub follow_up_id: Uuid,
+}
+
 pub const DEFAULT_PR_DESCRIPTION_PROMPT: &str = r#"Update the PR that was just created with a better title and description.
 The PR number is #{pr_number} and the URL is {pr_url}.
 
@@ -168,12 +181,14 @@ async fn trigger_pr_description_follow_up(
             session_id: agent_session_id,
             executor_profile_id: executor_profile_id.clone(),
             working_dir: working_dir.clone(),
+            instructions: None,
         })
     } else {
         ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
             prompt,
             executor_profile_id: executor_profile_id.clone(),
             working_dir,
+            instructions: None,
         })
     };
 
@@ -580,3 +595,105 @@ pub async fn get_pr_comments(
         }
     }
 }
+
+pub async fn get_pr_comment_watch(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<PrCommentWatch>>>, ApiError> {
+    let watch = PrCommentWatch::find_by_workspace_id(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(watch)))
+}
+
+/// Start watching the workspace's PRs for new review comments, or change the watch mode
+pub async fn update_pr_comment_watch(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(request): Json<UpdatePrCommentWatchRequest>,
+) -> Result<ResponseJson<ApiResponse<PrCommentWatch>>, ApiError> {
+    let watch = PrCommentWatch::upsert(&deployment.db().pool, workspace.id, request.mode).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "pr_comment_watch_updated",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "mode": request.mode,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(watch)))
+}
+
+pub async fn delete_pr_comment_watch(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    PrCommentWatch::delete_by_workspace_id(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub async fn get_pr_comment_follow_ups(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<PrCommentFollowUp>>>, ApiError> {
+    let follow_ups =
+        PrCommentFollowUp::find_by_workspace_id(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(follow_ups)))
+}
+
+async fn find_pending_follow_up(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    follow_up_id: Uuid,
+) -> Result<PrCommentFollowUp, ApiError> {
+    let follow_up = PrCommentFollowUp::find_by_id(&deployment.db().pool, follow_up_id)
+        .await?
+        .filter(|f| f.workspace_id == workspace.id)
+        .ok_or_else(|| ApiError::NotFound("PR comment follow-up not found".to_string()))?;
+    if follow_up.status != PrCommentFollowUpStatus::PendingConfirmation {
+        return Err(ApiError::Conflict(
+            "PR comment follow-up is no longer pending".to_string(),
+        ));
+    }
+    Ok(follow_up)
+}
+
+/// Send a pending batch of review comments to the agent
+pub async fn confirm_pr_comment_follow_up(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(request): Json<PrCommentFollowUpActionRequest>,
+) -> Result<ResponseJson<ApiResponse<PrCommentFollowUp>>, ApiError> {
+    let follow_up = find_pending_follow_up(&deployment, &workspace, request.follow_up_id).await?;
+    let follow_up = deployment
+        .dispatch_pr_comment_follow_up(follow_up.id)
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "pr_comment_follow_up_confirmed",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "comment_count": follow_up.comment_ids.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(follow_up)))
+}
+
+pub async fn dismiss_pr_comment_follow_up(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(request): Json<PrCommentFollowUpActionRequest>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let follow_up = find_pending_follow_up(&deployment, &workspace, request.follow_up_id).await?;
+    PrCommentFollowUp::update_status(
+        &deployment.db().pool,
+        follow_up.id,
+        PrCommentFollowUpStatus::Dismissed,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
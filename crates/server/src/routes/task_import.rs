@@ -0,0 +1,155 @@
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{project::Project, project_repo::ProjectRepo, repo::Repo, task::Task};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::{
+    git_host::{GitHostError, GitHostService, GitHubIssueFilter},
+    task_import::{self, TaskImportItem, TaskImportResult, TaskImporter},
+};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum TaskImportSource {
+    /// Issues of the repository's GitHub remote, fetched with `gh`
+    GithubIssues {
+        repo_id: Uuid,
+        #[serde(default)]
+        filter: GitHubIssueFilter,
+    },
+    Markdown {
+        content: String,
+    },
+    Csv {
+        content: String,
+    },
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct TaskImportPreviewRequest {
+    pub source: TaskImportSource,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct TaskImportRequest {
+    /// Items returned by the preview, optionally edited
+    pub items: Vec<TaskImportItem>,
+    /// Tags added to every imported task
+    #[serde(default)]
+    pub tag_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub skip_duplicates: bool,
+}
+
+async fn existing_tasks(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+) -> Result<Vec<Task>, ApiError> {
+    Ok(
+        Task::find_by_project_id_with_attempt_status(&deployment.db().pool, project_id)
+            .await?
+            .into_iter()
+            .map(|t| t.task)
+            .collect(),
+    )
+}
+
+async fn fetch_github_issues(
+    deployment: &DeploymentImpl,
+    project: &Project,
+    repo_id: Uuid,
+    filter: &GitHubIssueFilter,
+) -> Result<Vec<TaskImportItem>, ApiError> {
+    let pool = &deployment.db().pool;
+    ProjectRepo::find_by_project_and_repo(pool, project.id, repo_id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("Repository not found in project".to_string()))?;
+    let repo = Repo::find_by_id(pool, repo_id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("Repository not found".to_string()))?;
+
+    let remote_url = deployment.git().get_default_remote_url(&repo.path)?;
+    let GitHostService::GitHub(github) = GitHostService::from_url(&remote_url)? else {
+        return Err(ApiError::BadRequest(
+            "Issues can only be imported from GitHub repositories".to_string(),
+        ));
+    };
+    let issues = match github.list_issues(&repo.path, &remote_url, filter).await {
+        Ok(issues) => issues,
+        Err(GitHostError::CliNotInstalled { .. }) => {
+            return Err(ApiError::BadRequest(
+                "Install the GitHub CLI (`gh`) to import issues".to_string(),
+            ));
+        }
+        Err(GitHostError::AuthFailed(_)) => {
+            return Err(ApiError::BadRequest(
+                "Run `gh auth login` to import issues".to_string(),
+            ));
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(issues
+        .into_iter()
+        .map(task_import::from_github_issue)
+        .collect())
+}
+
+/// Parse the source into the tasks that would be created, flagging any that
+/// duplicate existing tasks. Nothing is written.
+pub async fn preview_task_import(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<TaskImportPreviewRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskImportItem>>>, ApiError> {
+    let mut items = match payload.source {
+        TaskImportSource::GithubIssues { repo_id, filter } => {
+            fetch_github_issues(&deployment, &project, repo_id, &filter).await?
+        }
+        TaskImportSource::Markdown { content } => task_import::parse_markdown(&content),
+        TaskImportSource::Csv { content } => task_import::parse_csv(&content)?,
+    };
+
+    task_import::mark_duplicates(&mut items, &existing_tasks(&deployment, project.id).await?);
+    Ok(ResponseJson(ApiResponse::success(items)))
+}
+
+pub async fn import_tasks(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(mut payload): Json<TaskImportRequest>,
+) -> Result<ResponseJson<ApiResponse<TaskImportResult>>, ApiError> {
+    // Re-check against tasks created since the preview was taken
+    task_import::mark_duplicates(
+        &mut payload.items,
+        &existing_tasks(&deployment, project.id).await?,
+    );
+
+    let importer = TaskImporter::new(
+        &deployment.db().pool,
+        deployment.image(),
+        project.id,
+        payload.tag_ids,
+        payload.skip_duplicates,
+    )
+    .await?;
+    let result = importer.import(&payload.items).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "tasks_imported",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "created": result.created.len(),
+                "skipped_duplicates": result.skipped_duplicates,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(result)))
+}
@@ -0,0 +1,144 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Query, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, post, put},
+};
+use chrono::Utc;
+use db::models::task_schedule::{
+    CreateTaskSchedule, TaskSchedule, TaskScheduleRepo, TaskScheduleRun, UpdateTaskSchedule,
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::scheduler::CronSchedule;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_task_schedule_middleware};
+
+/// Number of past runs returned with a schedule's history
+const RUN_HISTORY_LIMIT: i64 = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct TaskScheduleQuery {
+    pub project_id: Uuid,
+}
+
+/// Validate the expression and template, returning the first fire time for an
+/// enabled schedule
+fn next_run_at_for(
+    cron_expression: &str,
+    repos: &[TaskScheduleRepo],
+    enabled: bool,
+) -> Result<Option<chrono::DateTime<Utc>>, ApiError> {
+    let cron =
+        CronSchedule::parse(cron_expression).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    if repos.is_empty() {
+        return Err(ApiError::BadRequest(
+            "At least one repository is required".to_string(),
+        ));
+    }
+    let next_run_at = cron.next_after(Utc::now());
+    if next_run_at.is_none() {
+        return Err(ApiError::BadRequest(
+            "Cron expression never matches a date".to_string(),
+        ));
+    }
+    Ok(next_run_at.filter(|_| enabled))
+}
+
+pub async fn get_task_schedules(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TaskScheduleQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskSchedule>>>, ApiError> {
+    let schedules =
+        TaskSchedule::find_by_project_id(&deployment.db().pool, query.project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(schedules)))
+}
+
+pub async fn create_task_schedule(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateTaskSchedule>,
+) -> Result<ResponseJson<ApiResponse<TaskSchedule>>, ApiError> {
+    let next_run_at = next_run_at_for(
+        &payload.cron_expression,
+        &payload.repos,
+        payload.enabled.unwrap_or(true),
+    )?;
+    let schedule =
+        TaskSchedule::create(&deployment.db().pool, &payload, Uuid::new_v4(), next_run_at).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_schedule_created",
+            serde_json::json!({
+                "schedule_id": schedule.id.to_string(),
+                "project_id": schedule.project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(schedule)))
+}
+
+pub async fn update_task_schedule(
+    Extension(schedule): Extension<TaskSchedule>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateTaskSchedule>,
+) -> Result<ResponseJson<ApiResponse<TaskSchedule>>, ApiError> {
+    let next_run_at = next_run_at_for(&payload.cron_expression, &payload.repos, payload.enabled)?;
+    let schedule =
+        TaskSchedule::update(&deployment.db().pool, schedule.id, &payload, next_run_at).await?;
+    Ok(ResponseJson(ApiResponse::success(schedule)))
+}
+
+pub async fn delete_task_schedule(
+    Extension(schedule): Extension<TaskSchedule>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = TaskSchedule::delete(&deployment.db().pool, schedule.id).await?;
+    if rows_affected == 0 {
+        Err(ApiError::Database(sqlx::Error::RowNotFound))
+    } else {
+        Ok(ResponseJson(ApiResponse::success(())))
+    }
+}
+
+pub async fn get_task_schedule_runs(
+    Extension(schedule): Extension<TaskSchedule>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskScheduleRun>>>, ApiError> {
+    let runs =
+        TaskScheduleRun::find_by_schedule_id(&deployment.db().pool, schedule.id, RUN_HISTORY_LIMIT)
+            .await?;
+    Ok(ResponseJson(ApiResponse::success(runs)))
+}
+
+/// Fire the schedule immediately, without changing its next run time
+pub async fn run_task_schedule_now(
+    Extension(schedule): Extension<TaskSchedule>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskScheduleRun>>, ApiError> {
+    let run = deployment
+        .run_task_schedule(schedule.id, Utc::now())
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(run)))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let schedule_router = Router::new()
+        .route("/", put(update_task_schedule).delete(delete_task_schedule))
+        .route("/runs", get(get_task_schedule_runs))
+        .route("/run", post(run_task_schedule_now))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_task_schedule_middleware,
+        ));
+
+    let inner = Router::new()
+        .route("/", get(get_task_schedules).post(create_task_schedule))
+        .nest("/{schedule_id}", schedule_router);
+
+    Router::new().nest("/task-schedules", inner)
+}
@@ -14,10 +14,10 @@ use axum::{
 };
 use db::models::{
     image::TaskImage,
-    repo::{Repo, RepoError},
+    repo::Repo,
     task::{CreateTask, Task, TaskWithAttemptStatus, UpdateTask},
-    workspace::{CreateWorkspace, Workspace},
-    workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
+    workspace::Workspace,
+    workspace_repo::WorkspaceRepo,
 };
 use deployment::Deployment;
 use executors::profile::ExecutorProfileId;
@@ -30,8 +30,13 @@ use utils::response::ApiResponse;
 use uuid::Uuid;
 
 use crate::{
-    DeploymentImpl, error::ApiError, middleware::load_task_middleware,
-    routes::task_attempts::WorkspaceRepoInput,
+    DeploymentImpl,
+    error::ApiError,
+    middleware::load_task_middleware,
+    routes::{
+        github_issue_sync,
+        task_attempts::{WorkspaceRepoInput, create_workspace},
+    },
 };
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -148,6 +153,15 @@ pub async fn create_task_and_start(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateAndStartTaskRequest>,
 ) -> Result<ResponseJson<ApiResponse<TaskWithAttemptStatus>>, ApiError> {
+    let task = create_and_start_task(&deployment, payload).await?;
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
+
+/// Create a task and start a workspace for it on the given repos
+pub(crate) async fn create_and_start_task(
+    deployment: &DeploymentImpl,
+    payload: CreateAndStartTaskRequest,
+) -> Result<TaskWithAttemptStatus, ApiError> {
     if payload.repos.is_empty() {
         return Err(ApiError::BadRequest(
             "At least one repository is required".to_string(),
@@ -175,44 +189,7 @@ pub async fn create_task_and_start(
         )
         .await;
 
-    let attempt_id = Uuid::new_v4();
-    let git_branch_name = deployment
-        .container()
-        .git_branch_from_workspace(&attempt_id, &task.title)
-        .await;
-
-    // Compute agent_working_dir based on repo count:
-    // - Single repo: use repo name as working dir (agent runs in repo directory)
-    // - Multiple repos: use None (agent runs in workspace root)
-    let agent_working_dir = if payload.repos.len() == 1 {
-        let repo = Repo::find_by_id(pool, payload.repos[0].repo_id)
-            .await?
-            .ok_or(RepoError::NotFound)?;
-        Some(repo.name)
-    } else {
-        None
-    };
-
-    let workspace = Workspace::create(
-        pool,
-        &CreateWorkspace {
-            branch: git_branch_name,
-            agent_working_dir,
-        },
-        attempt_id,
-        task.id,
-    )
-    .await?;
-
-    let workspace_repos: Vec<CreateWorkspaceRepo> = payload
-        .repos
-        .iter()
-        .map(|r| CreateWorkspaceRepo {
-            repo_id: r.repo_id,
-            target_branch: r.target_branch.clone(),
-        })
-        .collect();
-    WorkspaceRepo::create_many(&deployment.db().pool, workspace.id, &workspace_repos).await?;
+    let workspace = create_workspace(&deployment, &task, &payload.repos).await?;
 
     let is_attempt_running = deployment
         .container()
@@ -237,12 +214,12 @@ pub async fn create_task_and_start(
         .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
 
     tracing::info!("Started attempt for task {}", task.id);
-    Ok(ResponseJson(ApiResponse::success(TaskWithAttemptStatus {
+    Ok(TaskWithAttemptStatus {
         task,
         has_in_progress_attempt: is_attempt_running,
         last_attempt_failed: false,
         executor: payload.executor_profile_id.executor.to_string(),
-    })))
+    })
 }
 
 pub async fn update_task(
@@ -396,6 +373,16 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     let task_id_router = Router::new()
         .route("/", get(get_task))
+        .route(
+            "/github-issue",
+            get(github_issue_sync::get_task_issue_link)
+                .post(github_issue_sync::link_task_issue)
+                .delete(github_issue_sync::unlink_task_issue),
+        )
+        .route(
+            "/github-issue/events",
+            get(github_issue_sync::get_task_issue_sync_events),
+        )
         .merge(task_actions_router)
         .layer(from_fn_with_state(deployment.clone(), load_task_middleware));
 
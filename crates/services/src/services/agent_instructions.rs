@@ -0,0 +1,123 @@
+use std::path::{Component, Path};
+
+use db::models::{project::Project, repo::Repo};
+
+/// Build the instructions block merged into coding agent prompts: the project's
+/// instructions, then each repo's inline instructions and instructions file as
+/// checked out in the workspace. Returns None when there is nothing to add.
+pub async fn resolve(project: &Project, repos: &[Repo], workspace_root: &Path) -> Option<String> {
+    let mut sections = Vec::new();
+
+    if let Some(text) = non_empty(project.agent_instructions.as_deref()) {
+        sections.push(("Project instructions".to_string(), text.to_string()));
+    }
+
+    for repo in repos {
+        let mut parts = Vec::new();
+        if let Some(text) = non_empty(repo.agent_instructions.as_deref()) {
+            parts.push(text.to_string());
+        }
+        if let Some(file) = non_empty(repo.agent_instructions_file.as_deref())
+            && let Some(text) = read_instructions_file(&workspace_root.join(&repo.name), file).await
+        {
+            parts.push(text);
+        }
+        if !parts.is_empty() {
+            sections.push((
+                format!("Instructions for repository `{}`", repo.name),
+                parts.join("\n\n"),
+            ));
+        }
+    }
+
+    compose(&sections)
+}
+
+fn non_empty(text: Option<&str>) -> Option<&str> {
+    text.map(str::trim).filter(|text| !text.is_empty())
+}
+
+async fn read_instructions_file(repo_root: &Path, file: &str) -> Option<String> {
+    let relative = Path::new(file);
+    if !relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+    {
+        tracing::warn!(
+            "Ignoring agent instructions file outside the repo: {}",
+            file
+        );
+        return None;
+    }
+
+    match tokio::fs::read_to_string(repo_root.join(relative)).await {
+        Ok(contents) => non_empty(Some(&contents)).map(str::to_string),
+        Err(e) => {
+            tracing::debug!(
+                "Agent instructions file {} not readable in {}: {}",
+                file,
+                repo_root.display(),
+                e
+            );
+            None
+        }
+    }
+}
+
+fn compose(sections: &[(String, String)]) -> Option<String> {
+    if sections.is_empty() {
+        return None;
+    }
+
+    Some(
+        sections
+            .iter()
+            .map(|(heading, body)| format!("## {heading}\n\n{body}"))
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compose_joins_sections_under_headings() {
+        assert_eq!(compose(&[]), None);
+        assert_eq!(
+            compose(&[
+                ("Project instructions".to_string(), "Be brief".to_string()),
+                (
+                    "Instructions for repository `api`".to_string(),
+                    "Run just fmt".to_string()
+                ),
+            ])
+            .as_deref(),
+            Some(
+                "## Project instructions\n\nBe brief\n\n## Instructions for repository `api`\n\nRun just fmt"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn instructions_file_must_stay_inside_the_repo() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo_root = dir.path().join("repo");
+        std::fs::create_dir_all(&repo_root).unwrap();
+        std::fs::write(repo_root.join("AGENTS.md"), "Never touch generated/\n").unwrap();
+        std::fs::write(dir.path().join("secret.md"), "outside").unwrap();
+
+        assert_eq!(
+            read_instructions_file(&repo_root, "AGENTS.md")
+                .await
+                .as_deref(),
+            Some("Never touch generated/")
+        );
+        assert_eq!(
+            read_instructions_file(&repo_root, "../secret.md").await,
+            None
+        );
+        assert_eq!(read_instructions_file(&repo_root, "missing.md").await, None);
+    }
+}
@@ -19,9 +19,11 @@ use executors::{
     },
 };
 use futures::future::{BoxFuture, FutureExt, Shared};
+use serde::{Deserialize, Serialize};
 use sqlx::{Error as SqlxError, SqlitePool};
 use thiserror::Error;
 use tokio::sync::{RwLock, oneshot};
+use ts_rs::TS;
 use utils::{
     approvals::{ApprovalRequest, ApprovalResponse, ApprovalStatus},
     log_msg::LogMsg,
@@ -46,6 +48,16 @@ pub struct ToolContext {
     pub execution_process_id: Uuid,
 }
 
+/// An approval that is still waiting for a response
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct PendingApprovalInfo {
+    pub approval_id: String,
+    pub execution_process_id: Uuid,
+    pub tool_name: String,
+    /// The tool use entry the agent is waiting on
+    pub entry: NormalizedEntry,
+}
+
 #[derive(Clone)]
 pub struct Approvals {
     pending: Arc<DashMap<String, PendingApproval>>,
@@ -261,6 +273,23 @@ impl Approvals {
         map.get(execution_process_id).cloned()
     }
 
+    /// Pending approvals belonging to any of the given execution processes
+    pub fn pending_for_execution_processes(
+        &self,
+        execution_process_ids: &[Uuid],
+    ) -> Vec<PendingApprovalInfo> {
+        self.pending
+            .iter()
+            .filter(|entry| execution_process_ids.contains(&entry.value().execution_process_id))
+            .map(|entry| PendingApprovalInfo {
+                approval_id: entry.key().clone(),
+                execution_process_id: entry.value().execution_process_id,
+                tool_name: entry.value().tool_name.clone(),
+                entry: entry.value().entry.clone(),
+            })
+            .collect()
+    }
+
     /// Check which execution processes have pending approvals.
     /// Returns a set of execution_process_ids that have at least one pending approval.
     pub fn get_pending_execution_process_ids(
@@ -17,6 +17,50 @@ fn default_pr_auto_description_enabled() -> bool {
     true
 }
 
+fn default_idle_after_hours() -> u32 {
+    24
+}
+
+fn default_artifact_dirs() -> Vec<String> {
+    vec!["node_modules".to_string(), "target".to_string()]
+}
+
+fn default_evict_worktrees() -> bool {
+    true
+}
+
+/// Limits on how much disk workspace worktrees may use. When a limit is
+/// exceeded, archived or idle workspaces are trimmed least recently used
+/// first: build artifacts go before whole worktrees.
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct WorkspaceDiskPolicy {
+    #[serde(default)]
+    pub max_total_gb: Option<f64>,
+    #[serde(default)]
+    pub max_project_gb: Option<f64>,
+    /// Unarchived workspaces count as idle after this many hours without activity
+    #[serde(default = "default_idle_after_hours")]
+    pub idle_after_hours: u32,
+    /// Directory names treated as disposable build artifacts
+    #[serde(default = "default_artifact_dirs")]
+    pub artifact_dirs: Vec<String>,
+    /// Remove whole worktrees when dropping artifacts is not enough
+    #[serde(default = "default_evict_worktrees")]
+    pub evict_worktrees: bool,
+}
+
+impl Default for WorkspaceDiskPolicy {
+    fn default() -> Self {
+        Self {
+            max_total_gb: None,
+            max_project_gb: None,
+            idle_after_hours: default_idle_after_hours(),
+            artifact_dirs: default_artifact_dirs(),
+            evict_worktrees: default_evict_worktrees(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, TS)]
 pub struct Config {
     pub config_version: String,
@@ -47,6 +91,8 @@ pub struct Config {
     pub beta_workspaces_invitation_sent: bool,
     #[serde(default)]
     pub commit_reminder: bool,
+    #[serde(default)]
+    pub workspace_disk: WorkspaceDiskPolicy,
 }
 
 impl Config {
@@ -75,6 +121,7 @@ impl Config {
             beta_workspaces: false,
             beta_workspaces_invitation_sent: false,
             commit_reminder: false,
+            workspace_disk: WorkspaceDiskPolicy::default(),
         }
     }
 
@@ -128,6 +175,7 @@ impl Default for Config {
             beta_workspaces: false,
             beta_workspaces_invitation_sent: false,
             commit_reminder: false,
+            workspace_disk: WorkspaceDiskPolicy::default(),
         }
     }
 }
@@ -1,7 +1,9 @@
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
+    str::FromStr,
     sync::Arc,
+    time::Duration,
 };
 
 use anyhow::{Error as AnyhowError, anyhow};
@@ -18,11 +20,16 @@ use db::{
         execution_process_repo_state::{
             CreateExecutionProcessRepoState, ExecutionProcessRepoState,
         },
+        execution_process_verification::ExecutionProcessVerification,
+        project::Project,
         repo::Repo,
+        scratch::DraftFollowUpData,
         session::{CreateSession, Session, SessionError},
         task::{Task, TaskStatus},
         workspace::{Workspace, WorkspaceError},
+        workspace_disk_usage::WorkspaceDiskUsage,
         workspace_repo::WorkspaceRepo,
+        worktree_pool_entry::WorktreePoolEntry,
     },
 };
 #[cfg(feature = "qa-mode")]
@@ -32,14 +39,16 @@ use executors::profile::ExecutorConfigs;
 use executors::{
     actions::{
         ExecutorAction, ExecutorActionType,
+        coding_agent_follow_up::CodingAgentFollowUpRequest,
         coding_agent_initial::CodingAgentInitialRequest,
         script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
     },
-    executors::{ExecutorError, StandardCodingAgentExecutor},
+    executors::{BaseCodingAgent, ExecutorError, StandardCodingAgentExecutor},
     logs::{NormalizedEntry, NormalizedEntryError, NormalizedEntryType, utils::ConversationPatch},
     profile::ExecutorProfileId,
 };
 use futures::{StreamExt, future};
+use moka::future::Cache;
 use sqlx::Error as SqlxError;
 use thiserror::Error;
 use tokio::{sync::RwLock, task::JoinHandle};
@@ -51,6 +60,9 @@ use utils::{
 use uuid::Uuid;
 
 use crate::services::{
+    agent_instructions,
+    config::WorkspaceDiskPolicy,
+    disk_usage::{self, DiskEviction, DiskEvictionAction},
     git::{GitService, GitServiceError},
     notification::NotificationService,
     workspace_manager::WorkspaceError as WorkspaceManagerError,
@@ -58,6 +70,27 @@ use crate::services::{
 };
 pub type ContainerRef = String;
 
+/// Normalized conversations of finished execution processes, keyed by process id.
+/// Their logs no longer change, so re-normalizing them on every read is wasted work.
+pub type FinishedConversationCache = Cache<Uuid, Arc<Vec<NormalizedEntry>>>;
+
+pub fn finished_conversation_cache() -> FinishedConversationCache {
+    Cache::builder()
+        .max_capacity(256)
+        .time_to_idle(Duration::from_secs(600))
+        .build()
+}
+
+/// Point in an existing conversation that a new workspace is forked from
+#[derive(Debug, Clone)]
+pub struct WorkspaceForkPoint {
+    /// Agent session the new workspace's first turn forks. None starts a fresh
+    /// session instead, for agents that can't resume from another directory.
+    pub agent_session_id: Option<String>,
+    /// Commit each repo's worktree is reset to, keyed by repo id
+    pub commits: HashMap<Uuid, String>,
+}
+
 #[derive(Debug, Error)]
 pub enum ContainerError {
     #[error(transparent)]
@@ -86,6 +119,8 @@ pub enum ContainerError {
 pub trait ContainerService {
     fn msg_stores(&self) -> &Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>;
 
+    fn finished_conversations(&self) -> &FinishedConversationCache;
+
     fn db(&self) -> &DBService;
 
     fn git(&self) -> &GitService;
@@ -162,6 +197,28 @@ pub trait ContainerService {
 
     /// Finalize task execution by updating status to InReview and sending notifications
     async fn finalize_task(&self, ctx: &ExecutionContext) {
+        // Work that failed its verify script stays in progress
+        let verification_failed = ExecutionProcessVerification::latest_coding_agent_turn_failed(
+            &self.db().pool,
+            ctx.session.id,
+        )
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("Failed to load verification results: {e}");
+            false
+        });
+        if verification_failed {
+            if !matches!(ctx.execution_process.status, ExecutionProcessStatus::Killed) {
+                let title = format!("Verification Failed: {}", ctx.task.title);
+                let message = format!(
+                    "⚠️ '{}' did not pass its verify script\nBranch: {:?}\nExecutor: {:?}",
+                    ctx.task.title, ctx.workspace.branch, ctx.session.executor
+                );
+                self.notification_service().notify(&title, &message).await;
+            }
+            return;
+        }
+
         if let Err(e) =
             Task::update_status(&self.db().pool, ctx.task.id, TaskStatus::InReview).await
         {
@@ -252,6 +309,7 @@ pub trait ContainerService {
                 ExecutionProcessRunReason::CodingAgent
                     | ExecutionProcessRunReason::SetupScript
                     | ExecutionProcessRunReason::CleanupScript
+                    | ExecutionProcessRunReason::VerifyScript
             ) && let Ok(Some(session)) =
                 Session::find_by_id(&self.db().pool, process.session_id).await
                 && let Ok(Some(workspace)) =
@@ -453,6 +511,31 @@ pub trait ContainerService {
         chained
     }
 
+    /// Chain each repo's verify script in front of `next_action`
+    fn verify_actions_for_repos(
+        &self,
+        repos: &[Repo],
+        next_action: Option<ExecutorAction>,
+    ) -> Option<ExecutorAction> {
+        let mut chained = next_action;
+        let mut any_verify = false;
+        for repo in repos.iter().rev() {
+            if let Some(script) = repo.verify_script.as_ref().filter(|s| !s.trim().is_empty()) {
+                any_verify = true;
+                chained = Some(ExecutorAction::new(
+                    ExecutorActionType::ScriptRequest(ScriptRequest {
+                        script: script.clone(),
+                        language: ScriptRequestLanguage::Bash,
+                        context: ScriptContext::VerifyScript,
+                        working_dir: Some(repo.name.clone()),
+                    }),
+                    chained.map(Box::new),
+                ));
+            }
+        }
+        chained.filter(|_| any_verify)
+    }
+
     async fn try_stop(&self, workspace: &Workspace, include_dev_server: bool) {
         // stop execution processes for this workspace's sessions
         let sessions = match Session::find_by_workspace_id(&self.db().pool, workspace.id).await {
@@ -488,6 +571,121 @@ pub trait ContainerService {
         }
     }
 
+    /// Re-measure a workspace directory and store the result. Best-effort:
+    /// failures are logged and the previous measurement is kept.
+    async fn refresh_disk_usage(&self, workspace: &Workspace, artifact_dirs: &[String]) {
+        let Some(container_ref) = &workspace.container_ref else {
+            return;
+        };
+        let dir = PathBuf::from(container_ref);
+        let artifact_dirs = artifact_dirs.to_vec();
+        let usage =
+            match tokio::task::spawn_blocking(move || disk_usage::measure(&dir, &artifact_dirs))
+                .await
+            {
+                Ok(Ok(usage)) => usage,
+                Ok(Err(e)) => {
+                    tracing::debug!(
+                        "Failed to measure disk usage of workspace {}: {}",
+                        workspace.id,
+                        e
+                    );
+                    return;
+                }
+                Err(e) => {
+                    tracing::warn!("Disk usage measurement task failed: {}", e);
+                    return;
+                }
+            };
+
+        if let Err(e) = WorkspaceDiskUsage::upsert(
+            &self.db().pool,
+            workspace.id,
+            usage.total_bytes as i64,
+            usage.artifact_bytes as i64,
+        )
+        .await
+        {
+            tracing::warn!(
+                "Failed to store disk usage of workspace {}: {}",
+                workspace.id,
+                e
+            );
+        }
+    }
+
+    /// Measure workspaces that have not been measured yet, then evict build
+    /// artifacts or worktrees until usage is within the policy's limits.
+    /// Returns the evictions that were carried out.
+    async fn enforce_disk_policy(
+        &self,
+        policy: &WorkspaceDiskPolicy,
+    ) -> Result<Vec<DiskEviction>, ContainerError> {
+        let pool = &self.db().pool;
+        for workspace in WorkspaceDiskUsage::find_unmeasured_workspaces(pool).await? {
+            self.refresh_disk_usage(&workspace, &policy.artifact_dirs)
+                .await;
+        }
+
+        let entries = WorkspaceDiskUsage::find_entries(pool).await?;
+        let plan = disk_usage::plan_evictions(&entries, policy, chrono::Utc::now());
+        let mut applied = Vec::new();
+
+        for eviction in plan {
+            let Some(workspace) = Workspace::find_by_id(pool, eviction.workspace_id).await? else {
+                continue;
+            };
+            // A process may have started since usage was read
+            if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(
+                pool,
+                workspace.id,
+            )
+            .await?
+                || !ExecutionProcess::find_running_dev_servers_by_workspace(pool, workspace.id)
+                    .await?
+                    .is_empty()
+            {
+                continue;
+            }
+
+            match eviction.action {
+                DiskEvictionAction::RemoveArtifacts => {
+                    let Some(container_ref) = &workspace.container_ref else {
+                        continue;
+                    };
+                    let dir = PathBuf::from(container_ref);
+                    let artifact_dirs = policy.artifact_dirs.clone();
+                    let freed = tokio::task::spawn_blocking(move || {
+                        disk_usage::remove_artifacts(&dir, &artifact_dirs)
+                    })
+                    .await
+                    .map_err(|e| ContainerError::Other(anyhow!(e)))??;
+                    tracing::info!(
+                        "Removed {} bytes of build artifacts from workspace {}",
+                        freed,
+                        workspace.id
+                    );
+                    self.refresh_disk_usage(&workspace, &policy.artifact_dirs)
+                        .await;
+                    applied.push(DiskEviction {
+                        freed_bytes: freed,
+                        ..eviction
+                    });
+                }
+                DiskEvictionAction::RemoveWorktree => {
+                    tracing::info!(
+                        "Removing worktrees of workspace {} to free disk space",
+                        workspace.id
+                    );
+                    self.delete(&workspace).await?;
+                    applied.push(eviction);
+                }
+            }
+        }
+
+        Ok(applied)
+    }
+
     async fn ensure_container_exists(
         &self,
         workspace: &Workspace,
@@ -766,6 +964,56 @@ pub trait ContainerService {
         }
     }
 
+    /// Normalized conversation of an execution process as it stands now. Unlike
+    /// `stream_normalized_logs` this does not wait for a running process to finish.
+    /// Processes without a live MsgStore have finished, so their result is cached.
+    async fn normalized_entries(&self, id: &Uuid) -> Option<Arc<Vec<NormalizedEntry>>> {
+        let (messages, finished): (Vec<LogMsg>, bool) = match self.get_msg_store_by_id(id).await {
+            Some(store) => (store.get_history(), false),
+            None => {
+                if let Some(entries) = self.finished_conversations().get(id).await {
+                    return Some(entries);
+                }
+                let messages = self
+                    .stream_normalized_logs(id)
+                    .await?
+                    .filter_map(|msg| future::ready(msg.ok()))
+                    .collect()
+                    .await;
+                (messages, true)
+            }
+        };
+
+        // Replay the patches the same way the frontend does, then keep only the
+        // normalized entries
+        let mut conversation = serde_json::json!({ "entries": [] });
+        for msg in messages {
+            if let LogMsg::JsonPatch(patch) = msg
+                && let Err(e) = json_patch::patch(&mut conversation, &patch)
+            {
+                tracing::warn!("Failed to apply conversation patch for {}: {}", id, e);
+            }
+        }
+
+        let entries = match conversation["entries"].take() {
+            serde_json::Value::Array(entries) => entries,
+            _ => Vec::new(),
+        };
+        let entries: Arc<Vec<NormalizedEntry>> = Arc::new(
+            entries
+                .into_iter()
+                .filter(|value| value["type"] == "NORMALIZED_ENTRY")
+                .filter_map(|mut value| serde_json::from_value(value["content"].take()).ok())
+                .collect(),
+        );
+        if finished {
+            self.finished_conversations()
+                .insert(*id, entries.clone())
+                .await;
+        }
+        Some(entries)
+    }
+
     fn spawn_stream_raw_logs_to_db(&self, execution_id: &Uuid) -> JoinHandle<()> {
         let execution_id = *execution_id;
         let msg_stores = self.msg_stores().clone();
@@ -854,6 +1102,83 @@ pub trait ContainerService {
             .await?
             .ok_or(SqlxError::RowNotFound)?;
 
+        let working_dir = workspace
+            .agent_working_dir
+            .as_ref()
+            .filter(|dir| !dir.is_empty())
+            .cloned();
+
+        let coding_action_type =
+            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt: task.to_prompt(),
+                executor_profile_id: executor_profile_id.clone(),
+                working_dir,
+                instructions: None,
+            });
+
+        self.start_workspace_session(workspace, &executor_profile_id, coding_action_type)
+            .await
+    }
+
+    /// Create a workspace whose worktrees start at the given commits and whose first
+    /// session continues a fork of an existing agent session
+    async fn fork_workspace(
+        &self,
+        workspace: &Workspace,
+        executor_profile_id: ExecutorProfileId,
+        fork_point: &WorkspaceForkPoint,
+        prompt: String,
+    ) -> Result<ExecutionProcess, ContainerError> {
+        let container_ref = self.create(workspace).await?;
+        let workspace_dir = PathBuf::from(container_ref);
+
+        let repos = WorkspaceRepo::find_repos_for_workspace(&self.db().pool, workspace.id).await?;
+        for repo in &repos {
+            if let Some(commit) = fork_point.commits.get(&repo.id) {
+                self.git().reset_worktree_to_commit(
+                    &workspace_dir.join(&repo.name),
+                    commit,
+                    true,
+                )?;
+            }
+        }
+
+        let working_dir = workspace
+            .agent_working_dir
+            .as_ref()
+            .filter(|dir| !dir.is_empty())
+            .cloned();
+
+        let coding_action_type = match &fork_point.agent_session_id {
+            Some(session_id) => {
+                ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                    prompt,
+                    session_id: session_id.clone(),
+                    executor_profile_id: executor_profile_id.clone(),
+                    working_dir,
+                    instructions: None,
+                })
+            }
+            None => ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt,
+                executor_profile_id: executor_profile_id.clone(),
+                working_dir,
+                instructions: None,
+            }),
+        };
+
+        self.start_workspace_session(workspace, &executor_profile_id, coding_action_type)
+            .await
+    }
+
+    /// Create the first session of a freshly created workspace and start the coding
+    /// agent in it, after the repos' setup scripts
+    async fn start_workspace_session(
+        &self,
+        workspace: &Workspace,
+        executor_profile_id: &ExecutorProfileId,
+        coding_action_type: ExecutorActionType,
+    ) -> Result<ExecutionProcess, ContainerError> {
         let repos = WorkspaceRepo::find_repos_for_workspace(&self.db().pool, workspace.id).await?;
 
         let workspace = Workspace::find_by_id(&self.db().pool, workspace.id)
@@ -871,28 +1196,19 @@ pub trait ContainerService {
         )
         .await?;
 
-        let prompt = task.to_prompt();
-
-        let repos_with_setup: Vec<_> = repos.iter().filter(|r| r.setup_script.is_some()).collect();
+        // Worktrees claimed from the pool were set up before they were handed over
+        let prewarmed =
+            WorktreePoolEntry::find_claimed_repo_ids(&self.db().pool, workspace.id).await?;
+        let repos_with_setup: Vec<_> = repos
+            .iter()
+            .filter(|r| r.setup_script.is_some() && !prewarmed.contains(&r.id))
+            .collect();
 
         let all_parallel = repos_with_setup.iter().all(|r| r.parallel_setup_script);
 
         let cleanup_action = self.cleanup_actions_for_repos(&repos);
 
-        let working_dir = workspace
-            .agent_working_dir
-            .as_ref()
-            .filter(|dir| !dir.is_empty())
-            .cloned();
-
-        let coding_action = ExecutorAction::new(
-            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
-                prompt,
-                executor_profile_id: executor_profile_id.clone(),
-                working_dir,
-            }),
-            cleanup_action.map(Box::new),
-        );
+        let coding_action = ExecutorAction::new(coding_action_type, cleanup_action.map(Box::new));
 
         let execution_process = if all_parallel {
             // All parallel: start each setup independently, then start coding agent
@@ -976,6 +1292,19 @@ pub trait ContainerService {
                 merge_commit: None,
             });
         }
+
+        // Resolve instructions now so the stored action shows the exact prompt the agent got
+        let mut executor_action = executor_action.clone();
+        if executor_action.base_executor().is_some() {
+            let project = Project::find_by_id(&self.db().pool, task.project_id)
+                .await?
+                .ok_or(SqlxError::RowNotFound)?;
+            let instructions =
+                agent_instructions::resolve(&project, &repositories, &workspace_root).await;
+            executor_action.typ.set_instructions(instructions);
+        }
+        let executor_action = &executor_action;
+
         let create_execution_process = CreateExecutionProcess {
             session_id: session.id,
             executor_action: executor_action.clone(),
@@ -1038,7 +1367,11 @@ pub trait ContainerService {
                     update_error
                 );
             }
-            Task::update_status(&self.db().pool, task.id, TaskStatus::InReview).await?;
+            // A verify script that never ran counts as failed verification,
+            // which keeps the task in progress
+            if run_reason != &ExecutionProcessRunReason::VerifyScript {
+                Task::update_status(&self.db().pool, task.id, TaskStatus::InReview).await?;
+            }
 
             // Emit stderr error message
             let log_message = LogMsg::Stderr(format!("Failed to start execution: {start_error}"));
@@ -1132,6 +1465,16 @@ pub trait ContainerService {
 
         // Determine the run reason of the next action
         let next_run_reason = match (action.typ(), next_action.typ()) {
+            (_, ExecutorActionType::ScriptRequest(request))
+                if request.context == ScriptContext::VerifyScript =>
+            {
+                ExecutionProcessRunReason::VerifyScript
+            }
+            (_, ExecutorActionType::ScriptRequest(request))
+                if request.context == ScriptContext::CleanupScript =>
+            {
+                ExecutionProcessRunReason::CleanupScript
+            }
             (ExecutorActionType::ScriptRequest(_), ExecutorActionType::ScriptRequest(_)) => {
                 ExecutionProcessRunReason::SetupScript
             }
@@ -1155,4 +1498,82 @@ pub trait ContainerService {
         tracing::debug!("Started next action: {:?}", next_action);
         Ok(())
     }
+
+    /// Start a coding agent follow-up in an existing session, continuing the
+    /// agent's own session when one exists
+    async fn start_follow_up(
+        &self,
+        workspace: &Workspace,
+        session: &Session,
+        data: &DraftFollowUpData,
+    ) -> Result<ExecutionProcess, ContainerError> {
+        // Get executor from the latest CodingAgent process, or fall back to session's executor
+        let base_executor = match ExecutionProcess::latest_executor_profile_for_session(
+            &self.db().pool,
+            session.id,
+        )
+        .await
+        .map_err(|e| ContainerError::Other(anyhow!("Failed to get executor profile: {e}")))?
+        {
+            Some(profile) => profile.executor,
+            None => {
+                // No prior execution - use session's executor field
+                let executor_str = session.executor.as_ref().ok_or_else(|| {
+                    ContainerError::Other(anyhow!(
+                        "No prior execution and no executor configured on session"
+                    ))
+                })?;
+                BaseCodingAgent::from_str(&executor_str.replace('-', "_").to_ascii_uppercase())
+                    .map_err(|_| {
+                        ContainerError::Other(anyhow!("Invalid executor: {}", executor_str))
+                    })?
+            }
+        };
+
+        let executor_profile_id = ExecutorProfileId {
+            executor: base_executor,
+            variant: data.variant.clone(),
+        };
+
+        // Get latest agent session ID for session continuity (from coding agent turns)
+        let latest_agent_session_id =
+            ExecutionProcess::find_latest_coding_agent_turn_session_id(&self.db().pool, session.id)
+                .await?;
+
+        let repos = WorkspaceRepo::find_repos_for_workspace(&self.db().pool, workspace.id).await?;
+        let cleanup_action = self.cleanup_actions_for_repos(&repos);
+
+        let working_dir = workspace
+            .agent_working_dir
+            .as_ref()
+            .filter(|dir| !dir.is_empty())
+            .cloned();
+
+        let action_type = if let Some(agent_session_id) = latest_agent_session_id {
+            ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                prompt: data.message.clone(),
+                session_id: agent_session_id,
+                executor_profile_id: executor_profile_id.clone(),
+                working_dir: working_dir.clone(),
+                instructions: None,
+            })
+        } else {
+            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt: data.message.clone(),
+                executor_profile_id: executor_profile_id.clone(),
+                working_dir,
+                instructions: None,
+            })
+        };
+
+        let action = ExecutorAction::new(action_type, cleanup_action.map(Box::new));
+
+        self.start_execution(
+            workspace,
+            session,
+            &action,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await
+    }
 }
@@ -0,0 +1,79 @@
+use std::path::PathBuf;
+
+use db::models::{workspace::Workspace, workspace_repo::WorkspaceRepo};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use ts_rs::TS;
+
+use super::git::{DiffTarget, GitService};
+
+#[derive(Debug, Clone, Default, Serialize, TS)]
+pub struct DiffStats {
+    pub files_changed: usize,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+}
+
+/// Sum the changes of every repo in a workspace against its target branch.
+/// Repos whose merge base or diff cannot be computed are skipped; returns
+/// `None` when the workspace has no container.
+pub async fn compute_workspace_diff_stats(
+    pool: &SqlitePool,
+    git: &GitService,
+    workspace: &Workspace,
+) -> Result<Option<DiffStats>, sqlx::Error> {
+    let Some(container_ref) = workspace.container_ref.as_ref() else {
+        return Ok(None);
+    };
+
+    let workspace_repos =
+        WorkspaceRepo::find_repos_with_target_branch_for_workspace(pool, workspace.id).await?;
+
+    let mut stats = DiffStats::default();
+
+    for repo_with_branch in workspace_repos {
+        let worktree_path = PathBuf::from(container_ref).join(&repo_with_branch.repo.name);
+        let repo_path = repo_with_branch.repo.path.clone();
+
+        // Get base commit (merge base) between workspace branch and target branch
+        let base_commit_result = tokio::task::spawn_blocking({
+            let git = git.clone();
+            let repo_path = repo_path.clone();
+            let workspace_branch = workspace.branch.clone();
+            let target_branch = repo_with_branch.target_branch.clone();
+            move || git.get_base_commit(&repo_path, &workspace_branch, &target_branch)
+        })
+        .await;
+
+        let base_commit = match base_commit_result {
+            Ok(Ok(commit)) => commit,
+            _ => continue,
+        };
+
+        // Get diffs
+        let diffs_result = tokio::task::spawn_blocking({
+            let git = git.clone();
+            let worktree = worktree_path.clone();
+            move || {
+                git.get_diffs(
+                    DiffTarget::Worktree {
+                        worktree_path: &worktree,
+                        base_commit: &base_commit,
+                    },
+                    None,
+                )
+            }
+        })
+        .await;
+
+        if let Ok(Ok(diffs)) = diffs_result {
+            for diff in diffs {
+                stats.files_changed += 1;
+                stats.lines_added += diff.additions.unwrap_or(0);
+                stats.lines_removed += diff.deletions.unwrap_or(0);
+            }
+        }
+    }
+
+    Ok(Some(stats))
+}
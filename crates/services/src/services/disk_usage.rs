@@ -0,0 +1,415 @@
+use std::{
+    collections::HashMap,
+    fs::Metadata,
+    io,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Duration, Utc};
+use db::models::workspace_disk_usage::WorkspaceDiskUsageEntry;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::services::config::WorkspaceDiskPolicy;
+
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiskUsage {
+    pub total_bytes: u64,
+    /// Part of `total_bytes` inside build artifact directories
+    pub artifact_bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum DiskEvictionAction {
+    RemoveArtifacts,
+    RemoveWorktree,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+pub struct DiskEviction {
+    pub workspace_id: Uuid,
+    pub project_id: Uuid,
+    pub action: DiskEvictionAction,
+    /// Measured when artifacts are removed; the last recorded size for worktrees
+    #[ts(type = "number")]
+    pub freed_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ProjectDiskUsage {
+    pub project_id: Uuid,
+    pub project_name: String,
+    pub workspace_count: usize,
+    #[ts(type = "number")]
+    pub total_bytes: u64,
+    #[ts(type = "number")]
+    pub artifact_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct DiskUsageSummary {
+    #[ts(type = "number")]
+    pub total_bytes: u64,
+    #[ts(type = "number")]
+    pub artifact_bytes: u64,
+    #[ts(type = "number | null")]
+    pub max_total_bytes: Option<u64>,
+    #[ts(type = "number | null")]
+    pub max_project_bytes: Option<u64>,
+    /// Largest first
+    pub projects: Vec<ProjectDiskUsage>,
+    /// Largest first
+    pub workspaces: Vec<WorkspaceDiskUsageEntry>,
+}
+
+fn gb_to_bytes(gb: f64) -> u64 {
+    (gb.max(0.0) * BYTES_PER_GB) as u64
+}
+
+/// Space a file actually takes on disk, so sparse files and small files in
+/// large blocks are counted the way `du` would
+fn allocated_size(metadata: &Metadata) -> u64 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        metadata.blocks() * 512
+    }
+    #[cfg(not(unix))]
+    {
+        metadata.len()
+    }
+}
+
+fn is_artifact_dir(name: &std::ffi::OsStr, artifact_dirs: &[String]) -> bool {
+    artifact_dirs.iter().any(|dir| name == dir.as_str())
+}
+
+/// Walk a workspace directory without following symlinks. Unreadable entries
+/// are skipped rather than failing the whole measurement.
+pub fn measure(dir: &Path, artifact_dirs: &[String]) -> io::Result<DiskUsage> {
+    let mut usage = DiskUsage::default();
+    let mut stack: Vec<(PathBuf, bool)> = vec![(dir.to_path_buf(), false)];
+
+    while let Some((current, in_artifact)) = stack.pop() {
+        let entries = match std::fs::read_dir(&current) {
+            Ok(entries) => entries,
+            Err(e) if current == dir => return Err(e),
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let Ok(metadata) = entry.path().symlink_metadata() else {
+                continue;
+            };
+            let size = allocated_size(&metadata);
+            usage.total_bytes += size;
+            if in_artifact {
+                usage.artifact_bytes += size;
+            }
+            if metadata.is_dir() {
+                let artifact = in_artifact || is_artifact_dir(&entry.file_name(), artifact_dirs);
+                if artifact && !in_artifact {
+                    usage.artifact_bytes += size;
+                }
+                stack.push((entry.path(), artifact));
+            }
+        }
+    }
+
+    Ok(usage)
+}
+
+/// Delete every build artifact directory under `dir`, returning the bytes freed
+pub fn remove_artifacts(dir: &Path, artifact_dirs: &[String]) -> io::Result<u64> {
+    let mut freed = 0;
+    let mut stack = vec![dir.to_path_buf()];
+
+    while let Some(current) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if !file_type.is_dir() || entry.file_name() == ".git" {
+                continue;
+            }
+            let path = entry.path();
+            if is_artifact_dir(&entry.file_name(), artifact_dirs) {
+                let size = measure(&path, &[])?.total_bytes + allocated_size(&entry.metadata()?);
+                std::fs::remove_dir_all(&path)?;
+                freed += size;
+            } else {
+                stack.push(path);
+            }
+        }
+    }
+
+    Ok(freed)
+}
+
+pub fn summarize(
+    entries: Vec<WorkspaceDiskUsageEntry>,
+    policy: &WorkspaceDiskPolicy,
+) -> DiskUsageSummary {
+    let mut projects: HashMap<Uuid, ProjectDiskUsage> = HashMap::new();
+    for entry in &entries {
+        let project = projects
+            .entry(entry.project_id)
+            .or_insert_with(|| ProjectDiskUsage {
+                project_id: entry.project_id,
+                project_name: entry.project_name.clone(),
+                workspace_count: 0,
+                total_bytes: 0,
+                artifact_bytes: 0,
+            });
+        project.workspace_count += 1;
+        project.total_bytes += entry.total_bytes.max(0) as u64;
+        project.artifact_bytes += entry.artifact_bytes.max(0) as u64;
+    }
+    let mut projects: Vec<ProjectDiskUsage> = projects.into_values().collect();
+    projects.sort_by(|a, b| b.total_bytes.cmp(&a.total_bytes));
+
+    DiskUsageSummary {
+        total_bytes: projects.iter().map(|p| p.total_bytes).sum(),
+        artifact_bytes: projects.iter().map(|p| p.artifact_bytes).sum(),
+        max_total_bytes: policy.max_total_gb.map(gb_to_bytes),
+        max_project_bytes: policy.max_project_gb.map(gb_to_bytes),
+        projects,
+        workspaces: entries,
+    }
+}
+
+/// Decide what to evict to bring usage back under the policy's limits.
+/// Only archived or idle workspaces that are not pinned or running are
+/// candidates, least recently used first. Artifacts are dropped across all
+/// candidates before any whole worktree is removed.
+pub fn plan_evictions(
+    entries: &[WorkspaceDiskUsageEntry],
+    policy: &WorkspaceDiskPolicy,
+    now: DateTime<Utc>,
+) -> Vec<DiskEviction> {
+    let max_total = policy.max_total_gb.map(gb_to_bytes);
+    let max_project = policy.max_project_gb.map(gb_to_bytes);
+    if max_total.is_none() && max_project.is_none() {
+        return Vec::new();
+    }
+
+    let mut total: u64 = 0;
+    let mut per_project: HashMap<Uuid, u64> = HashMap::new();
+    for entry in entries {
+        let bytes = entry.total_bytes.max(0) as u64;
+        total += bytes;
+        *per_project.entry(entry.project_id).or_default() += bytes;
+    }
+    let over = |total: u64, project: u64| {
+        max_total.is_some_and(|max| total > max) || max_project.is_some_and(|max| project > max)
+    };
+
+    let idle_cutoff = now - Duration::hours(policy.idle_after_hours as i64);
+    let mut candidates: Vec<&WorkspaceDiskUsageEntry> = entries
+        .iter()
+        .filter(|e| !e.is_running && !e.pinned && (e.archived || e.last_used_at < idle_cutoff))
+        .collect();
+    candidates.sort_by_key(|e| e.last_used_at);
+
+    let mut evictions: Vec<DiskEviction> = Vec::new();
+
+    for entry in &candidates {
+        let artifact_bytes = entry.artifact_bytes.max(0) as u64;
+        let project = per_project[&entry.project_id];
+        if artifact_bytes == 0 || !over(total, project) {
+            continue;
+        }
+        total -= artifact_bytes;
+        *per_project.get_mut(&entry.project_id).unwrap() -= artifact_bytes;
+        evictions.push(DiskEviction {
+            workspace_id: entry.workspace_id,
+            project_id: entry.project_id,
+            action: DiskEvictionAction::RemoveArtifacts,
+            freed_bytes: artifact_bytes,
+        });
+    }
+
+    if !policy.evict_worktrees {
+        return evictions;
+    }
+
+    for entry in &candidates {
+        let project = per_project[&entry.project_id];
+        if !over(total, project) {
+            continue;
+        }
+        let entry_bytes = entry.total_bytes.max(0) as u64;
+        // Removing the worktree supersedes dropping its artifacts
+        let already_freed = match evictions
+            .iter()
+            .position(|e| e.workspace_id == entry.workspace_id)
+        {
+            Some(index) => evictions.remove(index).freed_bytes,
+            None => 0,
+        };
+        let freed = entry_bytes.saturating_sub(already_freed);
+        total = total.saturating_sub(freed);
+        let project = per_project.get_mut(&entry.project_id).unwrap();
+        *project = project.saturating_sub(freed);
+        evictions.push(DiskEviction {
+            workspace_id: entry.workspace_id,
+            project_id: entry.project_id,
+            action: DiskEvictionAction::RemoveWorktree,
+            freed_bytes: entry_bytes,
+        });
+    }
+
+    evictions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: i64 = 1024 * 1024 * 1024;
+
+    fn entry(
+        project_id: Uuid,
+        hours_ago: i64,
+        total_gb: i64,
+        artifact_gb: i64,
+    ) -> WorkspaceDiskUsageEntry {
+        let now = Utc::now();
+        WorkspaceDiskUsageEntry {
+            workspace_id: Uuid::new_v4(),
+            task_id: Uuid::new_v4(),
+            task_title: "task".to_string(),
+            project_id,
+            project_name: "project".to_string(),
+            branch: "vk/branch".to_string(),
+            archived: false,
+            pinned: false,
+            is_running: false,
+            last_used_at: now - Duration::hours(hours_ago),
+            total_bytes: total_gb * GB,
+            artifact_bytes: artifact_gb * GB,
+            measured_at: now,
+        }
+    }
+
+    fn policy(max_total_gb: Option<f64>, max_project_gb: Option<f64>) -> WorkspaceDiskPolicy {
+        WorkspaceDiskPolicy {
+            max_total_gb,
+            max_project_gb,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn no_limits_means_no_evictions() {
+        let project = Uuid::new_v4();
+        let entries = vec![entry(project, 100, 50, 40)];
+        assert!(plan_evictions(&entries, &policy(None, None), Utc::now()).is_empty());
+    }
+
+    #[test]
+    fn artifacts_go_first_in_lru_order() {
+        let project = Uuid::new_v4();
+        let older = entry(project, 200, 10, 6);
+        let newer = entry(project, 100, 10, 6);
+        let entries = vec![newer.clone(), older.clone()];
+
+        let plan = plan_evictions(&entries, &policy(Some(15.0), None), Utc::now());
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan[0].workspace_id, older.workspace_id);
+        assert_eq!(plan[0].action, DiskEvictionAction::RemoveArtifacts);
+    }
+
+    #[test]
+    fn worktrees_are_removed_when_artifacts_are_not_enough() {
+        let project = Uuid::new_v4();
+        let older = entry(project, 200, 10, 2);
+        let newer = entry(project, 100, 10, 2);
+        let entries = vec![older.clone(), newer.clone()];
+
+        let plan = plan_evictions(&entries, &policy(Some(12.0), None), Utc::now());
+        assert_eq!(
+            plan.iter()
+                .map(|e| (e.workspace_id, e.action))
+                .collect::<Vec<_>>(),
+            vec![
+                (newer.workspace_id, DiskEvictionAction::RemoveArtifacts),
+                (older.workspace_id, DiskEvictionAction::RemoveWorktree),
+            ]
+        );
+
+        let mut keep_worktrees = policy(Some(12.0), None);
+        keep_worktrees.evict_worktrees = false;
+        assert!(
+            plan_evictions(&entries, &keep_worktrees, Utc::now())
+                .iter()
+                .all(|e| e.action == DiskEvictionAction::RemoveArtifacts)
+        );
+    }
+
+    #[test]
+    fn active_pinned_and_running_workspaces_are_kept() {
+        let project = Uuid::new_v4();
+        let recent = entry(project, 1, 10, 5);
+        let mut pinned = entry(project, 200, 10, 5);
+        pinned.pinned = true;
+        let mut running = entry(project, 200, 10, 5);
+        running.is_running = true;
+        let mut archived = entry(project, 1, 10, 5);
+        archived.archived = true;
+        let entries = vec![recent, pinned, running, archived.clone()];
+
+        let plan = plan_evictions(&entries, &policy(Some(1.0), None), Utc::now());
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan[0].workspace_id, archived.workspace_id);
+        assert_eq!(plan[0].action, DiskEvictionAction::RemoveWorktree);
+    }
+
+    #[test]
+    fn project_limit_only_touches_that_project() {
+        let big = Uuid::new_v4();
+        let small = Uuid::new_v4();
+        let big_ws = entry(big, 200, 10, 8);
+        let small_ws = entry(small, 300, 3, 2);
+        let entries = vec![big_ws.clone(), small_ws];
+
+        let plan = plan_evictions(&entries, &policy(None, Some(5.0)), Utc::now());
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan[0].workspace_id, big_ws.workspace_id);
+        assert_eq!(plan[0].action, DiskEvictionAction::RemoveArtifacts);
+    }
+
+    #[test]
+    fn measure_and_remove_artifacts() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path().join("app");
+        std::fs::create_dir_all(repo.join("node_modules/pkg/node_modules")).unwrap();
+        std::fs::create_dir_all(repo.join("src")).unwrap();
+        std::fs::write(repo.join("src/main.ts"), vec![b'a'; 64 * 1024]).unwrap();
+        std::fs::write(
+            repo.join("node_modules/pkg/index.js"),
+            vec![b'b'; 128 * 1024],
+        )
+        .unwrap();
+
+        let artifact_dirs = vec!["node_modules".to_string()];
+        let usage = measure(dir.path(), &artifact_dirs).unwrap();
+        assert!(usage.artifact_bytes >= 128 * 1024);
+        assert!(usage.total_bytes >= usage.artifact_bytes + 64 * 1024);
+
+        let freed = remove_artifacts(dir.path(), &artifact_dirs).unwrap();
+        assert!(freed >= 128 * 1024);
+        assert!(!repo.join("node_modules").exists());
+        assert!(repo.join("src/main.ts").exists());
+        assert_eq!(
+            measure(dir.path(), &artifact_dirs).unwrap().artifact_bytes,
+            0
+        );
+    }
+}
@@ -1607,6 +1607,13 @@ impl GitService {
             .map_err(GitServiceError::GitCLI)
     }
 
+    /// URL of the repository's default remote (`remote.pushDefault`, else the first remote)
+    pub fn get_default_remote_url(&self, repo_path: &Path) -> Result<String, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let remote_name = self.default_remote_name(&repo);
+        self.get_remote_url(repo_path, &remote_name)
+    }
+
     pub fn check_remote_branch_exists(
         &self,
         repo_path: &Path,
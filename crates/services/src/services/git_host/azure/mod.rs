@@ -272,6 +272,29 @@ impl GitHostProvider for AzureDevOpsProvider {
         .await
     }
 
+    // Review comments are surfaced by comment id only; replying and resolving
+    // need the thread id, which the unified comment model does not carry yet.
+    async fn reply_to_review_comment(
+        &self,
+        _repo_path: &Path,
+        _remote_url: &str,
+        _pr_number: i64,
+        _comment_id: i64,
+        _body: &str,
+    ) -> Result<(), GitHostError> {
+        Err(GitHostError::UnsupportedProvider)
+    }
+
+    async fn resolve_review_thread(
+        &self,
+        _repo_path: &Path,
+        _remote_url: &str,
+        _pr_number: i64,
+        _comment_id: i64,
+    ) -> Result<(), GitHostError> {
+        Err(GitHostError::UnsupportedProvider)
+    }
+
     fn provider_kind(&self) -> ProviderKind {
         ProviderKind::AzureDevOps
     }
@@ -18,7 +18,8 @@ use thiserror::Error;
 use utils::shell::resolve_executable_path_blocking;
 
 use crate::services::git_host::types::{
-    CreatePrRequest, PrComment, PrCommentAuthor, PrReviewComment, ReviewCommentUser,
+    CreatePrRequest, GitHubIssue, GitHubIssueFilter, GitHubIssueState, GitHubIssueUpdate,
+    PrComment, PrCommentAuthor, PrReviewComment, ReviewCommentUser,
 };
 
 #[derive(Debug, Clone)]
@@ -81,6 +82,47 @@ struct GhReviewCommentResponse {
     author_association: String,
 }
 
+#[derive(Deserialize)]
+struct GhGraphqlResponse<T> {
+    data: T,
+}
+
+#[derive(Deserialize)]
+struct GhReviewThreadsData {
+    repository: GhReviewThreadsRepository,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhReviewThreadsRepository {
+    pull_request: GhReviewThreadsPullRequest,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhReviewThreadsPullRequest {
+    review_threads: GhNodes<GhReviewThread>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhReviewThread {
+    id: String,
+    is_resolved: bool,
+    comments: GhNodes<GhReviewThreadComment>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhReviewThreadComment {
+    database_id: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct GhNodes<T> {
+    nodes: Vec<T>,
+}
+
 #[derive(Deserialize)]
 struct GhMergeCommit {
     oid: Option<String>,
@@ -97,6 +139,31 @@ struct GhPrResponse {
     merge_commit: Option<GhMergeCommit>,
 }
 
+#[derive(Deserialize)]
+struct GhLabel {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct GhMilestone {
+    title: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhIssueResponse {
+    number: i64,
+    title: String,
+    #[serde(default)]
+    body: String,
+    url: String,
+    state: String,
+    #[serde(default)]
+    labels: Vec<GhLabel>,
+    milestone: Option<GhMilestone>,
+    updated_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Error)]
 pub enum GhCliError {
     #[error("GitHub CLI (`gh`) executable not found or not runnable")]
@@ -318,8 +385,282 @@ impl GhCli {
         )?;
         Self::parse_pr_review_comments(&raw)
     }
+
+    /// Post a reply in the thread of an inline review comment.
+    pub fn reply_to_review_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: i64,
+        comment_id: i64,
+        body: &str,
+    ) -> Result<(), GhCliError> {
+        self.run(
+            [
+                "api",
+                "--method",
+                "POST",
+                &format!("repos/{owner}/{repo}/pulls/{pr_number}/comments/{comment_id}/replies"),
+                "-f",
+                &format!("body={body}"),
+            ],
+            None,
+        )?;
+        Ok(())
+    }
+
+    /// List issues in a repository matching the filter.
+    pub fn list_issues(
+        &self,
+        owner: &str,
+        repo: &str,
+        filter: &GitHubIssueFilter,
+    ) -> Result<Vec<GitHubIssue>, GhCliError> {
+        let mut args: Vec<OsString> = vec![
+            "issue".into(),
+            "list".into(),
+            "--repo".into(),
+            format!("{owner}/{repo}").into(),
+            "--state".into(),
+            if filter.include_closed { "all" } else { "open" }.into(),
+            "--limit".into(),
+            filter.limit.unwrap_or(100).to_string().into(),
+            "--json".into(),
+            ISSUE_JSON_FIELDS.into(),
+        ];
+        for label in &filter.labels {
+            args.push("--label".into());
+            args.push(label.into());
+        }
+        if let Some(milestone) = &filter.milestone {
+            args.push("--milestone".into());
+            args.push(milestone.into());
+        }
+
+        let raw = self.run(args, None)?;
+        Self::parse_issue_list(&raw)
+    }
+
+    /// Fetch a single issue by number.
+    pub fn view_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: i64,
+    ) -> Result<GitHubIssue, GhCliError> {
+        let raw = self.run(
+            [
+                "issue",
+                "view",
+                &number.to_string(),
+                "--repo",
+                &format!("{owner}/{repo}"),
+                "--json",
+                ISSUE_JSON_FIELDS,
+            ],
+            None,
+        )?;
+        let issue: GhIssueResponse = serde_json::from_str(raw.trim()).map_err(|err| {
+            GhCliError::UnexpectedOutput(format!(
+                "Failed to parse gh issue view response: {err}; raw: {raw}"
+            ))
+        })?;
+        Ok(Self::issue_response_to_issue(issue))
+    }
+
+    /// Issues, not pull requests, updated at or after `since`. Uses the REST
+    /// endpoint, whose `since` filter reads live data rather than the lagging
+    /// search index, and maps each item to the `gh issue` JSON shape.
+    pub fn list_issues_updated_since(
+        &self,
+        owner: &str,
+        repo: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<GitHubIssue>, GhCliError> {
+        let raw = self.run(
+            [
+                "api",
+                "--paginate",
+                &format!(
+                    "repos/{owner}/{repo}/issues?state=all&per_page=100&since={}",
+                    since.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+                ),
+                "--jq",
+                ISSUE_REST_JQ,
+            ],
+            None,
+        )?;
+        serde_json::Deserializer::from_str(&raw)
+            .into_iter::<GhIssueResponse>()
+            .map(|issue| {
+                issue.map(Self::issue_response_to_issue).map_err(|err| {
+                    GhCliError::UnexpectedOutput(format!(
+                        "Failed to parse issues API response: {err}; raw: {raw}"
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    /// Apply title, body, label and open/closed changes to an issue.
+    pub fn update_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: i64,
+        update: &GitHubIssueUpdate,
+    ) -> Result<(), GhCliError> {
+        let repo_arg = format!("{owner}/{repo}");
+        let number_arg = number.to_string();
+
+        let mut args: Vec<OsString> = vec![
+            "issue".into(),
+            "edit".into(),
+            (&number_arg).into(),
+            "--repo".into(),
+            (&repo_arg).into(),
+        ];
+        let edit_args_len = args.len();
+        if let Some(title) = &update.title {
+            args.push("--title".into());
+            args.push(title.into());
+        }
+        // Kept alive until the command has run
+        let body_file = match &update.body {
+            Some(body) => Some(Self::write_temp_file(body)?),
+            None => None,
+        };
+        if let Some(file) = &body_file {
+            args.push("--body-file".into());
+            args.push(file.path().as_os_str().to_os_string());
+        }
+        for label in &update.add_labels {
+            args.push("--add-label".into());
+            args.push(label.into());
+        }
+        for label in &update.remove_labels {
+            args.push("--remove-label".into());
+            args.push(label.into());
+        }
+        if args.len() > edit_args_len {
+            self.run(args, None)?;
+        }
+
+        if let Some(state) = update.state {
+            let action = match state {
+                GitHubIssueState::Open => "reopen",
+                GitHubIssueState::Closed => "close",
+            };
+            self.run(["issue", action, &number_arg, "--repo", &repo_arg], None)?;
+        }
+        Ok(())
+    }
+
+    /// Post a comment on an issue.
+    pub fn comment_on_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: i64,
+        body: &str,
+    ) -> Result<(), GhCliError> {
+        let body_file = Self::write_temp_file(body)?;
+        let args: Vec<OsString> = vec![
+            "issue".into(),
+            "comment".into(),
+            number.to_string().into(),
+            "--repo".into(),
+            format!("{owner}/{repo}").into(),
+            "--body-file".into(),
+            body_file.path().as_os_str().to_os_string(),
+        ];
+        self.run(args, None)?;
+        Ok(())
+    }
+
+    /// Create a label, or update its color and description if it already exists.
+    pub fn ensure_label(
+        &self,
+        owner: &str,
+        repo: &str,
+        name: &str,
+        color: &str,
+        description: &str,
+    ) -> Result<(), GhCliError> {
+        self.run(
+            [
+                "label",
+                "create",
+                name,
+                "--repo",
+                &format!("{owner}/{repo}"),
+                "--color",
+                color,
+                "--description",
+                description,
+                "--force",
+            ],
+            None,
+        )?;
+        Ok(())
+    }
+
+    /// Resolve the review thread containing the given inline comment.
+    /// Threads that are already resolved are left untouched.
+    pub fn resolve_review_thread(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: i64,
+        comment_id: i64,
+    ) -> Result<(), GhCliError> {
+        let raw = self.run(
+            [
+                "api",
+                "graphql",
+                "-f",
+                &format!("query={REVIEW_THREADS_QUERY}"),
+                "-f",
+                &format!("owner={owner}"),
+                "-f",
+                &format!("repo={repo}"),
+                "-F",
+                &format!("number={pr_number}"),
+            ],
+            None,
+        )?;
+        let Some(thread_id) = Self::parse_unresolved_thread_id(&raw, comment_id)? else {
+            return Ok(());
+        };
+        self.run(
+            [
+                "api",
+                "graphql",
+                "-f",
+                &format!("query={RESOLVE_REVIEW_THREAD_MUTATION}"),
+                "-f",
+                &format!("threadId={thread_id}"),
+            ],
+            None,
+        )?;
+        Ok(())
+    }
 }
 
+const ISSUE_JSON_FIELDS: &str = "number,title,body,url,state,labels,milestone,updatedAt";
+
+/// Reshapes REST issues into `ISSUE_JSON_FIELDS`, dropping pull requests
+const ISSUE_REST_JQ: &str = ".[] | select(.pull_request == null) | {number, title, \
+body: (.body // \"\"), url: .html_url, state, labels: [.labels[] | {name}], \
+milestone: (if .milestone then {title: .milestone.title} else null end), updatedAt: .updated_at}";
+
+const REVIEW_THREADS_QUERY: &str = "query($owner: String!, $repo: String!, $number: Int!) { \
+repository(owner: $owner, name: $repo) { pullRequest(number: $number) { \
+reviewThreads(first: 100) { nodes { id isResolved comments(first: 100) { nodes { databaseId } } } } } } }";
+
+const RESOLVE_REVIEW_THREAD_MUTATION: &str = "mutation($threadId: ID!) { \
+resolveReviewThread(input: { threadId: $threadId }) { thread { id } } }";
+
 impl GhCli {
     fn parse_pr_create_text(raw: &str) -> Result<PullRequestInfo, GhCliError> {
         let pr_url = raw
@@ -425,6 +766,73 @@ impl GhCli {
             .collect())
     }
 
+    /// Find the unresolved review thread that contains `comment_id`.
+    fn parse_unresolved_thread_id(
+        raw: &str,
+        comment_id: i64,
+    ) -> Result<Option<String>, GhCliError> {
+        let response: GhGraphqlResponse<GhReviewThreadsData> = serde_json::from_str(raw.trim())
+            .map_err(|err| {
+                GhCliError::UnexpectedOutput(format!(
+                    "Failed to parse review threads response: {err}; raw: {raw}"
+                ))
+            })?;
+
+        Ok(response
+            .data
+            .repository
+            .pull_request
+            .review_threads
+            .nodes
+            .into_iter()
+            .find(|thread| {
+                thread
+                    .comments
+                    .nodes
+                    .iter()
+                    .any(|c| c.database_id == Some(comment_id))
+            })
+            .filter(|thread| !thread.is_resolved)
+            .map(|thread| thread.id))
+    }
+
+    fn write_temp_file(contents: &str) -> Result<NamedTempFile, GhCliError> {
+        let mut file = NamedTempFile::new()
+            .map_err(|e| GhCliError::CommandFailed(format!("Failed to create temp file: {e}")))?;
+        file.write_all(contents.as_bytes())
+            .map_err(|e| GhCliError::CommandFailed(format!("Failed to write body: {e}")))?;
+        Ok(file)
+    }
+
+    fn parse_issue_list(raw: &str) -> Result<Vec<GitHubIssue>, GhCliError> {
+        let issues: Vec<GhIssueResponse> = serde_json::from_str(raw.trim()).map_err(|err| {
+            GhCliError::UnexpectedOutput(format!(
+                "Failed to parse gh issue list response: {err}; raw: {raw}"
+            ))
+        })?;
+        Ok(issues
+            .into_iter()
+            .map(Self::issue_response_to_issue)
+            .collect())
+    }
+
+    fn issue_response_to_issue(issue: GhIssueResponse) -> GitHubIssue {
+        GitHubIssue {
+            number: issue.number,
+            title: issue.title,
+            body: issue.body,
+            url: issue.url,
+            state: if issue.state.eq_ignore_ascii_case("closed") {
+                GitHubIssueState::Closed
+            } else {
+                GitHubIssueState::Open
+            },
+            labels: issue.labels.into_iter().map(|l| l.name).collect(),
+            milestone: issue.milestone.map(|m| m.title),
+            updated_at: issue.updated_at,
+        }
+    }
+
     fn parse_pr_review_comments(raw: &str) -> Result<Vec<PrReviewComment>, GhCliError> {
         let items: Vec<GhReviewCommentResponse> =
             serde_json::from_str(raw.trim()).map_err(|err| {
@@ -6,15 +6,19 @@ use std::{path::Path, time::Duration};
 
 use async_trait::async_trait;
 use backon::{ExponentialBuilder, Retryable};
-pub use cli::GhCli;
-use cli::{GhCliError, GitHubRepoInfo};
+use chrono::{DateTime, Utc};
+use cli::GhCliError;
+pub use cli::{GhCli, GitHubRepoInfo};
 use db::models::merge::PullRequestInfo;
 use tokio::task;
 use tracing::info;
 
 use super::{
     GitHostProvider,
-    types::{CreatePrRequest, GitHostError, ProviderKind, UnifiedPrComment},
+    types::{
+        CreatePrRequest, GitHostError, GitHubIssue, GitHubIssueFilter, GitHubIssueUpdate,
+        ProviderKind, UnifiedPrComment,
+    },
 };
 
 #[derive(Debug, Clone)]
@@ -29,7 +33,7 @@ impl GitHubProvider {
         })
     }
 
-    async fn get_repo_info(
+    pub async fn get_repo_info(
         &self,
         remote_url: &str,
         repo_path: &Path,
@@ -45,7 +49,117 @@ impl GitHubProvider {
             .map_err(Into::into)
     }
 
-    async fn check_auth(&self) -> Result<(), GitHostError> {
+    /// Run a blocking `gh` call off the async runtime
+    async fn run_cli<T, F>(&self, action: &str, f: F) -> Result<T, GitHostError>
+    where
+        T: Send + 'static,
+        F: FnOnce(GhCli) -> Result<T, GhCliError> + Send + 'static,
+    {
+        let cli = self.gh_cli.clone();
+        task::spawn_blocking(move || f(cli))
+            .await
+            .map_err(|err| {
+                GitHostError::Repository(format!(
+                    "Failed to execute GitHub CLI for {action}: {err}"
+                ))
+            })?
+            .map_err(Into::into)
+    }
+
+    // Issues are a GitHub-only concept here, so these live outside `GitHostProvider`.
+
+    /// List issues of the repository behind `remote_url`.
+    pub async fn list_issues(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        filter: &GitHubIssueFilter,
+    ) -> Result<Vec<GitHubIssue>, GitHostError> {
+        self.check_auth().await?;
+        let repo_info = self.get_repo_info(remote_url, repo_path).await?;
+        let filter = filter.clone();
+        self.run_cli("listing issues", move |cli| {
+            cli.list_issues(&repo_info.owner, &repo_info.repo_name, &filter)
+        })
+        .await
+    }
+
+    pub async fn view_issue(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        number: i64,
+    ) -> Result<GitHubIssue, GitHostError> {
+        let repo_info = repo_info.clone();
+        self.run_cli("viewing an issue", move |cli| {
+            cli.view_issue(&repo_info.owner, &repo_info.repo_name, number)
+        })
+        .await
+    }
+
+    /// Issues of the repository updated at or after `since`
+    pub async fn list_issues_updated_since(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<GitHubIssue>, GitHostError> {
+        let repo_info = repo_info.clone();
+        self.run_cli("listing updated issues", move |cli| {
+            cli.list_issues_updated_since(&repo_info.owner, &repo_info.repo_name, since)
+        })
+        .await
+    }
+
+    pub async fn update_issue(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        number: i64,
+        update: &GitHubIssueUpdate,
+    ) -> Result<(), GitHostError> {
+        let repo_info = repo_info.clone();
+        let update = update.clone();
+        self.run_cli("updating an issue", move |cli| {
+            cli.update_issue(&repo_info.owner, &repo_info.repo_name, number, &update)
+        })
+        .await
+    }
+
+    pub async fn comment_on_issue(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        number: i64,
+        body: &str,
+    ) -> Result<(), GitHostError> {
+        let repo_info = repo_info.clone();
+        let body = body.to_string();
+        self.run_cli("commenting on an issue", move |cli| {
+            cli.comment_on_issue(&repo_info.owner, &repo_info.repo_name, number, &body)
+        })
+        .await
+    }
+
+    pub async fn ensure_label(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        name: &str,
+        color: &str,
+        description: &str,
+    ) -> Result<(), GitHostError> {
+        let repo_info = repo_info.clone();
+        let (name, color, description) =
+            (name.to_string(), color.to_string(), description.to_string());
+        self.run_cli("creating a label", move |cli| {
+            cli.ensure_label(
+                &repo_info.owner,
+                &repo_info.repo_name,
+                &name,
+                &color,
+                &description,
+            )
+        })
+        .await
+    }
+
+    pub async fn check_auth(&self) -> Result<(), GitHostError> {
         let cli = self.gh_cli.clone();
         task::spawn_blocking(move || cli.check_auth())
             .await
@@ -385,6 +499,95 @@ impl GitHostProvider for GitHubProvider {
         Ok(unified)
     }
 
+    async fn reply_to_review_comment(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+        comment_id: i64,
+        body: &str,
+    ) -> Result<(), GitHostError> {
+        let repo_info = self.get_repo_info(remote_url, repo_path).await?;
+        let body = body.to_string();
+
+        (|| async {
+            let cli = self.gh_cli.clone();
+            let owner = repo_info.owner.clone();
+            let repo_name = repo_info.repo_name.clone();
+            let body = body.clone();
+
+            task::spawn_blocking(move || {
+                cli.reply_to_review_comment(&owner, &repo_name, pr_number, comment_id, &body)
+            })
+            .await
+            .map_err(|err| {
+                GitHostError::PullRequest(format!(
+                    "Failed to execute GitHub CLI for replying to review comment: {err}"
+                ))
+            })?
+            .map_err(GitHostError::from)
+        })
+        .retry(
+            &ExponentialBuilder::default()
+                .with_min_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(30))
+                .with_max_times(3)
+                .with_jitter(),
+        )
+        .when(|e: &GitHostError| e.should_retry())
+        .notify(|err: &GitHostError, dur: Duration| {
+            tracing::warn!(
+                "GitHub API call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                err
+            );
+        })
+        .await
+    }
+
+    async fn resolve_review_thread(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+        comment_id: i64,
+    ) -> Result<(), GitHostError> {
+        let repo_info = self.get_repo_info(remote_url, repo_path).await?;
+
+        (|| async {
+            let cli = self.gh_cli.clone();
+            let owner = repo_info.owner.clone();
+            let repo_name = repo_info.repo_name.clone();
+
+            task::spawn_blocking(move || {
+                cli.resolve_review_thread(&owner, &repo_name, pr_number, comment_id)
+            })
+            .await
+            .map_err(|err| {
+                GitHostError::PullRequest(format!(
+                    "Failed to execute GitHub CLI for resolving review thread: {err}"
+                ))
+            })?
+            .map_err(GitHostError::from)
+        })
+        .retry(
+            &ExponentialBuilder::default()
+                .with_min_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(30))
+                .with_max_times(3)
+                .with_jitter(),
+        )
+        .when(|e: &GitHostError| e.should_retry())
+        .notify(|err: &GitHostError, dur: Duration| {
+            tracing::warn!(
+                "GitHub API call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                err
+            );
+        })
+        .await
+    }
+
     fn provider_kind(&self) -> ProviderKind {
         ProviderKind::GitHub
     }
@@ -11,7 +11,8 @@ use db::models::merge::PullRequestInfo;
 use detection::detect_provider_from_url;
 use enum_dispatch::enum_dispatch;
 pub use types::{
-    CreatePrRequest, GitHostError, PrComment, PrCommentAuthor, PrReviewComment, ProviderKind,
+    CreatePrRequest, GitHostError, GitHubIssue, GitHubIssueFilter, GitHubIssueState,
+    GitHubIssueUpdate, PrComment, PrCommentAuthor, PrReviewComment, ProviderKind,
     ReviewCommentUser, UnifiedPrComment,
 };
 
@@ -43,6 +44,25 @@ pub trait GitHostProvider: Send + Sync {
         pr_number: i64,
     ) -> Result<Vec<UnifiedPrComment>, GitHostError>;
 
+    /// Reply in the thread of an inline review comment.
+    async fn reply_to_review_comment(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+        comment_id: i64,
+        body: &str,
+    ) -> Result<(), GitHostError>;
+
+    /// Mark the thread containing an inline review comment as resolved.
+    async fn resolve_review_thread(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+        comment_id: i64,
+    ) -> Result<(), GitHostError>;
+
     fn provider_kind(&self) -> ProviderKind;
 }
 
@@ -32,6 +32,57 @@ pub struct CreatePrRequest {
     pub head_repo_url: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum GitHubIssueState {
+    Open,
+    Closed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct GitHubIssue {
+    pub number: i64,
+    pub title: String,
+    pub body: String,
+    pub url: String,
+    pub state: GitHubIssueState,
+    pub labels: Vec<String>,
+    pub milestone: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Which issues `gh issue list` returns
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct GitHubIssueFilter {
+    /// Issues must carry every one of these labels
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub milestone: Option<String>,
+    #[serde(default)]
+    pub include_closed: bool,
+    pub limit: Option<u32>,
+}
+
+/// Changes applied to an issue in one sync; empty fields are left alone
+#[derive(Debug, Clone, Default)]
+pub struct GitHubIssueUpdate {
+    pub title: Option<String>,
+    pub body: Option<String>,
+    pub state: Option<GitHubIssueState>,
+    pub add_labels: Vec<String>,
+    pub remove_labels: Vec<String>,
+}
+
+impl GitHubIssueUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.body.is_none()
+            && self.state.is_none()
+            && self.add_labels.is_empty()
+            && self.remove_labels.is_empty()
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum GitHostError {
     #[error("Repository error: {0}")]
@@ -0,0 +1,788 @@
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use db::{
+    DBService,
+    models::{
+        coding_agent_turn::CodingAgentTurn,
+        github_issue_sync::{
+            CreateGitHubIssueSyncEvent, GitHubIssueSyncDirection, GitHubIssueSyncEvent,
+            GitHubIssueSyncSettings, GitHubIssueSyncState, TaskGitHubIssue,
+        },
+        merge::Merge,
+        repo::Repo,
+        task::{Task, TaskStatus},
+        workspace::{Workspace, WorkspaceError},
+    },
+};
+use thiserror::Error;
+use tokio::time::interval;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use crate::services::{
+    git::{GitService, GitServiceError},
+    git_host::{
+        GitHostError, GitHostService, GitHubIssue, GitHubIssueState, GitHubIssueUpdate,
+        github::{GitHubProvider, GitHubRepoInfo},
+    },
+};
+
+/// Prepended to comments posted by the issue sync
+pub const ISSUE_SYNC_MARKER: &str = "<!-- vibe-kanban:issue-sync -->";
+
+/// Labels mirroring the local task status, with their colors
+const STATUS_LABELS: [(TaskStatus, &str, &str); 3] = [
+    (TaskStatus::InProgress, "vibe-kanban: in progress", "fbca04"),
+    (TaskStatus::InReview, "vibe-kanban: in review", "1d76db"),
+    (TaskStatus::Done, "vibe-kanban: done", "0e8a16"),
+];
+
+/// Re-listing starts this far before the previous listing, covering clock skew
+/// between this machine and GitHub
+const ISSUE_LIST_OVERLAP_MINUTES: i64 = 5;
+
+#[derive(Debug, Error)]
+pub enum GitHubIssueSyncError {
+    #[error(transparent)]
+    GitHost(#[from] GitHostError),
+    #[error(transparent)]
+    GitService(#[from] GitServiceError),
+    #[error(transparent)]
+    Workspace(#[from] WorkspaceError),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Repository {0} not found")]
+    RepoNotFound(Uuid),
+    #[error("Issue sync only supports repositories hosted on GitHub")]
+    NotGitHub,
+}
+
+/// Which way a field that changed since the last sync is copied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldResolution {
+    pub direction: GitHubIssueSyncDirection,
+    /// Both sides had changed; the most recent edit wins
+    pub conflict: bool,
+}
+
+pub fn resolve_field(
+    local_changed: bool,
+    remote_changed: bool,
+    local_is_newer: bool,
+) -> Option<FieldResolution> {
+    let direction = match (local_changed, remote_changed) {
+        (false, false) => return None,
+        (true, false) => GitHubIssueSyncDirection::Push,
+        (false, true) => GitHubIssueSyncDirection::Pull,
+        (true, true) if local_is_newer => GitHubIssueSyncDirection::Push,
+        (true, true) => GitHubIssueSyncDirection::Pull,
+    };
+    Some(FieldResolution {
+        direction,
+        conflict: local_changed && remote_changed,
+    })
+}
+
+fn is_closed(status: &TaskStatus) -> bool {
+    matches!(status, TaskStatus::Done | TaskStatus::Cancelled)
+}
+
+fn import_footer(issue_url: &str) -> String {
+    format!("Imported from {issue_url}")
+}
+
+/// The issue body a task description corresponds to, without the footer added
+/// when the issue was imported
+fn issue_body_from_description(description: Option<&str>, issue_url: &str) -> String {
+    let description = description.unwrap_or_default().trim_end();
+    description
+        .strip_suffix(&import_footer(issue_url))
+        .unwrap_or(description)
+        .trim_end()
+        .to_string()
+}
+
+fn description_from_issue_body(body: &str, issue_url: &str, with_footer: bool) -> Option<String> {
+    let mut description = body.trim_end().to_string();
+    if with_footer {
+        if !description.is_empty() {
+            description.push_str("\n\n");
+        }
+        description.push_str(&import_footer(issue_url));
+    }
+    Some(description).filter(|d| !d.is_empty())
+}
+
+/// Issue number from an `Imported from <url>` footer pointing at `repo_info`
+fn imported_issue_number(description: &str, repo_info: &GitHubRepoInfo) -> Option<i64> {
+    let url = description
+        .lines()
+        .rev()
+        .find_map(|line| line.trim().strip_prefix("Imported from "))?;
+    let path = format!("/{}/{}/issues/", repo_info.owner, repo_info.repo_name).to_lowercase();
+    let url = url.to_lowercase();
+    let (_, number) = url.split_once(&path)?;
+    number.trim_end_matches('/').parse().ok()
+}
+
+/// Linked issues of one repository as of the last poll. Each poll lists only
+/// the issues updated since, so unchanged issues cost no request.
+struct IssueCache {
+    listed_at: DateTime<Utc>,
+    issues: HashMap<i64, GitHubIssue>,
+}
+
+/// Keeps tasks linked to GitHub issues in step with them.
+///
+/// Each poll compares both sides with how they looked after the previous sync.
+/// Title and body edits travel in whichever direction they were made, local
+/// status is mirrored as labels and by closing or reopening the issue, and
+/// issues closed on GitHub mark the task done. When both sides changed the
+/// same field the most recent edit wins. Every change is recorded as a
+/// [`GitHubIssueSyncEvent`]. Pull requests and agent summaries are posted to
+/// the issue as comments.
+pub struct GitHubIssueSyncService {
+    db: DBService,
+    git: GitService,
+    github: GitHubProvider,
+    poll_interval: Duration,
+    /// Keyed by repo id
+    issue_cache: Mutex<HashMap<Uuid, IssueCache>>,
+}
+
+impl GitHubIssueSyncService {
+    pub fn new(db: DBService, git: GitService) -> Result<Self, GitHubIssueSyncError> {
+        Ok(Self {
+            db,
+            git,
+            github: GitHubProvider::new()?,
+            poll_interval: Duration::from_secs(120),
+            issue_cache: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub async fn spawn(db: DBService, git: GitService) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            match Self::new(db, git) {
+                Ok(service) => service.start().await,
+                Err(e) => error!("Failed to start GitHub issue sync: {}", e),
+            }
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting GitHub issue sync with interval {:?}",
+            self.poll_interval
+        );
+
+        let mut interval = interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.sync_all_projects().await {
+                error!("Error syncing GitHub issues: {}", e);
+            }
+        }
+    }
+
+    async fn sync_all_projects(&self) -> Result<(), GitHubIssueSyncError> {
+        let settings = GitHubIssueSyncSettings::find_enabled(&self.db.pool).await?;
+        if settings.is_empty() {
+            return Ok(());
+        }
+        // Skip the whole round rather than failing every link when gh is unusable
+        if let Err(e) = self.github.check_auth().await {
+            debug!("Skipping GitHub issue sync: {}", e);
+            return Ok(());
+        }
+
+        for settings in settings {
+            if let Err(e) = self.sync_project(&settings).await {
+                error!(
+                    "Error syncing GitHub issues for project {}: {}",
+                    settings.project_id, e
+                );
+            }
+        }
+        Ok(())
+    }
+
+    async fn repo_info(&self, repo_id: Uuid) -> Result<GitHubRepoInfo, GitHubIssueSyncError> {
+        let repo = Repo::find_by_id(&self.db.pool, repo_id)
+            .await?
+            .ok_or(GitHubIssueSyncError::RepoNotFound(repo_id))?;
+        let remote_url = self.git.get_default_remote_url(&repo.path)?;
+        if !matches!(
+            GitHostService::from_url(&remote_url),
+            Ok(GitHostService::GitHub(_))
+        ) {
+            return Err(GitHubIssueSyncError::NotGitHub);
+        }
+        Ok(self.github.get_repo_info(&remote_url, &repo.path).await?)
+    }
+
+    /// Take the repository's cached issues, refreshed with those updated since
+    /// they were last listed. Issues not cached yet are fetched by `sync_link`.
+    async fn refreshed_issues(
+        &self,
+        repo_id: Uuid,
+        repo_info: &GitHubRepoInfo,
+    ) -> Result<IssueCache, GitHubIssueSyncError> {
+        let cached = self.issue_cache.lock().unwrap().remove(&repo_id);
+        let listed_at = Utc::now();
+        let Some(mut cache) = cached else {
+            return Ok(IssueCache {
+                listed_at,
+                issues: HashMap::new(),
+            });
+        };
+
+        let since = cache.listed_at - ChronoDuration::minutes(ISSUE_LIST_OVERLAP_MINUTES);
+        for issue in self
+            .github
+            .list_issues_updated_since(repo_info, since)
+            .await?
+        {
+            if let Some(cached) = cache.issues.get_mut(&issue.number) {
+                *cached = issue;
+            }
+        }
+        cache.listed_at = listed_at;
+        Ok(cache)
+    }
+
+    pub async fn sync_project(
+        &self,
+        settings: &GitHubIssueSyncSettings,
+    ) -> Result<(), GitHubIssueSyncError> {
+        let pool = &self.db.pool;
+        let repo_info = self.repo_info(settings.repo_id).await?;
+        self.link_imported_tasks(settings, &repo_info).await?;
+
+        let links =
+            TaskGitHubIssue::find_by_project_and_repo(pool, settings.project_id, settings.repo_id)
+                .await?;
+        debug!(
+            "Syncing {} linked issues for project {}",
+            links.len(),
+            settings.project_id
+        );
+        let mut cache = self.refreshed_issues(settings.repo_id, &repo_info).await?;
+        for link in links {
+            let Some(task) = Task::find_by_id(pool, link.task_id).await? else {
+                continue;
+            };
+            if let Err(e) = self
+                .sync_link(&repo_info, &task, &link, &mut cache.issues)
+                .await
+            {
+                warn!(
+                    "Error syncing task {} with issue #{}: {}",
+                    task.id, link.issue_number, e
+                );
+            }
+        }
+        self.issue_cache
+            .lock()
+            .unwrap()
+            .insert(settings.repo_id, cache);
+        Ok(())
+    }
+
+    /// Link tasks created by importing issues of the synced repository
+    async fn link_imported_tasks(
+        &self,
+        settings: &GitHubIssueSyncSettings,
+        repo_info: &GitHubRepoInfo,
+    ) -> Result<(), GitHubIssueSyncError> {
+        let pool = &self.db.pool;
+        for task in Task::find_by_project_id_with_attempt_status(pool, settings.project_id).await? {
+            let Some(number) = task
+                .description
+                .as_deref()
+                .and_then(|d| imported_issue_number(d, repo_info))
+            else {
+                continue;
+            };
+            if TaskGitHubIssue::find_by_task_id(pool, task.id)
+                .await?
+                .is_some()
+            {
+                continue;
+            }
+            match self
+                .link_task(&task, settings.repo_id, repo_info, number)
+                .await
+            {
+                Ok(_) => info!("Linked imported task {} to issue #{}", task.id, number),
+                Err(e) => warn!(
+                    "Failed to link imported task {} to issue #{}: {}",
+                    task.id, number, e
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// Link a task to an issue. The issue is taken as the starting point, so the
+    /// task's current status is pushed on the next sync.
+    pub async fn link_task(
+        &self,
+        task: &Task,
+        repo_id: Uuid,
+        repo_info: &GitHubRepoInfo,
+        issue_number: i64,
+    ) -> Result<TaskGitHubIssue, GitHubIssueSyncError> {
+        let issue = self.github.view_issue(repo_info, issue_number).await?;
+        let remote_closed = issue.state == GitHubIssueState::Closed;
+        let state = GitHubIssueSyncState {
+            local_title: task.title.clone(),
+            local_body: issue_body_from_description(task.description.as_deref(), &issue.url),
+            local_status: if remote_closed {
+                TaskStatus::Done
+            } else {
+                TaskStatus::Todo
+            },
+            remote_title: issue.title.clone(),
+            remote_body: issue.body.clone(),
+            remote_closed,
+        };
+        Ok(TaskGitHubIssue::create(
+            &self.db.pool,
+            task.id,
+            repo_id,
+            issue.number,
+            &issue.url,
+            &state,
+        )
+        .await?)
+    }
+
+    /// Link a task using the project's sync settings
+    pub async fn link_task_in_project(
+        &self,
+        task: &Task,
+        settings: &GitHubIssueSyncSettings,
+        issue_number: i64,
+    ) -> Result<TaskGitHubIssue, GitHubIssueSyncError> {
+        let repo_info = self.repo_info(settings.repo_id).await?;
+        self.link_task(task, settings.repo_id, &repo_info, issue_number)
+            .await
+    }
+
+    async fn sync_link(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        task: &Task,
+        link: &TaskGitHubIssue,
+        issues: &mut HashMap<i64, GitHubIssue>,
+    ) -> Result<(), GitHubIssueSyncError> {
+        let pool = &self.db.pool;
+        let issue = match issues.get(&link.issue_number) {
+            Some(issue) => issue.clone(),
+            None => {
+                let issue = self.github.view_issue(repo_info, link.issue_number).await?;
+                issues.insert(link.issue_number, issue.clone());
+                issue
+            }
+        };
+        let plan = plan_sync(task, &issue, &link.sync_state());
+
+        if plan.task_changed(task) {
+            let description = if plan.body_pulled {
+                description_from_issue_body(
+                    &plan.next.local_body,
+                    &link.issue_url,
+                    task.description
+                        .as_deref()
+                        .is_some_and(|d| d.trim_end().ends_with(&import_footer(&link.issue_url))),
+                )
+            } else {
+                task.description.clone()
+            };
+            Task::update(
+                pool,
+                task.id,
+                task.project_id,
+                plan.next.local_title.clone(),
+                description,
+                plan.next.local_status.clone(),
+                task.parent_workspace_id,
+            )
+            .await?;
+        }
+
+        let update = plan.update;
+        for label in &update.add_labels {
+            let Some((_, _, color)) = STATUS_LABELS
+                .iter()
+                .find(|(_, name, _)| *name == label.as_str())
+            else {
+                warn!(
+                    "Skipping sync of task {} with issue #{}: {} is not a status label",
+                    task.id, link.issue_number, label
+                );
+                return Ok(());
+            };
+            self.github
+                .ensure_label(repo_info, label, color, "Task status in Vibe Kanban")
+                .await?;
+        }
+        if !update.is_empty() {
+            self.github
+                .update_issue(repo_info, link.issue_number, &update)
+                .await?;
+            // Fetched again on the next poll rather than patched locally
+            issues.remove(&link.issue_number);
+        }
+
+        TaskGitHubIssue::update_synced(pool, link.id, &plan.next).await?;
+        for event in plan.events {
+            self.record_event(task.id, event).await?;
+        }
+
+        // Each comment is saved as soon as it is posted, so a failure part way
+        // through doesn't post the earlier ones again on the next poll
+        let mut commented_pr_urls = link.commented_pr_urls.0.clone();
+        for workspace in Workspace::fetch_all(pool, Some(task.id)).await? {
+            for merge in Merge::find_by_workspace_id(pool, workspace.id).await? {
+                let Merge::Pr(pr) = merge else {
+                    continue;
+                };
+                if commented_pr_urls.contains(&pr.pr_info.url) {
+                    continue;
+                }
+                self.github
+                    .comment_on_issue(
+                        repo_info,
+                        link.issue_number,
+                        &format!("{ISSUE_SYNC_MARKER}\nPull request: {}", pr.pr_info.url),
+                    )
+                    .await?;
+                commented_pr_urls.push(pr.pr_info.url.clone());
+                TaskGitHubIssue::update_commented_pr_urls(pool, link.id, &commented_pr_urls)
+                    .await?;
+                self.record_event(task.id, SyncChange::push("pr_link", Some(pr.pr_info.url)))
+                    .await?;
+            }
+        }
+
+        if let Some(turn) = CodingAgentTurn::find_latest_with_summary_by_task_id(pool, task.id)
+            .await?
+            .filter(|turn| Some(turn.id) != link.last_summary_turn_id)
+            && let Some(summary) = turn.summary.as_deref().filter(|s| !s.trim().is_empty())
+        {
+            self.github
+                .comment_on_issue(
+                    repo_info,
+                    link.issue_number,
+                    &format!("{ISSUE_SYNC_MARKER}\n**Agent summary**\n\n{summary}"),
+                )
+                .await?;
+            TaskGitHubIssue::update_last_summary_turn_id(pool, link.id, turn.id).await?;
+            self.record_event(
+                task.id,
+                SyncChange::push("summary", Some(summary.to_string())),
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn record_event(&self, task_id: Uuid, event: SyncChange) -> Result<(), sqlx::Error> {
+        GitHubIssueSyncEvent::create(
+            &self.db.pool,
+            &CreateGitHubIssueSyncEvent {
+                task_id,
+                direction: event.direction,
+                field: event.field.to_string(),
+                value: event.value,
+                conflict: event.conflict,
+            },
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct SyncChange {
+    direction: GitHubIssueSyncDirection,
+    field: &'static str,
+    value: Option<String>,
+    conflict: bool,
+}
+
+impl SyncChange {
+    fn new(field: &'static str, resolution: FieldResolution, value: Option<String>) -> Self {
+        Self {
+            direction: resolution.direction,
+            field,
+            value,
+            conflict: resolution.conflict,
+        }
+    }
+
+    fn push(field: &'static str, value: Option<String>) -> Self {
+        Self {
+            direction: GitHubIssueSyncDirection::Push,
+            field,
+            value,
+            conflict: false,
+        }
+    }
+}
+
+/// What one sync of a link changes on each side
+#[derive(Debug)]
+struct SyncPlan {
+    /// Both sides after the sync
+    next: GitHubIssueSyncState,
+    update: GitHubIssueUpdate,
+    body_pulled: bool,
+    events: Vec<SyncChange>,
+}
+
+impl SyncPlan {
+    fn task_changed(&self, task: &Task) -> bool {
+        self.next.local_title != task.title
+            || self.body_pulled
+            || self.next.local_status != task.status
+    }
+}
+
+fn plan_sync(task: &Task, issue: &GitHubIssue, synced: &GitHubIssueSyncState) -> SyncPlan {
+    let local_body = issue_body_from_description(task.description.as_deref(), &issue.url);
+    let remote_closed = issue.state == GitHubIssueState::Closed;
+    let local_is_newer = task.updated_at > issue.updated_at;
+
+    let mut next = GitHubIssueSyncState {
+        local_title: task.title.clone(),
+        local_body: local_body.clone(),
+        local_status: task.status.clone(),
+        remote_title: issue.title.clone(),
+        remote_body: issue.body.clone(),
+        remote_closed,
+    };
+    let mut update = GitHubIssueUpdate::default();
+    let mut body_pulled = false;
+    let mut events = Vec::new();
+
+    if let Some(resolution) = resolve_field(
+        task.title != synced.local_title,
+        issue.title != synced.remote_title,
+        local_is_newer,
+    ) {
+        if resolution.direction == GitHubIssueSyncDirection::Push {
+            update.title = Some(task.title.clone());
+            next.remote_title = task.title.clone();
+        } else {
+            next.local_title = issue.title.clone();
+        }
+        events.push(SyncChange::new(
+            "title",
+            resolution,
+            Some(next.local_title.clone()),
+        ));
+    }
+
+    if let Some(resolution) = resolve_field(
+        local_body != synced.local_body,
+        issue.body != synced.remote_body,
+        local_is_newer,
+    ) {
+        if resolution.direction == GitHubIssueSyncDirection::Push {
+            update.body = Some(local_body.clone());
+            next.remote_body = local_body.clone();
+        } else {
+            next.local_body = issue.body.clone();
+            body_pulled = true;
+        }
+        events.push(SyncChange::new("body", resolution, None));
+    }
+
+    if let Some(resolution) = resolve_field(
+        task.status != synced.local_status,
+        remote_closed != synced.remote_closed,
+        local_is_newer,
+    ) {
+        if resolution.direction == GitHubIssueSyncDirection::Push {
+            let closed = is_closed(&task.status);
+            if closed != remote_closed {
+                update.state = Some(if closed {
+                    GitHubIssueState::Closed
+                } else {
+                    GitHubIssueState::Open
+                });
+                next.remote_closed = closed;
+            }
+        } else if remote_closed && !is_closed(&task.status) {
+            next.local_status = TaskStatus::Done;
+        } else if !remote_closed && is_closed(&task.status) {
+            next.local_status = TaskStatus::Todo;
+        }
+        events.push(SyncChange::new(
+            "status",
+            resolution,
+            Some(next.local_status.to_string()),
+        ));
+    }
+
+    // Labels always mirror the resulting local status
+    let wanted = STATUS_LABELS
+        .iter()
+        .find(|(status, _, _)| *status == next.local_status)
+        .map(|(_, name, _)| *name);
+    for (_, name, _) in STATUS_LABELS {
+        let present = issue.labels.iter().any(|l| l == name);
+        if Some(name) == wanted && !present {
+            update.add_labels.push(name.to_string());
+        } else if Some(name) != wanted && present {
+            update.remove_labels.push(name.to_string());
+        }
+    }
+
+    SyncPlan {
+        next,
+        update,
+        body_pulled,
+        events,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "https://github.com/acme/app/issues/7";
+
+    fn task(title: &str, description: &str, status: TaskStatus) -> Task {
+        Task {
+            id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            title: title.to_string(),
+            description: Some(description.to_string()),
+            status,
+            parent_workspace_id: None,
+            parent_task_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn issue(title: &str, body: &str, state: GitHubIssueState, labels: &[&str]) -> GitHubIssue {
+        GitHubIssue {
+            number: 7,
+            title: title.to_string(),
+            body: body.to_string(),
+            url: URL.to_string(),
+            state,
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            milestone: None,
+            updated_at: Utc::now() - ChronoDuration::hours(1),
+        }
+    }
+
+    fn synced(title: &str, body: &str, status: TaskStatus, closed: bool) -> GitHubIssueSyncState {
+        GitHubIssueSyncState {
+            local_title: title.to_string(),
+            local_body: body.to_string(),
+            local_status: status,
+            remote_title: title.to_string(),
+            remote_body: body.to_string(),
+            remote_closed: closed,
+        }
+    }
+
+    #[test]
+    fn test_resolve_field() {
+        use GitHubIssueSyncDirection::*;
+        assert_eq!(resolve_field(false, false, true), None);
+        assert_eq!(
+            resolve_field(true, false, false).map(|r| (r.direction, r.conflict)),
+            Some((Push, false))
+        );
+        assert_eq!(
+            resolve_field(false, true, true).map(|r| (r.direction, r.conflict)),
+            Some((Pull, false))
+        );
+        assert_eq!(
+            resolve_field(true, true, true).map(|r| (r.direction, r.conflict)),
+            Some((Push, true))
+        );
+        assert_eq!(
+            resolve_field(true, true, false).map(|r| (r.direction, r.conflict)),
+            Some((Pull, true))
+        );
+    }
+
+    #[test]
+    fn test_plan_sync_pushes_status_and_pulls_edits() {
+        let task = task(
+            "Fix login",
+            &format!("Steps\n\nImported from {URL}"),
+            TaskStatus::InReview,
+        );
+        let issue = issue(
+            "Fix login on Safari",
+            "Steps",
+            GitHubIssueState::Open,
+            &["bug", "vibe-kanban: in progress"],
+        );
+        let plan = plan_sync(
+            &task,
+            &issue,
+            &synced("Fix login", "Steps", TaskStatus::InProgress, false),
+        );
+
+        assert_eq!(plan.next.local_title, "Fix login on Safari");
+        assert!(!plan.body_pulled);
+        assert_eq!(plan.update.title, None);
+        assert_eq!(plan.update.body, None);
+        assert_eq!(plan.update.state, None);
+        assert_eq!(plan.update.add_labels, vec!["vibe-kanban: in review"]);
+        assert_eq!(plan.update.remove_labels, vec!["vibe-kanban: in progress"]);
+        assert!(plan.task_changed(&task));
+    }
+
+    #[test]
+    fn test_plan_sync_conflict_keeps_newest_edit() {
+        let task = task("Local title", "", TaskStatus::Todo);
+        let issue = issue("Remote title", "", GitHubIssueState::Closed, &[]);
+        let plan = plan_sync(&task, &issue, &synced("Old", "", TaskStatus::Todo, false));
+
+        // The task was updated after the issue, so its title wins
+        assert_eq!(plan.update.title.as_deref(), Some("Local title"));
+        assert_eq!(plan.next.remote_title, "Local title");
+        assert!(plan.events.iter().any(|e| e.field == "title" && e.conflict));
+
+        // Closing on GitHub marks the task done
+        assert_eq!(plan.next.local_status, TaskStatus::Done);
+        assert_eq!(plan.update.add_labels, vec!["vibe-kanban: done"]);
+    }
+
+    #[test]
+    fn test_imported_issue_number() {
+        let repo_info = GitHubRepoInfo {
+            owner: "Acme".to_string(),
+            repo_name: "app".to_string(),
+        };
+        assert_eq!(
+            imported_issue_number(&format!("Body\n\nImported from {URL}"), &repo_info),
+            Some(7)
+        );
+        assert_eq!(
+            imported_issue_number(
+                "Imported from https://github.com/acme/other/issues/7",
+                &repo_info
+            ),
+            None
+        );
+        assert_eq!(
+            issue_body_from_description(Some(&format!("Body\n\nImported from {URL}")), URL),
+            "Body"
+        );
+    }
+}
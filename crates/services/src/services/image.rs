@@ -76,6 +76,10 @@ impl ImageService {
         })
     }
 
+    pub fn max_size_bytes(&self) -> u64 {
+        self.max_size_bytes
+    }
+
     pub async fn store_image(
         &self,
         data: &[u8],
@@ -0,0 +1,147 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::{Arc, OnceLock},
+};
+
+use dashmap::DashMap;
+use sha2::{Digest, Sha256};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+type TokenHash = [u8; 32];
+
+/// Bearer tokens that let a workspace's coding agent reach the MCP endpoint on
+/// the main server. Each workspace gets one token, handed to its coding agent via
+/// `VK_MCP_TOKEN`, so the server knows which workspace is calling.
+///
+/// Tokens are looked up by their SHA-256 hash, so a lookup never compares the
+/// presented token against the stored ones byte by byte.
+#[derive(Clone)]
+pub struct McpTokenService {
+    tokens: Arc<DashMap<Uuid, String>>,
+    workspaces: Arc<DashMap<TokenHash, Uuid>>,
+    revoked: broadcast::Sender<Uuid>,
+    server_url: Arc<OnceLock<String>>,
+}
+
+impl Default for McpTokenService {
+    fn default() -> Self {
+        let (revoked, _) = broadcast::channel(64);
+        Self {
+            tokens: Arc::default(),
+            workspaces: Arc::default(),
+            revoked,
+            server_url: Arc::default(),
+        }
+    }
+}
+
+fn hash_token(token: &str) -> TokenHash {
+    Sha256::digest(token.as_bytes()).into()
+}
+
+impl McpTokenService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the address the main server is listening on. Set once after it
+    /// binds; a wildcard address is reached through loopback.
+    pub fn set_server_addr(&self, addr: SocketAddr) {
+        let ip = match addr.ip() {
+            IpAddr::V4(ip) if ip.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
+            IpAddr::V6(ip) if ip.is_unspecified() => IpAddr::V6(Ipv6Addr::LOCALHOST),
+            ip => ip,
+        };
+        let url = format!("http://{}", SocketAddr::new(ip, addr.port()));
+        if self.server_url.set(url).is_err() {
+            tracing::warn!("MCP server URL was already set");
+        }
+    }
+
+    pub fn server_url(&self) -> Option<&str> {
+        self.server_url.get().map(String::as_str)
+    }
+
+    /// URL of the streamable HTTP MCP endpoint, once the server is listening
+    pub fn mcp_url(&self) -> Option<String> {
+        self.server_url().map(|url| format!("{url}/api/mcp"))
+    }
+
+    /// Token for the workspace, created on first use and stable until revoked
+    pub fn token_for_workspace(&self, workspace_id: Uuid) -> String {
+        self.tokens
+            .entry(workspace_id)
+            .or_insert_with(|| {
+                let token = format!(
+                    "vk_mcp_{}{}",
+                    Uuid::new_v4().simple(),
+                    Uuid::new_v4().simple()
+                );
+                self.workspaces.insert(hash_token(&token), workspace_id);
+                token
+            })
+            .clone()
+    }
+
+    pub fn workspace_for_token(&self, token: &str) -> Option<Uuid> {
+        self.workspaces
+            .get(&hash_token(token))
+            .map(|entry| *entry.value())
+    }
+
+    pub fn revoke(&self, workspace_id: Uuid) {
+        if let Some((_, token)) = self.tokens.remove(&workspace_id) {
+            self.workspaces.remove(&hash_token(&token));
+            // Nobody listening just means nothing is cached for the workspace
+            let _ = self.revoked.send(workspace_id);
+        }
+    }
+
+    /// Workspaces whose token was revoked, so state kept per token can be dropped
+    pub fn subscribe_revocations(&self) -> broadcast::Receiver<Uuid> {
+        self.revoked.subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_identify_their_workspace_until_revoked() {
+        let service = McpTokenService::new();
+        let mut revocations = service.subscribe_revocations();
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+
+        let token = service.token_for_workspace(first);
+        assert_eq!(service.token_for_workspace(first), token);
+        assert_ne!(service.token_for_workspace(second), token);
+        assert_eq!(service.workspace_for_token(&token), Some(first));
+        assert_eq!(service.workspace_for_token("vk_mcp_unknown"), None);
+
+        service.revoke(first);
+        assert_eq!(service.workspace_for_token(&token), None);
+        assert_eq!(revocations.try_recv().ok(), Some(first));
+        assert_ne!(service.token_for_workspace(first), token);
+    }
+
+    #[test]
+    fn server_url_reaches_wildcard_addresses_through_loopback() {
+        let v4 = McpTokenService::new();
+        v4.set_server_addr("0.0.0.0:4000".parse().unwrap());
+        assert_eq!(
+            v4.mcp_url().as_deref(),
+            Some("http://127.0.0.1:4000/api/mcp")
+        );
+
+        let v6 = McpTokenService::new();
+        v6.set_server_addr("[::]:4000".parse().unwrap());
+        assert_eq!(v6.server_url(), Some("http://[::1]:4000"));
+
+        let bound = McpTokenService::new();
+        bound.set_server_addr("192.168.1.20:4000".parse().unwrap());
+        assert_eq!(bound.server_url(), Some("http://192.168.1.20:4000"));
+    }
+}
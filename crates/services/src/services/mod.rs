@@ -1,9 +1,12 @@
+pub mod agent_instructions;
 pub mod analytics;
 pub mod approvals;
 pub mod auth;
 pub mod config;
 pub mod container;
+pub mod diff_stats;
 pub mod diff_stream;
+pub mod disk_usage;
 pub mod events;
 pub mod file_ranker;
 pub mod file_search;
@@ -11,15 +14,23 @@ pub mod filesystem;
 pub mod filesystem_watcher;
 pub mod git;
 pub mod git_host;
+pub mod github_issue_sync;
 pub mod image;
+pub mod mcp_tokens;
 pub mod notification;
 pub mod oauth_credentials;
 pub mod pr_monitor;
+pub mod preview;
 pub mod project;
 #[cfg(feature = "qa-mode")]
 pub mod qa_repos;
 pub mod queued_message;
 pub mod remote_client;
+pub mod remote_issue;
 pub mod repo;
+pub mod scheduler;
+pub mod secrets;
+pub mod task_import;
 pub mod workspace_manager;
+pub mod workspace_publisher;
 pub mod worktree_manager;
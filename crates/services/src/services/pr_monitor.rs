@@ -1,48 +1,97 @@
-use std::time::Duration;
+use std::{collections::BTreeMap, time::Duration};
 
+use chrono::{DateTime, Utc};
 use db::{
     DBService,
     models::{
+        execution_process::{ExecutionProcess, ExecutionProcessRunReason},
         merge::{Merge, MergeStatus, PrMerge},
+        pr_comment_watch::{
+            CreatePrCommentFollowUp, PrCommentFollowUp, PrCommentFollowUpStatus, PrCommentWatch,
+            PrCommentWatchMode,
+        },
+        repo::Repo,
         task::{Task, TaskStatus},
         workspace::{Workspace, WorkspaceError},
+        workspace_repo::WorkspaceRepo,
     },
 };
 use serde_json::json;
 use sqlx::error::Error as SqlxError;
 use thiserror::Error;
-use tokio::time::interval;
-use tracing::{debug, error, info};
+use tokio::{sync::mpsc, time::interval};
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
 
 use crate::services::{
     analytics::AnalyticsContext,
-    git_host::{self, GitHostError, GitHostProvider},
+    git::{GitService, GitServiceError},
+    git_host::{self, GitHostError, GitHostProvider, UnifiedPrComment},
+    notification::NotificationService,
 };
 
+/// Appended to replies posted by the PR monitor so they are not picked up
+/// again as new review comments.
+pub const PR_COMMENT_REPLY_MARKER: &str = "<!-- vibe-kanban:pr-comment-reply -->";
+
 #[derive(Debug, Error)]
 enum PrMonitorError {
     #[error(transparent)]
     GitHostError(#[from] GitHostError),
     #[error(transparent)]
+    GitServiceError(#[from] GitServiceError),
+    #[error(transparent)]
     WorkspaceError(#[from] WorkspaceError),
     #[error(transparent)]
     Sqlx(#[from] SqlxError),
+    #[error("Repository {0} not found for PR")]
+    RepoNotFound(Uuid),
 }
 
-/// Service to monitor PRs and update task status when they are merged
+/// Service to monitor PRs and update task status when they are merged.
+///
+/// Workspaces with a [`PrCommentWatch`] also have new inline review comments
+/// turned into agent follow-ups, and the corresponding threads are replied to
+/// and resolved once the agent's changes have been pushed.
 pub struct PrMonitorService {
     db: DBService,
+    git: GitService,
+    notification_service: NotificationService,
+    /// Receives ids of [`PrCommentFollowUp`]s that should be dispatched to the agent
+    follow_up_tx: mpsc::UnboundedSender<Uuid>,
     poll_interval: Duration,
     analytics: Option<AnalyticsContext>,
 }
 
+/// Review comments left on the same line of the same file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewCommentGroup {
+    pub path: String,
+    pub line: Option<i64>,
+    pub comments: Vec<ReviewCommentEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewCommentEntry {
+    pub id: i64,
+    pub author: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
 impl PrMonitorService {
     pub async fn spawn(
         db: DBService,
+        git: GitService,
+        notification_service: NotificationService,
+        follow_up_tx: mpsc::UnboundedSender<Uuid>,
         analytics: Option<AnalyticsContext>,
     ) -> tokio::task::JoinHandle<()> {
         let service = Self {
             db,
+            git,
+            notification_service,
+            follow_up_tx,
             poll_interval: Duration::from_secs(60), // Check every minute
             analytics,
         };
@@ -143,6 +192,369 @@ impl PrMonitorService {
             }
         }
 
+        if matches!(&pr_status.status, MergeStatus::Open) {
+            if let Err(e) = self.check_review_comments(pr_merge).await {
+                warn!(
+                    "Error checking review comments for PR #{}: {}",
+                    pr_merge.pr_info.number, e
+                );
+            }
+            if let Err(e) = self.reply_to_addressed_comments(pr_merge).await {
+                warn!(
+                    "Error replying to addressed review comments for PR #{}: {}",
+                    pr_merge.pr_info.number, e
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Resolve the local repository and remote URL a PR was opened against
+    async fn repo_and_remote_url(
+        &self,
+        pr_merge: &PrMerge,
+    ) -> Result<(Repo, String), PrMonitorError> {
+        let repo = Repo::find_by_id(&self.db.pool, pr_merge.repo_id)
+            .await?
+            .ok_or(PrMonitorError::RepoNotFound(pr_merge.repo_id))?;
+        let target_branch = WorkspaceRepo::find_by_workspace_and_repo_id(
+            &self.db.pool,
+            pr_merge.workspace_id,
+            pr_merge.repo_id,
+        )
+        .await?
+        .map(|wr| wr.target_branch)
+        .unwrap_or_else(|| pr_merge.target_branch_name.clone());
+        let remote_name = self
+            .git
+            .resolve_remote_name_for_branch(&repo.path, &target_branch)?;
+        let remote_url = self.git.get_remote_url(&repo.path, &remote_name)?;
+        Ok((repo, remote_url))
+    }
+
+    /// Turn review comments posted since the last check into a follow-up
+    async fn check_review_comments(&self, pr_merge: &PrMerge) -> Result<(), PrMonitorError> {
+        let Some(watch) =
+            PrCommentWatch::find_by_workspace_id(&self.db.pool, pr_merge.workspace_id).await?
+        else {
+            return Ok(());
+        };
+        let Some(workspace) = Workspace::find_by_id(&self.db.pool, pr_merge.workspace_id).await?
+        else {
+            return Ok(());
+        };
+
+        let (repo, remote_url) = self.repo_and_remote_url(pr_merge).await?;
+        let git_host = git_host::GitHostService::from_url(&remote_url)?;
+        let comments = git_host
+            .get_pr_comments(&repo.path, &remote_url, pr_merge.pr_info.number)
+            .await?;
+
+        let groups = group_new_review_comments(comments, watch.last_seen_at);
+        let Some(newest) = groups
+            .iter()
+            .flat_map(|g| g.comments.iter().map(|c| c.created_at))
+            .max()
+        else {
+            return Ok(());
+        };
+
+        let comment_ids: Vec<i64> = groups
+            .iter()
+            .flat_map(|g| g.comments.iter().map(|c| c.id))
+            .collect();
+        info!(
+            "Found {} new review comments on PR #{} for workspace {}",
+            comment_ids.len(),
+            pr_merge.pr_info.number,
+            workspace.id
+        );
+
+        let follow_up = PrCommentFollowUp::create(
+            &self.db.pool,
+            &CreatePrCommentFollowUp {
+                workspace_id: workspace.id,
+                merge_id: pr_merge.id,
+                status: PrCommentFollowUpStatus::PendingConfirmation,
+                prompt: build_review_follow_up_prompt(pr_merge.pr_info.number, &groups),
+                comment_ids,
+                head_commit: self.git.get_branch_oid(&repo.path, &workspace.branch).ok(),
+            },
+            Uuid::new_v4(),
+        )
+        .await?;
+        PrCommentWatch::update_last_seen_at(&self.db.pool, workspace.id, newest).await?;
+
+        match watch.mode {
+            PrCommentWatchMode::Auto => {
+                if self.follow_up_tx.send(follow_up.id).is_err() {
+                    error!("PR comment follow-up dispatcher is not running");
+                }
+            }
+            PrCommentWatchMode::Confirm => {
+                let task_title = Task::find_by_id(&self.db.pool, workspace.task_id)
+                    .await?
+                    .map(|t| t.title)
+                    .unwrap_or_default();
+                self.notification_service
+                    .notify(
+                        &format!("New review comments: {task_title}"),
+                        &format!(
+                            "PR #{} has new review comments. Confirm to send them to the agent.",
+                            pr_merge.pr_info.number
+                        ),
+                    )
+                    .await;
+            }
+        }
+
         Ok(())
     }
+
+    /// Reply to and resolve review threads once the agent's follow-up has
+    /// finished and its commits are on the remote branch
+    async fn reply_to_addressed_comments(&self, pr_merge: &PrMerge) -> Result<(), PrMonitorError> {
+        let awaiting =
+            PrCommentFollowUp::find_awaiting_reply_for_merge(&self.db.pool, pr_merge.id).await?;
+        if awaiting.is_empty() {
+            return Ok(());
+        }
+        if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(
+            &self.db.pool,
+            pr_merge.workspace_id,
+        )
+        .await?
+        {
+            return Ok(());
+        }
+        let Some(workspace) = Workspace::find_by_id(&self.db.pool, pr_merge.workspace_id).await?
+        else {
+            return Ok(());
+        };
+        let Some(latest_agent_run) = ExecutionProcess::find_latest_by_workspace_and_run_reason(
+            &self.db.pool,
+            workspace.id,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?
+        else {
+            return Ok(());
+        };
+
+        let (repo, remote_url) = self.repo_and_remote_url(pr_merge).await?;
+        let local_head = self.git.get_branch_oid(&repo.path, &workspace.branch)?;
+        let remote_name = self
+            .git
+            .resolve_remote_name_for_branch(&repo.path, &workspace.branch)?;
+        let remote_head = self
+            .git
+            .get_branch_oid(&repo.path, &format!("{remote_name}/{}", workspace.branch))
+            .ok();
+        if remote_head.as_deref() != Some(local_head.as_str()) {
+            debug!(
+                "Branch {} has unpushed commits, not replying to review comments yet",
+                workspace.branch
+            );
+            return Ok(());
+        }
+
+        let git_host = git_host::GitHostService::from_url(&remote_url)?;
+        let short_sha = &local_head[..local_head.len().min(7)];
+        let reply = format!("Addressed in {short_sha}.\n\n{PR_COMMENT_REPLY_MARKER}");
+
+        for follow_up in awaiting {
+            // The agent has not run (or committed) since the follow-up was handed over
+            let ran_after_dispatch = match follow_up.execution_process_id {
+                Some(id) => id == latest_agent_run.id,
+                None => latest_agent_run.created_at >= follow_up.updated_at,
+            };
+            if !ran_after_dispatch || follow_up.head_commit.as_deref() == Some(local_head.as_str())
+            {
+                continue;
+            }
+
+            for comment_id in follow_up.comment_ids.iter().copied() {
+                if let Err(e) = git_host
+                    .reply_to_review_comment(
+                        &repo.path,
+                        &remote_url,
+                        pr_merge.pr_info.number,
+                        comment_id,
+                        &reply,
+                    )
+                    .await
+                {
+                    warn!("Failed to reply to review comment {}: {}", comment_id, e);
+                    continue;
+                }
+                if let Err(e) = git_host
+                    .resolve_review_thread(
+                        &repo.path,
+                        &remote_url,
+                        pr_merge.pr_info.number,
+                        comment_id,
+                    )
+                    .await
+                {
+                    warn!(
+                        "Failed to resolve review thread for comment {}: {}",
+                        comment_id, e
+                    );
+                }
+            }
+
+            PrCommentFollowUp::update_status(
+                &self.db.pool,
+                follow_up.id,
+                PrCommentFollowUpStatus::Replied,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Group inline review comments created after `since` by file and line,
+/// skipping replies posted by the monitor itself.
+pub fn group_new_review_comments(
+    comments: Vec<UnifiedPrComment>,
+    since: DateTime<Utc>,
+) -> Vec<ReviewCommentGroup> {
+    let mut groups: BTreeMap<(String, Option<i64>), Vec<ReviewCommentEntry>> = BTreeMap::new();
+    for comment in comments {
+        let UnifiedPrComment::Review {
+            id,
+            author,
+            body,
+            created_at,
+            path,
+            line,
+            ..
+        } = comment
+        else {
+            continue;
+        };
+        if created_at <= since || body.contains(PR_COMMENT_REPLY_MARKER) {
+            continue;
+        }
+        groups
+            .entry((path, line))
+            .or_default()
+            .push(ReviewCommentEntry {
+                id,
+                author,
+                body,
+                created_at,
+            });
+    }
+    groups
+        .into_iter()
+        .map(|((path, line), mut comments)| {
+            comments.sort_by_key(|c| c.created_at);
+            ReviewCommentGroup {
+                path,
+                line,
+                comments,
+            }
+        })
+        .collect()
+}
+
+/// Build the agent prompt for a set of grouped review comments
+pub fn build_review_follow_up_prompt(pr_number: i64, groups: &[ReviewCommentGroup]) -> String {
+    let mut prompt = format!(
+        "New review comments were left on PR #{pr_number}. Address each of them below, \
+         then commit your changes.\n"
+    );
+    for group in groups {
+        let location = match group.line {
+            Some(line) => format!("{}:{}", group.path, line),
+            None => group.path.clone(),
+        };
+        prompt.push_str(&format!("\n## {location}\n"));
+        for comment in &group.comments {
+            let body = comment.body.trim().replace('\n', "\n  ");
+            prompt.push_str(&format!("- @{}: {}\n", comment.author, body));
+        }
+    }
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn review(id: i64, path: &str, line: Option<i64>, minute: u32, body: &str) -> UnifiedPrComment {
+        UnifiedPrComment::Review {
+            id,
+            author: "reviewer".to_string(),
+            author_association: None,
+            body: body.to_string(),
+            created_at: Utc.with_ymd_and_hms(2026, 1, 20, 12, minute, 0).unwrap(),
+            url: None,
+            path: path.to_string(),
+            line,
+            side: None,
+            diff_hunk: None,
+        }
+    }
+
+    #[test]
+    fn test_group_new_review_comments() {
+        let since = Utc.with_ymd_and_hms(2026, 1, 20, 12, 0, 0).unwrap();
+        let comments = vec![
+            review(1, "src/lib.rs", Some(10), 0, "already seen"),
+            review(2, "src/main.rs", Some(3), 5, "rename this"),
+            review(3, "src/lib.rs", Some(10), 2, "handle the error"),
+            review(
+                4,
+                "src/lib.rs",
+                Some(10),
+                3,
+                &format!("Addressed.\n\n{PR_COMMENT_REPLY_MARKER}"),
+            ),
+            review(5, "src/lib.rs", Some(10), 1, "and log it"),
+            UnifiedPrComment::General {
+                id: "c1".to_string(),
+                author: "reviewer".to_string(),
+                author_association: None,
+                body: "LGTM otherwise".to_string(),
+                created_at: Utc.with_ymd_and_hms(2026, 1, 20, 12, 4, 0).unwrap(),
+                url: None,
+            },
+        ];
+
+        let groups = group_new_review_comments(comments, since);
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].path, "src/lib.rs");
+        assert_eq!(
+            groups[0].comments.iter().map(|c| c.id).collect::<Vec<_>>(),
+            vec![5, 3]
+        );
+        assert_eq!(groups[1].path, "src/main.rs");
+        assert_eq!(groups[1].comments[0].id, 2);
+    }
+
+    #[test]
+    fn test_build_review_follow_up_prompt() {
+        let since = Utc.with_ymd_and_hms(2026, 1, 20, 11, 0, 0).unwrap();
+        let groups = group_new_review_comments(
+            vec![
+                review(1, "src/lib.rs", Some(10), 0, "first line\nsecond line"),
+                review(2, "README.md", None, 1, "typo"),
+            ],
+            since,
+        );
+
+        let prompt = build_review_follow_up_prompt(42, &groups);
+
+        assert!(prompt.starts_with("New review comments were left on PR #42."));
+        assert!(prompt.contains("\n## README.md\n- @reviewer: typo\n"));
+        assert!(prompt.contains("\n## src/lib.rs:10\n- @reviewer: first line\n  second line\n"));
+    }
 }
@@ -0,0 +1,301 @@
+use std::{
+    net::{Ipv4Addr, TcpListener},
+    sync::{Arc, LazyLock, Mutex},
+};
+
+use dashmap::DashMap;
+use regex::Regex;
+use serde::Serialize;
+use tokio::task::JoinHandle;
+use ts_rs::TS;
+use uuid::Uuid;
+
+static FULL_URL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(https?)://(\[::1?\]|localhost|127\.0\.0\.1|0\.0\.0\.0)(?::(\d{2,5}))?")
+        .unwrap()
+});
+
+static HOST_PORT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(?:localhost|127\.0\.0\.1|0\.0\.0\.0|\[::1?\]):(\d{2,5})").unwrap()
+});
+
+static ANSI_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\x1b\[[0-9;?]*[A-Za-z]").unwrap());
+
+/// Dev-server preview state for a workspace
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct WorkspacePreview {
+    pub workspace_id: Uuid,
+    /// Port handed to dev-server scripts via `PORT` and `VK_PREVIEW_PORT`
+    pub port: u16,
+    /// Origin the dev server reported it is listening on, if seen in its output
+    pub detected_url: Option<String>,
+    /// Origin of the local reverse proxy for this workspace, once started. It
+    /// is a separate origin from the app so previewed pages cannot call `/api`,
+    /// and keeps its port across restarts.
+    pub proxy_url: Option<String>,
+}
+
+impl WorkspacePreview {
+    /// Origin requests are forwarded to. Falls back to the allocated port when
+    /// the dev server never printed a URL.
+    pub fn upstream_origin(&self) -> String {
+        self.detected_url
+            .clone()
+            .unwrap_or_else(|| format!("http://127.0.0.1:{}", self.port))
+    }
+}
+
+/// A dev-server port that stays bound until the reservation is dropped, so no
+/// other allocation can be handed the same port in the meantime. Drop it right
+/// before starting the dev server.
+#[derive(Debug)]
+pub struct PortReservation {
+    port: u16,
+    _listener: TcpListener,
+}
+
+impl PortReservation {
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+}
+
+struct ProxyServer {
+    port: u16,
+    handle: JoinHandle<()>,
+}
+
+/// In-memory port allocation, URL detection and proxy servers for workspace
+/// dev servers. One preview target per workspace.
+#[derive(Clone, Default)]
+pub struct PreviewService {
+    targets: Arc<DashMap<Uuid, WorkspacePreview>>,
+    proxies: Arc<DashMap<Uuid, ProxyServer>>,
+    /// Serializes port allocation so two workspaces never pick the same port
+    allocation: Arc<Mutex<()>>,
+}
+
+impl PreviewService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Port for the workspace's dev server. The same port is handed out on every
+    /// start while it is still free; the previously detected URL is cleared
+    /// since the new process may report a different one.
+    pub fn allocate_port(&self, workspace_id: Uuid) -> std::io::Result<PortReservation> {
+        let _guard = self
+            .allocation
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if let Some(mut target) = self.targets.get_mut(&workspace_id) {
+            target.detected_url = None;
+            if let Ok(listener) = TcpListener::bind((Ipv4Addr::LOCALHOST, target.port)) {
+                return Ok(PortReservation {
+                    port: target.port,
+                    _listener: listener,
+                });
+            }
+        }
+
+        let taken: Vec<u16> = self
+            .targets
+            .iter()
+            .filter(|t| t.workspace_id != workspace_id)
+            .map(|t| t.port)
+            .collect();
+        let reservation = loop {
+            let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+            let port = listener.local_addr()?.port();
+            if !taken.contains(&port) {
+                break PortReservation {
+                    port,
+                    _listener: listener,
+                };
+            }
+        };
+
+        let proxy_url = self.proxy_url(workspace_id);
+        self.targets.insert(
+            workspace_id,
+            WorkspacePreview {
+                workspace_id,
+                port: reservation.port,
+                detected_url: None,
+                proxy_url,
+            },
+        );
+        Ok(reservation)
+    }
+
+    /// Scan a chunk of dev-server output for the URL it is listening on.
+    /// Only the first URL reported after the port was allocated is kept.
+    pub fn record_output(&self, workspace_id: Uuid, output: &str) {
+        let Some(mut target) = self.targets.get_mut(&workspace_id) else {
+            return;
+        };
+        if target.detected_url.is_some() {
+            return;
+        }
+        if let Some(url) = output.lines().find_map(detect_listening_url) {
+            tracing::info!(
+                "Detected dev server URL {} for workspace {}",
+                url,
+                workspace_id
+            );
+            target.detected_url = Some(url);
+        }
+    }
+
+    pub fn get(&self, workspace_id: Uuid) -> Option<WorkspacePreview> {
+        self.targets.get(&workspace_id).map(|t| t.clone())
+    }
+
+    /// Port of the workspace's proxy server, if it is running
+    pub fn proxy_port(&self, workspace_id: Uuid) -> Option<u16> {
+        self.proxies.get(&workspace_id).map(|proxy| proxy.port)
+    }
+
+    /// Port of the workspace's proxy server, starting it with `start` if it is
+    /// not running yet. `start` returns the port it listens on and its task.
+    pub fn ensure_proxy(
+        &self,
+        workspace_id: Uuid,
+        start: impl FnOnce() -> std::io::Result<(u16, JoinHandle<()>)>,
+    ) -> std::io::Result<u16> {
+        let proxy = self
+            .proxies
+            .entry(workspace_id)
+            .or_try_insert_with(|| start().map(|(port, handle)| ProxyServer { port, handle }))?;
+        let port = proxy.port;
+        drop(proxy);
+
+        if let Some(mut target) = self.targets.get_mut(&workspace_id) {
+            target.proxy_url = Some(proxy_origin(port));
+        }
+        Ok(port)
+    }
+
+    fn proxy_url(&self, workspace_id: Uuid) -> Option<String> {
+        self.proxies
+            .get(&workspace_id)
+            .map(|proxy| proxy_origin(proxy.port))
+    }
+
+    pub fn release(&self, workspace_id: Uuid) {
+        self.targets.remove(&workspace_id);
+        if let Some((_, proxy)) = self.proxies.remove(&workspace_id) {
+            proxy.handle.abort();
+        }
+    }
+}
+
+fn proxy_origin(port: u16) -> String {
+    format!("http://127.0.0.1:{port}")
+}
+
+/// Extract the origin a dev server reports it is listening on from a log line.
+/// Only loopback and wildcard hosts are accepted, so output can never point
+/// the proxy at another machine; wildcard hosts are rewritten to loopback.
+/// Bare `host:port` mentions are assumed to be IPv4 loopback.
+pub fn detect_listening_url(line: &str) -> Option<String> {
+    let cleaned = ANSI_RE.replace_all(line, "");
+
+    if let Some(caps) = FULL_URL_RE.captures(&cleaned) {
+        let scheme = caps[1].to_ascii_lowercase();
+        let host = normalize_host(&caps[2]);
+        return Some(match caps.get(3) {
+            Some(port) => format!("{scheme}://{host}:{}", port.as_str()),
+            None => format!("{scheme}://{host}"),
+        });
+    }
+
+    let caps = HOST_PORT_RE.captures(&cleaned)?;
+    let scheme = if cleaned.to_ascii_lowercase().contains("https") {
+        "https"
+    } else {
+        "http"
+    };
+    Some(format!("{scheme}://127.0.0.1:{}", &caps[1]))
+}
+
+fn normalize_host(host: &str) -> &str {
+    match host {
+        "0.0.0.0" => "127.0.0.1",
+        "[::]" => "[::1]",
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_listening_url() {
+        assert_eq!(
+            detect_listening_url("  \x1b[32m➜\x1b[39m  Local:   http://localhost:5173/"),
+            Some("http://localhost:5173".to_string())
+        );
+        assert_eq!(
+            detect_listening_url("Listening on 0.0.0.0:8080"),
+            Some("http://127.0.0.1:8080".to_string())
+        );
+        assert_eq!(
+            detect_listening_url("ready at https://127.0.0.1:3443/app"),
+            Some("https://127.0.0.1:3443".to_string())
+        );
+        assert_eq!(detect_listening_url("compiled 42 modules"), None);
+    }
+
+    #[test]
+    fn test_detect_listening_url_ignores_other_hosts() {
+        assert_eq!(
+            detect_listening_url("  ➜  Network: http://192.168.1.20:5173/"),
+            None
+        );
+        assert_eq!(detect_listening_url("proxying to 10.0.0.5:8080"), None);
+        assert_eq!(
+            detect_listening_url("Server listening on http://[::1]:3000"),
+            Some("http://[::1]:3000".to_string())
+        );
+    }
+
+    #[test]
+    fn test_allocate_port_is_stable_per_workspace() {
+        let service = PreviewService::new();
+        let workspace_id = Uuid::new_v4();
+        let port = service.allocate_port(workspace_id).unwrap().port();
+
+        service.record_output(workspace_id, "Local: http://localhost:4000");
+        assert_eq!(
+            service.get(workspace_id).unwrap().upstream_origin(),
+            "http://localhost:4000"
+        );
+
+        // Restarting keeps the port but forgets the old URL
+        assert_eq!(service.allocate_port(workspace_id).unwrap().port(), port);
+        assert_eq!(
+            service.get(workspace_id).unwrap().upstream_origin(),
+            format!("http://127.0.0.1:{port}")
+        );
+        assert_ne!(service.allocate_port(Uuid::new_v4()).unwrap().port(), port);
+    }
+
+    #[test]
+    fn test_allocate_port_moves_off_a_port_in_use() {
+        let service = PreviewService::new();
+        let workspace_id = Uuid::new_v4();
+        let reservation = service.allocate_port(workspace_id).unwrap();
+        let port = reservation.port();
+
+        // Something else grabbed the port while the dev server was stopped
+        drop(reservation);
+        let _squatter = TcpListener::bind((Ipv4Addr::LOCALHOST, port)).unwrap();
+
+        let moved = service.allocate_port(workspace_id).unwrap();
+        assert_ne!(moved.port(), port);
+        assert_eq!(service.get(workspace_id).unwrap().port, moved.port());
+    }
+}
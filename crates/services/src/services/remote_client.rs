@@ -12,6 +12,10 @@ use tracing::warn;
 use url::Url;
 use utils::{
     api::{
+        issues::{
+            ListRemoteIssueTagsResponse, ListRemoteProjectStatusesResponse, ListRemoteTagsResponse,
+            RemoteIssue, UpdateRemoteIssueRequest,
+        },
         oauth::{
             HandoffInitRequest, HandoffInitResponse, HandoffRedeemRequest, HandoffRedeemResponse,
             ProfileResponse, TokenRefreshRequest, TokenRefreshResponse,
@@ -24,6 +28,7 @@ use utils::{
             UpdateMemberRoleRequest, UpdateMemberRoleResponse, UpdateOrganizationRequest,
         },
         projects::{ListProjectsResponse, RemoteProject},
+        workspaces::{UpsertWorkspaceRequest, UpsertWorkspaceResponse},
     },
     jwt::extract_expiration,
 };
@@ -378,6 +383,19 @@ impl RemoteClient {
             .map_err(|e| RemoteClientError::Serde(e.to_string()))
     }
 
+    async fn put_authed<T, B>(&self, path: &str, body: &B) -> Result<T, RemoteClientError>
+    where
+        T: for<'de> Deserialize<'de>,
+        B: Serialize,
+    {
+        let res = self
+            .send(reqwest::Method::PUT, path, true, Some(body))
+            .await?;
+        res.json::<T>()
+            .await
+            .map_err(|e| RemoteClientError::Serde(e.to_string()))
+    }
+
     async fn delete_authed(&self, path: &str) -> Result<(), RemoteClientError> {
         self.send(reqwest::Method::DELETE, path, true, None::<&()>)
             .await?;
@@ -540,6 +558,75 @@ impl RemoteClient {
         )
         .await
     }
+
+    pub async fn get_issue(
+        &self,
+        project_id: Uuid,
+        issue_id: Uuid,
+    ) -> Result<RemoteIssue, RemoteClientError> {
+        self.get_authed(&format!("/v1/projects/{project_id}/issues/{issue_id}"))
+            .await
+    }
+
+    pub async fn update_issue(
+        &self,
+        project_id: Uuid,
+        issue_id: Uuid,
+        request: &UpdateRemoteIssueRequest,
+    ) -> Result<RemoteIssue, RemoteClientError> {
+        self.patch_authed(
+            &format!("/v1/projects/{project_id}/issues/{issue_id}"),
+            request,
+        )
+        .await
+    }
+
+    /// Lists the tags attached to an issue.
+    pub async fn list_issue_tags(
+        &self,
+        project_id: Uuid,
+        issue_id: Uuid,
+    ) -> Result<ListRemoteIssueTagsResponse, RemoteClientError> {
+        self.get_authed(&format!("/v1/projects/{project_id}/issues/{issue_id}/tags"))
+            .await
+    }
+
+    /// Lists the tags defined in a project.
+    pub async fn list_tags(
+        &self,
+        project_id: Uuid,
+    ) -> Result<ListRemoteTagsResponse, RemoteClientError> {
+        self.get_authed(&format!("/v1/projects/{project_id}/tags"))
+            .await
+    }
+
+    /// Lists the statuses (board columns) of a project.
+    pub async fn list_project_statuses(
+        &self,
+        project_id: Uuid,
+    ) -> Result<ListRemoteProjectStatusesResponse, RemoteClientError> {
+        self.get_authed(&format!("/v1/projects/{project_id}/statuses"))
+            .await
+    }
+
+    /// Pushes the latest snapshot of a local workspace.
+    pub async fn upsert_workspace(
+        &self,
+        local_workspace_id: Uuid,
+        request: &UpsertWorkspaceRequest,
+    ) -> Result<UpsertWorkspaceResponse, RemoteClientError> {
+        self.put_authed(&format!("/v1/workspaces/{local_workspace_id}"), request)
+            .await
+    }
+
+    /// Removes the remote copy of a local workspace.
+    pub async fn delete_workspace(
+        &self,
+        local_workspace_id: Uuid,
+    ) -> Result<(), RemoteClientError> {
+        self.delete_authed(&format!("/v1/workspaces/{local_workspace_id}"))
+            .await
+    }
 }
 
 #[derive(Debug, Serialize)]
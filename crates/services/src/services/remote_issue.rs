@@ -0,0 +1,161 @@
+//! Local tasks started from issues on the remote server.
+
+use utils::api::issues::{
+    RemoteIssue, RemoteProjectStatus, RemoteStatusCategory, UpdateRemoteIssueRequest,
+};
+use uuid::Uuid;
+
+use super::remote_client::{RemoteClient, RemoteClientError};
+
+/// An issue together with the names of its tags
+#[derive(Debug, Clone)]
+pub struct RemoteIssueDetails {
+    pub issue: RemoteIssue,
+    pub tag_names: Vec<String>,
+}
+
+impl RemoteIssueDetails {
+    pub async fn fetch(
+        client: &RemoteClient,
+        project_id: Uuid,
+        issue_id: Uuid,
+    ) -> Result<Self, RemoteClientError> {
+        let issue = client.get_issue(project_id, issue_id).await?;
+        let issue_tags = client.list_issue_tags(project_id, issue_id).await?.tags;
+        let tag_names = if issue_tags.is_empty() {
+            Vec::new()
+        } else {
+            let tags = client.list_tags(project_id).await?.tags;
+            issue_tags
+                .iter()
+                .filter_map(|issue_tag| tags.iter().find(|tag| tag.id == issue_tag.tag_id))
+                .map(|tag| tag.name.clone())
+                .collect()
+        };
+        Ok(Self { issue, tag_names })
+    }
+
+    /// The issue description with its tags appended, as used for the local task
+    pub fn task_description(&self) -> Option<String> {
+        let mut description = self
+            .issue
+            .description
+            .as_deref()
+            .unwrap_or_default()
+            .trim_end()
+            .to_string();
+        if !self.tag_names.is_empty() {
+            if !description.is_empty() {
+                description.push_str("\n\n");
+            }
+            description.push_str(&format!("Tags: {}", self.tag_names.join(", ")));
+        }
+        Some(description).filter(|d| !d.is_empty())
+    }
+}
+
+/// The first status of a category in the project's column order, so renamed
+/// or translated statuses are still found
+pub fn find_status(
+    statuses: &[RemoteProjectStatus],
+    category: RemoteStatusCategory,
+) -> Option<&RemoteProjectStatus> {
+    statuses
+        .iter()
+        .filter(|status| status.category == Some(category))
+        .min_by_key(|status| status.sort_order)
+}
+
+/// Move an issue to the project's first status of `category`. Returns false,
+/// after logging the statuses it looked at, if the project has none.
+pub async fn move_issue_to_status(
+    client: &RemoteClient,
+    project_id: Uuid,
+    issue_id: Uuid,
+    category: RemoteStatusCategory,
+) -> Result<bool, RemoteClientError> {
+    let statuses = client.list_project_statuses(project_id).await?.statuses;
+    let Some(status) = find_status(&statuses, category) else {
+        let available: Vec<&str> = statuses.iter().map(|s| s.name.as_str()).collect();
+        tracing::warn!(
+            "Remote project {} has no {:?} status (statuses: {}); leaving issue {} as is",
+            project_id,
+            category,
+            available.join(", "),
+            issue_id
+        );
+        return Ok(false);
+    };
+    client
+        .update_issue(
+            project_id,
+            issue_id,
+            &UpdateRemoteIssueRequest {
+                status_id: Some(status.id),
+            },
+        )
+        .await?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn details(description: Option<&str>, tag_names: &[&str]) -> RemoteIssueDetails {
+        RemoteIssueDetails {
+            issue: RemoteIssue {
+                id: Uuid::new_v4(),
+                project_id: Uuid::new_v4(),
+                status_id: Uuid::new_v4(),
+                title: "Fix login".to_string(),
+                description: description.map(str::to_string),
+            },
+            tag_names: tag_names.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_task_description() {
+        assert_eq!(
+            details(Some("Users are logged out.\n"), &["bug", "auth"]).task_description(),
+            Some("Users are logged out.\n\nTags: bug, auth".to_string())
+        );
+        assert_eq!(
+            details(None, &["bug"]).task_description(),
+            Some("Tags: bug".to_string())
+        );
+        assert_eq!(details(Some("  "), &[]).task_description(), None);
+    }
+
+    #[test]
+    fn test_find_status() {
+        let project_id = Uuid::new_v4();
+        let statuses: Vec<RemoteProjectStatus> = [
+            ("Zu erledigen", 0, Some(RemoteStatusCategory::Unstarted)),
+            ("Review", 3, Some(RemoteStatusCategory::Started)),
+            ("In Arbeit", 2, Some(RemoteStatusCategory::Started)),
+            ("Erledigt", 4, Some(RemoteStatusCategory::Completed)),
+            ("Done", 5, None),
+        ]
+        .into_iter()
+        .map(|(name, sort_order, category)| RemoteProjectStatus {
+            id: Uuid::new_v4(),
+            project_id,
+            name: name.to_string(),
+            sort_order,
+            category,
+        })
+        .collect();
+
+        assert_eq!(
+            find_status(&statuses, RemoteStatusCategory::Started).map(|s| s.id),
+            Some(statuses[2].id)
+        );
+        assert_eq!(
+            find_status(&statuses, RemoteStatusCategory::Completed).map(|s| s.id),
+            Some(statuses[3].id)
+        );
+        assert!(find_status(&statuses, RemoteStatusCategory::Cancelled).is_none());
+    }
+}
@@ -0,0 +1,325 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, NaiveDate, Timelike, Utc};
+use db::{DBService, models::task_schedule::TaskSchedule};
+use thiserror::Error;
+use tokio::{sync::mpsc, time::interval};
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Invalid cron expression: {0}")]
+pub struct CronParseError(String);
+
+/// Five-field cron expression (`minute hour day-of-month month day-of-week`),
+/// evaluated in UTC. Supports `*`, lists, ranges, steps, month and weekday names,
+/// and the `@hourly`, `@daily`, `@weekly`, `@monthly` and `@yearly` shorthands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u32,
+    days_of_month: u32,
+    months: u16,
+    days_of_week: u8,
+    /// Standard cron semantics: when both day fields are restricted a day
+    /// matches if either of them does
+    day_of_month_any: bool,
+    day_of_week_any: bool,
+}
+
+const MONTH_NAMES: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAY_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// How far ahead to look before concluding an expression never fires (e.g. `0 0 31 2 *`)
+const MAX_LOOKAHEAD_YEARS: i32 = 5;
+
+impl CronSchedule {
+    pub fn parse(expression: &str) -> Result<Self, CronParseError> {
+        let expression = expression.trim();
+        let expanded = match expression.to_ascii_lowercase().as_str() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            _ => expression,
+        };
+
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            return Err(CronParseError(format!(
+                "expected 5 fields, got {}",
+                fields.len()
+            )));
+        };
+
+        // Sunday may be written as 7
+        let days_of_week = parse_field(day_of_week, 0, 7, &WEEKDAY_NAMES, 0)?;
+        let days_of_week = ((days_of_week | (days_of_week >> 7)) & 0x7f) as u8;
+
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59, &[], 0)?,
+            hours: parse_field(hour, 0, 23, &[], 0)? as u32,
+            days_of_month: parse_field(day_of_month, 1, 31, &[], 0)? as u32,
+            months: parse_field(month, 1, 12, &MONTH_NAMES, 1)? as u16,
+            days_of_week,
+            day_of_month_any: day_of_month == "*",
+            day_of_week_any: day_of_week == "*",
+        })
+    }
+
+    /// First fire time strictly after `after`, or None if the expression can
+    /// never match
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = after.with_second(0)?.with_nanosecond(0)? + ChronoDuration::minutes(1);
+        let limit_year = start.year() + MAX_LOOKAHEAD_YEARS;
+        let mut t = start;
+
+        while t.year() <= limit_year {
+            if self.months & (1 << t.month()) == 0 {
+                let (year, month) = if t.month() == 12 {
+                    (t.year() + 1, 1)
+                } else {
+                    (t.year(), t.month() + 1)
+                };
+                t = NaiveDate::from_ymd_opt(year, month, 1)?
+                    .and_hms_opt(0, 0, 0)?
+                    .and_utc();
+                continue;
+            }
+            if !self.day_matches(t) {
+                t = (t.date_naive() + ChronoDuration::days(1))
+                    .and_hms_opt(0, 0, 0)?
+                    .and_utc();
+                continue;
+            }
+            if self.hours & (1 << t.hour()) == 0 {
+                t = t.with_minute(0)? + ChronoDuration::hours(1);
+                continue;
+            }
+            if self.minutes & (1 << t.minute()) == 0 {
+                t += ChronoDuration::minutes(1);
+                continue;
+            }
+            return Some(t);
+        }
+        None
+    }
+
+    fn day_matches(&self, t: DateTime<Utc>) -> bool {
+        let dom = self.days_of_month & (1 << t.day()) != 0;
+        let dow = self.days_of_week & (1 << t.weekday().num_days_from_sunday()) != 0;
+        match (self.day_of_month_any, self.day_of_week_any) {
+            (true, true) => true,
+            (false, true) => dom,
+            (true, false) => dow,
+            (false, false) => dom || dow,
+        }
+    }
+}
+
+/// Parse one cron field into a bitset where bit `n` is set when value `n` matches.
+/// `names` map to values starting at `name_offset`.
+fn parse_field(
+    field: &str,
+    min: u32,
+    max: u32,
+    names: &[&str],
+    name_offset: u32,
+) -> Result<u64, CronParseError> {
+    let parse_value = |value: &str| -> Result<u32, CronParseError> {
+        let lower = value.to_ascii_lowercase();
+        let parsed = match names.iter().position(|name| *name == lower) {
+            Some(index) => index as u32 + name_offset,
+            None => value
+                .parse::<u32>()
+                .map_err(|_| CronParseError(format!("'{value}' is not a number")))?,
+        };
+        if parsed < min || parsed > max {
+            return Err(CronParseError(format!("{parsed} is outside {min}-{max}")));
+        }
+        Ok(parsed)
+    };
+
+    let mut bits = 0u64;
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => {
+                let step = step
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| CronParseError(format!("invalid step in '{item}'")))?;
+                (range, step)
+            }
+            None => (item, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (parse_value(start)?, parse_value(end)?)
+        } else {
+            let value = parse_value(range)?;
+            // `5/15` means every 15 starting at 5
+            (value, if step > 1 { max } else { value })
+        };
+        if start > end {
+            return Err(CronParseError(format!("invalid range '{range}'")));
+        }
+
+        for value in (start..=end).step_by(step as usize) {
+            bits |= 1 << value;
+        }
+    }
+    Ok(bits)
+}
+
+/// A schedule whose fire time has come
+#[derive(Debug, Clone, Copy)]
+pub struct ScheduledFire {
+    pub schedule_id: Uuid,
+    pub scheduled_for: DateTime<Utc>,
+}
+
+/// Polls task schedules and hands due ones to the deployment, which creates the
+/// task and starts its workspace. Fire times missed while the app was closed
+/// are collapsed into a single run.
+pub struct TaskSchedulerService {
+    db: DBService,
+    fire_tx: mpsc::UnboundedSender<ScheduledFire>,
+    poll_interval: Duration,
+}
+
+impl TaskSchedulerService {
+    pub async fn spawn(
+        db: DBService,
+        fire_tx: mpsc::UnboundedSender<ScheduledFire>,
+    ) -> tokio::task::JoinHandle<()> {
+        let service = Self {
+            db,
+            fire_tx,
+            poll_interval: Duration::from_secs(30),
+        };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting task scheduler with interval {:?}",
+            self.poll_interval
+        );
+
+        let mut interval = interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.fire_due_schedules().await {
+                error!("Error checking task schedules: {}", e);
+            }
+        }
+    }
+
+    async fn fire_due_schedules(&self) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        for schedule in TaskSchedule::find_due(&self.db.pool, now).await? {
+            let Some(scheduled_for) = schedule.next_run_at else {
+                continue;
+            };
+
+            // Advance before firing so a slow start never fires twice
+            let next_run_at = match CronSchedule::parse(&schedule.cron_expression) {
+                Ok(cron) => cron.next_after(now),
+                Err(e) => {
+                    warn!("Disabling schedule {}: {}", schedule.id, e);
+                    None
+                }
+            };
+            TaskSchedule::set_next_run_at(&self.db.pool, schedule.id, next_run_at).await?;
+
+            debug!(
+                "Firing schedule {} for {}, next run at {:?}",
+                schedule.id, scheduled_for, next_run_at
+            );
+            if self
+                .fire_tx
+                .send(ScheduledFire {
+                    schedule_id: schedule.id,
+                    scheduled_for,
+                })
+                .is_err()
+            {
+                warn!("Task schedule receiver dropped");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn test_next_after() {
+        let nightly = CronSchedule::parse("30 2 * * *").unwrap();
+        assert_eq!(
+            nightly.next_after(at(2026, 1, 1, 2, 30)),
+            Some(at(2026, 1, 2, 2, 30))
+        );
+
+        // 2026-01-01 is a Thursday
+        let mondays = CronSchedule::parse("0 9 * * MON").unwrap();
+        assert_eq!(
+            mondays.next_after(at(2026, 1, 1, 12, 0)),
+            Some(at(2026, 1, 5, 9, 0))
+        );
+
+        let every_15 = CronSchedule::parse("*/15 8-9 * * 1-5").unwrap();
+        assert_eq!(
+            every_15.next_after(at(2026, 1, 2, 9, 50)),
+            Some(at(2026, 1, 5, 8, 0))
+        );
+
+        // Day-of-month OR day-of-week when both are restricted
+        let either = CronSchedule::parse("0 0 13 * 5").unwrap();
+        assert_eq!(
+            either.next_after(at(2026, 1, 1, 0, 0)),
+            Some(at(2026, 1, 2, 0, 0))
+        );
+
+        assert_eq!(
+            CronSchedule::parse("@monthly")
+                .unwrap()
+                .next_after(at(2026, 12, 15, 0, 0)),
+            Some(at(2027, 1, 1, 0, 0))
+        );
+        assert_eq!(
+            CronSchedule::parse("0 0 30 2 *")
+                .unwrap()
+                .next_after(at(2026, 1, 1, 0, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_expressions() {
+        assert!(CronSchedule::parse("* * * *").is_err());
+        assert!(CronSchedule::parse("60 * * * *").is_err());
+        assert!(CronSchedule::parse("*/0 * * * *").is_err());
+        assert!(CronSchedule::parse("0 0 * * funday").is_err());
+        assert_eq!(
+            CronSchedule::parse("0 0 * * 7"),
+            CronSchedule::parse("0 0 * * 0")
+        );
+    }
+}
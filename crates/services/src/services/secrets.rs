@@ -0,0 +1,277 @@
+use std::{path::PathBuf, sync::Arc};
+
+use aes_gcm::{
+    Aes256Gcm, Key, Nonce,
+    aead::{Aead, AeadCore, KeyInit, OsRng, rand_core::RngCore},
+};
+use db::models::{execution_process::ExecutionProcessRunReason, project_secret::ProjectSecret};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use uuid::Uuid;
+
+const KEY_SIZE: usize = 32;
+const NONCE_SIZE: usize = 12; // 96 bits for AES-256-GCM
+
+#[derive(Debug, Error)]
+pub enum SecretsError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Failed to encrypt or decrypt secret")]
+    Crypto,
+    #[error(
+        "Invalid secret name '{0}': use letters, digits and underscores, not starting with a digit or VK_"
+    )]
+    InvalidName(String),
+    #[error("Secret must be injected for at least one run reason")]
+    NoRunReasons,
+}
+
+/// Encrypts project secrets at rest and decrypts them for execution processes.
+/// The key lives in the macOS keychain or a local key file, never in the database.
+#[derive(Clone)]
+pub struct SecretsService {
+    cipher: Arc<Aes256Gcm>,
+}
+
+impl SecretsService {
+    /// Load the encryption key, creating one on first use
+    pub fn load(key_path: PathBuf) -> Result<Self, SecretsError> {
+        let backend = KeyBackend::detect(key_path);
+        let key = match backend.load()? {
+            Some(key) => key,
+            None => {
+                let mut key = [0u8; KEY_SIZE];
+                OsRng.fill_bytes(&mut key);
+                backend.save(&key)?;
+                key
+            }
+        };
+        Ok(Self::from_key(key))
+    }
+
+    pub fn from_key(key: [u8; KEY_SIZE]) -> Self {
+        let key = Key::<Aes256Gcm>::from(key);
+        Self {
+            cipher: Arc::new(Aes256Gcm::new(&key)),
+        }
+    }
+
+    /// Nonce followed by ciphertext
+    pub fn encrypt(&self, value: &str) -> Result<Vec<u8>, SecretsError> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, value.as_bytes())
+            .map_err(|_| SecretsError::Crypto)?;
+
+        let mut combined = nonce.to_vec();
+        combined.extend_from_slice(&ciphertext);
+        Ok(combined)
+    }
+
+    pub fn decrypt(&self, data: &[u8]) -> Result<String, SecretsError> {
+        if data.len() < NONCE_SIZE {
+            return Err(SecretsError::Crypto);
+        }
+        let (nonce, ciphertext) = data.split_at(NONCE_SIZE);
+        let nonce_bytes: [u8; NONCE_SIZE] = nonce.try_into().map_err(|_| SecretsError::Crypto)?;
+        let plaintext = self
+            .cipher
+            .decrypt(&Nonce::from(nonce_bytes), ciphertext)
+            .map_err(|_| SecretsError::Crypto)?;
+        String::from_utf8(plaintext).map_err(|_| SecretsError::Crypto)
+    }
+
+    /// Secret names become environment variable names; `VK_` is reserved for
+    /// the variables we set ourselves
+    pub fn validate_name(name: &str) -> Result<(), SecretsError> {
+        let mut chars = name.chars();
+        let valid = chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && !name.to_ascii_uppercase().starts_with("VK_");
+        if valid {
+            Ok(())
+        } else {
+            Err(SecretsError::InvalidName(name.to_string()))
+        }
+    }
+
+    /// Decrypted name/value pairs for a process in the given project and repos.
+    /// Repo-scoped secrets come last so they override project-wide ones. A secret
+    /// that can't be decrypted, e.g. after the key was replaced, is left out with
+    /// a warning rather than blocking every execution in the project.
+    pub async fn env_for_execution(
+        &self,
+        pool: &SqlitePool,
+        project_id: Uuid,
+        repo_ids: &[Uuid],
+        run_reason: &ExecutionProcessRunReason,
+    ) -> Result<Vec<(String, String)>, SecretsError> {
+        let mut secrets: Vec<ProjectSecret> = ProjectSecret::find_by_project_id(pool, project_id)
+            .await?
+            .into_iter()
+            .filter(|secret| secret.applies_to(repo_ids, run_reason))
+            .collect();
+        secrets.sort_by_key(|secret| secret.repo_id.is_some());
+
+        Ok(secrets
+            .into_iter()
+            .filter_map(|secret| match self.decrypt(&secret.ciphertext) {
+                Ok(value) => Some((secret.name, value)),
+                Err(e) => {
+                    tracing::warn!(
+                        "Skipping project secret {} ({}): {}",
+                        secret.name,
+                        secret.id,
+                        e
+                    );
+                    None
+                }
+            })
+            .collect())
+    }
+}
+
+enum KeyBackend {
+    File(PathBuf),
+    #[cfg(target_os = "macos")]
+    Keychain,
+}
+
+impl KeyBackend {
+    fn detect(path: PathBuf) -> Self {
+        #[cfg(target_os = "macos")]
+        {
+            let use_file = match std::env::var("SECRETS_KEY_BACKEND") {
+                Ok(v) if v.eq_ignore_ascii_case("file") => true,
+                Ok(v) if v.eq_ignore_ascii_case("keychain") => false,
+                _ => cfg!(debug_assertions),
+            };
+            if use_file {
+                tracing::info!("Secrets key backend: file");
+                KeyBackend::File(path)
+            } else {
+                tracing::info!("Secrets key backend: keychain");
+                KeyBackend::Keychain
+            }
+        }
+        #[cfg(not(target_os = "macos"))]
+        {
+            tracing::info!("Secrets key backend: file");
+            KeyBackend::File(path)
+        }
+    }
+
+    fn load(&self) -> std::io::Result<Option<[u8; KEY_SIZE]>> {
+        let bytes = match self {
+            KeyBackend::File(path) => {
+                if !path.exists() {
+                    return Ok(None);
+                }
+                std::fs::read(path)?
+            }
+            #[cfg(target_os = "macos")]
+            KeyBackend::Keychain => match keychain::load()? {
+                Some(bytes) => bytes,
+                None => return Ok(None),
+            },
+        };
+
+        // A key of the wrong size means every stored secret is unreadable; refuse
+        // to silently replace it
+        bytes
+            .try_into()
+            .map(Some)
+            .map_err(|_| std::io::Error::other("secrets key has an invalid length"))
+    }
+
+    fn save(&self, key: &[u8; KEY_SIZE]) -> std::io::Result<()> {
+        match self {
+            KeyBackend::File(path) => {
+                let tmp = path.with_extension("tmp");
+                let mut opts = std::fs::OpenOptions::new();
+                opts.create(true).truncate(true).write(true);
+
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs::OpenOptionsExt;
+                    opts.mode(0o600);
+                }
+
+                let mut file = opts.open(&tmp)?;
+                std::io::Write::write_all(&mut file, key)?;
+                file.sync_all()?;
+                drop(file);
+
+                std::fs::rename(&tmp, path)
+            }
+            #[cfg(target_os = "macos")]
+            KeyBackend::Keychain => keychain::save(key),
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod keychain {
+    use security_framework::passwords::{get_generic_password, set_generic_password};
+
+    const SERVICE_NAME: &str = concat!(env!("CARGO_PKG_NAME"), ":secrets");
+    const ACCOUNT_NAME: &str = "default";
+    const ERR_SEC_ITEM_NOT_FOUND: i32 = -25300;
+
+    pub fn load() -> std::io::Result<Option<Vec<u8>>> {
+        match get_generic_password(SERVICE_NAME, ACCOUNT_NAME) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.code() == ERR_SEC_ITEM_NOT_FOUND => Ok(None),
+            Err(e) => Err(std::io::Error::other(e)),
+        }
+    }
+
+    pub fn save(key: &[u8]) -> std::io::Result<()> {
+        set_generic_password(SERVICE_NAME, ACCOUNT_NAME, key).map_err(std::io::Error::other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_round_trips_and_rejects_other_keys() {
+        let service = SecretsService::from_key([7; KEY_SIZE]);
+        let encrypted = service.encrypt("postgres://user:pw@db/app").unwrap();
+        assert_eq!(
+            service.decrypt(&encrypted).unwrap(),
+            "postgres://user:pw@db/app"
+        );
+
+        let other = SecretsService::from_key([8; KEY_SIZE]);
+        assert!(other.decrypt(&encrypted).is_err());
+        assert!(service.decrypt(&encrypted[..4]).is_err());
+    }
+
+    #[test]
+    fn names_must_be_usable_env_vars() {
+        assert!(SecretsService::validate_name("DATABASE_URL").is_ok());
+        assert!(SecretsService::validate_name("_token").is_ok());
+        assert!(SecretsService::validate_name("").is_err());
+        assert!(SecretsService::validate_name("1PASSWORD").is_err());
+        assert!(SecretsService::validate_name("NPM-TOKEN").is_err());
+        assert!(SecretsService::validate_name("vk_project_id").is_err());
+    }
+
+    #[test]
+    fn key_file_is_created_once_and_reused() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secrets.key");
+
+        let first = SecretsService::load(path.clone()).unwrap();
+        let encrypted = first.encrypt("s3cret").unwrap();
+        let second = SecretsService::load(path).unwrap();
+        assert_eq!(second.decrypt(&encrypted).unwrap(), "s3cret");
+    }
+}
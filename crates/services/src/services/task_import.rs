@@ -0,0 +1,748 @@
+//! Bulk task import from GitHub issues, Markdown outlines and CSV files.
+//!
+//! Every source is parsed into a tree of [`TaskImportItem`]s. The tree is shown
+//! to the user as a preview, with items that look like existing tasks flagged,
+//! and is then committed as-is.
+
+use std::{collections::HashMap, path::Path, time::Duration};
+
+use db::models::{
+    image::{Image, TaskImage},
+    tag::Tag,
+    task::{CreateTask, Task, TaskStatus},
+};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::{
+    git_host::{GitHostError, GitHubIssue, GitHubIssueState},
+    image::ImageService,
+};
+
+/// Per-request limit so one slow image host can't stall the whole import
+const IMAGE_DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Error)]
+pub enum TaskImportError {
+    #[error("Invalid CSV: {0}")]
+    Csv(String),
+    #[error(transparent)]
+    GitHost(#[from] GitHostError),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+/// One task to be created, with the tasks nested under it in the source
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+pub struct TaskImportItem {
+    pub title: String,
+    pub description: Option<String>,
+    pub status: TaskStatus,
+    /// Labels from the source; those matching a tag name pull in that tag
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Remote images downloaded and attached when the item is imported
+    #[serde(default)]
+    pub image_urls: Vec<String>,
+    /// Link back to the source, e.g. the GitHub issue URL
+    pub source_url: Option<String>,
+    /// Existing task this item appears to duplicate, set by the preview
+    #[serde(default)]
+    pub duplicate_of: Option<Uuid>,
+    #[serde(default)]
+    pub children: Vec<TaskImportItem>,
+}
+
+impl TaskImportItem {
+    fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct TaskImportResult {
+    pub created: Vec<Task>,
+    /// Items not created because they duplicate an existing task
+    pub skipped_duplicates: usize,
+    /// Images that could not be downloaded; the tasks were created without them
+    pub failed_images: Vec<String>,
+}
+
+/// Parse a Markdown outline. Headings and list items become tasks, nested under
+/// the closest enclosing heading or less-indented item. Checked checklist items
+/// are imported as done, and plain text lines are added to the description of
+/// the item above them.
+pub fn parse_markdown(content: &str) -> Vec<TaskImportItem> {
+    // Headings sort above every list indentation level
+    const HEADING_BASE: i64 = -10;
+
+    let mut flat: Vec<(Option<usize>, TaskImportItem)> = Vec::new();
+    let mut stack: Vec<(i64, usize)> = Vec::new();
+    let mut in_code_block = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            append_description_line(&mut flat, line);
+            continue;
+        }
+        if in_code_block {
+            append_description_line(&mut flat, line);
+            continue;
+        }
+
+        let (level, text) = if let Some((depth, text)) = parse_heading(trimmed) {
+            (HEADING_BASE + depth as i64, text)
+        } else if let Some(text) = strip_list_marker(trimmed) {
+            (indent_width(line) as i64, text)
+        } else {
+            append_description_line(&mut flat, line);
+            continue;
+        };
+
+        let (done, text) = strip_checkbox(text);
+        let (title, image_urls) = extract_image_urls(text);
+        if title.is_empty() {
+            continue;
+        }
+
+        while stack.last().is_some_and(|(l, _)| *l >= level) {
+            stack.pop();
+        }
+        let parent = stack.last().map(|(_, index)| *index);
+
+        let mut item = TaskImportItem::new(title);
+        item.image_urls = image_urls;
+        if done {
+            item.status = TaskStatus::Done;
+        }
+        stack.push((level, flat.len()));
+        flat.push((parent, item));
+    }
+
+    for (_, item) in &mut flat {
+        if let Some(description) = item.description.take() {
+            let (description, image_urls) = extract_image_urls(&description);
+            item.image_urls.extend(image_urls);
+            item.description = Some(description).filter(|d| !d.is_empty());
+        }
+    }
+
+    build_tree(flat)
+}
+
+fn append_description_line(flat: &mut [(Option<usize>, TaskImportItem)], line: &str) {
+    let Some((_, item)) = flat.last_mut() else {
+        return;
+    };
+    let description = item.description.get_or_insert_with(String::new);
+    if !description.is_empty() {
+        description.push('\n');
+    }
+    description.push_str(line.trim_end());
+}
+
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let depth = line.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&depth) {
+        return None;
+    }
+    let rest = &line[depth..];
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    Some((depth, rest.trim().trim_end_matches('#').trim_end()))
+}
+
+fn strip_list_marker(line: &str) -> Option<&str> {
+    if let Some(rest) = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("+ "))
+    {
+        return Some(rest.trim());
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits == 0 {
+        return None;
+    }
+    line[digits..]
+        .strip_prefix(". ")
+        .or_else(|| line[digits..].strip_prefix(") "))
+        .map(str::trim)
+}
+
+fn strip_checkbox(text: &str) -> (bool, &str) {
+    if let Some(rest) = text.strip_prefix("[ ]") {
+        (false, rest.trim_start())
+    } else if let Some(rest) = text
+        .strip_prefix("[x]")
+        .or_else(|| text.strip_prefix("[X]"))
+    {
+        (true, rest.trim_start())
+    } else {
+        (false, text)
+    }
+}
+
+fn indent_width(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+/// Pull remote `![alt](url)` images out of `text`, returning the remaining text
+/// and the image URLs. Relative images are left in place.
+fn extract_image_urls(text: &str) -> (String, Vec<String>) {
+    let mut remaining = String::with_capacity(text.len());
+    let mut urls = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find("![") {
+        let candidate = &rest[start..];
+        let url = candidate
+            .find("](")
+            .and_then(|open| {
+                let after = &candidate[open + 2..];
+                after
+                    .find(')')
+                    .map(|close| (open + 2 + close + 1, &after[..close]))
+            })
+            .map(|(end, url)| (end, url.split_whitespace().next().unwrap_or_default()))
+            .filter(|(_, url)| url.starts_with("http://") || url.starts_with("https://"));
+
+        match url {
+            Some((end, url)) => {
+                remaining.push_str(&rest[..start]);
+                urls.push(url.to_string());
+                rest = &candidate[end..];
+            }
+            None => {
+                remaining.push_str(&rest[..start + 2]);
+                rest = &candidate[2..];
+            }
+        }
+    }
+    remaining.push_str(rest);
+
+    let remaining = remaining
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+    (remaining.trim().to_string(), urls)
+}
+
+/// Turn a list of `(parent index, item)` pairs, where parents always precede
+/// their children, into a tree
+fn build_tree(flat: Vec<(Option<usize>, TaskImportItem)>) -> Vec<TaskImportItem> {
+    let parents: Vec<Option<usize>> = flat.iter().map(|(parent, _)| *parent).collect();
+    let mut items: Vec<Option<TaskImportItem>> = flat.into_iter().map(|(_, i)| Some(i)).collect();
+    let mut roots = Vec::new();
+
+    for index in (0..items.len()).rev() {
+        let item = items[index]
+            .take()
+            .expect("children are taken after parents");
+        match parents[index] {
+            Some(parent) => items[parent]
+                .as_mut()
+                .expect("parent precedes child")
+                .children
+                .insert(0, item),
+            None => roots.insert(0, item),
+        }
+    }
+    roots
+}
+
+/// Parse a CSV file with a header row. Recognised columns are `title`
+/// (required), `description`, `status`, `labels`, `images`, `url` and
+/// `parent`, which names the title of an earlier row to nest under.
+pub fn parse_csv(content: &str) -> Result<Vec<TaskImportItem>, TaskImportError> {
+    let mut rows = parse_csv_records(content)?.into_iter();
+    let header: Vec<String> = rows
+        .next()
+        .ok_or_else(|| TaskImportError::Csv("file is empty".to_string()))?
+        .into_iter()
+        .map(|h| h.trim().to_lowercase())
+        .collect();
+    let column = |name: &str| header.iter().position(|h| h == name);
+    let title_col = column("title")
+        .ok_or_else(|| TaskImportError::Csv("missing a 'title' column".to_string()))?;
+    let description_col = column("description");
+    let status_col = column("status");
+    let labels_col = column("labels");
+    let images_col = column("images");
+    let url_col = column("url");
+    let parent_col = column("parent");
+
+    let mut flat: Vec<(Option<usize>, TaskImportItem)> = Vec::new();
+    let mut by_title: HashMap<String, usize> = HashMap::new();
+
+    for (line, row) in rows.enumerate() {
+        let field = |col: Option<usize>| {
+            col.and_then(|c| row.get(c))
+                .map(|v| v.trim())
+                .filter(|v| !v.is_empty())
+        };
+        let Some(title) = field(Some(title_col)) else {
+            continue;
+        };
+
+        let mut item = TaskImportItem::new(title);
+        item.description = field(description_col).map(str::to_string);
+        if let Some(status) = field(status_col) {
+            item.status = parse_status(status).ok_or_else(|| {
+                TaskImportError::Csv(format!("row {}: unknown status '{status}'", line + 2))
+            })?;
+        }
+        item.labels = split_list(field(labels_col));
+        item.image_urls = split_list(field(images_col));
+        item.source_url = field(url_col).map(str::to_string);
+
+        let parent = match field(parent_col) {
+            Some(parent) => Some(*by_title.get(&normalize_title(parent)).ok_or_else(|| {
+                TaskImportError::Csv(format!(
+                    "row {}: parent '{parent}' must appear on an earlier row",
+                    line + 2
+                ))
+            })?),
+            None => None,
+        };
+
+        by_title.insert(normalize_title(title), flat.len());
+        flat.push((parent, item));
+    }
+
+    Ok(build_tree(flat))
+}
+
+/// RFC 4180 records: quoted fields may contain commas, newlines and `""`
+fn parse_csv_records(content: &str) -> Result<Vec<Vec<String>>, TaskImportError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (in_quotes, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => in_quotes = false,
+            (true, c) => field.push(c),
+            (false, '"') if field.is_empty() => in_quotes = true,
+            (false, ',') => record.push(std::mem::take(&mut field)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            (false, c) => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(TaskImportError::Csv(
+            "unterminated quoted field".to_string(),
+        ));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    records.retain(|r| r.iter().any(|f| !f.trim().is_empty()));
+    Ok(records)
+}
+
+fn split_list(value: Option<&str>) -> Vec<String> {
+    value
+        .map(|v| {
+            v.split([';', ','])
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_status(value: &str) -> Option<TaskStatus> {
+    let key: String = value
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+    match key.as_str() {
+        "todo" | "open" => Some(TaskStatus::Todo),
+        "inprogress" | "doing" => Some(TaskStatus::InProgress),
+        "inreview" | "review" => Some(TaskStatus::InReview),
+        "done" | "closed" | "complete" | "completed" => Some(TaskStatus::Done),
+        "cancelled" | "canceled" => Some(TaskStatus::Cancelled),
+        _ => None,
+    }
+}
+
+pub fn from_github_issue(issue: GitHubIssue) -> TaskImportItem {
+    let (body, image_urls) = extract_image_urls(&issue.body);
+    TaskImportItem {
+        title: issue.title,
+        description: Some(body).filter(|b| !b.is_empty()),
+        status: match issue.state {
+            GitHubIssueState::Open => TaskStatus::Todo,
+            GitHubIssueState::Closed => TaskStatus::Done,
+        },
+        labels: issue.labels,
+        image_urls,
+        source_url: Some(issue.url),
+        duplicate_of: None,
+        children: Vec::new(),
+    }
+}
+
+fn normalize_title(title: &str) -> String {
+    title
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Flag items whose title matches an existing task in the project, or whose
+/// source URL is already referenced by one
+pub fn mark_duplicates(items: &mut [TaskImportItem], existing: &[Task]) {
+    let by_title: HashMap<String, Uuid> = existing
+        .iter()
+        .map(|task| (normalize_title(&task.title), task.id))
+        .collect();
+
+    for item in items {
+        item.duplicate_of = by_title
+            .get(&normalize_title(&item.title))
+            .copied()
+            .or_else(|| {
+                let url = item.source_url.as_deref()?;
+                existing
+                    .iter()
+                    .find(|task| task.description.as_deref().is_some_and(|d| d.contains(url)))
+                    .map(|task| task.id)
+            });
+        mark_duplicates(&mut item.children, existing);
+    }
+}
+
+/// Creates the previewed items as tasks
+pub struct TaskImporter<'a> {
+    pool: &'a SqlitePool,
+    images: &'a ImageService,
+    http: reqwest::Client,
+    project_id: Uuid,
+    tags: Vec<Tag>,
+    /// Tags chosen for every item, by id
+    selected_tag_ids: Vec<Uuid>,
+    skip_duplicates: bool,
+}
+
+impl<'a> TaskImporter<'a> {
+    pub async fn new(
+        pool: &'a SqlitePool,
+        images: &'a ImageService,
+        project_id: Uuid,
+        selected_tag_ids: Vec<Uuid>,
+        skip_duplicates: bool,
+    ) -> Result<Self, TaskImportError> {
+        Ok(Self {
+            pool,
+            images,
+            http: reqwest::Client::new(),
+            project_id,
+            tags: Tag::find_all(pool).await?,
+            selected_tag_ids,
+            skip_duplicates,
+        })
+    }
+
+    /// Create every item, or none of them if any insert fails. Images are fetched
+    /// before the transaction opens since storing one writes through the pool;
+    /// images left unattached by a failed import are removed by the orphan cleanup.
+    pub async fn import(
+        &self,
+        items: &[TaskImportItem],
+    ) -> Result<TaskImportResult, TaskImportError> {
+        let mut result = TaskImportResult {
+            created: Vec::new(),
+            skipped_duplicates: 0,
+            failed_images: Vec::new(),
+        };
+
+        // Depth-first so parents exist before their children
+        let mut planned = Vec::new();
+        let mut pending: Vec<(&TaskImportItem, Option<Uuid>)> =
+            items.iter().rev().map(|item| (item, None)).collect();
+        while let Some((item, parent_task_id)) = pending.pop() {
+            let task_id = if self.skip_duplicates && item.duplicate_of.is_some() {
+                result.skipped_duplicates += 1;
+                // Children of a skipped item nest under the existing task
+                item.duplicate_of
+            } else {
+                let task = self.plan_task(item, parent_task_id, &mut result).await;
+                let id = task.id;
+                planned.push(task);
+                Some(id)
+            };
+            pending.extend(item.children.iter().rev().map(|child| (child, task_id)));
+        }
+
+        let mut tx = self.pool.begin().await?;
+        for task in planned {
+            let created = Task::create(&mut *tx, &task.create, task.id).await?;
+            if task.parent_task_id.is_some() {
+                Task::update_parent_task_id(&mut *tx, task.id, task.parent_task_id).await?;
+            }
+            for image_id in task.image_ids {
+                TaskImage::associate_dedup(&mut *tx, task.id, image_id).await?;
+            }
+            result.created.push(Task {
+                parent_task_id: task.parent_task_id,
+                ..created
+            });
+        }
+        tx.commit().await?;
+
+        Ok(result)
+    }
+
+    async fn plan_task(
+        &self,
+        item: &TaskImportItem,
+        parent_task_id: Option<Uuid>,
+        result: &mut TaskImportResult,
+    ) -> PlannedTask {
+        let mut description = item.description.clone().unwrap_or_default();
+        let mut image_ids = Vec::new();
+
+        for url in &item.image_urls {
+            match self.download_image(url).await {
+                Some(image) => {
+                    push_paragraph(
+                        &mut description,
+                        &format!(
+                            "![{}](.vibe-images/{})",
+                            image.original_name, image.file_path
+                        ),
+                    );
+                    image_ids.push(image.id);
+                }
+                None => result.failed_images.push(url.clone()),
+            }
+        }
+
+        for tag in self.tags.iter().filter(|tag| {
+            self.selected_tag_ids.contains(&tag.id)
+                || item
+                    .labels
+                    .iter()
+                    .any(|label| label.eq_ignore_ascii_case(&tag.tag_name))
+        }) {
+            push_paragraph(&mut description, &tag.content);
+        }
+
+        if let Some(url) = &item.source_url {
+            push_paragraph(&mut description, &format!("Imported from {url}"));
+        }
+
+        PlannedTask {
+            id: Uuid::new_v4(),
+            parent_task_id,
+            create: CreateTask {
+                project_id: self.project_id,
+                title: item.title.clone(),
+                description: Some(description).filter(|d| !d.is_empty()),
+                status: Some(item.status.clone()),
+                parent_workspace_id: None,
+                image_ids: None,
+            },
+            image_ids,
+        }
+    }
+
+    async fn download_image(&self, url: &str) -> Option<Image> {
+        let max_bytes = self.images.max_size_bytes();
+        let mut response = match self
+            .http
+            .get(url)
+            .timeout(IMAGE_DOWNLOAD_TIMEOUT)
+            .send()
+            .await
+        {
+            Ok(response) if response.status().is_success() => response,
+            Ok(response) => {
+                tracing::warn!("Image download {} returned {}", url, response.status());
+                return None;
+            }
+            Err(e) => {
+                tracing::warn!("Failed to download image {}: {}", url, e);
+                return None;
+            }
+        };
+        if response.content_length().is_some_and(|len| len > max_bytes) {
+            tracing::warn!("Image {} is larger than {} bytes", url, max_bytes);
+            return None;
+        }
+        let extension = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|mime| mime.strip_prefix("image/"))
+            .map(|ext| if ext == "svg+xml" { "svg" } else { ext }.to_string());
+
+        // Content-Length can be missing or wrong, so the limit is also enforced
+        // while reading
+        let mut data = Vec::new();
+        loop {
+            match response.chunk().await {
+                Ok(Some(chunk)) => {
+                    if (data.len() + chunk.len()) as u64 > max_bytes {
+                        tracing::warn!("Image {} is larger than {} bytes", url, max_bytes);
+                        return None;
+                    }
+                    data.extend_from_slice(&chunk);
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    tracing::warn!("Failed to read image {}: {}", url, e);
+                    return None;
+                }
+            }
+        }
+
+        let name = url
+            .split(['?', '#'])
+            .next()
+            .and_then(|path| path.rsplit('/').next())
+            .filter(|name| !name.is_empty())
+            .unwrap_or("image");
+        let filename = match extension {
+            Some(ext) if Path::new(name).extension().is_none() => format!("{name}.{ext}"),
+            _ => name.to_string(),
+        };
+
+        match self.images.store_image(&data, &filename).await {
+            Ok(image) => Some(image),
+            Err(e) => {
+                tracing::warn!("Failed to store image {}: {}", url, e);
+                None
+            }
+        }
+    }
+}
+
+/// A task ready to insert, with its images already stored
+struct PlannedTask {
+    id: Uuid,
+    parent_task_id: Option<Uuid>,
+    create: CreateTask,
+    image_ids: Vec<Uuid>,
+}
+
+fn push_paragraph(text: &mut String, paragraph: &str) {
+    if !text.is_empty() {
+        text.push_str("\n\n");
+    }
+    text.push_str(paragraph);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_markdown_outline() {
+        let content = "\
+# Auth
+Login work for the beta.
+
+- [ ] Add login form
+  - [x] Design mockups
+  - Wire up validation
+    Errors should be shown inline.
+- [X] Session cookie
+
+## Later
+1. Password reset ![flow](https://example.com/flow.png)
+
+```
+- not a task
+```
+";
+        let items = parse_markdown(content);
+        assert_eq!(items.len(), 1);
+        let auth = &items[0];
+        assert_eq!(auth.title, "Auth");
+        assert_eq!(
+            auth.description.as_deref(),
+            Some("Login work for the beta.")
+        );
+        assert_eq!(auth.children.len(), 3);
+
+        let form = &auth.children[0];
+        assert_eq!(form.title, "Add login form");
+        assert_eq!(form.status, TaskStatus::Todo);
+        assert_eq!(form.children[0].title, "Design mockups");
+        assert_eq!(form.children[0].status, TaskStatus::Done);
+        assert_eq!(
+            form.children[1].description.as_deref(),
+            Some("Errors should be shown inline.")
+        );
+        assert_eq!(auth.children[1].status, TaskStatus::Done);
+
+        let later = &auth.children[2];
+        assert_eq!(later.title, "Later");
+        assert_eq!(later.children.len(), 1);
+        assert_eq!(later.children[0].title, "Password reset");
+        assert_eq!(
+            later.children[0].image_urls,
+            vec!["https://example.com/flow.png"]
+        );
+        assert!(
+            later.children[0]
+                .description
+                .as_deref()
+                .is_some_and(|d| d.contains("- not a task"))
+        );
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let content = "Title,Description,Status,Labels,Parent\r\n\
+Epic,\"Multi-line,\nwith \"\"quotes\"\"\",in progress,backend; api,\n\
+Child,,done,,epic\n\
+,,,,\n";
+        let items = parse_csv(content).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].title, "Epic");
+        assert_eq!(
+            items[0].description.as_deref(),
+            Some("Multi-line,\nwith \"quotes\"")
+        );
+        assert_eq!(items[0].status, TaskStatus::InProgress);
+        assert_eq!(items[0].labels, vec!["backend", "api"]);
+        assert_eq!(items[0].children[0].title, "Child");
+        assert_eq!(items[0].children[0].status, TaskStatus::Done);
+
+        assert!(parse_csv("name\nfoo\n").is_err());
+        assert!(parse_csv("title,parent\nfoo,missing\n").is_err());
+        assert!(parse_csv("title\n\"unterminated\n").is_err());
+    }
+}
@@ -0,0 +1,259 @@
+use std::{collections::HashMap, time::Duration};
+
+use db::{
+    DBService,
+    models::{
+        merge::{Merge, MergeStatus},
+        remote_workspace_publication::{LinkedWorkspace, RemoteWorkspacePublication},
+        task_remote_issue::TaskRemoteIssue,
+        workspace::Workspace,
+        workspace_repo::WorkspaceRepo,
+    },
+};
+use thiserror::Error;
+use tokio::time::interval;
+use tracing::{debug, info, warn};
+use utils::api::{
+    issues::RemoteStatusCategory,
+    workspaces::{
+        UpsertWorkspacePr, UpsertWorkspaceRepo, UpsertWorkspaceRequest, WorkspacePrStatus,
+    },
+};
+use uuid::Uuid;
+
+use crate::services::{
+    diff_stats::compute_workspace_diff_stats,
+    git::GitService,
+    remote_client::{RemoteClient, RemoteClientError},
+    remote_issue::move_issue_to_status,
+};
+
+#[derive(Debug, Error)]
+enum WorkspacePublisherError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Remote(#[from] RemoteClientError),
+    #[error(transparent)]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// Pushes workspaces of projects linked to a remote project to the remote
+/// server, so the team board can show who is working on what.
+///
+/// Each round builds a snapshot of every linked workspace (archive state,
+/// diff stats and PR status per repo) and sends it only if it differs from
+/// the last one pushed. Workspaces that were deleted or whose project was
+/// unlinked are removed from the remote server. Tasks started from a remote
+/// issue have the issue moved to the done status once one of their PRs merges.
+pub struct WorkspacePublisherService {
+    db: DBService,
+    git: GitService,
+    remote_client: RemoteClient,
+    poll_interval: Duration,
+}
+
+impl WorkspacePublisherService {
+    pub async fn spawn(
+        db: DBService,
+        git: GitService,
+        remote_client: RemoteClient,
+    ) -> tokio::task::JoinHandle<()> {
+        let service = Self {
+            db,
+            git,
+            remote_client,
+            poll_interval: Duration::from_secs(60),
+        };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting workspace publisher with interval {:?}",
+            self.poll_interval
+        );
+
+        let mut interval = interval(self.poll_interval);
+
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.publish_all().await {
+                warn!("Error publishing workspaces: {}", e);
+            }
+        }
+    }
+
+    async fn publish_all(&self) -> Result<(), WorkspacePublisherError> {
+        let pool = &self.db.pool;
+        let linked = RemoteWorkspacePublication::find_linked_workspaces(pool).await?;
+        let mut published: HashMap<Uuid, RemoteWorkspacePublication> =
+            RemoteWorkspacePublication::find_all(pool)
+                .await?
+                .into_iter()
+                .map(|publication| (publication.workspace_id, publication))
+                .collect();
+
+        if linked.is_empty() && published.is_empty() {
+            return Ok(());
+        }
+        if self.remote_client.access_token().await.is_err() {
+            debug!("Not signed in; skipping workspace publishing");
+            return Ok(());
+        }
+
+        for workspace in linked {
+            let previous = published.remove(&workspace.workspace_id);
+            if let Err(e) = self.publish(&workspace, previous.as_ref()).await {
+                warn!(
+                    "Failed to publish workspace {}: {}",
+                    workspace.workspace_id, e
+                );
+            }
+        }
+
+        // Left over are workspaces that were deleted or are no longer linked
+        for stale in published.into_values() {
+            if let Err(e) = self
+                .remote_client
+                .delete_workspace(stale.workspace_id)
+                .await
+            {
+                warn!(
+                    "Failed to remove published workspace {}: {}",
+                    stale.workspace_id, e
+                );
+                continue;
+            }
+            RemoteWorkspacePublication::delete(pool, stale.workspace_id).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn publish(
+        &self,
+        linked: &LinkedWorkspace,
+        previous: Option<&RemoteWorkspacePublication>,
+    ) -> Result<(), WorkspacePublisherError> {
+        let pool = &self.db.pool;
+        let Some(workspace) = Workspace::find_by_id(pool, linked.workspace_id).await? else {
+            return Ok(());
+        };
+        let previous_request = previous
+            .filter(|publication| publication.remote_project_id == linked.remote_project_id)
+            .and_then(|publication| {
+                serde_json::from_str::<UpsertWorkspaceRequest>(&publication.payload).ok()
+            });
+
+        let request = self
+            .build_request(&workspace, linked, previous_request.as_ref())
+            .await?;
+        if previous_request.as_ref() != Some(&request) {
+            self.remote_client
+                .upsert_workspace(workspace.id, &request)
+                .await?;
+            RemoteWorkspacePublication::upsert(
+                pool,
+                workspace.id,
+                linked.remote_project_id,
+                &serde_json::to_string(&request)?,
+            )
+            .await?;
+            debug!("Published workspace {}", workspace.id);
+        }
+
+        let pr_merged = request.repos.iter().any(|repo| {
+            repo.pr
+                .as_ref()
+                .is_some_and(|pr| pr.pr_status == WorkspacePrStatus::Merged)
+        });
+        if let Some(issue_id) = linked.remote_issue_id
+            && pr_merged
+            && !linked.remote_issue_completed
+        {
+            move_issue_to_status(
+                &self.remote_client,
+                linked.remote_project_id,
+                issue_id,
+                RemoteStatusCategory::Completed,
+            )
+            .await?;
+            TaskRemoteIssue::mark_completed(pool, linked.task_id).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn build_request(
+        &self,
+        workspace: &Workspace,
+        linked: &LinkedWorkspace,
+        previous: Option<&UpsertWorkspaceRequest>,
+    ) -> Result<UpsertWorkspaceRequest, WorkspacePublisherError> {
+        let pool = &self.db.pool;
+
+        // Archived workspaces may no longer have a worktree to diff, so keep
+        // the stats they were last published with
+        let stats = if workspace.archived {
+            None
+        } else {
+            compute_workspace_diff_stats(pool, &self.git, workspace).await?
+        };
+        let (files_changed, lines_added, lines_removed) = match stats {
+            Some(stats) => (
+                Some(clamp_count(stats.files_changed)),
+                Some(clamp_count(stats.lines_added)),
+                Some(clamp_count(stats.lines_removed)),
+            ),
+            None => previous
+                .map(|p| (p.files_changed, p.lines_added, p.lines_removed))
+                .unwrap_or_default(),
+        };
+
+        // Merges are ordered newest first, so the first PR per repo is the latest
+        let mut latest_prs: HashMap<Uuid, UpsertWorkspacePr> = HashMap::new();
+        for merge in Merge::find_by_workspace_id(pool, workspace.id).await? {
+            if let Merge::Pr(pr) = merge {
+                latest_prs
+                    .entry(pr.repo_id)
+                    .or_insert_with(|| UpsertWorkspacePr {
+                        pr_url: pr.pr_info.url,
+                        pr_number: pr.pr_info.number as i32,
+                        pr_status: match pr.pr_info.status {
+                            MergeStatus::Merged => WorkspacePrStatus::Merged,
+                            MergeStatus::Closed => WorkspacePrStatus::Closed,
+                            MergeStatus::Open | MergeStatus::Unknown => WorkspacePrStatus::Open,
+                        },
+                        merged_at: pr.pr_info.merged_at,
+                        closed_at: None,
+                    });
+            }
+        }
+
+        let repos = WorkspaceRepo::find_repos_with_target_branch_for_workspace(pool, workspace.id)
+            .await?
+            .into_iter()
+            .map(|repo_with_branch| UpsertWorkspaceRepo {
+                pr: latest_prs.remove(&repo_with_branch.repo.id),
+                repo_name: repo_with_branch.repo.name,
+            })
+            .collect();
+
+        Ok(UpsertWorkspaceRequest {
+            project_id: linked.remote_project_id,
+            issue_id: linked.remote_issue_id,
+            archived: workspace.archived,
+            files_changed,
+            lines_added,
+            lines_removed,
+            repos,
+        })
+    }
+}
+
+fn clamp_count(count: usize) -> i32 {
+    i32::try_from(count).unwrap_or(i32::MAX)
+}
@@ -0,0 +1,69 @@
+//! Remote issue types as seen by local clients. Only the fields local
+//! clients use are declared; the remote server sends more.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteIssue {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub status_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+}
+
+/// What a project status means, independent of its user-editable name
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteStatusCategory {
+    Backlog,
+    Unstarted,
+    Started,
+    Completed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteProjectStatus {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub sort_order: i32,
+    /// Missing from servers that predate status categories
+    #[serde(default)]
+    pub category: Option<RemoteStatusCategory>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListRemoteProjectStatusesResponse {
+    pub statuses: Vec<RemoteProjectStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteTag {
+    pub id: Uuid,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListRemoteTagsResponse {
+    pub tags: Vec<RemoteTag>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteIssueTag {
+    pub issue_id: Uuid,
+    pub tag_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListRemoteIssueTagsResponse {
+    pub tags: Vec<RemoteIssueTag>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateRemoteIssueRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_id: Option<Uuid>,
+}
@@ -1,3 +1,5 @@
+pub mod issues;
 pub mod oauth;
 pub mod organizations;
 pub mod projects;
+pub mod workspaces;
@@ -13,6 +13,10 @@ use uuid::Uuid;
 pub enum MemberRole {
     Admin,
     Member,
+    /// Read-only access to the organization's non-private projects
+    Viewer,
+    /// Access only to the projects the guest has been added to
+    Guest,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
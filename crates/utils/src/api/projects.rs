@@ -11,6 +11,8 @@ pub struct RemoteProject {
     pub organization_id: Uuid,
     pub name: String,
     pub color: String,
+    #[serde(default)]
+    pub is_private: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -0,0 +1,49 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "workspace_pr_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum WorkspacePrStatus {
+    Open,
+    Merged,
+    Closed,
+}
+
+/// Full snapshot of a local workspace. Repos missing from `repos` are removed
+/// from the remote copy.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpsertWorkspaceRequest {
+    pub project_id: Uuid,
+    pub issue_id: Option<Uuid>,
+    pub archived: bool,
+    pub files_changed: Option<i32>,
+    pub lines_added: Option<i32>,
+    pub lines_removed: Option<i32>,
+    pub repos: Vec<UpsertWorkspaceRepo>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpsertWorkspaceRepo {
+    pub repo_name: String,
+    pub pr: Option<UpsertWorkspacePr>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpsertWorkspacePr {
+    pub pr_url: String,
+    pub pr_number: i32,
+    pub pr_status: WorkspacePrStatus,
+    pub merged_at: Option<DateTime<Utc>>,
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpsertWorkspaceResponse {
+    /// Id of the workspace on the remote server
+    pub id: Uuid,
+}
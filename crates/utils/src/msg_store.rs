@@ -13,6 +13,11 @@ use crate::{log_msg::LogMsg, stream_lines::LinesStreamExt};
 // 100 MB Limit
 const HISTORY_BYTES: usize = 100000 * 1024;
 
+/// Values shorter than this are not redacted; masking every "1" or "on" would
+/// make logs unreadable. The secrets settings tell users about this limit.
+pub const MIN_REDACTED_LEN: usize = 4;
+const REDACTED: &str = "[REDACTED]";
+
 #[derive(Clone)]
 struct StoredMsg {
     msg: LogMsg,
@@ -27,6 +32,7 @@ struct Inner {
 pub struct MsgStore {
     inner: RwLock<Inner>,
     sender: broadcast::Sender<LogMsg>,
+    redactions: RwLock<Vec<String>>,
 }
 
 impl Default for MsgStore {
@@ -44,10 +50,53 @@ impl MsgStore {
                 total_bytes: 0,
             }),
             sender,
+            redactions: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Mask these values in every message pushed from now on, before it is stored
+    /// or streamed. Values shorter than [`MIN_REDACTED_LEN`] bytes are ignored.
+    /// Matching is per message, so a value split across two output chunks is not
+    /// caught.
+    pub fn add_redactions(&self, values: impl IntoIterator<Item = String>) {
+        let mut redactions = self.redactions.write().unwrap();
+        redactions.extend(
+            values
+                .into_iter()
+                .filter(|value| value.len() >= MIN_REDACTED_LEN),
+        );
+        // Longest first so a value containing another is masked whole
+        redactions.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        redactions.dedup();
+    }
+
+    fn redact(&self, msg: LogMsg) -> LogMsg {
+        let redactions = self.redactions.read().unwrap();
+        if redactions.is_empty() {
+            return msg;
+        }
+
+        match msg {
+            LogMsg::Stdout(s) => LogMsg::Stdout(redact_str(&redactions, s)),
+            LogMsg::Stderr(s) => LogMsg::Stderr(redact_str(&redactions, s)),
+            LogMsg::JsonPatch(patch) => {
+                let Ok(mut value) = serde_json::to_value(&patch) else {
+                    return LogMsg::JsonPatch(patch);
+                };
+                if !redact_value(&redactions, &mut value) {
+                    return LogMsg::JsonPatch(patch);
+                }
+                match serde_json::from_value(value) {
+                    Ok(redacted) => LogMsg::JsonPatch(redacted),
+                    Err(_) => LogMsg::JsonPatch(patch),
+                }
+            }
+            other => other,
         }
     }
 
     pub fn push(&self, msg: LogMsg) {
+        let msg = self.redact(msg);
         let _ = self.sender.send(msg.clone()); // live listeners
         let bytes = msg.approx_bytes();
 
@@ -175,3 +224,66 @@ impl MsgStore {
         })
     }
 }
+
+fn redact_str(redactions: &[String], s: String) -> String {
+    redactions.iter().fold(s, |s, secret| {
+        if s.contains(secret.as_str()) {
+            s.replace(secret.as_str(), REDACTED)
+        } else {
+            s
+        }
+    })
+}
+
+/// Redact every string in the value, returning whether anything changed
+fn redact_value(redactions: &[String], value: &mut serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::String(s) => {
+            if redactions.iter().any(|secret| s.contains(secret.as_str())) {
+                *s = redact_str(redactions, std::mem::take(s));
+                true
+            } else {
+                false
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().fold(false, |changed, item| {
+            redact_value(redactions, item) | changed
+        }),
+        serde_json::Value::Object(map) => map.values_mut().fold(false, |changed, item| {
+            redact_value(redactions, item) | changed
+        }),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_values_in_output_and_patches() {
+        let store = MsgStore::new();
+        store.push_stdout("before ghp_abcdef");
+        store.add_redactions(["ghp_abcdef".to_string(), "on".to_string()]);
+
+        store.push_stdout("token=ghp_abcdef on");
+        store.push_stderr("no secrets here");
+        let patch: json_patch::Patch = serde_json::from_value(serde_json::json!([
+            { "op": "add", "path": "/entries/0", "value": { "content": "ghp_abcdef" } }
+        ]))
+        .unwrap();
+        store.push_patch(patch);
+
+        let history = store.get_history();
+        assert!(matches!(&history[0], LogMsg::Stdout(s) if s == "before ghp_abcdef"));
+        assert!(matches!(&history[1], LogMsg::Stdout(s) if s == "token=[REDACTED] on"));
+        assert!(matches!(&history[2], LogMsg::Stderr(s) if s == "no secrets here"));
+        let LogMsg::JsonPatch(patch) = &history[3] else {
+            panic!("expected a patch");
+        };
+        assert_eq!(
+            serde_json::to_value(patch).unwrap()[0]["value"]["content"],
+            "[REDACTED]"
+        );
+    }
+}
@@ -0,0 +1,316 @@
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from '@/components/ui/card';
+import { Button } from '@/components/ui/button';
+import { Checkbox } from '@/components/ui/checkbox';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
+import { Alert, AlertDescription } from '@/components/ui/alert';
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select';
+import { Loader2, Plus, Trash2 } from 'lucide-react';
+import { projectsApi } from '@/lib/api';
+import type {
+  ExecutionProcessRunReason,
+  ProjectSecret,
+  Repo,
+} from 'shared/types';
+
+const RUN_REASONS: ExecutionProcessRunReason[] = [
+  'setupscript',
+  'codingagent',
+  'devserver',
+  'cleanupscript',
+  'verifyscript',
+];
+
+const ALL_REPOS = 'all';
+
+interface ProjectSecretsCardProps {
+  projectId: string;
+  repositories: Repo[];
+}
+
+export function ProjectSecretsCard({
+  projectId,
+  repositories,
+}: ProjectSecretsCardProps) {
+  const { t } = useTranslation('settings');
+  const [secrets, setSecrets] = useState<ProjectSecret[]>([]);
+  const [loading, setLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const [busyId, setBusyId] = useState<string | null>(null);
+
+  const [name, setName] = useState('');
+  const [value, setValue] = useState('');
+  const [scope, setScope] = useState<string>(ALL_REPOS);
+  const [runReasons, setRunReasons] =
+    useState<ExecutionProcessRunReason[]>(RUN_REASONS);
+  const [creating, setCreating] = useState(false);
+
+  useEffect(() => {
+    setLoading(true);
+    setError(null);
+    projectsApi
+      .getSecrets(projectId)
+      .then(setSecrets)
+      .catch((err) => {
+        setError(
+          err instanceof Error
+            ? err.message
+            : t('settings.projects.secrets.loadError')
+        );
+        setSecrets([]);
+      })
+      .finally(() => setLoading(false));
+  }, [projectId, t]);
+
+  const repoName = (repoId: string | null) =>
+    repoId
+      ? (repositories.find((r) => r.id === repoId)?.display_name ?? repoId)
+      : t('settings.projects.secrets.scope.allRepos');
+
+  const toggle = (
+    reasons: ExecutionProcessRunReason[],
+    reason: ExecutionProcessRunReason
+  ) =>
+    reasons.includes(reason)
+      ? reasons.filter((r) => r !== reason)
+      : [...reasons, reason];
+
+  const handleCreate = async () => {
+    setCreating(true);
+    setError(null);
+    try {
+      const secret = await projectsApi.createSecret(projectId, {
+        name: name.trim(),
+        value,
+        repo_id: scope === ALL_REPOS ? null : scope,
+        run_reasons: runReasons,
+      });
+      setSecrets((prev) =>
+        [...prev, secret].sort((a, b) => a.name.localeCompare(b.name))
+      );
+      setName('');
+      setValue('');
+    } catch (err) {
+      setError(
+        err instanceof Error
+          ? err.message
+          : t('settings.projects.secrets.saveError')
+      );
+    } finally {
+      setCreating(false);
+    }
+  };
+
+  const handleToggleReason = async (
+    secret: ProjectSecret,
+    reason: ExecutionProcessRunReason
+  ) => {
+    setBusyId(secret.id);
+    setError(null);
+    try {
+      const updated = await projectsApi.updateSecret(projectId, secret.id, {
+        value: null,
+        run_reasons: toggle(secret.run_reasons, reason),
+      });
+      setSecrets((prev) =>
+        prev.map((s) => (s.id === updated.id ? updated : s))
+      );
+    } catch (err) {
+      setError(
+        err instanceof Error
+          ? err.message
+          : t('settings.projects.secrets.saveError')
+      );
+    } finally {
+      setBusyId(null);
+    }
+  };
+
+  const handleDelete = async (secretId: string) => {
+    setBusyId(secretId);
+    setError(null);
+    try {
+      await projectsApi.deleteSecret(projectId, secretId);
+      setSecrets((prev) => prev.filter((s) => s.id !== secretId));
+    } catch (err) {
+      setError(
+        err instanceof Error
+          ? err.message
+          : t('settings.projects.secrets.deleteError')
+      );
+    } finally {
+      setBusyId(null);
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>{t('settings.projects.secrets.title')}</CardTitle>
+        <CardDescription>
+          {t('settings.projects.secrets.description')}
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        {error && (
+          <Alert variant="destructive">
+            <AlertDescription>{error}</AlertDescription>
+          </Alert>
+        )}
+
+        {loading ? (
+          <div className="flex items-center justify-center py-4">
+            <Loader2 className="h-5 w-5 animate-spin" />
+          </div>
+        ) : (
+          <div className="space-y-2">
+            {secrets.map((secret) => (
+              <div
+                key={secret.id}
+                className="flex items-center justify-between gap-4 p-3 border rounded-md"
+              >
+                <div className="min-w-0 flex-1 space-y-1">
+                  <div className="font-mono text-sm">{secret.name}</div>
+                  <div className="text-sm text-muted-foreground">
+                    {repoName(secret.repo_id)}
+                  </div>
+                  <div className="flex flex-wrap gap-3">
+                    {RUN_REASONS.map((reason) => (
+                      <label
+                        key={reason}
+                        className="flex items-center gap-1 text-xs"
+                      >
+                        <Checkbox
+                          checked={secret.run_reasons.includes(reason)}
+                          onCheckedChange={() =>
+                            handleToggleReason(secret, reason)
+                          }
+                          disabled={busyId === secret.id}
+                        />
+                        {t(`settings.projects.secrets.runReasons.${reason}`)}
+                      </label>
+                    ))}
+                  </div>
+                </div>
+                <Button
+                  variant="ghost"
+                  size="sm"
+                  onClick={() => handleDelete(secret.id)}
+                  disabled={busyId === secret.id}
+                  title={t('settings.projects.secrets.delete')}
+                >
+                  {busyId === secret.id ? (
+                    <Loader2 className="h-4 w-4 animate-spin" />
+                  ) : (
+                    <Trash2 className="h-4 w-4" />
+                  )}
+                </Button>
+              </div>
+            ))}
+
+            {secrets.length === 0 && (
+              <div className="text-center py-4 text-sm text-muted-foreground">
+                {t('settings.projects.secrets.empty')}
+              </div>
+            )}
+          </div>
+        )}
+
+        <div className="space-y-3 pt-4 border-t">
+          <div className="grid gap-3 sm:grid-cols-2">
+            <div className="space-y-2">
+              <Label htmlFor="secret-name">
+                {t('settings.projects.secrets.name.label')}
+              </Label>
+              <Input
+                id="secret-name"
+                value={name}
+                onChange={(e) => setName(e.target.value)}
+                placeholder="DATABASE_URL"
+                className="font-mono"
+              />
+            </div>
+            <div className="space-y-2">
+              <Label htmlFor="secret-value">
+                {t('settings.projects.secrets.value.label')}
+              </Label>
+              <Input
+                id="secret-value"
+                type="password"
+                autoComplete="off"
+                value={value}
+                onChange={(e) => setValue(e.target.value)}
+              />
+            </div>
+          </div>
+          <div className="space-y-2">
+            <Label htmlFor="secret-scope">
+              {t('settings.projects.secrets.scope.label')}
+            </Label>
+            <Select value={scope} onValueChange={setScope}>
+              <SelectTrigger id="secret-scope">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                <SelectItem value={ALL_REPOS}>
+                  {t('settings.projects.secrets.scope.allRepos')}
+                </SelectItem>
+                {repositories.map((repo) => (
+                  <SelectItem key={repo.id} value={repo.id}>
+                    {repo.display_name}
+                  </SelectItem>
+                ))}
+              </SelectContent>
+            </Select>
+          </div>
+          <div className="flex flex-wrap gap-4">
+            {RUN_REASONS.map((reason) => (
+              <label key={reason} className="flex items-center gap-2 text-sm">
+                <Checkbox
+                  checked={runReasons.includes(reason)}
+                  onCheckedChange={() =>
+                    setRunReasons((prev) => toggle(prev, reason))
+                  }
+                />
+                {t(`settings.projects.secrets.runReasons.${reason}`)}
+              </label>
+            ))}
+          </div>
+          <p className="text-sm text-muted-foreground">
+            {t('settings.projects.secrets.helper')}
+          </p>
+          <Button
+            variant="outline"
+            size="sm"
+            onClick={handleCreate}
+            disabled={
+              creating || !name.trim() || !value || runReasons.length === 0
+            }
+            className="w-full"
+          >
+            {creating ? (
+              <Loader2 className="h-4 w-4 mr-2 animate-spin" />
+            ) : (
+              <Plus className="h-4 w-4 mr-2" />
+            )}
+            {t('settings.projects.secrets.add')}
+          </Button>
+        </div>
+      </CardContent>
+    </Card>
+  );
+}
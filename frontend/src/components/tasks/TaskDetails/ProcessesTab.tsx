@@ -19,6 +19,15 @@ import type { ExecutionProcessStatus, ExecutionProcess } from 'shared/types';
 import { useProcessSelection } from '@/contexts/ProcessSelectionContext';
 import { useRetryUi } from '@/contexts/RetryUiContext';
 
+// Mirrors prompt_with_instructions on the backend
+function effectivePrompt(process: ExecutionProcess): string | null {
+  const { typ } = process.executor_action;
+  if (typ.type === 'ScriptRequest') return null;
+  const instructions = typ.instructions?.trim();
+  if (!instructions) return null;
+  return `${instructions}\n\n---\n\n${typ.prompt}`;
+}
+
 interface ProcessesTabProps {
   sessionId?: string;
 }
@@ -304,6 +313,16 @@ function ProcessesTab({ sessionId }: ProcessesTabProps) {
               </button>
             </div>
           </div>
+          {selectedProcess && effectivePrompt(selectedProcess) && (
+            <details className="px-4 py-2 border-b flex-shrink-0">
+              <summary className="text-sm font-medium cursor-pointer">
+                {t('processes.effectivePrompt')}
+              </summary>
+              <pre className="mt-2 max-h-64 overflow-auto whitespace-pre-wrap text-xs text-muted-foreground">
+                {effectivePrompt(selectedProcess)}
+              </pre>
+            </details>
+          )}
           <div className="flex-1">
             {selectedProcess ? (
               <ProcessLogsViewerContent logs={logs} error={logsError} />
@@ -52,6 +52,7 @@ export const useConversationHistoryOld = ({
       (ep) =>
         ep.run_reason === 'setupscript' ||
         ep.run_reason === 'cleanupscript' ||
+        ep.run_reason === 'verifyscript' ||
         ep.run_reason === 'codingagent'
     );
   }, [executionProcessesRaw]);
@@ -260,6 +261,9 @@ export const useConversationHistoryOld = ({
               case 'CleanupScript':
                 toolName = 'Cleanup Script';
                 break;
+              case 'VerifyScript':
+                toolName = 'Verify Script';
+                break;
               case 'ToolInstallScript':
                 toolName = 'Tool Install Script';
                 break;
@@ -21,6 +21,9 @@ import {
   RepoWithTargetBranch,
   CreateProject,
   CreateProjectRepo,
+  ProjectSecret,
+  CreateProjectSecret,
+  UpdateProjectSecret,
   UpdateRepo,
   SearchMode,
   SearchResult,
@@ -87,7 +90,30 @@ import {
   Session,
   Workspace,
   StartReviewRequest,
+  StartRemoteIssueRequest,
   ReviewError,
+  WorkspacePreview,
+  CreateFanOutBody,
+  FanOutGroupWithAttempts,
+  FanOutComparison,
+  PromoteFanOutWinnerRequest,
+  PromoteFanOutWinnerResponse,
+  ForkWorkspaceRequest,
+  TaskSchedule,
+  CreateTaskSchedule,
+  UpdateTaskSchedule,
+  DiskUsageSummary,
+  DiskEviction,
+  TaskScheduleRun,
+  TaskImportItem,
+  TaskImportPreviewRequest,
+  TaskImportRequest,
+  TaskImportResult,
+  GitHubIssueSyncSettings,
+  UpsertGitHubIssueSyncSettings,
+  TaskGitHubIssue,
+  GitHubIssueSyncEvent,
+  LinkTaskGitHubIssueRequest,
 } from 'shared/types';
 import type { WorkspaceWithSession } from '@/types/attempt';
 import { createWorkspaceWithSession } from '@/types/attempt';
@@ -357,6 +383,132 @@ export const projectsApi = {
     );
     return handleApiResponse<void>(response);
   },
+
+  getSecrets: async (projectId: string): Promise<ProjectSecret[]> => {
+    const response = await makeRequest(`/api/projects/${projectId}/secrets`);
+    return handleApiResponse<ProjectSecret[]>(response);
+  },
+
+  createSecret: async (
+    projectId: string,
+    data: CreateProjectSecret
+  ): Promise<ProjectSecret> => {
+    const response = await makeRequest(`/api/projects/${projectId}/secrets`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<ProjectSecret>(response);
+  },
+
+  updateSecret: async (
+    projectId: string,
+    secretId: string,
+    data: UpdateProjectSecret
+  ): Promise<ProjectSecret> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/secrets/${secretId}`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ProjectSecret>(response);
+  },
+
+  deleteSecret: async (projectId: string, secretId: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/secrets/${secretId}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  getGitHubIssueSync: async (
+    projectId: string
+  ): Promise<GitHubIssueSyncSettings | null> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/github-issue-sync`
+    );
+    return handleApiResponse<GitHubIssueSyncSettings | null>(response);
+  },
+
+  setGitHubIssueSync: async (
+    projectId: string,
+    data: UpsertGitHubIssueSyncSettings
+  ): Promise<GitHubIssueSyncSettings> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/github-issue-sync`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<GitHubIssueSyncSettings>(response);
+  },
+
+  removeGitHubIssueSync: async (projectId: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/github-issue-sync`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  runGitHubIssueSync: async (projectId: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/github-issue-sync/run`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  previewTaskImport: async (
+    projectId: string,
+    data: TaskImportPreviewRequest
+  ): Promise<TaskImportItem[]> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/task-import/preview`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<TaskImportItem[]>(response);
+  },
+
+  importTasks: async (
+    projectId: string,
+    data: TaskImportRequest
+  ): Promise<TaskImportResult> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/task-import`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<TaskImportResult>(response);
+  },
+
+  startRemoteIssue: async (
+    projectId: string,
+    data: StartRemoteIssueRequest
+  ): Promise<TaskWithAttemptStatus> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/remote-issues/start`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<TaskWithAttemptStatus>(response);
+  },
 };
 
 // Task Management APIs
@@ -398,6 +550,38 @@ export const tasksApi = {
     });
     return handleApiResponse<void>(response);
   },
+
+  getGitHubIssue: async (taskId: string): Promise<TaskGitHubIssue | null> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/github-issue`);
+    return handleApiResponse<TaskGitHubIssue | null>(response);
+  },
+
+  linkGitHubIssue: async (
+    taskId: string,
+    data: LinkTaskGitHubIssueRequest
+  ): Promise<TaskGitHubIssue> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/github-issue`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<TaskGitHubIssue>(response);
+  },
+
+  unlinkGitHubIssue: async (taskId: string): Promise<void> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/github-issue`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
+
+  getGitHubIssueSyncEvents: async (
+    taskId: string
+  ): Promise<GitHubIssueSyncEvent[]> => {
+    const response = await makeRequest(
+      `/api/tasks/${taskId}/github-issue/events`
+    );
+    return handleApiResponse<GitHubIssueSyncEvent[]>(response);
+  },
 };
 
 // Sessions API
@@ -699,6 +883,69 @@ export const attemptsApi = {
     return handleApiResponse<ExecutionProcess[]>(response);
   },
 
+  getDevServerPreview: async (
+    attemptId: string
+  ): Promise<WorkspacePreview | null> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/dev-server/preview`
+    );
+    return handleApiResponse<WorkspacePreview | null>(response);
+  },
+
+  createFanOut: async (
+    data: CreateFanOutBody
+  ): Promise<FanOutGroupWithAttempts> => {
+    const response = await makeRequest('/api/task-attempts/fan-out', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<FanOutGroupWithAttempts>(response);
+  },
+
+  getTaskFanOuts: async (
+    taskId: string
+  ): Promise<FanOutGroupWithAttempts[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/fan-out?task_id=${taskId}`
+    );
+    return handleApiResponse<FanOutGroupWithAttempts[]>(response);
+  },
+
+  getFanOutComparison: async (groupId: string): Promise<FanOutComparison> => {
+    const response = await makeRequest(
+      `/api/task-attempts/fan-out/${groupId}/comparison`
+    );
+    return handleApiResponse<FanOutComparison>(response);
+  },
+
+  promoteFanOutWinner: async (
+    groupId: string,
+    data: PromoteFanOutWinnerRequest
+  ): Promise<PromoteFanOutWinnerResponse> => {
+    const response = await makeRequest(
+      `/api/task-attempts/fan-out/${groupId}/promote`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<PromoteFanOutWinnerResponse>(response);
+  },
+
+  fork: async (
+    attemptId: string,
+    data: ForkWorkspaceRequest
+  ): Promise<Workspace> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/fork`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<Workspace>(response);
+  },
+
   setupGhCli: async (attemptId: string): Promise<ExecutionProcess> => {
     const response = await makeRequest(
       `/api/task-attempts/${attemptId}/gh-cli-setup`,
@@ -917,6 +1164,20 @@ export const configApi = {
   },
 };
 
+// Workspace disk usage APIs
+export const diskUsageApi = {
+  get: async (): Promise<DiskUsageSummary> => {
+    const response = await makeRequest('/api/disk-usage');
+    return handleApiResponse<DiskUsageSummary>(response);
+  },
+  evict: async (): Promise<DiskEviction[]> => {
+    const response = await makeRequest('/api/disk-usage/evict', {
+      method: 'POST',
+    });
+    return handleApiResponse<DiskEviction[]>(response);
+  },
+};
+
 // Task Tags APIs (all tags are global)
 export const tagsApi = {
   list: async (params?: TagSearchParams): Promise<Tag[]> => {
@@ -951,6 +1212,59 @@ export const tagsApi = {
   },
 };
 
+// Task Schedules APIs
+export const taskSchedulesApi = {
+  list: async (projectId: string): Promise<TaskSchedule[]> => {
+    const response = await makeRequest(
+      `/api/task-schedules?project_id=${projectId}`
+    );
+    return handleApiResponse<TaskSchedule[]>(response);
+  },
+
+  create: async (data: CreateTaskSchedule): Promise<TaskSchedule> => {
+    const response = await makeRequest('/api/task-schedules', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<TaskSchedule>(response);
+  },
+
+  update: async (
+    scheduleId: string,
+    data: UpdateTaskSchedule
+  ): Promise<TaskSchedule> => {
+    const response = await makeRequest(`/api/task-schedules/${scheduleId}`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<TaskSchedule>(response);
+  },
+
+  delete: async (scheduleId: string): Promise<void> => {
+    const response = await makeRequest(`/api/task-schedules/${scheduleId}`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
+
+  getRuns: async (scheduleId: string): Promise<TaskScheduleRun[]> => {
+    const response = await makeRequest(
+      `/api/task-schedules/${scheduleId}/runs`
+    );
+    return handleApiResponse<TaskScheduleRun[]>(response);
+  },
+
+  runNow: async (scheduleId: string): Promise<TaskScheduleRun> => {
+    const response = await makeRequest(
+      `/api/task-schedules/${scheduleId}/run`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<TaskScheduleRun>(response);
+  },
+};
+
 // MCP Servers APIs
 export const mcpServersApi = {
   load: async (query: McpServerQuery): Promise<GetMcpServerResponse> => {
@@ -21,21 +21,25 @@ import {
 import { Label } from '@/components/ui/label';
 import { Input } from '@/components/ui/input';
 import { Alert, AlertDescription } from '@/components/ui/alert';
+import { AutoExpandingTextarea } from '@/components/ui/auto-expanding-textarea';
 import { Loader2, Plus, Trash2 } from 'lucide-react';
 import { useProjects } from '@/hooks/useProjects';
 import { useProjectMutations } from '@/hooks/useProjectMutations';
 import { RepoPickerDialog } from '@/components/dialogs/shared/RepoPickerDialog';
+import { ProjectSecretsCard } from '@/components/projects/ProjectSecretsCard';
 import { projectsApi } from '@/lib/api';
 import { repoBranchKeys } from '@/hooks/useRepoBranches';
 import type { Project, Repo, UpdateProject } from 'shared/types';
 
 interface ProjectFormState {
   name: string;
+  agent_instructions: string;
 }
 
 function projectToFormState(project: Project): ProjectFormState {
   return {
     name: project.name,
+    agent_instructions: project.agent_instructions ?? '',
   };
 }
 
@@ -292,6 +296,7 @@ export function ProjectSettings() {
     try {
       const updateData: UpdateProject = {
         name: draft.name.trim(),
+        agent_instructions: draft.agent_instructions.trim() || null,
       };
 
       updateProject.mutate({
@@ -425,6 +430,27 @@ export function ProjectSettings() {
                 </p>
               </div>
 
+              <div className="space-y-2">
+                <Label htmlFor="project-agent-instructions">
+                  {t('settings.projects.general.agentInstructions.label')}
+                </Label>
+                <AutoExpandingTextarea
+                  id="project-agent-instructions"
+                  value={draft.agent_instructions}
+                  onChange={(e) =>
+                    updateDraft({ agent_instructions: e.target.value })
+                  }
+                  placeholder={t(
+                    'settings.projects.general.agentInstructions.placeholder'
+                  )}
+                  maxRows={12}
+                  className="w-full px-3 py-2 border border-input bg-background text-foreground rounded-md focus:outline-none focus:ring-2 focus:ring-ring"
+                />
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.projects.general.agentInstructions.helper')}
+                </p>
+              </div>
+
               {/* Save Button */}
               <div className="flex items-center justify-between pt-4 border-t">
                 {hasUnsavedChanges ? (
@@ -542,6 +568,11 @@ export function ProjectSettings() {
             </CardContent>
           </Card>
 
+          <ProjectSecretsCard
+            projectId={selectedProject.id}
+            repositories={repositories}
+          />
+
           {/* Sticky Save Button for Project Name */}
           {hasUnsavedChanges && (
             <div className="sticky bottom-0 z-10 bg-background/80 backdrop-blur-sm border-t py-4">
@@ -29,6 +29,9 @@ import { repoApi } from '@/lib/api';
 import { useQuery, useQueryClient } from '@tanstack/react-query';
 import type { Repo, UpdateRepo } from 'shared/types';
 
+// Matches the server-side clamp in Repo::update
+const MAX_VERIFY_RETRIES = 10;
+
 interface RepoScriptsFormState {
   display_name: string;
   setup_script: string;
@@ -36,6 +39,11 @@ interface RepoScriptsFormState {
   cleanup_script: string;
   copy_files: string;
   dev_server_script: string;
+  verify_script: string;
+  verify_max_retries: number;
+  agent_instructions: string;
+  agent_instructions_file: string;
+  worktree_pool_size: number;
 }
 
 function repoToFormState(repo: Repo): RepoScriptsFormState {
@@ -46,6 +54,11 @@ function repoToFormState(repo: Repo): RepoScriptsFormState {
     cleanup_script: repo.cleanup_script ?? '',
     copy_files: repo.copy_files ?? '',
     dev_server_script: repo.dev_server_script ?? '',
+    verify_script: repo.verify_script ?? '',
+    verify_max_retries: repo.verify_max_retries,
+    agent_instructions: repo.agent_instructions ?? '',
+    agent_instructions_file: repo.agent_instructions_file ?? '',
+    worktree_pool_size: repo.worktree_pool_size,
   };
 }
 
@@ -182,6 +195,11 @@ export function ReposSettings() {
         copy_files: draft.copy_files.trim() || null,
         parallel_setup_script: draft.parallel_setup_script,
         dev_server_script: draft.dev_server_script.trim() || null,
+        verify_script: draft.verify_script.trim() || null,
+        verify_max_retries: draft.verify_max_retries,
+        agent_instructions: draft.agent_instructions.trim() || null,
+        agent_instructions_file: draft.agent_instructions_file.trim() || null,
+        worktree_pool_size: draft.worktree_pool_size,
       };
 
       const updatedRepo = await repoApi.update(selectedRepo.id, updateData);
@@ -398,6 +416,30 @@ export function ReposSettings() {
                 </p>
               </div>
 
+              <div className="space-y-2">
+                <Label htmlFor="worktree-pool-size">
+                  {t('settings.repos.scripts.worktreePool.label')}
+                </Label>
+                <Input
+                  id="worktree-pool-size"
+                  type="number"
+                  min={0}
+                  className="w-24"
+                  value={draft.worktree_pool_size}
+                  onChange={(e) =>
+                    updateDraft({
+                      worktree_pool_size: Math.max(
+                        0,
+                        parseInt(e.target.value, 10) || 0
+                      ),
+                    })
+                  }
+                />
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.repos.scripts.worktreePool.helper')}
+                </p>
+              </div>
+
               <div className="space-y-2">
                 <Label htmlFor="cleanup-script">
                   {t('settings.repos.scripts.cleanup.label')}
@@ -419,6 +461,53 @@ export function ReposSettings() {
                 </p>
               </div>
 
+              <div className="space-y-2">
+                <Label htmlFor="verify-script">
+                  {t('settings.repos.scripts.verify.label')}
+                </Label>
+                <AutoExpandingTextarea
+                  id="verify-script"
+                  value={draft.verify_script}
+                  onChange={(e) =>
+                    updateDraft({
+                      verify_script: e.target.value,
+                    })
+                  }
+                  placeholder="cargo test"
+                  maxRows={12}
+                  className="w-full px-3 py-2 border border-input bg-background text-foreground rounded-md focus:outline-none focus:ring-2 focus:ring-ring font-mono"
+                />
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.repos.scripts.verify.helper')}
+                </p>
+
+                <div className="space-y-2 pt-2">
+                  <Label htmlFor="verify-max-retries">
+                    {t('settings.repos.scripts.verify.retriesLabel')}
+                  </Label>
+                  <Input
+                    id="verify-max-retries"
+                    type="number"
+                    min={0}
+                    max={MAX_VERIFY_RETRIES}
+                    className="w-24"
+                    value={draft.verify_max_retries}
+                    onChange={(e) =>
+                      updateDraft({
+                        verify_max_retries: Math.min(
+                          MAX_VERIFY_RETRIES,
+                          Math.max(0, parseInt(e.target.value, 10) || 0)
+                        ),
+                      })
+                    }
+                    disabled={!draft.verify_script.trim()}
+                  />
+                  <p className="text-sm text-muted-foreground">
+                    {t('settings.repos.scripts.verify.retriesHelper')}
+                  </p>
+                </div>
+              </div>
+
               <div className="space-y-2">
                 <Label htmlFor="copy-files">
                   {t('settings.repos.scripts.copyFiles.label')}
@@ -436,6 +525,53 @@ export function ReposSettings() {
                 <p className="text-sm text-muted-foreground">
                   {t('settings.repos.scripts.copyFiles.helper')}
                 </p>
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.repos.scripts.copyFiles.modesHelper')}
+                </p>
+              </div>
+
+              <div className="space-y-2">
+                <Label htmlFor="agent-instructions">
+                  {t('settings.repos.scripts.agentInstructions.label')}
+                </Label>
+                <AutoExpandingTextarea
+                  id="agent-instructions"
+                  value={draft.agent_instructions}
+                  onChange={(e) =>
+                    updateDraft({
+                      agent_instructions: e.target.value,
+                    })
+                  }
+                  placeholder={t(
+                    'settings.repos.scripts.agentInstructions.placeholder'
+                  )}
+                  maxRows={12}
+                  className="w-full px-3 py-2 border border-input bg-background text-foreground rounded-md focus:outline-none focus:ring-2 focus:ring-ring"
+                />
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.repos.scripts.agentInstructions.helper')}
+                </p>
+
+                <div className="space-y-2 pt-2">
+                  <Label htmlFor="agent-instructions-file">
+                    {t('settings.repos.scripts.agentInstructions.fileLabel')}
+                  </Label>
+                  <Input
+                    id="agent-instructions-file"
+                    type="text"
+                    value={draft.agent_instructions_file}
+                    onChange={(e) =>
+                      updateDraft({
+                        agent_instructions_file: e.target.value,
+                      })
+                    }
+                    placeholder="AGENTS.md"
+                    className="font-mono"
+                  />
+                  <p className="text-sm text-muted-foreground">
+                    {t('settings.repos.scripts.agentInstructions.fileHelper')}
+                  </p>
+                </div>
               </div>
 
               {/* Save Buttons */}
@@ -4,15 +4,15 @@
 // Electric row types
 export type JsonValue = number | string | boolean | Array<JsonValue> | { [key in string]?: JsonValue } | null;
 
-export type Project = { id: string, organization_id: string, name: string, color: string, created_at: string, updated_at: string, };
+export type Project = { id: string, organization_id: string, name: string, color: string, is_private: boolean, created_at: string, updated_at: string, };
 
 export type Notification = { id: string, organization_id: string, user_id: string, notification_type: NotificationType, payload: JsonValue, issue_id: string | null, comment_id: string | null, seen: boolean, dismissed_at: string | null, created_at: string, };
 
-export type NotificationType = "IssueCommentAdded" | "IssueStatusChanged" | "IssueAssigneeChanged" | "IssueDeleted";
+export type NotificationType = "IssueCommentAdded" | "IssueStatusChanged" | "IssueAssigneeChanged" | "IssueDeleted" | "UserMentioned";
 
 export type Workspace = { id: string, project_id: string, owner_user_id: string, issue_id: string | null, local_workspace_id: string, archived: boolean, files_changed: number | null, lines_added: number | null, lines_removed: number | null, created_at: string, updated_at: string, };
 
-export type ProjectStatus = { id: string, project_id: string, name: string, color: string, sort_order: number, created_at: string, };
+export type ProjectStatus = { id: string, project_id: string, name: string, color: string, sort_order: number, category: StatusCategory, created_at: string, };
 
 export type Tag = { id: string, project_id: string, name: string, color: string, };
 
@@ -30,8 +30,14 @@ export type IssueComment = { id: string, issue_id: string, author_id: string, me
 
 export type IssueCommentReaction = { id: string, comment_id: string, user_id: string, emoji: string, created_at: string, };
 
+export type IssueEvent = { id: string, issue_id: string, actor_user_id: string | null, event_type: IssueEventType, old_value: JsonValue | null, new_value: JsonValue | null, created_at: string, };
+
+export type IssueEventType = "created" | "status_changed" | "priority_changed" | "assignee_added" | "assignee_removed" | "start_date_changed" | "target_date_changed" | "completed_at_changed";
+
 export type IssuePriority = "urgent" | "high" | "medium" | "low";
 
+export type StatusCategory = "backlog" | "unstarted" | "started" | "completed" | "cancelled";
+
 export type WorkspacePrStatus = "open" | "merged" | "closed";
 
 export type UserData = { user_id: string, first_name: string | null, last_name: string | null, username: string | null, };
@@ -56,7 +62,7 @@ function defineShape<T>(
 // Individual shape definitions with embedded types
 export const PROJECTS_SHAPE = defineShape<Project>(
   'projects',
-  ['organization_id'] as const,
+  ['organization_id', 'user_id'] as const,
   '/shape/projects'
 );
 
@@ -126,6 +132,12 @@ export const ISSUE_COMMENT_REACTIONS_SHAPE = defineShape<IssueCommentReaction>(
   '/shape/issue/{issue_id}/reactions'
 );
 
+export const ISSUE_EVENTS_SHAPE = defineShape<IssueEvent>(
+  'issue_events',
+  ['issue_id'] as const,
+  '/shape/issue/{issue_id}/events'
+);
+
 // All shapes as an array for iteration and factory building
 export const ALL_SHAPES = [
   PROJECTS_SHAPE,
@@ -140,6 +152,7 @@ export const ALL_SHAPES = [
   ISSUE_DEPENDENCIES_SHAPE,
   ISSUE_COMMENTS_SHAPE,
   ISSUE_COMMENT_REACTIONS_SHAPE,
+  ISSUE_EVENTS_SHAPE,
 ] as const;
 
 // Type helper to extract row type from a shape
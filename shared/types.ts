@@ -4,11 +4,15 @@
 
 // If you are an AI, and you absolutely have to edit this file, please confirm with the user first.
 
-export type Project = { id: string, name: string, default_agent_working_dir: string | null, remote_project_id: string | null, created_at: Date, updated_at: Date, };
+export type Project = { id: string, name: string, default_agent_working_dir: string | null, remote_project_id: string | null, 
+/**
+ * Merged into every coding agent prompt in this project
+ */
+agent_instructions: string | null, created_at: Date, updated_at: Date, };
 
 export type CreateProject = { name: string, repositories: Array<CreateProjectRepo>, };
 
-export type UpdateProject = { name: string | null, };
+export type UpdateProject = { name: string | null, agent_instructions?: string | null, };
 
 export type SearchResult = { path: string, is_file: boolean, match_type: SearchMatchType, 
 /**
@@ -18,14 +22,54 @@ score: bigint, };
 
 export type SearchMatchType = "FileName" | "DirectoryName" | "FullPath";
 
-export type Repo = { id: string, path: string, name: string, display_name: string, setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean, dev_server_script: string | null, created_at: Date, updated_at: Date, };
+export type Repo = { id: string, path: string, name: string, display_name: string, setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean, dev_server_script: string | null, 
+/**
+ * Run in the worktree after each coding agent turn; the task only moves
+ * to review once it exits successfully
+ */
+verify_script: string | null, 
+/**
+ * Automatic follow-ups fed the failing output before giving up
+ */
+verify_max_retries: number, 
+/**
+ * Merged into every coding agent prompt for workspaces using this repo
+ */
+agent_instructions: string | null, 
+/**
+ * File in the repo, e.g. `AGENTS.md`, whose contents are merged in as well
+ */
+agent_instructions_file: string | null, 
+/**
+ * Detached worktrees kept checked out at the latest target branch with
+ * setup already run, claimed by new workspaces instead of creating one
+ */
+worktree_pool_size: number, created_at: Date, updated_at: Date, };
 
-export type UpdateRepo = { display_name?: string | null, setup_script?: string | null, cleanup_script?: string | null, copy_files?: string | null, parallel_setup_script?: boolean | null, dev_server_script?: string | null, };
+export type UpdateRepo = { display_name?: string | null, setup_script?: string | null, cleanup_script?: string | null, copy_files?: string | null, parallel_setup_script?: boolean | null, dev_server_script?: string | null, verify_script?: string | null, verify_max_retries?: number | null, agent_instructions?: string | null, agent_instructions_file?: string | null, worktree_pool_size?: number | null, };
 
 export type ProjectRepo = { id: string, project_id: string, repo_id: string, };
 
 export type CreateProjectRepo = { display_name: string, git_repo_path: string, };
 
+export type ProjectSecret = { id: string, project_id: string, 
+/**
+ * None applies the secret to every repo in the project
+ */
+repo_id: string | null, name: string, 
+/**
+ * Kinds of execution process the secret is injected into
+ */
+run_reasons: Array<ExecutionProcessRunReason>, created_at: string, updated_at: string, };
+
+export type CreateProjectSecret = { name: string, value: string, repo_id: string | null, run_reasons: Array<ExecutionProcessRunReason>, };
+
+export type UpdateProjectSecret = { 
+/**
+ * Omit to keep the current value
+ */
+value: string | null, run_reasons: Array<ExecutionProcessRunReason> | null, };
+
 export type WorkspaceRepo = { id: string, workspace_id: string, repo_id: string, target_branch: string, created_at: Date, updated_at: Date, };
 
 export type CreateWorkspaceRepo = { repo_id: string, target_branch: string, };
@@ -40,9 +84,9 @@ export type UpdateTag = { tag_name: string | null, content: string | null, };
 
 export type TaskStatus = "todo" | "inprogress" | "inreview" | "done" | "cancelled";
 
-export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_workspace_id: string | null, created_at: string, updated_at: string, };
+export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_workspace_id: string | null, parent_task_id: string | null, created_at: string, updated_at: string, };
 
-export type TaskWithAttemptStatus = { has_in_progress_attempt: boolean, last_attempt_failed: boolean, executor: string, id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_workspace_id: string | null, created_at: string, updated_at: string, };
+export type TaskWithAttemptStatus = { has_in_progress_attempt: boolean, last_attempt_failed: boolean, executor: string, id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_workspace_id: string | null, parent_task_id: string | null, created_at: string, updated_at: string, };
 
 export type TaskRelationships = { parent_task: Task | null, current_workspace: Workspace, children: Array<Task>, };
 
@@ -56,7 +100,13 @@ export type DraftWorkspaceData = { message: string, project_id: string | null, r
 
 export type DraftWorkspaceRepo = { repo_id: string, target_branch: string, };
 
-export type PreviewSettingsData = { url: string, screen_size: string | null, responsive_width: number | null, responsive_height: number | null, };
+export type PreviewSettingsData = { 
+/**
+ * URL override. The workspace's preview proxy reuses a saved port whenever
+ * it starts, so its URL survives dev server and app restarts unless another
+ * program has taken that port in the meantime.
+ */
+url: string, screen_size: string | null, responsive_width: number | null, responsive_height: number | null, };
 
 export type WorkspaceNotesData = { content: string, };
 
@@ -78,6 +128,14 @@ export type Workspace = { id: string, task_id: string, container_ref: string | n
 
 export type WorkspaceWithStatus = { is_running: boolean, is_errored: boolean, id: string, task_id: string, container_ref: string | null, branch: string, agent_working_dir: string | null, setup_completed_at: string | null, created_at: string, updated_at: string, archived: boolean, pinned: boolean, name: string | null, };
 
+export type WorkspaceDiskUsage = { workspace_id: string, total_bytes: number, artifact_bytes: number, measured_at: string, };
+
+export type WorkspaceDiskUsageEntry = { workspace_id: string, task_id: string, task_title: string, project_id: string, project_name: string, branch: string, archived: boolean, pinned: boolean, is_running: boolean, 
+/**
+ * Latest of the workspace's last update and its last finished process
+ */
+last_used_at: string, total_bytes: number, artifact_bytes: number, measured_at: string, };
+
 export type Session = { id: string, workspace_id: string, executor: string | null, created_at: string, updated_at: string, };
 
 export type ExecutionProcess = { id: string, session_id: string, run_reason: ExecutionProcessRunReason, executor_action: ExecutorAction, status: ExecutionProcessStatus, exit_code: bigint | null, 
@@ -90,10 +148,22 @@ dropped: boolean, started_at: string, completed_at: string | null, created_at: s
 
 export enum ExecutionProcessStatus { running = "running", completed = "completed", failed = "failed", killed = "killed" }
 
-export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "codingagent" | "devserver";
+export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "codingagent" | "devserver" | "verifyscript";
 
 export type ExecutionProcessRepoState = { id: string, execution_process_id: string, repo_id: string, before_head_commit: string | null, after_head_commit: string | null, merge_commit: string | null, created_at: Date, updated_at: Date, };
 
+/**
+ * Result of a repo's verify script after a coding agent execution process
+ */
+export type ExecutionProcessVerification = { id: string, execution_process_id: string, repo_id: string, passed: boolean, exit_code: bigint | null, output: string, attempt: bigint, retry_execution_process_id: string | null, verify_execution_process_id: string | null, created_at: string, };
+
+/**
+ * A task run in several workspaces at once with different executors
+ */
+export type FanOutGroup = { id: string, task_id: string, winner_workspace_id: string | null, promoted_at: string | null, created_at: string, };
+
+export type FanOutAttempt = { id: string, fan_out_group_id: string, workspace_id: string, executor_profile_id: ExecutorProfileId, created_at: string, };
+
 export type Merge = { "type": "direct" } & DirectMerge | { "type": "pr" } & PrMerge;
 
 export type DirectMerge = { id: string, workspace_id: string, repo_id: string, merge_commit: string, target_branch_name: string, created_at: string, };
@@ -104,12 +174,77 @@ export type MergeStatus = "open" | "merged" | "closed" | "unknown";
 
 export type PullRequestInfo = { number: bigint, url: string, status: MergeStatus, merged_at: string | null, merge_commit_sha: string | null, };
 
+export type PrCommentWatch = { id: string, workspace_id: string, mode: PrCommentWatchMode, last_seen_at: string, created_at: string, updated_at: string, };
+
+export type PrCommentWatchMode = "auto" | "confirm";
+
+export type PrCommentFollowUp = { id: string, workspace_id: string, merge_id: string, session_id: string | null, execution_process_id: string | null, status: PrCommentFollowUpStatus, prompt: string, comment_ids: Array<bigint>, head_commit: string | null, created_at: string, updated_at: string, };
+
+export type PrCommentFollowUpStatus = "pending_confirmation" | "queued" | "started" | "replied" | "dismissed";
+
+/**
+ * Repo and target branch the scheduled workspace is created on
+ */
+export type TaskScheduleRepo = { repo_id: string, target_branch: string, };
+
+export type TaskSchedule = { id: string, project_id: string, name: string, 
+/**
+ * Five-field cron expression, evaluated in UTC
+ */
+cron_expression: string, enabled: boolean, task_title: string, task_description: string | null, executor_profile_id: ExecutorProfileId, repos: Array<TaskScheduleRepo>, 
+/**
+ * None while the schedule is disabled
+ */
+next_run_at: string | null, created_at: string, updated_at: string, };
+
+export type CreateTaskSchedule = { project_id: string, name: string, cron_expression: string, enabled: boolean | null, task_title: string, task_description: string | null, executor_profile_id: ExecutorProfileId, repos: Array<TaskScheduleRepo>, };
+
+/**
+ * Replaces every editable field of a schedule
+ */
+export type UpdateTaskSchedule = { name: string, cron_expression: string, enabled: boolean, task_title: string, task_description: string | null, executor_profile_id: ExecutorProfileId, repos: Array<TaskScheduleRepo>, };
+
+export type TaskScheduleRunStatus = "started" | "skipped" | "failed";
+
+export type TaskScheduleRun = { id: string, schedule_id: string, scheduled_for: string, status: TaskScheduleRunStatus, task_id: string | null, workspace_id: string | null, message: string | null, created_at: string, };
+
+export type GitHubIssueSyncSettings = { project_id: string, 
+/**
+ * Repository whose GitHub remote holds the issues
+ */
+repo_id: string, enabled: boolean, created_at: string, updated_at: string, };
+
+export type UpsertGitHubIssueSyncSettings = { repo_id: string, enabled: boolean, };
+
+/**
+ * A task linked to a GitHub issue
+ */
+export type TaskGitHubIssue = { id: string, task_id: string, repo_id: string, issue_number: number, issue_url: string, commented_pr_urls: Array<string>, last_summary_turn_id: string | null, last_synced_at: string | null, created_at: string, updated_at: string, };
+
+export type GitHubIssueSyncDirection = "push" | "pull";
+
+export type GitHubIssueSyncEvent = { id: string, task_id: string, direction: GitHubIssueSyncDirection, 
+/**
+ * title, body, status, pr_link or summary
+ */
+field: string, value: string | null, 
+/**
+ * Both sides had changed; the most recent edit won
+ */
+conflict: boolean, created_at: string, };
+
 export type ApprovalStatus = { "status": "pending" } | { "status": "approved" } | { "status": "denied", reason?: string, } | { "status": "timed_out" };
 
 export type CreateApprovalRequest = { tool_name: string, tool_input: JsonValue, tool_call_id: string, };
 
 export type ApprovalResponse = { execution_process_id: string, status: ApprovalStatus, };
 
+export type PendingApprovalInfo = { approval_id: string, execution_process_id: string, tool_name: string, 
+/**
+ * The tool use entry the agent is waiting on
+ */
+entry: NormalizedEntry, };
+
 export type Diff = { change: DiffChangeKind, oldPath: string | null, newPath: string | null, oldContent: string | null, newContent: string | null, 
 /**
  * True when file contents are intentionally omitted (e.g., too large)
@@ -132,7 +267,7 @@ export type ProviderProfile = { provider: string, username: string | null, displ
 
 export type StatusResponse = { logged_in: boolean, profile: ProfileResponse | null, degraded: boolean | null, };
 
-export enum MemberRole { ADMIN = "ADMIN", MEMBER = "MEMBER" }
+export enum MemberRole { ADMIN = "ADMIN", MEMBER = "MEMBER", VIEWER = "VIEWER", GUEST = "GUEST" }
 
 export enum InvitationStatus { PENDING = "PENDING", ACCEPTED = "ACCEPTED", DECLINED = "DECLINED", EXPIRED = "EXPIRED" }
 
@@ -174,7 +309,7 @@ export type UpdateMemberRoleRequest = { role: MemberRole, };
 
 export type UpdateMemberRoleResponse = { user_id: string, role: MemberRole, };
 
-export type RemoteProject = { id: string, organization_id: string, name: string, color: string, created_at: string, updated_at: string, };
+export type RemoteProject = { id: string, organization_id: string, name: string, color: string, is_private: boolean, created_at: string, updated_at: string, };
 
 export type ListProjectsResponse = { projects: Array<RemoteProject>, };
 
@@ -216,6 +351,18 @@ export type CurrentUserResponse = { user_id: string, };
 
 export type CreateFollowUpAttempt = { prompt: string, variant: string | null, retry_process_id: string | null, force_when_dirty: boolean | null, perform_git_reset: boolean | null, };
 
+export type SessionConversationEntry = { execution_process_id: string, entry: NormalizedEntry, };
+
+export type SessionConversationPage = { entries: Array<SessionConversationEntry>, 
+/**
+ * Index of the first returned entry within the whole conversation
+ */
+offset: number, total: number, 
+/**
+ * True while a coding agent process in the session is still running
+ */
+running: boolean, };
+
 export type ChangeTargetBranchRequest = { repo_id: string, new_target_branch: string, };
 
 export type ChangeTargetBranchResponse = { repo_id: string, new_target_branch: string, status: [number, number], };
@@ -238,6 +385,12 @@ export type OpenEditorResponse = { url: string | null, };
 
 export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, };
 
+export type StartRemoteIssueRequest = { 
+/**
+ * Issue in the remote project this project is linked to
+ */
+remote_issue_id: string, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, };
+
 export type CreatePrApiRequest = { title: string, body: string | null, target_branch: string | null, draft: boolean | null, repo_id: string, auto_generate_description: boolean, };
 
 export type ImageResponse = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
@@ -290,10 +443,54 @@ export type GetPrCommentsError = { "type": "no_pr_attached" } | { "type": "cli_n
 
 export type GetPrCommentsQuery = { repo_id: string, };
 
+export type UpdatePrCommentWatchRequest = { mode: PrCommentWatchMode, };
+
+export type PrCommentFollowUpActionRequest = { follow_up_id: string, };
+
 export type UnifiedPrComment = { "comment_type": "general", id: string, author: string, author_association: string | null, body: string, created_at: string, url: string | null, } | { "comment_type": "review", id: bigint, author: string, author_association: string | null, body: string, created_at: string, url: string | null, path: string, line: bigint | null, side: string | null, diff_hunk: string | null, };
 
 export type ProviderKind = "git_hub" | "azure_dev_ops" | "unknown";
 
+/**
+ * Which issues `gh issue list` returns
+ */
+export type GitHubIssueFilter = { 
+/**
+ * Issues must carry every one of these labels
+ */
+labels: Array<string>, milestone: string | null, include_closed: boolean, limit: number | null, };
+
+/**
+ * One task to be created, with the tasks nested under it in the source
+ */
+export type TaskImportItem = { title: string, description: string | null, status: TaskStatus, 
+/**
+ * Labels from the source; those matching a tag name pull in that tag
+ */
+labels: Array<string>, 
+/**
+ * Remote images downloaded and attached when the item is imported
+ */
+image_urls: Array<string>, 
+/**
+ * Link back to the source, e.g. the GitHub issue URL
+ */
+source_url: string | null, 
+/**
+ * Existing task this item appears to duplicate, set by the preview
+ */
+duplicate_of: string | null, children: Array<TaskImportItem>, };
+
+export type TaskImportResult = { created: Array<Task>, 
+/**
+ * Items not created because they duplicate an existing task
+ */
+skipped_duplicates: number, 
+/**
+ * Images that could not be downloaded; the tasks were created without them
+ */
+failed_images: Array<string>, };
+
 export type RepoBranchStatus = { repo_id: string, repo_name: string, commits_behind: number | null, commits_ahead: number | null, has_uncommitted_changes: boolean | null, head_oid: string | null, uncommitted_count: number | null, untracked_count: number | null, target_branch_name: string, remote_commits_behind: number | null, remote_commits_ahead: number | null, merges: Array<Merge>, 
 /**
  * True if a `git rebase` is currently in progress in this worktree
@@ -358,13 +555,88 @@ export type WorkspaceSummaryResponse = { summaries: Array<WorkspaceSummary>, };
 
 export type DiffStats = { files_changed: number, lines_added: number, lines_removed: number, };
 
+export type CreateFanOutBody = { task_id: string, 
+/**
+ * One workspace is started per distinct profile
+ */
+executor_profile_ids: Array<ExecutorProfileId>, repos: Array<WorkspaceRepoInput>, };
+
+export type FanOutGroupWithAttempts = { group: FanOutGroup, attempts: Array<FanOutAttempt>, };
+
+/**
+ * One attempt's results, for side-by-side comparison
+ */
+export type FanOutAttemptComparison = { workspace: Workspace, executor_profile_id: ExecutorProfileId, 
+/**
+ * None when the worktree no longer exists
+ */
+diff_stats: DiffStats | null, 
+/**
+ * Verify script results for the latest coding agent turn
+ */
+verifications: Array<ExecutionProcessVerification>, latest_status: ExecutionProcessStatus | null, 
+/**
+ * Time spent in finished coding agent turns
+ */
+duration_ms: bigint, 
+/**
+ * Context window usage at the end of the latest turn that reported it.
+ * None when the executor does not report usage
+ */
+context_tokens: bigint | null, 
+/**
+ * Input tokens consumed across all coding agent turns. None when no turn
+ * reported them
+ */
+input_tokens: bigint | null, 
+/**
+ * Output tokens generated across all coding agent turns
+ */
+output_tokens: bigint | null, };
+
+export type FanOutComparison = { group: FanOutGroup, attempts: Array<FanOutAttemptComparison>, };
+
+export type PromoteFanOutWinnerRequest = { workspace_id: string, };
+
+export type PromoteFanOutWinnerResponse = { group: FanOutGroup, 
+/**
+ * Losing workspaces left unarchived because they are pinned
+ */
+pinned_workspace_ids: Array<string>, };
+
+export type ForkWorkspaceRequest = { 
+/**
+ * Coding agent turn to fork after; its changes are included in the new workspace
+ */
+execution_process_id: string, 
+/**
+ * First message sent to the forked agent session
+ */
+prompt: string, variant: string | null, };
+
+export type TaskImportSource = { "type": "github_issues", repo_id: string, filter: GitHubIssueFilter, } | { "type": "markdown", content: string, } | { "type": "csv", content: string, };
+
+export type TaskImportPreviewRequest = { source: TaskImportSource, };
+
+export type TaskImportRequest = { 
+/**
+ * Items returned by the preview, optionally edited
+ */
+items: Array<TaskImportItem>, 
+/**
+ * Tags added to every imported task
+ */
+tag_ids: Array<string>, skip_duplicates: boolean, };
+
+export type LinkTaskGitHubIssueRequest = { issue_number: number, };
+
 export type DirectoryEntry = { name: string, path: string, is_directory: boolean, is_git_repo: boolean, last_modified: bigint | null, };
 
 export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_path: string, };
 
 export type SearchMode = "taskform" | "settings";
 
-export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, beta_workspaces: boolean, beta_workspaces_invitation_sent: boolean, commit_reminder: boolean, };
+export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, beta_workspaces: boolean, beta_workspaces_invitation_sent: boolean, commit_reminder: boolean, workspace_disk: WorkspaceDiskPolicy, };
 
 export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, };
 
@@ -384,6 +656,20 @@ export type UiLanguage = "BROWSER" | "EN" | "FR" | "JA" | "ES" | "KO" | "ZH_HANS
 
 export type ShowcaseState = { seen_features: Array<string>, };
 
+export type WorkspaceDiskPolicy = { max_total_gb: number | null, max_project_gb: number | null, 
+/**
+ * Unarchived workspaces count as idle after this many hours without activity
+ */
+idle_after_hours: number, 
+/**
+ * Directory names treated as disposable build artifacts
+ */
+artifact_dirs: Array<string>, 
+/**
+ * Remove whole worktrees when dropping artifacts is not enough
+ */
+evict_worktrees: boolean, };
+
 export type GitBranch = { name: string, is_current: boolean, is_remote: boolean, last_commit_date: Date, };
 
 export type QueuedMessage = { 
@@ -402,6 +688,45 @@ queued_at: string, };
 
 export type QueueStatus = { "status": "empty" } | { "status": "queued", message: QueuedMessage, };
 
+/**
+ * Dev-server preview state for a workspace
+ */
+export type WorkspacePreview = { workspace_id: string, 
+/**
+ * Port handed to dev-server scripts via `PORT` and `VK_PREVIEW_PORT`
+ */
+port: number, 
+/**
+ * Origin the dev server reported it is listening on, if seen in its output
+ */
+detected_url: string | null, 
+/**
+ * Origin of the local reverse proxy for this workspace, once started. It
+ * is a separate origin from the app so previewed pages cannot call `/api`,
+ * and keeps its port across restarts.
+ */
+proxy_url: string | null, };
+
+export type DiskEvictionAction = "remove_artifacts" | "remove_worktree";
+
+export type DiskEviction = { workspace_id: string, project_id: string, action: DiskEvictionAction, 
+/**
+ * Measured when artifacts are removed; the last recorded size for worktrees
+ */
+freed_bytes: number, };
+
+export type ProjectDiskUsage = { project_id: string, project_name: string, workspace_count: number, total_bytes: number, artifact_bytes: number, };
+
+export type DiskUsageSummary = { total_bytes: number, artifact_bytes: number, max_total_bytes: number | null, max_project_bytes: number | null, 
+/**
+ * Largest first
+ */
+projects: Array<ProjectDiskUsage>, 
+/**
+ * Largest first
+ */
+workspaces: Array<WorkspaceDiskUsageEntry>, };
+
 export type ConflictOp = "rebase" | "merge" | "cherry_pick" | "revert";
 
 export type ExecutorAction = { typ: ExecutorActionType, next_action: ExecutorAction | null, };
@@ -410,7 +735,7 @@ export type McpConfig = { servers: { [key in string]?: JsonValue }, servers_path
 
 export type ExecutorActionType = { "type": "CodingAgentInitialRequest" } & CodingAgentInitialRequest | { "type": "CodingAgentFollowUpRequest" } & CodingAgentFollowUpRequest | { "type": "ScriptRequest" } & ScriptRequest | { "type": "ReviewRequest" } & ReviewRequest;
 
-export type ScriptContext = "SetupScript" | "CleanupScript" | "DevServer" | "ToolInstallScript";
+export type ScriptContext = "SetupScript" | "CleanupScript" | "DevServer" | "ToolInstallScript" | "VerifyScript";
 
 export type ScriptRequest = { script: string, language: ScriptRequestLanguage, context: ScriptContext, 
 /**
@@ -500,7 +825,12 @@ executor_profile_id: ExecutorProfileId,
  * Optional relative path to execute the agent in (relative to container_ref).
  * If None, uses the container_ref directory directly.
  */
-working_dir: string | null, };
+working_dir: string | null, 
+/**
+ * Project and repository instructions merged into the prompt, resolved when
+ * the process starts
+ */
+instructions: string | null, };
 
 export type CodingAgentFollowUpRequest = { prompt: string, session_id: string, 
 /**
@@ -511,7 +841,12 @@ executor_profile_id: ExecutorProfileId,
  * Optional relative path to execute the agent in (relative to container_ref).
  * If None, uses the container_ref directory directly.
  */
-working_dir: string | null, };
+working_dir: string | null, 
+/**
+ * Project and repository instructions merged into the prompt, resolved when
+ * the process starts
+ */
+instructions: string | null, };
 
 export type ReviewRequest = { executor_profile_id: ExecutorProfileId, context: Array<RepoReviewContext> | null, prompt: string, 
 /**
@@ -521,7 +856,12 @@ session_id: string | null,
 /**
  * Optional relative path to execute the agent in (relative to container_ref).
  */
-working_dir: string | null, };
+working_dir: string | null, 
+/**
+ * Project and repository instructions merged into the prompt, resolved when
+ * the process starts
+ */
+instructions: string | null, };
 
 export type RepoReviewContext = { repo_id: string, repo_name: string, base_commit: string, };
 
@@ -533,7 +873,16 @@ export type NormalizedEntry = { timestamp: string | null, entry_type: Normalized
 
 export type NormalizedEntryType = { "type": "user_message" } | { "type": "user_feedback", denied_tool: string, } | { "type": "assistant_message" } | { "type": "tool_use", tool_name: string, action_type: ActionType, status: ToolStatus, } | { "type": "system_message" } | { "type": "error_message", error_type: NormalizedEntryError, } | { "type": "thinking" } | { "type": "loading" } | { "type": "next_action", failed: boolean, execution_processes: number, needs_setup: boolean, } | { "type": "token_usage_info" } & TokenUsageInfo;
 
-export type TokenUsageInfo = { total_tokens: number, model_context_window: number, };
+export type TokenUsageInfo = { total_tokens: number, model_context_window: number, 
+/**
+ * Input tokens, cached ones included, consumed by the execution so far.
+ * None when the executor does not report them.
+ */
+input_tokens: number | null, 
+/**
+ * Output tokens generated by the execution so far
+ */
+output_tokens: number | null, };
 
 export type FileChange = { "action": "write", content: string, } | { "action": "delete" } | { "action": "rename", new_path: string, } | { "action": "edit", 
 /**